@@ -1,4 +1,4 @@
-use serde_derive::Deserialize;
+use serde_derive::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize)]
 pub struct AssetEditListResponse {
@@ -6,7 +6,7 @@ pub struct AssetEditListResponse {
     pub pages: usize,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AssetEditListItem {
     pub edit_id: String,
     pub asset_id: String,
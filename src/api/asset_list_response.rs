@@ -1,31 +1,57 @@
-use serde_derive::Deserialize;
+use crate::api::AssetResponse;
+
+use serde_derive::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter, Result as FmtResult};
 
 #[derive(Deserialize, Debug)]
 pub struct AssetListResponse {
     pub result: Vec<AssetListItem>,
+    /// Page of the results, counting from 0
+    #[serde(default)]
+    pub page: usize,
+    /// Number of pages of results
+    #[serde(default)]
+    pub pages: usize,
+    #[serde(default)]
+    pub total_items: usize,
 }
 
 impl AssetListResponse {
     #[allow(unused)]
     pub fn new(result: Vec<AssetListItem>) -> AssetListResponse {
-        AssetListResponse { result }
+        AssetListResponse {
+            result,
+            page: 0,
+            pages: 1,
+            total_items: 0,
+        }
     }
+}
 
-    pub fn print_info(&self) {
-        if self.result.is_empty() {
-            return;
-        }
+/// Order of assets listed by `gdm browse`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AssetSort {
+    /// Highest rated first
+    #[default]
+    Rating,
+    /// Most recently updated first
+    Updated,
+    /// Alphabetically by title
+    Name,
+}
 
-        for asset in &self.result {
-            println!();
-            println!("{}", asset);
-            println!();
+impl AssetSort {
+    /// Value of the `sort` query parameter
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            AssetSort::Rating => "rating",
+            AssetSort::Updated => "updated",
+            AssetSort::Name => "name",
         }
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AssetListItem {
     pub asset_id: String,
     pub title: String,
@@ -40,6 +66,24 @@ pub struct AssetListItem {
     pub modify_date: String,
 }
 
+/// The search result for an asset fetched by ID, which doesn't include its author,
+/// category or support level
+impl From<&AssetResponse> for AssetListItem {
+    fn from(asset: &AssetResponse) -> Self {
+        AssetListItem {
+            asset_id: asset.asset_id.clone(),
+            title: asset.title.clone(),
+            godot_version: asset.godot_version.clone(),
+            rating: asset.rating.clone(),
+            cost: asset.cost.clone(),
+            version: asset.version.clone(),
+            version_string: asset.version_string.clone(),
+            modify_date: asset.modify_date.clone(),
+            ..Default::default()
+        }
+    }
+}
+
 impl AssetListItem {
     #[allow(unused, clippy::too_many_arguments)]
     pub fn new(
@@ -0,0 +1,86 @@
+use crate::error::{ErrorKind, GdmError};
+
+use anyhow::{Result, bail};
+use serde_derive::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Deserialize, Debug)]
+pub struct CategoryListResponse {
+    pub categories: Vec<Category>,
+}
+
+/// Asset Library category, `type` is `0` for addons and `1` for projects
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Category {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub category_type: String,
+}
+
+impl Category {
+    /// The category with the ID or name `value`, ignoring case
+    pub fn find<'a>(categories: &'a [Category], value: &str) -> Result<&'a Category> {
+        match categories
+            .iter()
+            .find(|category| category.id == value || category.name.eq_ignore_ascii_case(value))
+        {
+            Some(category) => Ok(category),
+            None => bail!(
+                GdmError::new(ErrorKind::Usage, format!("Unknown category: {}", value))
+                    .with_hint("Run gdm categories to list them.")
+            ),
+        }
+    }
+
+    /// "Addons" or "Projects"
+    pub fn kind(&self) -> &str {
+        match self.category_type.as_str() {
+            "0" => "Addons",
+            "1" => "Projects",
+            other => other,
+        }
+    }
+}
+
+impl Display for Category {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{0: <6} {1: <20} {2}", self.id, self.name, self.kind())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn categories() -> Vec<Category> {
+        vec![
+            Category {
+                id: "5".to_string(),
+                name: "3D Tools".to_string(),
+                category_type: "0".to_string(),
+            },
+            Category {
+                id: "10".to_string(),
+                name: "Projects".to_string(),
+                category_type: "1".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_find_should_match_id_or_name() {
+        let categories = categories();
+        assert_eq!(Category::find(&categories, "5").unwrap().name, "3D Tools");
+        assert_eq!(Category::find(&categories, "3d tools").unwrap().id, "5");
+        let err = Category::find(&categories, "Shaders").unwrap_err();
+        assert_eq!(GdmError::find(&err).map(|e| e.kind), Some(ErrorKind::Usage));
+    }
+
+    #[test]
+    fn test_kind_should_name_type() {
+        let categories = categories();
+        assert_eq!(categories[0].kind(), "Addons");
+        assert_eq!(categories[1].kind(), "Projects");
+    }
+}
@@ -3,31 +3,47 @@ mod asset_edit_list_response;
 mod asset_edit_response;
 mod asset_list_response;
 mod asset_response;
+mod category_response;
 
 pub use asset::Asset;
-pub use asset_edit_list_response::AssetEditListResponse;
+pub use asset_edit_list_response::{AssetEditListItem, AssetEditListResponse};
 pub use asset_edit_response::AssetEditResponse;
-#[cfg(test)]
-#[allow(unused)]
-pub use asset_list_response::AssetListItem;
-pub use asset_list_response::AssetListResponse;
+pub use asset_list_response::{AssetListItem, AssetListResponse, AssetSort};
 pub use asset_response::AssetResponse;
+pub use category_response::{Category, CategoryListResponse};
 
 use crate::config::{AppConfig, DefaultAppConfig};
-use crate::services::{DefaultFileService, DefaultHttpService, FileService, HttpService};
+use crate::error::{ErrorKind, GdmError};
+use crate::services::{
+    AssetIndex, DefaultFileService, DefaultHttpService, FileService, HttpService, MetadataCache,
+};
+use crate::ui::ProgressTask;
+use crate::utils::Utils;
 
 use anyhow::{Result, bail};
-use indicatif::ProgressBar;
-use std::collections::HashMap;
-use std::sync::Arc;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use tokio::io::AsyncWriteExt;
-use tracing::{error, info};
+use tokio::sync::OnceCell;
+use tracing::{debug, error, info};
 use url::Url;
 
 pub struct DefaultAssetStoreAPI {
     pub http_service: Arc<dyn HttpService + Send + Sync>,
     pub app_config: DefaultAppConfig,
     pub file_service: Arc<dyn FileService + Send + Sync + 'static>,
+    /// Responses fetched during this run, keyed by URL and query parameters
+    responses: Mutex<HashMap<String, Arc<OnceCell<Value>>>>,
+    /// Responses kept on disk between runs, only used by read-only commands
+    metadata_cache: Option<MetadataCache>,
+    /// Edit listings of assets kept on disk between runs, also used by commands that install
+    edit_list_cache: Option<MetadataCache>,
+    /// Archives downloaded during this run, keyed by download URL
+    downloads: Mutex<HashMap<String, Arc<OnceCell<PathBuf>>>>,
+    /// Assets seen in searches and lookups by ID, for `gdm search --offline`
+    asset_index: Option<AssetIndex>,
 }
 
 impl DefaultAssetStoreAPI {
@@ -41,21 +57,146 @@ impl DefaultAssetStoreAPI {
             http_service,
             app_config,
             file_service,
+            responses: Mutex::new(HashMap::new()),
+            metadata_cache: None,
+            edit_list_cache: None,
+            downloads: Mutex::new(HashMap::new()),
+            asset_index: None,
+        }
+    }
+
+    /// Records the assets of searches and lookups by ID in `asset_index`
+    pub fn with_asset_index(mut self, asset_index: Option<AssetIndex>) -> Self {
+        self.asset_index = asset_index;
+        self
+    }
+
+    fn record_assets(&self, assets: &[AssetListItem]) {
+        if let Some(asset_index) = &self.asset_index
+            && let Err(e) = asset_index.record(assets)
+        {
+            debug!("Failed to record assets in the index: {:#}", e);
         }
     }
 
+    /// Reuses responses stored in `metadata_cache` until they expire
+    pub fn with_metadata_cache(mut self, metadata_cache: Option<MetadataCache>) -> Self {
+        self.metadata_cache = metadata_cache;
+        self
+    }
+
+    /// Reuses the edit listings stored in `edit_list_cache` to resolve versions, so each run
+    /// doesn't page through the whole history of an asset
+    pub fn with_edit_list_cache(mut self, edit_list_cache: Option<MetadataCache>) -> Self {
+        self.edit_list_cache = edit_list_cache;
+        self
+    }
+
+    /// Every edit of an asset, from `edit_list_cache` unless `refresh` is set.
+    /// Also returns whether the listing came from the cache.
+    async fn get_asset_edit_list(
+        &self,
+        asset_id: &str,
+        refresh: bool,
+    ) -> Result<(Vec<AssetEditListItem>, bool)> {
+        let key = format!(
+            "{}?asset={}&pages=all",
+            self.get_url("/asset/edit"),
+            asset_id
+        );
+        if !refresh
+            && let Some(edit_list_cache) = &self.edit_list_cache
+            && let Some(data) = edit_list_cache.get(&key)
+            && let Ok(edits) = serde_json::from_value(data)
+        {
+            return Ok((edits, true));
+        }
+        let mut edits = Vec::new();
+        let mut page = 0;
+        loop {
+            let edits_response = self.get_asset_edits_by_asset_id(asset_id, page).await?;
+            if edits_response.result.is_empty() {
+                break;
+            }
+            edits.extend(
+                edits_response
+                    .result
+                    .into_iter()
+                    .filter(|edit| edit.asset_id == asset_id),
+            );
+            page += 1;
+            if page >= edits_response.pages {
+                break;
+            }
+        }
+        if let Some(edit_list_cache) = &self.edit_list_cache
+            && let Err(e) = edit_list_cache.insert(&key, &serde_json::to_value(&edits)?)
+        {
+            debug!("Failed to cache edit list of asset {}: {:#}", asset_id, e);
+        }
+        Ok((edits, false))
+    }
+
     fn get_url(&self, path: &str) -> String {
         format!("{}{}", self.app_config.api_base_url, path)
     }
+
+    /// Errors of the credential helper and requests refused by --frozen are reported as they
+    /// are, not as network errors
+    fn is_local_error(e: &anyhow::Error) -> bool {
+        GdmError::find(e)
+            .is_some_and(|e| matches!(e.kind, ErrorKind::ConfigInvalid | ErrorKind::Usage))
+    }
+
+    /// Sends each unique request once per run and answers repeats from memory.
+    /// Failed requests aren't remembered, so they are tried again.
+    async fn get_cached(&self, path: &str, params: HashMap<String, String>) -> Result<Value> {
+        let url = self.get_url(path);
+        let sorted_params: BTreeMap<&String, &String> = params.iter().collect();
+        let key = format!("{}?{:?}", url, sorted_params);
+        let response = self
+            .responses
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(key.clone())
+            .or_default()
+            .clone();
+        let data = response
+            .get_or_try_init(|| async {
+                if let Some(data) = self.metadata_cache.as_ref().and_then(|c| c.get(&key)) {
+                    return Ok(data);
+                }
+                let data = self.http_service.get(url, params).await?;
+                if let Some(metadata_cache) = &self.metadata_cache
+                    && let Err(e) = metadata_cache.insert(&key, &data)
+                {
+                    debug!("Failed to cache metadata: {:#}", e);
+                }
+                anyhow::Ok(data)
+            })
+            .await?;
+        Ok(data.clone())
+    }
 }
 
 impl Default for DefaultAssetStoreAPI {
     fn default() -> Self {
-        DefaultAssetStoreAPI {
-            http_service: Arc::new(DefaultHttpService::default()),
-            app_config: DefaultAppConfig::default(),
-            file_service: Arc::new(DefaultFileService),
-        }
+        Self::for_app_config(DefaultAppConfig::default())
+    }
+}
+
+impl DefaultAssetStoreAPI {
+    /// Queries the registry of `app_config` with its credentials, caching in gdm's cache directory
+    pub fn for_app_config(app_config: DefaultAppConfig) -> Self {
+        let edit_list_cache = MetadataCache::in_cache_dir(app_config.get_metadata_ttl());
+        let asset_index = AssetIndex::in_cache_dir(&app_config.api_base_url);
+        DefaultAssetStoreAPI::new(
+            Arc::new(DefaultHttpService::new(&app_config)),
+            app_config,
+            Arc::new(DefaultFileService),
+        )
+        .with_edit_list_cache(edit_list_cache)
+        .with_asset_index(asset_index)
     }
 }
 
@@ -76,10 +217,13 @@ impl Default for DefaultAssetStoreAPI {
 /// - `get_asset_by_id`: Fetches an asset by its ID.
 /// - `get_assets`: Fetches a list of assets based on query parameters.
 /// - `get_asset_by_id_and_version`: Fetches a specific version of an asset by ID.
+/// - `get_asset_versions`: Lists the versions in an asset's edits.
+/// - `get_categories`: Fetches the categories assets are listed in.
 ///
 /// # Asset Edits
 /// - `get_asset_edits_by_asset_id`: Retrieves a paginated list of edits for an asset.
 /// - `get_asset_edit_by_edit_id`: Retrieves a specific asset edit by its edit ID.
+/// - `get_newest_asset_edit`: Fetches the newest version submitted for an asset, reviewed or not.
 ///
 /// # Downloading
 /// - `download_file`: Downloads a file from a given URL.
@@ -113,6 +257,13 @@ pub trait AssetStoreAPI: Send + Sync {
         version: &str,
     ) -> Result<AssetResponse>;
 
+    /// Lists every version in an asset's edits, from the cached edit list unless it
+    /// doesn't have `newest` yet.
+    async fn get_asset_versions(&self, asset_id: &str, newest: &str) -> Result<Vec<String>>;
+
+    /// Fetches the categories of addons and projects.
+    async fn get_categories(&self) -> Result<Vec<Category>>;
+
     /// Retrieves a paginated list of edits for an asset.
     async fn get_asset_edits_by_asset_id(
         &self,
@@ -123,8 +274,15 @@ pub trait AssetStoreAPI: Send + Sync {
     /// Retrieves a specific asset edit by its edit ID.
     async fn get_asset_edit_by_edit_id(&self, edit_id: &str) -> Result<AssetEditResponse>;
 
+    /// Fetches the newest version submitted for an asset, including edits not reviewed yet.
+    async fn get_newest_asset_edit(&self, asset_id: &str) -> Result<Option<AssetResponse>>;
+
     /// Downloads an asset and reports progress via a progress bar.
-    async fn download_asset(&self, asset: &AssetResponse, pb_task: ProgressBar) -> Result<Asset>;
+    async fn download_asset(
+        &self,
+        asset: &AssetResponse,
+        pb_task: Arc<dyn ProgressTask>,
+    ) -> Result<Asset>;
 }
 
 #[cfg_attr(test, mockall::automock)]
@@ -144,7 +302,10 @@ impl AssetStoreAPI for DefaultAssetStoreAPI {
                 .await
         } else {
             error!("Asset name or version is empty");
-            bail!("Both asset name and version must be provided to search by version.")
+            bail!(GdmError::new(
+                ErrorKind::Usage,
+                "Both asset name and version must be provided to search by version."
+            ))
         }
     }
 
@@ -165,11 +326,35 @@ impl AssetStoreAPI for DefaultAssetStoreAPI {
             let asset_results = self.get_assets(params).await?;
 
             if asset_results.result.len() != 1 {
-                bail!(
-                    "Expected to find exactly one asset matching \"{}\", but found {}. Please refine your search or use --asset-id.",
-                    name,
-                    asset_results.result.len()
-                )
+                let kind = if asset_results.result.is_empty() {
+                    ErrorKind::NotFound
+                } else {
+                    ErrorKind::Conflict
+                };
+                let error = GdmError::new(
+                    kind,
+                    format!(
+                        "Expected to find exactly one asset matching \"{}\", but found {}. Please refine your search or use --asset-id.",
+                        name,
+                        asset_results.result.len()
+                    ),
+                );
+                let titles = asset_results
+                    .result
+                    .iter()
+                    .map(|asset| asset.title.as_str());
+                match Utils::closest_match(name, titles).and_then(|title| {
+                    asset_results
+                        .result
+                        .iter()
+                        .find(|asset| asset.title == title)
+                }) {
+                    Some(asset) => bail!(error.with_hint(format!(
+                        "Did you mean \"{}\"? Add it with gdm add --asset-id {}",
+                        asset.title, asset.asset_id
+                    ))),
+                    None => bail!(error),
+                }
             }
             let asset = asset_results.result.first().unwrap();
             let asset = self.get_asset_by_id(&asset.asset_id).await?;
@@ -177,34 +362,76 @@ impl AssetStoreAPI for DefaultAssetStoreAPI {
             info!("Found asset: {}", asset.title);
             Ok(asset)
         } else {
-            bail!("No name or asset ID provided")
+            bail!(GdmError::new(
+                ErrorKind::Usage,
+                "No name or asset ID provided"
+            ))
         }
     }
 
     async fn get_asset_by_id(&self, asset_id: &str) -> Result<AssetResponse> {
         match self
-            .http_service
-            .get(self.get_url(&format!("/asset/{}", asset_id)), [].into())
+            .get_cached(&format!("/asset/{}", asset_id), [].into())
             .await
         {
-            Ok(data) => Ok(serde_json::from_value(data)?),
+            Ok(data) => {
+                let asset: AssetResponse = serde_json::from_value(data)?;
+                self.record_assets(&[AssetListItem::from(&asset)]);
+                Ok(asset)
+            }
             Err(e) => {
                 error!("Failed to get asset by ID '{}': {}", asset_id, e);
-                bail!("No asset found with ID '{}'", asset_id)
+                if Self::is_local_error(&e) {
+                    return Err(e);
+                }
+                // Only a 404 means the asset doesn't exist, e.g. it was removed from the library
+                if GdmError::find(&e).is_some_and(|e| e.kind == ErrorKind::NotFound) {
+                    bail!(GdmError::new(
+                        ErrorKind::NotFound,
+                        format!("No asset found with ID '{}'", asset_id)
+                    ))
+                }
+                bail!(GdmError::new(
+                    ErrorKind::Network,
+                    format!("Failed to get asset with ID '{}'", asset_id)
+                ))
             }
         }
     }
 
     async fn get_assets(&self, params: HashMap<String, String>) -> Result<AssetListResponse> {
-        match self
-            .http_service
-            .get(self.get_url("/asset"), params.clone())
-            .await
-        {
-            Ok(data) => Ok(serde_json::from_value(data)?),
+        match self.get_cached("/asset", params.clone()).await {
+            Ok(data) => {
+                let assets: AssetListResponse = serde_json::from_value(data)?;
+                self.record_assets(&assets.result);
+                Ok(assets)
+            }
             Err(e) => {
                 error!("Failed to get assets with params {:?}: {}", params, e);
-                bail!("Failed to get assets")
+                if Self::is_local_error(&e) {
+                    return Err(e);
+                }
+                bail!(GdmError::new(ErrorKind::Network, "Failed to get assets"))
+            }
+        }
+    }
+
+    async fn get_categories(&self) -> Result<Vec<Category>> {
+        let params = HashMap::from([("type".to_string(), "any".to_string())]);
+        match self.get_cached("/configure", params).await {
+            Ok(data) => {
+                let response: CategoryListResponse = serde_json::from_value(data)?;
+                Ok(response.categories)
+            }
+            Err(e) => {
+                error!("Failed to get categories: {}", e);
+                if Self::is_local_error(&e) {
+                    return Err(e);
+                }
+                bail!(GdmError::new(
+                    ErrorKind::Network,
+                    "Failed to get categories"
+                ))
             }
         }
     }
@@ -215,31 +442,53 @@ impl AssetStoreAPI for DefaultAssetStoreAPI {
         version: &str,
     ) -> Result<AssetResponse> {
         if asset_id.is_empty() || version.is_empty() {
-            bail!("Both asset ID and version must be provided to search by version.")
+            bail!(GdmError::new(
+                ErrorKind::Usage,
+                "Both asset ID and version must be provided to search by version."
+            ))
         }
-        let mut page = 0;
-        loop {
-            let edits_response = self.get_asset_edits_by_asset_id(asset_id, page).await?;
-            if edits_response.result.is_empty() {
-                break;
-            }
-            for edit in edits_response.result.iter() {
-                if edit.version_string == version && edit.asset_id == asset_id {
-                    let edit_result = self.get_asset_edit_by_edit_id(&edit.edit_id).await?;
-                    let asset_response = AssetResponse::from(edit_result);
-                    return Ok(asset_response);
-                }
-            }
-            if page == edits_response.pages - 1 {
-                break;
-            }
-            page += 1;
+        let find_edit = |edits: Vec<AssetEditListItem>| {
+            edits
+                .into_iter()
+                .find(|edit| edit.version_string == version)
+        };
+        let (edits, cached) = self.get_asset_edit_list(asset_id, false).await?;
+        let mut edit = find_edit(edits);
+        if edit.is_none() && cached {
+            // The version may have been published after the listing was cached
+            debug!(
+                "{} {} isn't in the cached edit list, fetching it",
+                asset_id, version
+            );
+            edit = find_edit(self.get_asset_edit_list(asset_id, true).await?.0);
         }
-        bail!(
-            "No asset found for asset_id: {} with version: {}",
-            asset_id,
-            version
-        )
+        if let Some(edit) = edit {
+            let edit_result = self.get_asset_edit_by_edit_id(&edit.edit_id).await?;
+            return Ok(AssetResponse::from(edit_result));
+        }
+        bail!(GdmError::new(
+            ErrorKind::NotFound,
+            format!(
+                "No asset found for asset_id: {} with version: {}",
+                asset_id, version
+            )
+        ))
+    }
+
+    async fn get_asset_versions(&self, asset_id: &str, newest: &str) -> Result<Vec<String>> {
+        let versions = |edits: Vec<AssetEditListItem>| -> Vec<String> {
+            edits.into_iter().map(|edit| edit.version_string).collect()
+        };
+        let (edits, cached) = self.get_asset_edit_list(asset_id, false).await?;
+        let mut asset_versions = versions(edits);
+        if cached && !asset_versions.iter().any(|version| version == newest) {
+            debug!(
+                "{} {} isn't in the cached edit list, fetching it",
+                asset_id, newest
+            );
+            asset_versions = versions(self.get_asset_edit_list(asset_id, true).await?.0);
+        }
+        Ok(asset_versions)
     }
 
     async fn get_asset_edits_by_asset_id(
@@ -252,23 +501,24 @@ impl AssetStoreAPI for DefaultAssetStoreAPI {
             ("status".to_string(), "new accepted".to_string()),
             ("page".to_string(), page.to_string()),
         ]);
-        match self
-            .http_service
-            .get(self.get_url("/asset/edit"), params)
-            .await
-        {
+        match self.get_cached("/asset/edit", params).await {
             Ok(data) => Ok(serde_json::from_value(data)?),
             Err(e) => {
                 error!("Failed to get asset edits for asset ID {}: {}", asset_id, e);
-                bail!("Failed to get asset edits for asset ID {}", asset_id)
+                if Self::is_local_error(&e) {
+                    return Err(e);
+                }
+                bail!(GdmError::new(
+                    ErrorKind::Network,
+                    format!("Failed to get asset edits for asset ID {}", asset_id)
+                ))
             }
         }
     }
 
     async fn get_asset_edit_by_edit_id(&self, edit_id: &str) -> Result<AssetEditResponse> {
         match self
-            .http_service
-            .get(self.get_url(&format!("/asset/edit/{}", edit_id)), [].into())
+            .get_cached(&format!("/asset/edit/{}", edit_id), [].into())
             .await
         {
             Ok(data) => {
@@ -277,15 +527,74 @@ impl AssetStoreAPI for DefaultAssetStoreAPI {
             }
             Err(e) => {
                 error!("Failed to get asset edit by edit ID {}: {}", edit_id, e);
-                bail!("Failed to get asset edit by edit ID {}", edit_id)
+                if Self::is_local_error(&e) {
+                    return Err(e);
+                }
+                bail!(GdmError::new(
+                    ErrorKind::Network,
+                    format!("Failed to get asset edit by edit ID {}", edit_id)
+                ))
             }
         }
     }
 
+    /// Looks at the latest page of `new` and `accepted` edits and fetches the one with the highest version
+    async fn get_newest_asset_edit(&self, asset_id: &str) -> Result<Option<AssetResponse>> {
+        let edits_response = self.get_asset_edits_by_asset_id(asset_id, 0).await?;
+        let Some(newest_edit) = edits_response
+            .result
+            .iter()
+            .filter(|edit| edit.asset_id == asset_id)
+            .max_by_key(|edit| Utils::parse_semantic_version(&edit.version_string))
+        else {
+            return Ok(None);
+        };
+        let edit_result = self.get_asset_edit_by_edit_id(&newest_edit.edit_id).await?;
+        Ok(Some(AssetResponse::from(edit_result)))
+    }
+
     /// Downloads a plugin from the Asset Store and returns a Asset struct
     ///
-    /// Downloaded files are saved to the cache folder defined in the AppConfig
-    async fn download_asset(&self, asset: &AssetResponse, pb_task: ProgressBar) -> Result<Asset> {
+    /// Downloaded files are saved to the cache folder defined in the AppConfig. Plugins resolving
+    /// to the same download URL in one run share a single download, even when installed in parallel.
+    async fn download_asset(
+        &self,
+        asset: &AssetResponse,
+        pb_task: Arc<dyn ProgressTask>,
+    ) -> Result<Asset> {
+        let download = self
+            .downloads
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(asset.download_url.clone())
+            .or_default()
+            .clone();
+        let mut downloaded_here = false;
+        let file_path = download
+            .get_or_try_init(|| {
+                downloaded_here = true;
+                self.download_uncached(asset, pb_task.clone())
+            })
+            .await?;
+        if !downloaded_here {
+            debug!(
+                "Reusing download of {} {}: {}",
+                asset.title,
+                asset.version_string,
+                file_path.display()
+            );
+            pb_task.finished();
+        }
+        Ok(Asset::new(file_path.clone(), asset.clone()))
+    }
+}
+
+impl DefaultAssetStoreAPI {
+    async fn download_uncached(
+        &self,
+        asset: &AssetResponse,
+        pb_task: Arc<dyn ProgressTask>,
+    ) -> Result<PathBuf> {
         let cache_folder = self.app_config.get_cache_folder_path();
         let download_url = &asset.download_url;
 
@@ -297,6 +606,37 @@ impl AssetStoreAPI for DefaultAssetStoreAPI {
             .unwrap_or("temp_file.zip");
         let filepath = cache_folder.join(filename);
 
+        // During a workspace install, the projects share downloads of the same asset version
+        if let Some(shared_cache_folder) = self.app_config.get_shared_cache_folder_path() {
+            let shared_filepath = shared_cache_folder.join(format!(
+                "{}-{}-{}",
+                asset.asset_id, asset.version_string, filename
+            ));
+
+            if self.file_service.file_exists(&shared_filepath)? {
+                info!("Using shared download: {}", shared_filepath.display());
+                pb_task.finished();
+                return Ok(shared_filepath);
+            }
+
+            return self
+                .download_to_file(asset, &shared_cache_folder, shared_filepath, pb_task)
+                .await;
+        }
+
+        self.download_to_file(asset, cache_folder, filepath, pb_task)
+            .await
+    }
+
+    async fn download_to_file(
+        &self,
+        asset: &AssetResponse,
+        cache_folder: &Path,
+        filepath: PathBuf,
+        pb_task: Arc<dyn ProgressTask>,
+    ) -> Result<PathBuf> {
+        let download_url = &asset.download_url;
+
         if !self.file_service.directory_exists(cache_folder) {
             self.file_service.create_directory(cache_folder)?;
         }
@@ -307,21 +647,24 @@ impl AssetStoreAPI for DefaultAssetStoreAPI {
 
         let mut res = self.http_service.get_file(download_url.to_string()).await?;
 
-        pb_task.set_length(100);
+        pb_task.set_length(res.content_length().unwrap_or(100));
 
         let mut file = self.file_service.create_file_async(&filepath).await?;
 
         while let Some(chunk) = res.chunk().await? {
-            pb_task.inc(chunk.len() as u64);
+            pb_task.bytes(chunk.len() as u64);
             self.file_service.write_all_async(&mut file, &chunk).await?;
         }
 
         file.flush().await?;
-        pb_task.finish_and_clear();
+        pb_task.finished();
 
         match res.error_for_status() {
-            Ok(_) => Ok(Asset::new(filepath, asset.clone())),
-            Err(e) => bail!("Failed to fetch file: {}", e),
+            Ok(_) => Ok(filepath),
+            Err(e) => bail!(GdmError::new(
+                ErrorKind::Network,
+                format!("Failed to fetch file: {}", e)
+            )),
         }
     }
 }
@@ -330,13 +673,22 @@ impl AssetStoreAPI for DefaultAssetStoreAPI {
 mod tests {
     use std::path::PathBuf;
 
-    use crate::services::{MockDefaultFileService, MockDefaultHttpService};
+    use crate::services::{MockDefaultFileService, MockDefaultHttpService, RecordingHttpService};
 
     use super::*;
     use mockall::predicate::*;
 
+    /// Replays Asset Library responses from tests/mocks/http, see `RecordingHttpService`
     fn setup_test_api() -> DefaultAssetStoreAPI {
-        DefaultAssetStoreAPI::default()
+        let http_service = RecordingHttpService::from_env(
+            Arc::new(DefaultHttpService::default()),
+            "tests/mocks/http",
+        );
+        DefaultAssetStoreAPI::new(
+            Arc::new(http_service),
+            DefaultAppConfig::default(),
+            Arc::new(DefaultFileService),
+        )
     }
 
     // get_asset_by_id
@@ -351,6 +703,79 @@ mod tests {
         assert_eq!(asset.asset_id, asset_id);
     }
 
+    #[tokio::test]
+    async fn test_get_asset_by_id_twice_should_request_once() {
+        let mut mock_http_service = MockDefaultHttpService::new();
+        mock_http_service
+            .expect_get()
+            .with(eq("http://mock/asset/1709".to_string()), always())
+            .times(1)
+            .returning(|_, _| {
+                Ok(
+                    serde_json::from_str(include_str!("../../tests/mocks/http/asset_1709.json"))
+                        .unwrap(),
+                )
+            });
+        let api = DefaultAssetStoreAPI::new(
+            Arc::new(mock_http_service),
+            DefaultAppConfig::new(Some(String::from("http://mock")), None, None, None, None),
+            Arc::new(MockDefaultFileService::new()),
+        );
+
+        let first = api.get_asset_by_id("1709").await.unwrap();
+        let second = api.get_asset_by_id("1709").await.unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[tokio::test]
+    async fn test_metadata_cache_should_be_shared_between_runs() {
+        let cache_dir = temp_dir::TempDir::new().unwrap();
+        let setup_api = |expected_requests: usize| {
+            let mut mock_http_service = MockDefaultHttpService::new();
+            mock_http_service
+                .expect_get()
+                .times(expected_requests)
+                .returning(|_, _| {
+                    Ok(
+                        serde_json::from_str(include_str!(
+                            "../../tests/mocks/http/asset_1709.json"
+                        ))
+                        .unwrap(),
+                    )
+                });
+            DefaultAssetStoreAPI::new(
+                Arc::new(mock_http_service),
+                DefaultAppConfig::new(Some(String::from("http://mock")), None, None, None, None),
+                Arc::new(MockDefaultFileService::new()),
+            )
+            .with_metadata_cache(Some(MetadataCache::new(
+                cache_dir.path(),
+                crate::services::DEFAULT_METADATA_TTL,
+            )))
+        };
+
+        let first = setup_api(1).get_asset_by_id("1709").await.unwrap();
+        let second = setup_api(0).get_asset_by_id("1709").await.unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[tokio::test]
+    async fn test_failed_request_should_be_retried() {
+        let mut mock_http_service = MockDefaultHttpService::new();
+        mock_http_service
+            .expect_get()
+            .times(2)
+            .returning(|_, _| Err(GdmError::new(ErrorKind::Network, "offline").into()));
+        let api = DefaultAssetStoreAPI::new(
+            Arc::new(mock_http_service),
+            DefaultAppConfig::new(Some(String::from("http://mock")), None, None, None, None),
+            Arc::new(MockDefaultFileService::new()),
+        );
+
+        assert!(api.get_asset_by_id("1709").await.is_err());
+        assert!(api.get_asset_by_id("1709").await.is_err());
+    }
+
     // get_assets
 
     #[tokio::test]
@@ -382,6 +807,21 @@ mod tests {
         assert_eq!(asset.asset_id, "1709");
     }
 
+    // get_categories
+
+    #[tokio::test]
+    async fn test_get_categories_should_return_addon_and_project_categories() {
+        let api = setup_test_api();
+        let categories = api.get_categories().await.unwrap();
+        let tools = Category::find(&categories, "Tools").unwrap();
+        assert_eq!(tools.kind(), "Addons");
+        assert!(
+            categories
+                .iter()
+                .any(|category| category.kind() == "Projects")
+        );
+    }
+
     // get_asset_edits_by_asset_id
 
     #[tokio::test]
@@ -443,6 +883,144 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_get_asset_by_id_and_version_should_reuse_cached_edit_list() {
+        let cache_dir = temp_dir::TempDir::new().unwrap();
+        let setup_api = |listing_requests: usize| {
+            let mut mock_http_service = MockDefaultHttpService::new();
+            mock_http_service
+                .expect_get()
+                .withf(|url, _| url == "http://mock/asset/edit")
+                .times(listing_requests)
+                .returning(|_, params| {
+                    let versions: &[&str] = match params["page"].as_str() {
+                        "0" => &["1.0.0", "1.1.0"],
+                        _ => &["1.2.0"],
+                    };
+                    let result: Vec<Value> = versions
+                        .iter()
+                        .map(|version| {
+                            serde_json::json!({
+                                "edit_id": version.replace('.', ""),
+                                "asset_id": "1234",
+                                "version_string": version
+                            })
+                        })
+                        .collect();
+                    Ok(serde_json::json!({ "result": result, "pages": 2 }))
+                });
+            mock_http_service
+                .expect_get()
+                .withf(|url, _| url.starts_with("http://mock/asset/edit/"))
+                .returning(|url, _| {
+                    let original = AssetResponse {
+                        asset_id: "1234".to_string(),
+                        ..AssetResponse::default()
+                    };
+                    let version = if url.ends_with("120") {
+                        "1.2.0"
+                    } else {
+                        "1.0.0"
+                    };
+                    Ok(serde_json::to_value(AssetEditResponse::new(
+                        "1".to_string(),
+                        "1234".to_string(),
+                        None,
+                        Some(version.to_string()),
+                        None,
+                        "accepted".to_string(),
+                        "author".to_string(),
+                        None,
+                        original,
+                    ))
+                    .unwrap())
+                });
+            DefaultAssetStoreAPI::new(
+                Arc::new(mock_http_service),
+                DefaultAppConfig::new(Some(String::from("http://mock")), None, None, None, None),
+                Arc::new(MockDefaultFileService::new()),
+            )
+            .with_edit_list_cache(Some(MetadataCache::new(
+                cache_dir.path(),
+                std::time::Duration::from_secs(60),
+            )))
+        };
+
+        let asset = setup_api(2)
+            .get_asset_by_id_and_version("1234", "1.2.0")
+            .await
+            .unwrap();
+        assert_eq!(asset.version_string, "1.2.0");
+
+        // Both pages come from the cache in the next run
+        let asset = setup_api(0)
+            .get_asset_by_id_and_version("1234", "1.2.0")
+            .await
+            .unwrap();
+        assert_eq!(asset.version_string, "1.2.0");
+
+        // A version missing from the cached listing pages through the edits again
+        assert!(
+            setup_api(2)
+                .get_asset_by_id_and_version("1234", "2.0.0")
+                .await
+                .is_err()
+        );
+    }
+
+    // get_newest_asset_edit
+
+    #[tokio::test]
+    async fn test_get_newest_asset_edit_should_return_highest_version() {
+        let mut mock_http_service = MockDefaultHttpService::new();
+        mock_http_service
+            .expect_get()
+            .withf(|url, _| url == "http://mock/asset/edit")
+            .returning(|_, _| {
+                Ok(serde_json::json!({
+                    "result": [
+                        { "edit_id": "1", "asset_id": "1234", "version_string": "1.1.0" },
+                        { "edit_id": "2", "asset_id": "1234", "version_string": "1.2.0" },
+                        { "edit_id": "3", "asset_id": "1234", "version_string": "1.0.0" }
+                    ],
+                    "pages": 1
+                }))
+            });
+        mock_http_service
+            .expect_get()
+            .withf(|url, _| url == "http://mock/asset/edit/2")
+            .returning(|_, _| {
+                let original = AssetResponse {
+                    asset_id: "1234".to_string(),
+                    version_string: "1.0.0".to_string(),
+                    download_url: "https://example.com/1.0.0.zip".to_string(),
+                    ..AssetResponse::default()
+                };
+                Ok(serde_json::to_value(AssetEditResponse::new(
+                    "2".to_string(),
+                    "1234".to_string(),
+                    None,
+                    Some("1.2.0".to_string()),
+                    None,
+                    "new".to_string(),
+                    "author".to_string(),
+                    Some("https://example.com/1.2.0.zip".to_string()),
+                    original,
+                ))
+                .unwrap())
+            });
+
+        let api = DefaultAssetStoreAPI::new(
+            Arc::new(mock_http_service),
+            DefaultAppConfig::new(Some(String::from("http://mock")), None, None, None, None),
+            Arc::new(MockDefaultFileService::new()),
+        );
+
+        let asset = api.get_newest_asset_edit("1234").await.unwrap().unwrap();
+        assert_eq!(asset.version_string, "1.2.0");
+        assert_eq!(asset.download_url, "https://example.com/1.2.0.zip");
+    }
+
     // download_asset
     #[tokio::test]
     async fn test_download_asset_should_download_to_cache_folder() {
@@ -507,9 +1085,50 @@ mod tests {
             "https://some-url-with.com/asset.zip".to_string(),
         );
 
-        let pb_task = ProgressBar::no_length();
+        let pb_task = Arc::new(crate::ui::NoopTask::default());
         let result = api.download_asset(&mock_asset, pb_task).await;
         assert!(result.is_ok());
         std::fs::remove_dir_all("tests/mocks/cache").unwrap();
     }
+
+    #[tokio::test]
+    async fn test_download_asset_should_download_same_url_once() {
+        let cache_dir = temp_dir::TempDir::new().unwrap();
+        let mut mock_http_service = MockDefaultHttpService::new();
+        mock_http_service
+            .expect_get_file()
+            .with(eq("https://some-url-with.com/asset.zip".to_string()))
+            .times(1)
+            .returning(|_url| {
+                let http_response = http::Response::builder().status(200).body("zip").unwrap();
+                Ok(reqwest::Response::from(http_response))
+            });
+        let api = DefaultAssetStoreAPI::new(
+            Arc::new(mock_http_service),
+            DefaultAppConfig::new(
+                Some(String::from("http://mock")),
+                None,
+                Some(cache_dir.path().display().to_string()),
+                None,
+                None,
+            ),
+            Arc::new(DefaultFileService),
+        );
+        let asset = AssetResponse {
+            asset_id: "1234".to_string(),
+            title: "Mock Asset".to_string(),
+            version_string: "1.1.1".to_string(),
+            download_url: "https://some-url-with.com/asset.zip".to_string(),
+            ..AssetResponse::default()
+        };
+
+        let (first, second) = tokio::join!(
+            api.download_asset(&asset, Arc::new(crate::ui::NoopTask::default())),
+            api.download_asset(&asset, Arc::new(crate::ui::NoopTask::default())),
+        );
+
+        let first = first.unwrap();
+        assert_eq!(first.file_path, second.unwrap().file_path);
+        assert_eq!(std::fs::read_to_string(first.file_path).unwrap(), "zip");
+    }
 }
@@ -1,7 +1,11 @@
-use crate::services::{DefaultPluginService, PluginService};
+use gdm_core::models::GitFetchOptions;
+use gdm_core::services::{AddOptions, DefaultPluginService, PluginService};
+use gdm_core::ui::UserInterface;
 
 use anyhow::Result;
 use clap::Args;
+use std::num::NonZeroU32;
+use std::sync::Arc;
 
 #[derive(Args, Debug)]
 #[command(
@@ -21,10 +25,46 @@ pub struct AddArgs {
     git: Option<String>,
     #[arg(long = "ref", help = "Git reference of the plugin, e.g. \"main\"")]
     reference: Option<String>,
+    #[arg(
+        long,
+        requires = "git",
+        help = "Commits of the git reference to fetch, so a commit behind the branch head can be found [default: 1]"
+    )]
+    depth: Option<NonZeroU32>,
+    #[arg(
+        long,
+        requires = "git",
+        help = "Also fetch the tags of the git repository"
+    )]
+    tags: bool,
+    #[arg(
+        long,
+        requires = "git",
+        conflicts_with = "depth",
+        help = "Fetch the whole history of the git repository"
+    )]
+    full_history: bool,
+    #[arg(
+        long,
+        help = "Add the plugin to dev_plugins, which `gdm install --no-dev` skips"
+    )]
+    dev: bool,
+    #[arg(
+        long,
+        help = "Keep the plugin disabled in project.godot, e.g. for optional editor tools"
+    )]
+    no_enable: bool,
+    #[arg(
+        long,
+        help = "Only warn when the plugin's license isn't allowed by project.licenses in gdm.json"
+    )]
+    no_verify_license: bool,
 }
 
-pub async fn handle(args: &AddArgs) -> Result<()> {
-    let plugin_service = DefaultPluginService::default();
+pub async fn handle(args: &AddArgs, ui: Arc<dyn UserInterface>) -> Result<()> {
+    let plugin_service = DefaultPluginService::default()
+        .with_ui(ui)
+        .with_verify_license(!args.no_verify_license);
     plugin_service
         .add_plugin(
             args.asset_id.clone(),
@@ -32,6 +72,15 @@ pub async fn handle(args: &AddArgs) -> Result<()> {
             args.version.clone(),
             args.git.clone(),
             args.reference.clone(),
+            AddOptions {
+                git_fetch: GitFetchOptions {
+                    depth: args.depth,
+                    tags: args.tags,
+                    full_history: args.full_history,
+                },
+                dev: args.dev,
+                no_enable: Some(args.no_enable),
+            },
         )
         .await?;
     Ok(())
@@ -0,0 +1,149 @@
+use crate::terminal::{self, Annotation, AnnotationLevel, ReportFormat};
+use gdm_core::config::{AppConfig, DefaultAppConfig};
+use gdm_core::error::{ErrorKind, GdmError};
+use gdm_core::models::Severity;
+use gdm_core::services::{
+    AdvisoryFeedLoader, DEFAULT_ADVISORY_FEED_URL, DefaultPluginService, PluginService,
+};
+
+use anyhow::{Result, bail};
+use clap::Args;
+
+#[derive(Args)]
+#[command(
+    about = "Check the installed plugins against an advisory feed of malicious, abandoned and broken assets"
+)]
+pub struct AuditArgs {
+    #[arg(
+        long,
+        env = "GDM_ADVISORY_FEED",
+        default_value = DEFAULT_ADVISORY_FEED_URL,
+        help = "URL or path of the advisory feed, e.g. --feed ./advisories.json"
+    )]
+    feed: String,
+    #[arg(
+        long,
+        default_value = "low",
+        help = "Fail when an advisory of this severity or higher matches: low, medium, high or critical"
+    )]
+    fail_on: Severity,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = ReportFormat::Text,
+        help = "Output format: text, github for GitHub Actions annotations, or csv or tsv for spreadsheets"
+    )]
+    format: ReportFormat,
+}
+
+pub async fn handle(args: &AuditArgs) -> Result<()> {
+    let feed = AdvisoryFeedLoader::default().load(&args.feed).await?;
+    let plugin_service = DefaultPluginService::default();
+    let findings = plugin_service.audit_plugins(&feed)?;
+
+    if args.format == ReportFormat::Github {
+        let config_file = DefaultAppConfig::default()
+            .get_config_file_path()
+            .to_path_buf();
+        for finding in &findings {
+            let advisory = &finding.advisory;
+            let level = if advisory.severity >= args.fail_on {
+                AnnotationLevel::Error
+            } else {
+                AnnotationLevel::Warning
+            };
+            let mut message = format!(
+                "{} {}: {} ({}, {} severity)",
+                finding.name,
+                finding.plugin.get_version(),
+                advisory.summary,
+                advisory.kind,
+                advisory.severity
+            );
+            if let Some(action) = &advisory.action {
+                message = format!("{}. Suggested action: {}", message, action);
+            }
+            println!(
+                "{}",
+                Annotation::for_plugin(
+                    level,
+                    &config_file,
+                    &finding.plugin.title,
+                    &format!("Advisory {}", advisory.id),
+                    message
+                )
+            );
+        }
+    } else if let Some(delimiter) = args.format.delimiter() {
+        println!(
+            "{}",
+            delimiter.row(&[
+                "severity", "plugin", "version", "advisory", "kind", "summary", "action",
+            ])
+        );
+        for finding in &findings {
+            let advisory = &finding.advisory;
+            println!(
+                "{}",
+                delimiter.row(&[
+                    advisory.severity.to_string().as_str(),
+                    &finding.name,
+                    &finding.plugin.get_version(),
+                    &advisory.id,
+                    &advisory.kind.to_string(),
+                    &advisory.summary,
+                    advisory.action.as_deref().unwrap_or_default(),
+                ])
+            );
+        }
+    } else if findings.is_empty() {
+        println!(
+            "{}",
+            terminal::success("No known advisories affect the installed plugins.")
+        );
+        return Ok(());
+    } else {
+        for finding in &findings {
+            let advisory = &finding.advisory;
+            let severity = format!("{: <9}", advisory.severity);
+            let severity = if advisory.severity >= Severity::High {
+                terminal::error(severity).to_string()
+            } else {
+                terminal::warning(severity).to_string()
+            };
+            println!(
+                "{} {} {}: {} ({}, {})",
+                severity,
+                finding.name,
+                finding.plugin.get_version(),
+                advisory.summary,
+                advisory.kind,
+                advisory.id
+            );
+            if let Some(action) = &advisory.action {
+                println!("{: <9} Suggested action: {}", "", action);
+            }
+        }
+        println!();
+    }
+
+    let failing = findings
+        .iter()
+        .filter(|finding| finding.advisory.severity >= args.fail_on)
+        .count();
+    if failing > 0 {
+        bail!(
+            GdmError::new(
+                ErrorKind::Advisory,
+                format!(
+                    "{} advisories of severity {} or higher affect the installed plugins.",
+                    failing, args.fail_on
+                )
+            )
+            .with_hint(
+                "Follow the suggested actions, or raise the threshold with --fail-on, e.g. --fail-on high."
+            )
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use gdm_core::api::{AssetSort, AssetStoreAPI, Category, DefaultAssetStoreAPI};
+use gdm_core::services::{DefaultPluginService, MetadataCache, PluginService};
+
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use std::sync::Arc;
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+enum BrowseSort {
+    /// Highest rated first
+    #[default]
+    Rating,
+    /// Most recently updated first
+    Updated,
+    /// Alphabetically by title
+    Name,
+}
+
+#[derive(Args)]
+#[command(
+    about = "List top-rated or recently updated addons for the project's Godot version, or the one given with --godot-version"
+)]
+pub struct BrowseArgs {
+    #[arg(long, value_enum, default_value_t, help = "Order of the addons")]
+    sort: BrowseSort,
+    #[arg(
+        long,
+        default_value_t = 1,
+        value_parser = clap::value_parser!(u64).range(1..),
+        help = "Page of the list, starting from 1"
+    )]
+    page: u64,
+    #[arg(
+        long,
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u64).range(1..=500),
+        help = "Addons per page"
+    )]
+    limit: u64,
+    #[arg(
+        long,
+        help = "Only list addons in this category, by ID or name, see gdm categories"
+    )]
+    category: Option<String>,
+}
+
+impl BrowseArgs {
+    fn sort(&self) -> AssetSort {
+        match self.sort {
+            BrowseSort::Rating => AssetSort::Rating,
+            BrowseSort::Updated => AssetSort::Updated,
+            BrowseSort::Name => AssetSort::Name,
+        }
+    }
+}
+
+pub async fn handle(
+    args: &BrowseArgs,
+    godot_version: Option<&str>,
+    metadata_cache: Option<MetadataCache>,
+) -> Result<()> {
+    let asset_store_api =
+        Arc::new(DefaultAssetStoreAPI::default().with_metadata_cache(metadata_cache));
+    let category_id = match &args.category {
+        Some(category) => {
+            let categories = asset_store_api.get_categories().await?;
+            Some(Category::find(&categories, category)?.id.clone())
+        }
+        None => None,
+    };
+    let plugin_service = DefaultPluginService::default().with_asset_store_api(asset_store_api);
+    let response = plugin_service
+        .browse_assets(
+            args.sort(),
+            (args.page - 1) as usize,
+            args.limit as usize,
+            category_id.as_deref(),
+            godot_version.unwrap_or_default(),
+        )
+        .await?;
+
+    if response.result.is_empty() {
+        println!("No addons found on page {}.", args.page);
+        return Ok(());
+    }
+
+    println!(
+        "{0: <8} {1: <40} {2: <12} {3: <12} {4: <8} Updated",
+        "ID", "Title", "Version", "Category", "Rating"
+    );
+    for asset in &response.result {
+        println!(
+            "{0: <8} {1: <40} {2: <12} {3: <12} {4: <8} {5}",
+            asset.asset_id,
+            asset.title,
+            asset.version_string,
+            asset.category,
+            asset.rating,
+            asset.modify_date
+        );
+    }
+
+    println!();
+    println!("Page {} of {}", args.page, response.pages.max(1));
+    if (args.page as usize) < response.pages {
+        println!("Use --page {} for more.", args.page + 1);
+    }
+    println!("To install an addon, use: gdm add --asset-id <asset_id>");
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use crate::terminal;
+use gdm_core::error::{ErrorKind, GdmError};
+use gdm_core::services::{ArchiveCache, ArchiveStatus};
+use gdm_core::ui::{Operation, UserInterface};
+
+use anyhow::{Result, bail};
+use clap::{Args, Subcommand};
+use std::sync::Arc;
+
+#[derive(Args)]
+#[command(about = "Inspect gdm's cache of downloaded archives")]
+pub struct CacheArgs {
+    #[command(subcommand)]
+    action: CacheAction,
+}
+
+#[derive(Subcommand)]
+enum CacheAction {
+    #[command(
+        about = "Check that cached archives are readable zips matching the checksums recorded when they were cached"
+    )]
+    Verify {
+        #[arg(
+            long,
+            help = "Remove corrupted archives, they're downloaded again by the next install"
+        )]
+        delete: bool,
+    },
+}
+
+/// Doesn't require a Godot project, the cache is shared by every project
+pub async fn handle(args: &CacheArgs, ui: Arc<dyn UserInterface>) -> Result<()> {
+    match args.action {
+        CacheAction::Verify { delete } => verify(delete, ui),
+    }
+}
+
+fn verify(delete: bool, ui: Arc<dyn UserInterface>) -> Result<()> {
+    let Some(archive_cache) = ArchiveCache::in_cache_dir() else {
+        println!("No cache directory, nothing to verify.");
+        return Ok(());
+    };
+    let reporter = ui.progress(Operation::Verify)?;
+    let task = reporter.started(&Operation::Verify, 0, 1, "cached archives", "")?;
+    task.message("Verifying cached archives");
+    let archives = archive_cache.verify(task.as_ref());
+    reporter.finish();
+    let archives = archives?;
+    let mut corrupted = 0;
+    for archive in &archives {
+        match &archive.status {
+            ArchiveStatus::Valid => {
+                println!("{} {}", terminal::success("ok"), archive.path.display())
+            }
+            ArchiveStatus::Unverified => println!(
+                "{} {} (readable, no recorded checksum)",
+                terminal::warning("ok"),
+                archive.path.display()
+            ),
+            ArchiveStatus::Corrupted(reason) if delete => {
+                archive_cache.remove(&archive.path)?;
+                println!(
+                    "{} {}: {}",
+                    terminal::warning("removed"),
+                    archive.path.display(),
+                    reason
+                );
+            }
+            ArchiveStatus::Corrupted(reason) => {
+                corrupted += 1;
+                println!(
+                    "{} {}: {}",
+                    terminal::error("corrupted"),
+                    archive.path.display(),
+                    reason
+                );
+            }
+        }
+    }
+    if corrupted > 0 {
+        bail!(
+            GdmError::new(
+                ErrorKind::ArchiveInvalid,
+                format!("{} of {} cached archives are corrupted", corrupted, archives.len())
+            )
+            .with_hint("Run gdm cache verify --delete to remove them, the next install downloads them again.")
+        );
+    }
+    println!(
+        "{}",
+        terminal::success(format!("{} cached archives verified.", archives.len()))
+    );
+    Ok(())
+}
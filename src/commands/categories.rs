@@ -0,0 +1,21 @@
+use gdm_core::api::{AssetStoreAPI, DefaultAssetStoreAPI};
+use gdm_core::services::MetadataCache;
+
+use anyhow::Result;
+use clap::Args;
+
+#[derive(Args)]
+#[command(about = "List the Asset Library's categories, for gdm search --category")]
+pub struct CategoriesArgs {}
+
+/// Doesn't require a Godot project, categories are the same for every project
+pub async fn handle(metadata_cache: Option<MetadataCache>) -> Result<()> {
+    let asset_store_api = DefaultAssetStoreAPI::default().with_metadata_cache(metadata_cache);
+    let categories = asset_store_api.get_categories().await?;
+
+    println!("{0: <6} {1: <20} Type", "ID", "Name");
+    for category in &categories {
+        println!("{}", category);
+    }
+    Ok(())
+}
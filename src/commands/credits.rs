@@ -0,0 +1,40 @@
+use crate::terminal;
+use gdm_core::config::{AppConfig, DefaultAppConfig};
+use gdm_core::services::{DefaultPluginService, PluginService};
+
+use anyhow::Result;
+use clap::Args;
+use std::path::PathBuf;
+
+#[derive(Args)]
+#[command(
+    about = "Write the title, author, version, license and source of the plugins as a Markdown attribution list"
+)]
+pub struct CreditsArgs {
+    #[arg(
+        short,
+        long,
+        help = "File to write the credits to, e.g. --output CREDITS.md. Defaults to project.credits in gdm.json, or standard output."
+    )]
+    output: Option<PathBuf>,
+}
+
+pub async fn handle(args: &CreditsArgs) -> Result<()> {
+    let plugin_service = DefaultPluginService::default();
+    let output = args
+        .output
+        .clone()
+        .or_else(|| DefaultAppConfig::default().get_credits_file_path());
+
+    let Some(output) = output else {
+        print!("{}", plugin_service.credits()?);
+        return Ok(());
+    };
+
+    plugin_service.write_credits(&output)?;
+    println!(
+        "{}",
+        terminal::success(format!("Wrote credits to {}.", output.display()))
+    );
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use gdm_core::services::{DefaultPluginService, PluginService};
+use gdm_core::ui::UserInterface;
+
+use anyhow::Result;
+use clap::Args;
+use std::sync::Arc;
+
+#[derive(Args)]
+#[command(
+    about = "Install an older version of an Asset Library plugin and pin it, so gdm update leaves it there"
+)]
+pub struct DowngradeArgs {
+    #[arg(help = "Name or alias of the plugin to downgrade, e.g. \"gut\"")]
+    name: String,
+    #[arg(help = "Older version to install, e.g. \"9.4.0\"")]
+    version: String,
+}
+
+pub async fn handle(args: &DowngradeArgs, ui: Arc<dyn UserInterface>) -> Result<()> {
+    let plugin_service = DefaultPluginService::default().with_ui(ui);
+    plugin_service
+        .downgrade_plugin(&args.name, &args.version)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,429 @@
+use crate::commands::Cli;
+use gdm_core::config::{
+    AppConfig, DefaultAppConfig, DefaultGdmConfig, DefaultGodotConfig, GdmConfig, GdmConfigFormat,
+    GdmProjectMetadata, GlobalConfig, GodotConfig,
+};
+use gdm_core::services::{
+    CacheBudget, DEFAULT_ADVISORY_FEED_URL, DEFAULT_CACHE_SIZE, DefaultFileService, MetadataCache,
+};
+use gdm_core::utils::Utils;
+
+use anyhow::Result;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Args, CommandFactory, ValueEnum};
+use indicatif::HumanBytes;
+use serde_derive::Serialize;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Output format of `gdm env`
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum EnvFormat {
+    /// One setting per line with the value and where it came from
+    #[default]
+    Text,
+    /// A JSON array of settings, e.g. to attach to a bug report
+    Json,
+}
+
+#[derive(Args)]
+#[command(
+    about = "Print the effective configuration and where each value came from, e.g. a flag, an environment variable, gdm.json or the default"
+)]
+pub struct EnvArgs {
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = EnvFormat::Text,
+        help = "Output format: text or json"
+    )]
+    format: EnvFormat,
+}
+
+/// A resolved setting, e.g. the addons folder set by addons_dir in gdm.json
+#[derive(Debug, Serialize)]
+struct Setting {
+    name: &'static str,
+    value: Option<String>,
+    source: String,
+}
+
+impl Setting {
+    fn new(name: &'static str, value: Option<String>, source: impl Into<String>) -> Setting {
+        Setting {
+            name,
+            value,
+            source: source.into(),
+        }
+    }
+
+    /// Replaces the default with a value from a configuration file
+    fn overridden_by(self, value: Option<String>, source: String) -> Setting {
+        match value {
+            Some(value) => Setting::new(self.name, Some(value), source),
+            None => self,
+        }
+    }
+}
+
+/// Doesn't require a Godot project or a configuration file, so it also helps when they're missing
+pub async fn handle(
+    args: &EnvArgs,
+    cli: &Cli,
+    app_config: &DefaultAppConfig,
+    global_config: &GlobalConfig,
+) -> Result<()> {
+    // The parsed Cli doesn't keep whether a value came from a flag or an environment variable
+    let matches = Cli::command().get_matches();
+    let settings = settings(cli, &matches, app_config, global_config)?;
+    match args.format {
+        EnvFormat::Text => {
+            let width = settings
+                .iter()
+                .map(|setting| setting.name.len())
+                .max()
+                .unwrap_or_default();
+            for setting in settings {
+                println!(
+                    "{:<width$}  {} ({})",
+                    setting.name,
+                    setting.value.as_deref().unwrap_or("not set"),
+                    setting.source,
+                    width = width
+                );
+            }
+        }
+        EnvFormat::Json => println!("{}", serde_json::to_string_pretty(&settings)?),
+    }
+    Ok(())
+}
+
+fn settings(
+    cli: &Cli,
+    matches: &ArgMatches,
+    app_config: &DefaultAppConfig,
+    global_config: &GlobalConfig,
+) -> Result<Vec<Setting>> {
+    let gdm_config = DefaultGdmConfig::new(app_config.clone(), Arc::new(DefaultFileService));
+    let config_file_path = app_config.get_config_file_path();
+    let gdm_config = if config_file_path.exists() {
+        Some(gdm_config.load()?)
+    } else {
+        None
+    };
+    let project = gdm_config
+        .as_ref()
+        .and_then(|config| config.project.as_ref());
+    let config_file = config_file_path.display().to_string();
+    let from_config_file = |key: &str| format!("{} in {}", key, config_file);
+
+    let local_config_file_path = GdmConfigFormat::local_config_file_path(config_file_path);
+    let addons_dir = gdm_config
+        .as_ref()
+        .and_then(|config| config.addons_dir.clone());
+    let metadata_cache = MetadataCache::in_cache_dir(Duration::from_secs(cli.metadata_ttl));
+    let advisory_feed = std::env::var("GDM_ADVISORY_FEED").ok();
+    let global_config_file_path = GlobalConfig::file_path();
+    let global_config_file = global_config_file_path
+        .as_ref()
+        .map(|path| path.display().to_string())
+        .unwrap_or_default();
+    let cache_size = match &cli.cache_size {
+        Some(cache_size) => CacheBudget::parse_size(cache_size)?,
+        None => DEFAULT_CACHE_SIZE,
+    };
+    let credits = project.and_then(|project| project.credits.clone());
+    let (godot_version, godot_version_source) = godot_version(cli, matches, app_config, project)?;
+
+    Ok(vec![
+        Setting::new(
+            "project_dir",
+            Some(std::env::current_dir()?.display().to_string()),
+            source(matches, "project", "GDM_PROJECT", "current directory"),
+        ),
+        Setting::new(
+            "config_file",
+            Some(config_file.clone()),
+            if config_file_path.exists() {
+                "found in the project directory"
+            } else {
+                "default, doesn't exist yet"
+            },
+        ),
+        Setting::new(
+            "local_config_file",
+            Some(local_config_file_path.display().to_string()),
+            if local_config_file_path.exists() {
+                "local overrides, found in the project directory"
+            } else {
+                "local overrides, doesn't exist"
+            },
+        ),
+        Setting::new(
+            "godot_project_file",
+            Some(
+                app_config
+                    .get_godot_project_file_path()
+                    .display()
+                    .to_string(),
+            ),
+            if app_config.get_godot_project_file_path().exists() {
+                "default"
+            } else {
+                "default, doesn't exist"
+            },
+        ),
+        Setting::new(
+            "api_base_url",
+            Some(app_config.api_base_url.clone()),
+            "default Asset Library registry",
+        ),
+        Setting::new(
+            "advisory_feed",
+            Some(
+                advisory_feed
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_ADVISORY_FEED_URL.to_string()),
+            ),
+            if advisory_feed.is_some() {
+                "GDM_ADVISORY_FEED environment variable"
+            } else {
+                "default"
+            },
+        ),
+        Setting::new(
+            "addons_dir",
+            Some(app_config.get_addon_folder_path().display().to_string()),
+            "default",
+        )
+        .overridden_by(addons_dir, from_config_file("addons_dir")),
+        Setting::new(
+            "global_config_file",
+            global_config_file_path
+                .as_ref()
+                .map(|path| path.display().to_string()),
+            match &global_config_file_path {
+                Some(path) if path.exists() => "found in the platform config directory",
+                Some(_) => "platform config directory, doesn't exist",
+                None => "no platform config directory",
+            },
+        ),
+        Setting::new(
+            "cache_dir",
+            Utils::cache_dir().map(|dir| dir.display().to_string()),
+            "platform cache directory",
+        ),
+        Setting::new(
+            "cache_size",
+            Some(HumanBytes(cache_size).to_string()),
+            source(matches, "cache_size", "GDM_CACHE_SIZE", "default"),
+        )
+        .overridden_by(
+            global_config
+                .cache_size
+                .clone()
+                .filter(|_| cli.cache_size.is_none()),
+            format!("cache_size in {}", global_config_file),
+        ),
+        Setting::new(
+            "staging_dir",
+            Some(app_config.get_cache_folder_path().display().to_string()),
+            "default, removed after installing",
+        ),
+        Setting::new(
+            "shared_cache_dir",
+            cli.shared_cache.clone(),
+            source(matches, "shared_cache", "GDM_SHARED_CACHE", "default"),
+        ),
+        Setting::new(
+            "metadata_cache_dir",
+            metadata_cache.map(|cache| cache.dir().display().to_string()),
+            if cli.metadata_ttl == 0 {
+                "disabled by --metadata-ttl 0".to_string()
+            } else {
+                "cache_dir".to_string()
+            },
+        ),
+        Setting::new(
+            "metadata_ttl",
+            Some(format!("{}s", cli.metadata_ttl)),
+            source(matches, "metadata_ttl", "GDM_METADATA_TTL", "default"),
+        ),
+        Setting::new("godot_version", godot_version, godot_version_source),
+        Setting::new(
+            "godot_binary",
+            Some(app_config.get_godot_binary_path().display().to_string()),
+            source(matches, "godot_binary", "GDM_GODOT_BINARY", "default"),
+        ),
+        Setting::new(
+            "credential_helper",
+            cli.credential_helper.clone(),
+            source(
+                matches,
+                "credential_helper",
+                "GDM_CREDENTIAL_HELPER",
+                "default",
+            ),
+        )
+        .overridden_by(
+            global_config
+                .credential_helper
+                .clone()
+                .filter(|_| cli.credential_helper.is_none()),
+            format!("credential_helper in {}", global_config_file),
+        ),
+        Setting::new(
+            "install_mode",
+            Some(app_config.get_install_mode().to_string()),
+            source(matches, "install_mode", "GDM_INSTALL_MODE", "default"),
+        )
+        .overridden_by(
+            global_config
+                .install_mode
+                .map(|install_mode| install_mode.to_string())
+                .filter(|_| cli.install_mode.is_none()),
+            format!("install_mode in {}", global_config_file),
+        ),
+        Setting::new(
+            "git_fetch",
+            Some(app_config.get_git_fetch().to_string()),
+            if global_config.git_fetch.is_default() {
+                "default".to_string()
+            } else {
+                format!("git_fetch in {}", global_config_file)
+            },
+        ),
+        // Only the names, values are often API keys
+        Setting::new(
+            "headers",
+            Some(global_config.headers.keys().cloned().collect::<Vec<_>>())
+                .filter(|names| !names.is_empty())
+                .map(|names| names.join(", ")),
+            if global_config.headers.is_empty() {
+                "default".to_string()
+            } else {
+                format!("headers in {}", global_config_file)
+            },
+        ),
+        // Only the prefixes, replacements can hold credentials
+        Setting::new(
+            "git_url_rewrites",
+            Some(
+                global_config
+                    .git_url_rewrites
+                    .keys()
+                    .cloned()
+                    .collect::<Vec<_>>(),
+            )
+            .filter(|prefixes| !prefixes.is_empty())
+            .map(|prefixes| prefixes.join(", ")),
+            if global_config.git_url_rewrites.is_empty() {
+                "default".to_string()
+            } else {
+                format!("git_url_rewrites in {}", global_config_file)
+            },
+        ),
+        Setting::new(
+            "credits_file",
+            credits.clone(),
+            if credits.is_some() {
+                from_config_file("project.credits")
+            } else {
+                "default".to_string()
+            },
+        ),
+        Setting::new(
+            "color",
+            Some(cli.color_choice(global_config).to_string()),
+            source(matches, "color", "GDM_COLOR", "default"),
+        )
+        .overridden_by(
+            global_config
+                .color
+                .map(|color| color.to_string())
+                .filter(|_| cli.color.is_none()),
+            format!("color in {}", global_config_file),
+        ),
+        Setting::new(
+            "progress",
+            cli.progress
+                .to_possible_value()
+                .map(|value| value.get_name().to_string()),
+            source(matches, "progress", "GDM_PROGRESS", "default"),
+        ),
+        Setting::new(
+            "log_file",
+            cli.log_file.as_ref().map(|path| path.display().to_string()),
+            source(matches, "log_file", "GDM_LOG_FILE", "default"),
+        ),
+        Setting::new(
+            "log_level",
+            Some(cli.log_level.to_string().to_lowercase()),
+            source(matches, "log_level", "GDM_LOG_LEVEL", "default"),
+        ),
+    ])
+}
+
+/// The version used for Asset Library queries, in the order gdm checks them
+fn godot_version(
+    cli: &Cli,
+    matches: &ArgMatches,
+    app_config: &DefaultAppConfig,
+    project: Option<&GdmProjectMetadata>,
+) -> Result<(Option<String>, String)> {
+    if cli.godot_version.is_some() {
+        return Ok((
+            cli.godot_version.clone(),
+            source(matches, "godot_version", "GDM_GODOT_VERSION", "default"),
+        ));
+    }
+    if let Some(project) = project
+        && let Some(godot_version) = project.lowest_godot_version()?
+    {
+        return Ok((
+            Some(godot_version),
+            format!(
+                "lowest version allowed by project.godot_version in {}",
+                app_config.get_config_file_path().display()
+            ),
+        ));
+    }
+    let godot_project_file_path = app_config.get_godot_project_file_path();
+    if !Path::new(godot_project_file_path).exists() {
+        return Ok((None, "no project.godot to detect it from".to_string()));
+    }
+    let godot_config = DefaultGodotConfig::new(Box::new(DefaultFileService), app_config.clone());
+    let project_file = godot_config.load()?;
+    if project_file.has_godot_version() {
+        return Ok((
+            Some(project_file.get_godot_version()?),
+            format!("detected from {}", godot_project_file_path.display()),
+        ));
+    }
+    match godot_config.get_godot_version_from_binary() {
+        Ok(godot_version) => Ok((
+            Some(godot_version),
+            format!(
+                "detected with {} --version",
+                app_config.get_godot_binary_path().display()
+            ),
+        )),
+        Err(_) => Ok((
+            Some(project_file.get_godot_version()?),
+            format!(
+                "default of config_version in {}",
+                godot_project_file_path.display()
+            ),
+        )),
+    }
+}
+
+/// e.g. `--project flag` or `GDM_PROJECT environment variable`
+fn source(matches: &ArgMatches, id: &str, env: &str, default: &str) -> String {
+    match matches.value_source(id) {
+        Some(ValueSource::CommandLine) => format!("--{} flag", id.replace('_', "-")),
+        Some(ValueSource::EnvVariable) => format!("{} environment variable", env),
+        _ => default.to_string(),
+    }
+}
@@ -0,0 +1,36 @@
+use gdm_core::services::{DefaultPluginService, PluginService};
+
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+
+/// Output format of `gdm graph`
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT, e.g. gdm graph | dot -Tsvg > plugins.svg
+    #[default]
+    Dot,
+    /// Mermaid flowchart, e.g. for a fenced mermaid block in Markdown
+    Mermaid,
+}
+
+#[derive(Args)]
+#[command(about = "Print the graph of the project's plugins and their sub-assets")]
+pub struct GraphArgs {
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = GraphFormat::Dot,
+        help = "Graph format: dot or mermaid"
+    )]
+    format: GraphFormat,
+}
+
+pub async fn handle(args: &GraphArgs) -> Result<()> {
+    let plugin_service = DefaultPluginService::default();
+    let graph = plugin_service.dependency_graph()?;
+    match args.format {
+        GraphFormat::Dot => print!("{}", graph.to_dot()),
+        GraphFormat::Mermaid => print!("{}", graph.to_mermaid()),
+    }
+    Ok(())
+}
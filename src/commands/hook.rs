@@ -0,0 +1,49 @@
+use crate::terminal;
+use gdm_core::services::{GitHooks, HookChange};
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+#[derive(Args)]
+#[command(
+    about = "Manage git hooks that validate gdm.json before committing and install plugins after pulling"
+)]
+pub struct HookArgs {
+    #[command(subcommand)]
+    action: HookAction,
+}
+
+#[derive(Subcommand)]
+enum HookAction {
+    #[command(
+        about = "Add gdm validate to the pre-commit hook and gdm install to the post-merge hook, keeping existing hooks"
+    )]
+    Install,
+    #[command(about = "Remove gdm's commands from the git hooks")]
+    Uninstall,
+}
+
+pub async fn handle(args: &HookArgs) -> Result<()> {
+    let hooks = GitHooks::discover(&std::env::current_dir()?)?;
+    let changes = match args.action {
+        HookAction::Install => hooks.install()?,
+        HookAction::Uninstall => hooks.uninstall()?,
+    };
+    for change in &changes {
+        match change {
+            HookChange::Created(path) => println!("Created {}", path.display()),
+            HookChange::Updated(path) => println!("Updated {}", path.display()),
+            HookChange::Removed(path) => println!("Removed {}", path.display()),
+            HookChange::Unchanged(path) if path.exists() => {
+                println!("{} is up to date", path.display())
+            }
+            HookChange::Unchanged(_) => {}
+        }
+    }
+    let message = match args.action {
+        HookAction::Install => "Git hooks installed.",
+        HookAction::Uninstall => "Git hooks uninstalled.",
+    };
+    println!("{}", terminal::success(message));
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use crate::terminal;
+use gdm_core::api::{AssetStoreAPI, DefaultAssetStoreAPI};
+use gdm_core::config::DefaultAppConfig;
+use gdm_core::error::{ErrorKind, GdmError};
+use gdm_core::models::PluginSource;
+use gdm_core::services::{DefaultPluginService, MetadataCache, PluginService};
+
+use anyhow::{Result, bail};
+use clap::Args;
+use std::process::{Command, Stdio};
+
+#[derive(Args)]
+#[command(
+    about = "Show the details and description of an installed plugin or an Asset Library asset"
+)]
+pub struct InfoArgs {
+    #[arg(
+        required_unless_present = "asset_id",
+        conflicts_with = "asset_id",
+        help = "Name or alias of an installed plugin, e.g. \"gut\""
+    )]
+    name: Option<String>,
+    #[arg(long, help = "Asset ID of any Asset Library asset, e.g. \"1709\"")]
+    asset_id: Option<String>,
+    #[arg(
+        long,
+        help = "Open the Asset Library page, or the repository of a git plugin, in a browser"
+    )]
+    open: bool,
+}
+
+pub async fn handle(args: &InfoArgs, metadata_cache: Option<MetadataCache>) -> Result<()> {
+    let plugin_service = DefaultPluginService::default();
+    let installed = match &args.name {
+        Some(name) => plugin_service
+            .select_plugins_by_names(std::slice::from_ref(name))?
+            .into_iter()
+            .next(),
+        None => None,
+    };
+    let source = match (&args.asset_id, &installed) {
+        (Some(asset_id), _) => Some(PluginSource::AssetLibrary {
+            asset_id: asset_id.clone(),
+        }),
+        (None, Some((_, plugin))) => plugin.source.clone(),
+        (None, None) => None,
+    };
+    let app_config = DefaultAppConfig::default();
+    if args.open
+        && let Some(source) = &source
+    {
+        return open_in_browser(&source.web_url(&app_config.api_base_url));
+    }
+    let asset_id = match (&source, &installed) {
+        (Some(PluginSource::AssetLibrary { asset_id }), _) => asset_id.clone(),
+        (_, Some(_)) => {
+            bail!(
+                GdmError::new(
+                    ErrorKind::Usage,
+                    format!(
+                        "Plugin {} isn't from the Asset Library.",
+                        args.name.as_deref().unwrap_or_default()
+                    )
+                )
+                .with_plugin(args.name.as_deref().unwrap_or_default())
+                .with_hint("Use gdm info --open to open its repository in a browser.")
+            )
+        }
+        _ => bail!(GdmError::new(
+            ErrorKind::Usage,
+            "No plugin name or asset ID given"
+        )),
+    };
+
+    let asset_store_api = DefaultAssetStoreAPI::default().with_metadata_cache(metadata_cache);
+    let asset = asset_store_api.get_asset_by_id(&asset_id).await?;
+
+    println!("{} {}", asset.title, asset.version_string);
+    println!("Asset ID: {}", asset.asset_id);
+    println!("Godot Ver.: {}", asset.godot_version);
+    println!("License: {}", asset.cost);
+    println!("Rating: {}", asset.rating);
+    println!("Last Updated: {}", asset.modify_date);
+    if let Some(source) = &source {
+        println!("Asset URL: {}", source.web_url(&app_config.api_base_url));
+    }
+    if let Some((key, plugin)) = &installed {
+        println!("Installed: {} as \"{}\"", plugin.version, key);
+    }
+
+    let description =
+        terminal::render_description(&asset.description, terminal::description_width());
+    if !description.is_empty() {
+        println!();
+        println!("{}", description);
+    }
+    Ok(())
+}
+
+/// Opens `url` with $BROWSER if it's set, otherwise with the system's opener
+fn open_in_browser(url: &str) -> Result<()> {
+    let mut command = match std::env::var("BROWSER") {
+        Ok(browser) if !browser.trim().is_empty() => Command::new(browser.trim()),
+        _ if cfg!(target_os = "macos") => Command::new("open"),
+        _ if cfg!(windows) => {
+            let mut command = Command::new("cmd");
+            command.args(["/C", "start", ""]);
+            command
+        }
+        _ => Command::new("xdg-open"),
+    };
+    let status = command
+        .arg(url)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    match status {
+        Ok(status) if status.success() => {
+            println!("Opened {}", url);
+            Ok(())
+        }
+        _ => bail!(
+            GdmError::new(
+                ErrorKind::NotFound,
+                format!("Failed to open {} in a browser", url)
+            )
+            .with_hint("Open the page yourself, or set BROWSER to your browser's command.")
+        ),
+    }
+}
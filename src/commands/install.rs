@@ -1,14 +1,147 @@
-use crate::services::{DefaultPluginService, PluginService};
+use gdm_core::config::{
+    DefaultGodotConfig, DefaultWorkspaceConfig, DefaultWorkspaceConfigMetadata, GodotConfig,
+    WorkspaceConfig,
+};
+use gdm_core::services::{DefaultPluginService, PluginService};
+use gdm_core::ui::UserInterface;
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use clap::Args;
+use std::path::Path;
+use std::process::Command;
+use std::sync::Arc;
 
 #[derive(Args)]
-#[command(about = "Install all plugins with versions listed in the configuration file.")]
-pub struct InstallArgs {}
+#[command(
+    about = "Install all plugins with versions listed in the configuration file, or only the given ones."
+)]
+pub struct InstallArgs {
+    #[arg(
+        conflicts_with = "workspace",
+        help = "Names, aliases or asset IDs of the plugins to install, e.g. \"gut\". Installs all plugins if omitted"
+    )]
+    names: Vec<String>,
+    #[arg(
+        long,
+        help = "Install plugins into every project listed in gdm-workspace.json"
+    )]
+    pub workspace: bool,
+    #[arg(
+        long,
+        help = "Skip plugins listed in dev_plugins, e.g. for export builds"
+    )]
+    pub no_dev: bool,
+    #[arg(
+        long,
+        help = "Fail unless every plugin matches the checksum and content hash recorded in gdm.json, e.g. in CI"
+    )]
+    pub locked: bool,
+    #[arg(
+        long,
+        help = "Run godot --headless --import afterwards to regenerate .import and .uid files. Needs Godot 4.2 or newer"
+    )]
+    pub import: bool,
+}
+
+pub async fn handle(args: &InstallArgs, ui: Arc<dyn UserInterface>) -> Result<()> {
+    let plugin_service = DefaultPluginService::default().with_ui(ui.clone());
+    plugin_service
+        .install_plugins(&args.names, !args.no_dev, args.locked)
+        .await?;
+    if args.import {
+        import_resources(ui.as_ref())?;
+    }
+    Ok(())
+}
+
+/// Lets Godot import the installed plugins, so their resources work when the editor is opened
+pub fn import_resources(ui: &dyn UserInterface) -> Result<()> {
+    ui.status("Importing resources with Godot...");
+    DefaultGodotConfig::default().import_resources()?;
+    ui.success("Imported resources");
+    Ok(())
+}
+
+/// Installs each workspace project in its own gdm process, sharing downloads between them
+pub async fn handle_workspace(
+    args: &InstallArgs,
+    quiet: bool,
+    global_args: &[String],
+) -> Result<()> {
+    let workspace_config = DefaultWorkspaceConfig::default();
+    let workspace = workspace_config.load()?;
+
+    if workspace.projects.is_empty() {
+        if !quiet {
+            println!("No projects listed in the workspace.");
+        }
+        return Ok(());
+    }
+
+    let shared_cache_folder =
+        std::env::temp_dir().join(format!("gdm-workspace-{}", std::process::id()));
+    let result = install_workspace_projects(
+        args,
+        quiet,
+        &workspace_config,
+        &workspace,
+        &shared_cache_folder,
+        global_args,
+    );
+
+    if shared_cache_folder.exists() {
+        std::fs::remove_dir_all(&shared_cache_folder)?;
+    }
+    result
+}
+
+fn install_workspace_projects(
+    args: &InstallArgs,
+    quiet: bool,
+    workspace_config: &DefaultWorkspaceConfig,
+    workspace: &DefaultWorkspaceConfigMetadata,
+    shared_cache_folder: &Path,
+    global_args: &[String],
+) -> Result<()> {
+    let gdm_binary = std::env::current_exe()?;
+
+    for project in &workspace.projects {
+        let project_dir = Path::new(project);
+        let project_config = workspace_config.sync_project(workspace, project_dir)?;
+
+        if project_config.plugins.is_empty() {
+            if !quiet {
+                println!("No plugins to install for {}", project_dir.display());
+            }
+            continue;
+        }
+
+        if !quiet {
+            println!("Installing plugins for {}", project_dir.display());
+        }
+
+        let mut command = Command::new(&gdm_binary);
+        command
+            .arg("--project")
+            .arg(project_dir)
+            .arg("install")
+            .env("GDM_SHARED_CACHE", shared_cache_folder);
+        if args.no_dev {
+            command.arg("--no-dev");
+        }
+        if args.locked {
+            command.arg("--locked");
+        }
+        if args.import {
+            command.arg("--import");
+        }
+        command.args(global_args);
+
+        let status = command.status()?;
+        if !status.success() {
+            bail!("Failed to install plugins for {}", project_dir.display())
+        }
+    }
 
-pub async fn handle() -> Result<()> {
-    let plugin_service = DefaultPluginService::default();
-    plugin_service.install_all_plugins().await?;
     Ok(())
 }
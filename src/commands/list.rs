@@ -0,0 +1,170 @@
+use crate::terminal::{Delimiter, TableFormat, porcelain};
+use gdm_core::models::{PluginListing, PluginSource};
+use gdm_core::services::{DefaultPluginService, PluginService};
+
+use anyhow::Result;
+use clap::Args;
+
+#[derive(Args)]
+#[command(
+    about = "List plugins in the configuration file and plugins enabled in project.godot that aren't managed by gdm"
+)]
+pub struct ListArgs {
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = TableFormat::Text,
+        help = "Output format: text, or csv or tsv for spreadsheets"
+    )]
+    format: TableFormat,
+    #[arg(
+        long,
+        conflicts_with = "format",
+        help = "Print tab-separated records for scripts whose fields don't change between releases"
+    )]
+    porcelain: bool,
+}
+
+pub async fn handle(args: &ListArgs) -> Result<()> {
+    let plugin_service = DefaultPluginService::default();
+    let listing = plugin_service.list_plugins()?;
+
+    if args.porcelain {
+        print_porcelain(&listing);
+        return Ok(());
+    }
+    if let Some(delimiter) = args.format.delimiter() {
+        print_delimited(&listing, delimiter);
+        return Ok(());
+    }
+
+    if listing.is_empty() {
+        println!("No plugins installed.");
+        return Ok(());
+    }
+
+    println!("{0: <40} {1: <20} {2: <20}", "Plugin", "Version", "Source");
+
+    for (name, plugin) in &listing.plugins {
+        let mut source = plugin
+            .source
+            .as_ref()
+            .map(|source| source.to_string())
+            .unwrap_or_default();
+        if let Some(commit) = plugin.short_commit() {
+            source = format!("{} @ {}", source, commit);
+        }
+        let mut name = name.to_string();
+        if let Some(alias) = &plugin.alias {
+            name = format!("{} (alias: {})", name, alias);
+        }
+        if plugin.dev {
+            name = format!("{} (dev)", name);
+        }
+        if !plugin.enabled {
+            name = format!("{} (disabled)", name);
+        }
+        println!(
+            "{0: <40} {1: <20} {2: <20}",
+            name,
+            plugin.get_version(),
+            source
+        );
+    }
+
+    for path in &listing.unmanaged {
+        println!("{0: <40} {1: <20} {2: <20}", path, "-", "unmanaged");
+    }
+
+    if !listing.unmanaged.is_empty() {
+        println!();
+        println!(
+            "Unmanaged plugins are enabled in project.godot but not listed in gdm.json. gdm keeps them enabled."
+        );
+    }
+    Ok(())
+}
+
+/// One row per plugin, with the alias, commit and flags in their own columns.
+/// Unmanaged plugins have their plugin.cfg path as name and `unmanaged` as source.
+fn print_delimited(listing: &PluginListing, delimiter: Delimiter) {
+    println!(
+        "{}",
+        delimiter.row(&[
+            "name", "alias", "title", "version", "source", "commit", "license", "enabled", "dev",
+        ])
+    );
+    for (name, plugin) in &listing.plugins {
+        println!(
+            "{}",
+            delimiter.row(&[
+                name.as_str(),
+                plugin.alias.as_deref().unwrap_or_default(),
+                &plugin.title,
+                &plugin.version,
+                &plugin
+                    .source
+                    .as_ref()
+                    .map(|source| source.to_string())
+                    .unwrap_or_default(),
+                plugin.commit.as_deref().unwrap_or_default(),
+                plugin.license.as_deref().unwrap_or_default(),
+                &plugin.enabled.to_string(),
+                &plugin.dev.to_string(),
+            ])
+        );
+    }
+    for path in &listing.unmanaged {
+        println!(
+            "{}",
+            delimiter.row(&[
+                path.as_str(),
+                "",
+                "",
+                "",
+                "unmanaged",
+                "",
+                "",
+                "true",
+                "false"
+            ])
+        );
+    }
+}
+
+/// `plugin` records with the name, version, source type (`asset-library` or `git`), asset ID
+/// or URL, git reference, commit, enabled, dev, alias, title and license, then `unmanaged`
+/// records with the plugin.cfg path
+fn print_porcelain(listing: &PluginListing) {
+    for (name, plugin) in &listing.plugins {
+        let (source_type, source, reference) = match &plugin.source {
+            Some(PluginSource::AssetLibrary { asset_id }) => {
+                ("asset-library", asset_id.as_str(), "")
+            }
+            Some(PluginSource::Git { url, reference }) => ("git", url.as_str(), reference.as_str()),
+            None => ("", "", ""),
+        };
+        println!(
+            "{}",
+            porcelain::line(
+                "plugin",
+                &[
+                    name.as_str(),
+                    &plugin.version,
+                    source_type,
+                    source,
+                    reference,
+                    plugin.commit.as_deref().unwrap_or_default(),
+                    &plugin.enabled.to_string(),
+                    &plugin.dev.to_string(),
+                    plugin.alias.as_deref().unwrap_or_default(),
+                    &plugin.title,
+                    plugin.license.as_deref().unwrap_or_default(),
+                ]
+            )
+        );
+    }
+    for path in &listing.unmanaged {
+        println!("{}", porcelain::line("unmanaged", &[path]));
+    }
+}
@@ -1,21 +1,62 @@
 mod add;
+mod audit;
+mod browse;
+mod cache;
+mod categories;
+mod credits;
+mod downgrade;
+mod env;
+mod graph;
+mod hook;
+mod info;
 mod install;
+mod list;
 mod outdated;
+mod pack;
 mod remove;
+mod restore_config;
 mod search;
+mod stats;
+mod unpack;
 mod update;
+mod validate;
+mod watch;
+mod why;
 
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
-use clap::{Parser, Subcommand};
-use clap_verbosity_flag::{OffLevel, Verbosity};
+use clap::{Parser, Subcommand, ValueEnum};
+use clap_verbosity_flag::{LogLevel, Verbosity, VerbosityFilter};
+use indicatif::HumanBytes;
+use tracing::debug;
 
 use crate::{
     commands::{
-        add::AddArgs, install::InstallArgs, outdated::OutdatedArgs, remove::RemoveArgs,
-        search::SearchArgs, update::UpdateArgs,
+        add::AddArgs, audit::AuditArgs, browse::BrowseArgs, cache::CacheArgs,
+        categories::CategoriesArgs, credits::CreditsArgs, downgrade::DowngradeArgs, env::EnvArgs,
+        graph::GraphArgs, hook::HookArgs, info::InfoArgs, install::InstallArgs, list::ListArgs,
+        outdated::OutdatedArgs, pack::PackArgs, remove::RemoveArgs,
+        restore_config::RestoreConfigArgs, search::SearchArgs, stats::StatsArgs,
+        unpack::UnpackArgs, update::UpdateArgs, validate::ValidateArgs, watch::WatchArgs,
+        why::WhyArgs,
     },
-    config::{DefaultGodotConfig, GodotConfig},
+    terminal::{OutputFormat, ProgressFormat, Terminal},
+};
+use gdm_core::{
+    config::{
+        AppConfig, ColorChoice, DefaultAppConfig, DefaultGdmConfig, DefaultGodotConfig, GdmConfig,
+        GdmConfigFormat, GlobalConfig, GodotConfig, InstallMode,
+    },
+    error::{ErrorKind, GdmError},
+    services::{
+        CacheBudget, ConfigBackups, DEFAULT_CACHE_SIZE, DEFAULT_CONFIG_BACKUPS,
+        DEFAULT_METADATA_TTL, DefaultFileService, GodotEditor, MetadataCache,
+    },
+    ui::UserInterface,
 };
 
 #[derive(Parser)]
@@ -25,42 +66,561 @@ pub struct Cli {
     pub command: Commands,
 
     #[command(flatten)]
-    pub verbosity: Verbosity<OffLevel>,
+    pub verbosity: Verbosity<QuietLevel>,
+
+    #[arg(
+        long,
+        global = true,
+        env = "GDM_GODOT_VERSION",
+        help = "Use this Godot version instead of the one detected from project.godot, e.g. --godot-version 4.5"
+    )]
+    pub godot_version: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        env = "GDM_GODOT_BINARY",
+        help = "Godot binary used to detect the engine version when project.godot doesn't list it, e.g. --godot-binary /usr/bin/godot"
+    )]
+    pub godot_binary: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        env = "GDM_PROJECT",
+        help = "Run in this Godot project directory instead of the current one, e.g. --project ../my-game"
+    )]
+    pub project: Option<PathBuf>,
+
+    #[arg(
+        short = 'y',
+        long,
+        global = true,
+        help = "Answer yes to confirmation prompts, e.g. before removing a plugin. Prompts are skipped when stdin isn't a terminal"
+    )]
+    pub yes: bool,
+
+    #[arg(
+        long,
+        global = true,
+        env = "GDM_FORCE",
+        value_parser = clap::builder::FalseyValueParser::new(),
+        help = "Change the project even if a Godot editor has it open, which may overwrite the changes when the editor saves"
+    )]
+    pub force: bool,
+
+    #[arg(
+        long,
+        global = true,
+        env = "GDM_COLOR",
+        help = "When to color the output: auto, always or never. auto colors terminals unless NO_COLOR is set [default: auto]"
+    )]
+    pub color: Option<ColorChoice>,
+
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value_t = ProgressFormat::Bars,
+        env = "GDM_PROGRESS",
+        help = "How to report progress: bars, json lines on stderr for CI, or none"
+    )]
+    pub progress: ProgressFormat,
+
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value_t = OutputFormat::Text,
+        env = "GDM_OUTPUT_FORMAT",
+        help = "How errors are printed on stderr: text, or json for one object with the code, message, plugin and hint"
+    )]
+    pub output_format: OutputFormat,
+
+    #[arg(
+        long,
+        global = true,
+        env = "GDM_LOG_FILE",
+        help = "Append logs to this file, independent of -v, e.g. --log-file gdm.log"
+    )]
+    pub log_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        global = true,
+        default_value = "debug",
+        env = "GDM_LOG_LEVEL",
+        help = "Level of the logs written to --log-file: error, warn, info, debug or trace"
+    )]
+    pub log_level: tracing::Level,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Print how long metadata, downloads, extraction, moving files and config writes took per plugin"
+    )]
+    pub timings: bool,
+
+    #[arg(
+        long,
+        global = true,
+        env = "GDM_NO_UPDATE_CHECK",
+        value_parser = clap::builder::FalseyValueParser::new(),
+        help = "Don't check once a day whether a newer gdm is available"
+    )]
+    pub no_update_check: bool,
+
+    #[arg(
+        long,
+        global = true,
+        env = "GDM_FROZEN",
+        value_parser = clap::builder::FalseyValueParser::new(),
+        help = "Fail instead of resolving or downloading anything: install only plugins whose checksum and content hash are in gdm.json, from the archive cache. For release builds"
+    )]
+    pub frozen: bool,
+
+    #[arg(
+        long,
+        global = true,
+        default_value_t = DEFAULT_METADATA_TTL.as_secs(),
+        env = "GDM_METADATA_TTL",
+        help = "Seconds outdated and search reuse Asset Library responses cached in gdm's cache directory, and all commands reuse the version lists of assets, 0 to always query"
+    )]
+    pub metadata_ttl: u64,
+
+    #[arg(
+        long,
+        global = true,
+        env = "GDM_CREDENTIAL_HELPER",
+        help = "Command that prints a token for a host, run with the host as the last argument, e.g. --credential-helper \"vault-token gdm\". Used for the Asset Library and git over HTTPS"
+    )]
+    pub credential_helper: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        env = "GDM_CACHE_SIZE",
+        help = "Largest size of gdm's cache directory, e.g. --cache-size 2GB. The least recently used archives and Asset Library responses are removed after each command to stay below it [default: 1GB]"
+    )]
+    pub cache_size: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        env = "GDM_INSTALL_MODE",
+        help = "How plugins get into the project: copy, or link to link their folders to a store in gdm's cache directory shared by all projects, with junctions on Windows. Falls back to copying where links aren't supported [default: copy]"
+    )]
+    pub install_mode: Option<InstallMode>,
+
+    #[arg(long, global = true, hide = true, env = "GDM_SHARED_CACHE")]
+    pub shared_cache: Option<String>,
+}
+
+impl Cli {
+    /// Checks for a newer gdm only in interactive runs, not in CI, scripts, with --quiet or --frozen
+    pub fn should_check_for_updates(&self) -> bool {
+        !self.no_update_check
+            && !self.frozen
+            && !self.is_quiet()
+            && std::env::var_os("CI").is_none()
+            && std::io::stderr().is_terminal()
+    }
+
+    /// Cache for the read-only commands, None when --metadata-ttl is 0
+    pub fn metadata_cache(&self) -> Option<MetadataCache> {
+        MetadataCache::in_cache_dir(Duration::from_secs(self.metadata_ttl))
+    }
+
+    /// Budget of the cache directory from --cache-size, the global config or the default
+    pub fn cache_budget(&self, global_config: &GlobalConfig) -> Result<Option<CacheBudget>> {
+        let max_size = match self
+            .cache_size
+            .as_ref()
+            .or(global_config.cache_size.as_ref())
+        {
+            Some(cache_size) => CacheBudget::parse_size(cache_size)?,
+            None => DEFAULT_CACHE_SIZE,
+        };
+        Ok(CacheBudget::in_cache_dir(max_size))
+    }
+
+    /// When to color the output, from --color, GDM_COLOR, the global config or auto
+    pub fn color_choice(&self, global_config: &GlobalConfig) -> ColorChoice {
+        self.color.or(global_config.color).unwrap_or_default()
+    }
+
+    /// Whether `-q` silenced gdm's own output, not only its logging
+    pub fn is_quiet(&self) -> bool {
+        self.verbosity.is_present() && self.verbosity.is_silent()
+    }
+
+    /// Global options the gdm process of each workspace project runs with
+    pub fn workspace_args(&self, godot_binary: Option<&str>) -> Vec<String> {
+        let flags = [
+            (self.is_quiet(), "--quiet"),
+            (self.yes, "--yes"),
+            (self.force, "--force"),
+            (self.frozen, "--frozen"),
+            (self.timings, "--timings"),
+            (self.no_update_check, "--no-update-check"),
+        ];
+        let mut args: Vec<String> = flags
+            .into_iter()
+            .filter(|(set, _)| *set)
+            .map(|(_, flag)| flag.to_string())
+            .collect();
+
+        let options = [
+            ("--godot-version", self.godot_version.clone()),
+            ("--godot-binary", godot_binary.map(str::to_string)),
+            ("--color", self.color.map(|color| color.to_string())),
+            ("--progress", value_name(self.progress)),
+            ("--output-format", value_name(self.output_format)),
+            ("--metadata-ttl", Some(self.metadata_ttl.to_string())),
+            ("--credential-helper", self.credential_helper.clone()),
+            ("--cache-size", self.cache_size.clone()),
+            (
+                "--install-mode",
+                self.install_mode.map(|mode| mode.to_string()),
+            ),
+        ];
+        for (option, value) in options {
+            if let Some(value) = value {
+                args.extend([option.to_string(), value]);
+            }
+        }
+        args
+    }
+}
+
+/// Name clap parses back into `value`
+fn value_name(value: impl ValueEnum) -> Option<String> {
+    value
+        .to_possible_value()
+        .map(|possible_value| possible_value.get_name().to_string())
+}
+
+/// Logging is off by default, so `-q/--quiet` hides progress bars and status messages instead
+#[derive(Clone, Copy, Debug, Default)]
+pub struct QuietLevel;
+
+impl LogLevel for QuietLevel {
+    fn default_filter() -> VerbosityFilter {
+        VerbosityFilter::Off
+    }
+
+    fn quiet_help() -> Option<&'static str> {
+        Some("Only print errors and results, without progress bars or status messages")
+    }
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
     Add(AddArgs),
+    Audit(AuditArgs),
+    Browse(BrowseArgs),
+    Cache(CacheArgs),
+    Categories(CategoriesArgs),
+    Credits(CreditsArgs),
+    Downgrade(DowngradeArgs),
+    Env(EnvArgs),
+    Graph(GraphArgs),
+    Hook(HookArgs),
+    Info(InfoArgs),
+    #[command(visible_alias = "i")]
     Install(InstallArgs),
+    #[command(visible_alias = "ls")]
+    List(ListArgs),
     Outdated(OutdatedArgs),
+    Pack(PackArgs),
+    #[command(visible_alias = "rm")]
     Remove(RemoveArgs),
+    RestoreConfig(RestoreConfigArgs),
+    #[command(visible_alias = "s")]
     Search(SearchArgs),
+    Stats(StatsArgs),
+    Unpack(UnpackArgs),
+    #[command(visible_alias = "up")]
     Update(UpdateArgs),
+    Validate(ValidateArgs),
+    Watch(WatchArgs),
+    Why(WhyArgs),
 }
 
-pub async fn handle(command: &Commands) -> Result<()> {
-    DefaultGodotConfig::default().validate_project_file()?;
+impl Commands {
+    /// Commands that may write project.godot or gdm.json, which are backed up before they run.
+    /// The gdm process of each project in a workspace install backs up its own files.
+    fn modifies_config(&self) -> bool {
+        match self {
+            Commands::Install(install_args) => !install_args.workspace,
+            command => matches!(
+                command,
+                Commands::Add(_)
+                    | Commands::Downgrade(_)
+                    | Commands::Remove(_)
+                    | Commands::Update(_)
+                    | Commands::Watch(_)
+            ),
+        }
+    }
 
-    match command {
+    /// Commands that load the project's configuration file before they run. Reporting the
+    /// configuration, managing gdm's cache and listing the Asset Library's categories don't need
+    /// a Godot project or a configuration file, unpacking restores the configuration file
+    /// into a fresh clone, and validating reads it without a Godot project. A workspace install
+    /// runs gdm in each of its projects, and restoring doesn't load the configuration file, which
+    /// may be the broken one.
+    fn loads_project(&self) -> bool {
+        match self {
+            Commands::Env(_)
+            | Commands::Cache(_)
+            | Commands::Categories(_)
+            | Commands::RestoreConfig(_)
+            | Commands::Unpack(_)
+            | Commands::Validate(_) => false,
+            Commands::Install(install_args) => !install_args.workspace,
+            _ => true,
+        }
+    }
+
+    /// Commands that write project.godot or the addons folder, which the Godot editor may
+    /// overwrite or reimport while it has the project open
+    fn modifies_project(&self) -> bool {
+        match self {
+            Commands::RestoreConfig(restore_config_args) => restore_config_args.to.is_some(),
+            command => command.modifies_config(),
+        }
+    }
+}
+
+pub async fn handle(cli: &Cli) -> Result<()> {
+    let godot_binary = resolve_godot_binary(cli.godot_binary.as_deref())?;
+    if let Some(project) = &cli.project {
+        if !project.is_dir() {
+            bail!(
+                GdmError::new(
+                    ErrorKind::NotFound,
+                    format!("Failed to open project directory {}", project.display())
+                )
+                .with_hint("Check the path given with --project or GDM_PROJECT.")
+            );
+        }
+        std::env::set_current_dir(project)
+            .with_context(|| format!("Failed to open project directory {}", project.display()))?;
+    }
+
+    let global_config = GlobalConfig::load()?;
+    let cache_budget = cli.cache_budget(&global_config)?;
+    let app_config = DefaultAppConfig::default();
+    let config_file_path = GdmConfigFormat::resolve_config_file_path(
+        app_config.get_config_file_path(),
+        &DefaultFileService,
+    )?;
+    let app_config = app_config
+        .with_config_file_path(&config_file_path)
+        .with_godot_version(cli.godot_version.clone())
+        .with_godot_binary_path(godot_binary.clone())
+        .with_credential_helper(
+            cli.credential_helper
+                .clone()
+                .or(global_config.credential_helper.clone()),
+        )
+        .with_install_mode(
+            cli.install_mode
+                .or(global_config.install_mode)
+                .unwrap_or_default(),
+        )
+        .with_git_fetch(global_config.git_fetch)
+        .with_headers(global_config.headers.clone())
+        .with_git_url_rewrites(global_config.git_url_rewrites.clone())
+        .with_metadata_ttl(Duration::from_secs(cli.metadata_ttl))
+        .with_shared_cache_folder_path(cli.shared_cache.clone())
+        .with_assume_yes(cli.yes)
+        .with_quiet(cli.is_quiet())
+        .with_frozen(cli.frozen);
+    let ui: Arc<dyn UserInterface> = Arc::new(Terminal::new(cli.is_quiet(), cli.yes, cli.progress));
+
+    if cli.command.modifies_project()
+        && !cli.force
+        && let Some(editor) = GodotEditor::find_running(&std::env::current_dir()?)
+    {
+        bail!(
+            GdmError::new(
+                ErrorKind::Conflict,
+                format!(
+                    "A Godot editor has this project open (process {}: {})",
+                    editor.pid, editor.command
+                )
+            )
+            .with_hint("Close the editor and run the command again, or pass --force to change the project anyway.")
+        );
+    }
+
+    let config_backups = ConfigBackups::in_cache_dir(
+        &std::env::current_dir()?,
+        global_config
+            .config_backups
+            .unwrap_or(DEFAULT_CONFIG_BACKUPS),
+    );
+    if cli.command.loads_project() {
+        if cli.command.modifies_config()
+            && let Some(config_backups) = &config_backups
+            && let Err(e) = config_backups.backup(&[
+                app_config.get_godot_project_file_path(),
+                app_config.get_config_file_path(),
+            ])
+        {
+            ui.warning(&format!("Failed to back up the configuration: {:#}", e));
+        }
+
+        let gdm_config = DefaultGdmConfig::new(app_config.clone(), Arc::new(DefaultFileService));
+        if let Some(from_version) = gdm_config.migrate()? {
+            ui.status(&DefaultGdmConfig::migration_message(
+                &config_file_path,
+                from_version,
+            ));
+        }
+        let gdm_config = gdm_config.load()?;
+        DefaultAppConfig::init(app_config.clone().with_project_settings(&gdm_config)?)?;
+        let godot_config = DefaultGodotConfig::default();
+        godot_config.validate_project_file()?;
+        if let Some(project) = &gdm_config.project
+            && let Some(warning) = godot_config.check_godot_version_range(project)?
+        {
+            ui.warning(&warning);
+        }
+    } else {
+        DefaultAppConfig::init(app_config.clone())?;
+    }
+
+    match &cli.command {
         Commands::Add(add_args) => {
-            add::handle(add_args).await?;
+            add::handle(add_args, ui).await?;
+        }
+        Commands::Audit(audit_args) => {
+            audit::handle(audit_args).await?;
+        }
+        Commands::Browse(browse_args) => {
+            browse::handle(
+                browse_args,
+                cli.godot_version.as_deref(),
+                cli.metadata_cache(),
+            )
+            .await?;
+        }
+        Commands::Cache(cache_args) => {
+            cache::handle(cache_args, ui).await?;
+        }
+        Commands::Categories(_) => {
+            categories::handle(cli.metadata_cache()).await?;
+        }
+        Commands::Credits(credits_args) => {
+            credits::handle(credits_args).await?;
+        }
+        Commands::Downgrade(downgrade_args) => {
+            downgrade::handle(downgrade_args, ui).await?;
+        }
+        Commands::Graph(graph_args) => {
+            graph::handle(graph_args).await?;
         }
-        Commands::Install(_) => {
-            install::handle().await?;
+        Commands::Hook(hook_args) => {
+            hook::handle(hook_args).await?;
         }
-        Commands::Outdated(_) => {
-            outdated::handle().await?;
+        Commands::Info(info_args) => {
+            info::handle(info_args, cli.metadata_cache()).await?;
+        }
+        Commands::Env(env_args) => {
+            env::handle(env_args, cli, &app_config, &global_config).await?;
+        }
+        Commands::Install(install_args) if install_args.workspace => {
+            install::handle_workspace(
+                install_args,
+                cli.is_quiet(),
+                &cli.workspace_args(godot_binary.as_deref()),
+            )
+            .await?;
+        }
+        Commands::Install(install_args) => {
+            install::handle(install_args, ui).await?;
+        }
+        Commands::List(list_args) => {
+            list::handle(list_args).await?;
+        }
+        Commands::Outdated(outdated_args) => {
+            outdated::handle(outdated_args, ui, cli.metadata_cache()).await?;
+        }
+        Commands::Pack(pack_args) => {
+            pack::handle(pack_args, ui).await?;
         }
         Commands::Remove(remove_args) => {
-            remove::handle(remove_args).await?;
+            remove::handle(remove_args, ui).await?;
+        }
+        Commands::RestoreConfig(restore_config_args) => {
+            restore_config::handle(restore_config_args, config_backups, ui).await?;
         }
         Commands::Search(search_args) => {
-            search::handle(search_args).await?;
+            search::handle(
+                search_args,
+                cli.godot_version.as_deref(),
+                cli.metadata_cache(),
+            )
+            .await?;
+        }
+        Commands::Stats(_) => {
+            stats::handle().await?;
+        }
+        Commands::Unpack(unpack_args) => {
+            unpack::handle(unpack_args, ui).await?;
+        }
+        Commands::Update(update_args) => {
+            update::handle(update_args, ui).await?;
+        }
+        Commands::Validate(validate_args) => {
+            validate::handle(validate_args).await?;
+        }
+        Commands::Watch(watch_args) => {
+            watch::handle(watch_args, ui).await?;
         }
-        Commands::Update(_) => {
-            update::handle().await?;
+        Commands::Why(why_args) => {
+            why::handle(why_args).await?;
         }
     }
 
+    if let Some(cache_budget) = cache_budget {
+        evict_cache(cli, &cache_budget);
+    }
+
     Ok(())
 }
+
+/// Keeps the cache within its budget, listing what was removed with -v
+fn evict_cache(cli: &Cli, cache_budget: &CacheBudget) {
+    let evicted = match cache_budget.evict() {
+        Ok(evicted) => evicted,
+        Err(e) => {
+            debug!("Failed to evict cached files: {:#}", e);
+            return;
+        }
+    };
+    if cli.verbosity.is_present() && !cli.is_quiet() {
+        for entry in evicted {
+            eprintln!(
+                "Evicted {} ({}) from the cache",
+                entry.path.display(),
+                HumanBytes(entry.size)
+            );
+        }
+    }
+}
+
+/// Resolves a relative Godot binary path like `./bin/godot` before switching to the --project directory,
+/// so it keeps pointing to the same file. Plain names like `godot` are still looked up from PATH.
+fn resolve_godot_binary(godot_binary: Option<&str>) -> Result<Option<String>> {
+    match godot_binary {
+        Some(path) if PathBuf::from(path).components().count() > 1 => {
+            let absolute_path = std::path::absolute(path)?;
+            Ok(Some(absolute_path.to_string_lossy().to_string()))
+        }
+        other => Ok(other.map(str::to_string)),
+    }
+}
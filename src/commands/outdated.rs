@@ -1,14 +1,435 @@
-use crate::services::{DefaultPluginService, PluginService};
+use crate::terminal::{self, Annotation, AnnotationLevel, Delimiter, ReportFormat, porcelain};
+use gdm_core::api::DefaultAssetStoreAPI;
+use gdm_core::config::{AppConfig, DefaultAppConfig, DefaultGdmConfig, GdmConfig};
+use gdm_core::error::{ErrorKind, GdmError};
+use gdm_core::models::{
+    OutdatedPlugin, Plugin, PluginSource, UpdateLevel, UpdateStatus, VersionChange,
+};
+use gdm_core::services::{DefaultPluginService, MetadataCache, PluginService};
+use gdm_core::ui::UserInterface;
 
-use anyhow::Result;
-use clap::Args;
+use anyhow::{Result, bail};
+use clap::{Args, ValueEnum};
+use std::sync::Arc;
+
+/// Which plugins make `gdm outdated --fail-on` exit with an error
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum FailOn {
+    /// Plugins that `gdm update` would update
+    Update,
+    /// Also plugins whose newer version is blocked by a pin or the update level
+    Blocked,
+}
 
 #[derive(Args)]
 #[command(about = "Show outdated plugins")]
-pub struct OutdatedArgs {}
+pub struct OutdatedArgs {
+    #[arg(
+        long,
+        help = "Include pre-release versions, e.g. \"9.2.0-rc1\", for all plugins"
+    )]
+    pre: bool,
+    #[arg(
+        long,
+        group = "level",
+        help = "Only count patch updates as wanted, like gdm update --patch"
+    )]
+    patch: bool,
+    #[arg(
+        long,
+        group = "level",
+        help = "Count patch and minor updates as wanted, like gdm update. This is the default"
+    )]
+    minor: bool,
+    #[arg(
+        long,
+        group = "level",
+        help = "Also count major updates as wanted, like gdm update --major"
+    )]
+    major: bool,
+    #[arg(
+        long,
+        value_enum,
+        help = "Exit with GDM-403 when plugins have updates: update for updates gdm update would install, blocked to include blocked ones"
+    )]
+    fail_on: Option<FailOn>,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = ReportFormat::Text,
+        help = "Output format: text, github for GitHub Actions annotations, or csv or tsv for spreadsheets"
+    )]
+    format: ReportFormat,
+    #[arg(
+        long,
+        conflicts_with = "format",
+        help = "Print tab-separated records for scripts whose fields don't change between releases"
+    )]
+    porcelain: bool,
+}
+
+impl OutdatedArgs {
+    fn level(&self) -> UpdateLevel {
+        if self.patch {
+            UpdateLevel::Patch
+        } else if self.major {
+            UpdateLevel::Major
+        } else {
+            UpdateLevel::Minor
+        }
+    }
+}
+
+pub async fn handle(
+    args: &OutdatedArgs,
+    ui: Arc<dyn UserInterface>,
+    metadata_cache: Option<MetadataCache>,
+) -> Result<()> {
+    let asset_store_api = DefaultAssetStoreAPI::default().with_metadata_cache(metadata_cache);
+    let plugin_service = DefaultPluginService::default()
+        .with_ui(ui.clone())
+        .with_asset_store_api(Arc::new(asset_store_api));
+    let level = args.level();
+    let outdated_plugins = plugin_service
+        .check_outdated_plugins(args.pre, level)
+        .await?;
+
+    if args.porcelain {
+        print_porcelain(&outdated_plugins)?;
+        return fail_on(&outdated_plugins, args.fail_on);
+    }
+    match (args.format, args.format.delimiter()) {
+        (ReportFormat::Github, _) => {
+            for plugin in &outdated_plugins {
+                if let Some(annotation) = annotation(plugin, level) {
+                    println!("{}", annotation);
+                }
+            }
+        }
+        (_, Some(delimiter)) => print_delimited(&outdated_plugins, level, delimiter),
+        _ => print_table(&outdated_plugins, level, ui.as_ref()),
+    }
+    fail_on(&outdated_plugins, args.fail_on)
+}
+
+/// A `plugin` record per plugin with its name in gdm.json, status (`up-to-date`,
+/// `update-available`, `pre-release`, `blocked` or `removed`), current, wanted and latest
+/// version, and the part of the version that changes (`major`, `minor`, `patch`, `pre-release`)
+fn print_porcelain(outdated_plugins: &[OutdatedPlugin]) -> Result<()> {
+    let plugins = DefaultGdmConfig::default().get_plugins()?;
+    for plugin in outdated_plugins {
+        let name = plugins
+            .iter()
+            .find(|(_, installed)| installed.source == plugin.current.source)
+            .map_or(plugin.current.title.as_str(), |(name, _)| name.as_str());
+        let status = match plugin.status {
+            UpdateStatus::UpToDate => "up-to-date",
+            UpdateStatus::UpdateAvailable => "update-available",
+            UpdateStatus::PreRelease => "pre-release",
+            UpdateStatus::Blocked => "blocked",
+            UpdateStatus::Delisted => "removed",
+        };
+        println!(
+            "{}",
+            porcelain::line(
+                "plugin",
+                &[
+                    name,
+                    status,
+                    &version_label(&plugin.current),
+                    &version_label(&plugin.wanted),
+                    &version_label(&plugin.latest),
+                    change_name(plugin.change_to(&plugin.latest)),
+                ]
+            )
+        );
+    }
+    Ok(())
+}
+
+fn change_name(change: Option<VersionChange>) -> &'static str {
+    match change {
+        Some(VersionChange::Major) => "major",
+        Some(VersionChange::Minor) => "minor",
+        Some(VersionChange::Patch) => "patch",
+        Some(VersionChange::PreRelease) => "pre-release",
+        None => "",
+    }
+}
+
+/// One row per plugin with the status and the part of the version that changes, e.g.
+/// `Gut,9.2.0,9.3.0,10.0.0,update available,minor`
+fn print_delimited(outdated_plugins: &[OutdatedPlugin], level: UpdateLevel, delimiter: Delimiter) {
+    println!(
+        "{}",
+        delimiter.row(&["plugin", "current", "wanted", "latest", "status", "change"])
+    );
+    for plugin in outdated_plugins {
+        let status = match plugin.status {
+            UpdateStatus::UpdateAvailable => "update available".to_string(),
+            UpdateStatus::PreRelease => "pre-release".to_string(),
+            UpdateStatus::Delisted => "removed from the Asset Library".to_string(),
+            UpdateStatus::Blocked => blocked_reason(plugin, level),
+            UpdateStatus::UpToDate => "up to date".to_string(),
+        };
+        let change = change_name(plugin.change_to(&plugin.latest));
+        println!(
+            "{}",
+            delimiter.row(&[
+                plugin.current.title.as_str(),
+                &version_label(&plugin.current),
+                &version_label(&plugin.wanted),
+                &version_label(&plugin.latest),
+                &status,
+                change,
+            ])
+        );
+    }
+}
+
+fn print_table(outdated_plugins: &[OutdatedPlugin], level: UpdateLevel, ui: &dyn UserInterface) {
+    let title_width = column_width(
+        "Plugin",
+        outdated_plugins.iter().map(|p| p.current.title.as_str()),
+        MAX_TITLE_WIDTH,
+    );
+    let current_labels: Vec<String> = outdated_plugins
+        .iter()
+        .map(|plugin| version_label(&plugin.current))
+        .collect();
+    let wanted_labels: Vec<String> = outdated_plugins
+        .iter()
+        .map(|plugin| version_label(&plugin.wanted))
+        .collect();
+    let latest_labels: Vec<String> = outdated_plugins
+        .iter()
+        .map(|plugin| version_label(&plugin.latest))
+        .collect();
+    let current_width = column_width(
+        "Current",
+        current_labels.iter().map(String::as_str),
+        MAX_VERSION_WIDTH,
+    );
+    let wanted_width = column_width(
+        "Wanted",
+        wanted_labels.iter().map(String::as_str),
+        MAX_VERSION_WIDTH,
+    );
+    let latest_width = column_width(
+        "Latest",
+        latest_labels.iter().map(String::as_str),
+        MAX_VERSION_WIDTH,
+    );
 
-pub async fn handle() -> Result<()> {
-    let plugin_service = DefaultPluginService::default();
-    plugin_service.check_outdated_plugins().await?;
+    println!(
+        "{} {} {} {}",
+        cell("Plugin", title_width),
+        cell("Current", current_width),
+        cell("Wanted", wanted_width),
+        cell("Latest", latest_width)
+    );
+    for (((plugin, current), wanted), latest) in outdated_plugins
+        .iter()
+        .zip(&current_labels)
+        .zip(&wanted_labels)
+        .zip(&latest_labels)
+    {
+        let status = match plugin.status {
+            UpdateStatus::UpdateAvailable => "(update available)".to_string(),
+            UpdateStatus::PreRelease => "(pre-release, use --pre to include)".to_string(),
+            UpdateStatus::Delisted => "(removed from the Asset Library)".to_string(),
+            UpdateStatus::Blocked => format!("({})", blocked_reason(plugin, level)),
+            UpdateStatus::UpToDate => String::new(),
+        };
+        let styled = |label: &str, width: usize, target: &Plugin| match (
+            plugin.status,
+            plugin.change_to(target),
+        ) {
+            (
+                UpdateStatus::UpdateAvailable | UpdateStatus::PreRelease | UpdateStatus::Blocked,
+                Some(change),
+            ) => highlight_change(&cell(label, width), change),
+            _ => cell(label, width),
+        };
+        let line = format!(
+            "{} {} {} {} {}",
+            cell(&plugin.current.title, title_width),
+            cell(current, current_width),
+            styled(wanted, wanted_width, &plugin.wanted),
+            styled(latest, latest_width, &plugin.latest),
+            status
+        );
+        println!("{}", line.trim_end());
+    }
+    println!();
+
+    let has_status = |status| {
+        outdated_plugins
+            .iter()
+            .any(|plugin| plugin.status == status)
+    };
+    if has_status(UpdateStatus::UpdateAvailable) {
+        let command = match level {
+            UpdateLevel::Minor => "gdm update".to_string(),
+            UpdateLevel::Patch => "gdm update --patch".to_string(),
+            UpdateLevel::Major => "gdm update --major".to_string(),
+        };
+        ui.status(&format!("To update plugins, use: {}", command));
+    } else if has_status(UpdateStatus::Blocked) {
+        println!(
+            "{}",
+            terminal::success("All plugins are up to date within their pins and update level.")
+        );
+    } else {
+        println!("{}", terminal::success("All plugins are up to date."));
+    }
+}
+
+/// Why `gdm update` leaves a blocked plugin at its version
+fn blocked_reason(plugin: &OutdatedPlugin, level: UpdateLevel) -> String {
+    if plugin.current.pinned {
+        "pinned by gdm downgrade".to_string()
+    } else {
+        format!("blocked, use gdm update {}", level.next_flag())
+    }
+}
+
+/// Exits with GDM-403 when a plugin has an update `fail_on` counts
+fn fail_on(outdated_plugins: &[OutdatedPlugin], fail_on: Option<FailOn>) -> Result<()> {
+    let Some(fail_on) = fail_on else {
+        return Ok(());
+    };
+    let count = |status| {
+        outdated_plugins
+            .iter()
+            .filter(|plugin| plugin.status == status)
+            .count()
+    };
+    let updates = count(UpdateStatus::UpdateAvailable);
+    let blocked = count(UpdateStatus::Blocked);
+    if updates > 0 {
+        bail!(GdmError::new(
+            ErrorKind::Outdated,
+            format!("{} plugins can be updated with gdm update.", updates)
+        ));
+    }
+    if fail_on == FailOn::Blocked && blocked > 0 {
+        bail!(
+            GdmError::new(
+                ErrorKind::Outdated,
+                format!(
+                    "{} plugins have newer versions blocked by a pin or the update level.",
+                    blocked
+                )
+            )
+            .with_hint("Update them with gdm update <name> or gdm update --major.")
+        );
+    }
     Ok(())
 }
+
+/// Titles longer than this are truncated with `…`
+const MAX_TITLE_WIDTH: usize = 40;
+/// Versions longer than this, e.g. long branch names, are truncated with `…`
+const MAX_VERSION_WIDTH: usize = 24;
+
+/// Width of the widest value or header, at most `max`
+fn column_width<'a>(header: &str, values: impl Iterator<Item = &'a str>, max: usize) -> usize {
+    values
+        .map(console::measure_text_width)
+        .chain([header.len()])
+        .max()
+        .unwrap_or(0)
+        .min(max)
+}
+
+/// `value` padded to `width` columns, or truncated with `…` when it's wider
+fn cell(value: &str, width: usize) -> String {
+    console::pad_str(value, width, console::Alignment::Left, Some("…")).into_owned()
+}
+
+/// Styles a version from the part that changed, e.g. `3.0` of `9.3.0` for a minor update.
+/// Labels without enough dots, e.g. `main@1a2b3c4`, are styled entirely.
+fn highlight_change(label: &str, change: VersionChange) -> String {
+    let start = match change.component_index() {
+        0 => 0,
+        index => label
+            .match_indices('.')
+            .nth(index - 1)
+            .map_or(0, |(dot, _)| dot + 1),
+    };
+    let (unchanged, changed) = label.split_at(start);
+    let (changed, padding) = changed.split_at(changed.trim_end().len());
+    format!(
+        "{}{}{}",
+        unchanged,
+        terminal::version_change(changed, change),
+        padding
+    )
+}
+
+/// Git plugins show the tag or branch they follow and the commit, e.g. `v9.2.0` or `main@1a2b3c4`
+fn version_label(plugin: &Plugin) -> String {
+    match (&plugin.source, plugin.short_commit()) {
+        (Some(PluginSource::Git { reference, .. }), Some(commit)) => {
+            format!("{}@{}", reference, commit)
+        }
+        (Some(PluginSource::Git { reference, .. }), None) => reference.clone(),
+        _ => plugin.get_version(),
+    }
+}
+
+/// Warning for an available update or a removed asset, notice for a pre-release or a blocked update
+fn annotation(plugin: &OutdatedPlugin, level: UpdateLevel) -> Option<Annotation> {
+    let current = &plugin.current;
+    let (level, title, message) = match plugin.status {
+        UpdateStatus::UpdateAvailable => (
+            AnnotationLevel::Warning,
+            "Outdated plugin",
+            format!(
+                "{} {} is outdated, {} is available. Update it with gdm update.",
+                current.title,
+                version_label(current),
+                version_label(&plugin.wanted)
+            ),
+        ),
+        UpdateStatus::PreRelease => (
+            AnnotationLevel::Notice,
+            "Pre-release available",
+            format!(
+                "{} {} is available as a pre-release of {}.",
+                current.title,
+                version_label(&plugin.latest),
+                version_label(current)
+            ),
+        ),
+        UpdateStatus::Delisted => (
+            AnnotationLevel::Warning,
+            "Removed asset",
+            format!(
+                "{} {} was removed from the Asset Library.",
+                current.title,
+                version_label(current)
+            ),
+        ),
+        UpdateStatus::Blocked => (
+            AnnotationLevel::Notice,
+            "Update blocked",
+            format!(
+                "{} {} has a newer version {}, {}.",
+                current.title,
+                version_label(current),
+                version_label(&plugin.latest),
+                blocked_reason(plugin, level)
+            ),
+        ),
+        UpdateStatus::UpToDate => return None,
+    };
+    Some(Annotation::for_plugin(
+        level,
+        DefaultAppConfig::default().get_config_file_path(),
+        &current.title,
+        title,
+        message,
+    ))
+}
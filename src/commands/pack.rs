@@ -0,0 +1,50 @@
+use gdm_core::config::{AppConfig, DefaultAppConfig};
+use gdm_core::error::{ErrorKind, GdmError};
+use gdm_core::services::{ArchiveCache, Bundle};
+use gdm_core::ui::UserInterface;
+
+use anyhow::{Result, bail};
+use clap::Args;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[derive(Args)]
+#[command(
+    about = "Write gdm.json and the cached archives of its Asset Library plugins to one zip, restored with gdm unpack"
+)]
+pub struct PackArgs {
+    #[arg(
+        default_value = "gdm-bundle.zip",
+        help = "Bundle to write, e.g. gdm pack release/plugins.zip"
+    )]
+    output: PathBuf,
+}
+
+pub async fn handle(args: &PackArgs, ui: Arc<dyn UserInterface>) -> Result<()> {
+    let Some(archive_cache) = ArchiveCache::in_cache_dir() else {
+        bail!(
+            GdmError::new(
+                ErrorKind::NotFound,
+                "No cache directory to pack the archives of plugins from"
+            )
+            .with_hint("Set XDG_CACHE_HOME or HOME, and run gdm install to cache the archives.")
+        );
+    };
+    let config_file_path = DefaultAppConfig::default()
+        .get_config_file_path()
+        .to_path_buf();
+    let contents = Bundle::new(archive_cache).pack(&config_file_path, &args.output)?;
+    if !contents.git_plugins.is_empty() {
+        ui.warning(&format!(
+            "Git plugins aren't packed and are fetched from their repository: {}",
+            contents.git_plugins.join(", ")
+        ));
+    }
+    ui.success(&format!(
+        "Packed {} and {} plugin archives into {}",
+        config_file_path.display(),
+        contents.archives.len(),
+        args.output.display()
+    ));
+    Ok(())
+}
@@ -1,19 +1,34 @@
-use crate::services::{DefaultPluginService, PluginService};
+use gdm_core::services::{DefaultPluginService, PluginService};
+use gdm_core::ui::UserInterface;
 
 use anyhow::Result;
 use clap::Args;
+use std::sync::Arc;
 
 #[derive(Args)]
 #[command(
-    about = "Remove a plugin by name. Use the exact name as listed in the configuration file, e.g. \"gut\""
+    about = "Remove a plugin by name. Use the exact name or alias as listed in the configuration file, e.g. \"gut\", or the asset ID with --asset-id"
 )]
 pub struct RemoveArgs {
-    #[arg(help = "Name of the plugin to remove, e.g. \"gut\"")]
-    name: String,
+    #[arg(
+        required_unless_present = "asset_id",
+        help = "Name or alias of the plugin to remove, e.g. \"gut\""
+    )]
+    name: Option<String>,
+    #[arg(
+        long,
+        conflicts_with = "name",
+        help = "Asset Library ID of the plugin to remove, e.g. --asset-id 1709"
+    )]
+    asset_id: Option<String>,
 }
 
-pub async fn handle(args: &RemoveArgs) -> Result<()> {
-    let plugin_service = DefaultPluginService::default();
-    plugin_service.remove_plugin_by_name(&args.name).await?;
+pub async fn handle(args: &RemoveArgs, ui: Arc<dyn UserInterface>) -> Result<()> {
+    let plugin_service = DefaultPluginService::default().with_ui(ui);
+    match (&args.name, &args.asset_id) {
+        (_, Some(asset_id)) => plugin_service.remove_plugin_by_asset_id(asset_id).await?,
+        (Some(name), None) => plugin_service.remove_plugin_by_name(name).await?,
+        (None, None) => unreachable!("clap requires a name or --asset-id"),
+    };
     Ok(())
 }
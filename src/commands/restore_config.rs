@@ -0,0 +1,77 @@
+use gdm_core::error::{ErrorKind, GdmError};
+use gdm_core::services::ConfigBackups;
+use gdm_core::ui::UserInterface;
+
+use anyhow::{Result, bail};
+use clap::Args;
+use std::sync::Arc;
+
+#[derive(Args)]
+#[command(
+    about = "List or restore the backups of project.godot and gdm.json taken before commands change them. Plugin files aren't touched"
+)]
+pub struct RestoreConfigArgs {
+    #[arg(long, help = "List the backups of this project, newest first")]
+    list: bool,
+    #[arg(
+        long,
+        value_name = "BACKUP",
+        required_unless_present = "list",
+        conflicts_with = "list",
+        help = "Restore a backup from --list, or latest, e.g. --to 20261015T142233.123Z"
+    )]
+    pub to: Option<String>,
+}
+
+/// Doesn't load gdm.json or project.godot, so a broken file can be restored
+pub async fn handle(
+    args: &RestoreConfigArgs,
+    config_backups: Option<ConfigBackups>,
+    ui: Arc<dyn UserInterface>,
+) -> Result<()> {
+    let Some(config_backups) = config_backups else {
+        bail!(
+            GdmError::new(
+                ErrorKind::NotFound,
+                "Configuration backups are turned off or there's no cache directory"
+            )
+            .with_hint(
+                "Remove config_backups: 0 from the global config, or set XDG_CACHE_HOME or HOME."
+            )
+        );
+    };
+    let backups = config_backups.list()?;
+    let Some(to) = &args.to else {
+        if backups.is_empty() {
+            println!("No configuration backups of this project.");
+        }
+        for backup in backups {
+            println!("{}  {}", backup.id, backup.files.join(", "));
+        }
+        return Ok(());
+    };
+
+    let id = match to.as_str() {
+        "latest" => match backups.first() {
+            Some(backup) => backup.id.clone(),
+            None => bail!(GdmError::new(
+                ErrorKind::NotFound,
+                "No configuration backups of this project"
+            )),
+        },
+        id => id.to_string(),
+    };
+    let (backup, previous) = config_backups.restore(&id, &std::env::current_dir()?)?;
+    ui.success(&format!(
+        "Restored {} from backup {}",
+        backup.files.join(" and "),
+        backup.id
+    ));
+    if let Some(previous) = previous {
+        ui.status(&format!(
+            "The replaced files were backed up, to undo use: gdm restore-config --to {}",
+            previous.id
+        ));
+    }
+    Ok(())
+}
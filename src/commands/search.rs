@@ -1,7 +1,14 @@
-use crate::services::{DefaultPluginService, PluginService};
+use crate::terminal;
+use gdm_core::api::{AssetListItem, AssetStoreAPI, Category, DefaultAssetStoreAPI};
+use gdm_core::config::{DefaultAppConfig, DefaultGdmConfig, GdmConfig};
+use gdm_core::models::{Plugin, PluginSource};
+use gdm_core::services::{AssetIndex, DefaultPluginService, MetadataCache, PluginService};
+use gdm_core::utils::Utils;
 
 use anyhow::Result;
 use clap::Args;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 #[derive(Args)]
 #[command(
@@ -12,19 +19,137 @@ pub struct SearchArgs {
     name: String,
     #[arg(
         long,
-        help = "Specify the Godot version if it can't be determined from the project, e.g. --godot-version 4.5"
+        help = "Search assets seen in earlier searches and installs instead of the Asset Library"
     )]
-    godot_version: Option<String>,
+    offline: bool,
+    #[arg(
+        long,
+        conflicts_with = "offline",
+        help = "Only search in this category, by ID or name, see gdm categories"
+    )]
+    category: Option<String>,
 }
 
-pub async fn handle(args: &SearchArgs) -> Result<()> {
-    let plugin_service = DefaultPluginService::default();
-    plugin_service
-        .search_assets_by_name_or_version(
-            &args.name,
-            args.godot_version.as_ref().unwrap_or(&"".into()),
-        )
-        .await?;
+pub async fn handle(
+    args: &SearchArgs,
+    godot_version: Option<&str>,
+    metadata_cache: Option<MetadataCache>,
+) -> Result<()> {
+    let name = &args.name;
+    let assets = if args.offline {
+        search_offline(name)
+    } else {
+        let asset_store_api =
+            Arc::new(DefaultAssetStoreAPI::default().with_metadata_cache(metadata_cache));
+        let category_id = match &args.category {
+            Some(category) => {
+                let categories = asset_store_api.get_categories().await?;
+                Some(Category::find(&categories, category)?.id.clone())
+            }
+            None => None,
+        };
+        let plugin_service = DefaultPluginService::default().with_asset_store_api(asset_store_api);
+        let mut asset_list_response = plugin_service
+            .get_asset_list_response_by_name_or_version(
+                name,
+                godot_version.unwrap_or_default(),
+                category_id.as_deref(),
+            )
+            .await?;
+        rank_by_similarity(&mut asset_list_response.result, name);
+        asset_list_response.result
+    };
+
+    let installed = installed_by_asset_id()?;
+    let known = if args.offline { "known " } else { "" };
+    match assets.len() {
+        0 => println!("No {}assets found matching \"{}\"", known, name),
+        1 => println!("Found 1 {}asset matching \"{}\":", known, name),
+        n => println!("Found {} {}assets matching \"{}\":", n, known, name),
+    }
 
+    for asset in &assets {
+        println!();
+        println!("{}", highlight_title(asset, name));
+        if let Some((key, plugin)) = installed.get(&asset.asset_id) {
+            println!("{}", installed_note(asset, key, plugin));
+        }
+        println!();
+    }
+
+    if args.offline && assets.is_empty() {
+        println!(
+            "Only assets seen in earlier searches and installs are known offline, search without --offline to query the Asset Library"
+        );
+    } else if let [asset] = assets.as_slice() {
+        println!(
+            "To install the plugin, use: gdm add \"{}\" or gdm add --asset-id {}",
+            asset.title, asset.asset_id
+        );
+    } else {
+        println!(
+            "To install a plugin, use: gdm add --asset-id <asset_id> or narrow down your search"
+        );
+    }
     Ok(())
 }
+
+/// Assets of the Asset Library in the index of the cache directory, the details are from when
+/// each asset was last seen
+fn search_offline(name: &str) -> Vec<AssetListItem> {
+    AssetIndex::in_cache_dir(&DefaultAppConfig::default().api_base_url)
+        .map(|asset_index| asset_index.search(name))
+        .unwrap_or_default()
+}
+
+/// Asset Library plugins of gdm.json by asset ID, with their keys
+fn installed_by_asset_id() -> Result<HashMap<String, (String, Plugin)>> {
+    Ok(DefaultGdmConfig::default()
+        .get_plugins()?
+        .into_iter()
+        .filter_map(|(key, plugin)| match &plugin.source {
+            Some(PluginSource::AssetLibrary { asset_id }) => {
+                Some((asset_id.clone(), (key, plugin)))
+            }
+            _ => None,
+        })
+        .collect())
+}
+
+/// e.g. `Installed: 9.1.0 as "gut", 9.2.0 is available`
+fn installed_note(asset: &AssetListItem, key: &str, plugin: &Plugin) -> String {
+    let installed = format!("Installed: {} as \"{}\"", plugin.version, key);
+    if Utils::parse_semantic_version(&plugin.version)
+        < Utils::parse_semantic_version(&asset.version_string)
+    {
+        format!("{}, {} is available", installed, asset.version_string)
+    } else {
+        format!("{} (latest)", terminal::success(installed))
+    }
+}
+
+/// Orders results by how closely their titles match the query, the Asset Library's own order
+/// breaks ties
+fn rank_by_similarity(assets: &mut [AssetListItem], query: &str) {
+    assets.sort_by(|a, b| {
+        Utils::fuzzy_match_score(query, &b.title)
+            .total_cmp(&Utils::fuzzy_match_score(query, &a.title))
+    });
+}
+
+/// The asset's details with the parts of its title that match the query highlighted
+fn highlight_title(asset: &AssetListItem, query: &str) -> String {
+    let mut title = String::new();
+    let mut end = 0;
+    for range in Utils::fuzzy_match_ranges(query, &asset.title) {
+        title.push_str(&asset.title[end..range.start]);
+        title.push_str(&terminal::highlight(&asset.title[range.clone()]).to_string());
+        end = range.end;
+    }
+    title.push_str(&asset.title[end..]);
+    asset.to_string().replacen(
+        &format!("Title: {}", asset.title),
+        &format!("Title: {}", title),
+        1,
+    )
+}
@@ -0,0 +1,71 @@
+use gdm_core::models::ProjectStats;
+use gdm_core::services::{DefaultPluginService, PluginService};
+
+use anyhow::Result;
+use clap::Args;
+use indicatif::HumanBytes;
+
+#[derive(Args)]
+#[command(
+    about = "Summarize the managed plugins: sources, disk size, licenses and the least recently updated asset"
+)]
+pub struct StatsArgs {}
+
+pub async fn handle() -> Result<()> {
+    let plugin_service = DefaultPluginService::default();
+    let stats = plugin_service.project_stats()?;
+
+    let sources = stats
+        .count_by_source()
+        .into_iter()
+        .map(|(source, count)| format!("{} {}", count, source))
+        .collect::<Vec<_>>()
+        .join(", ");
+    println!("Plugins: {} ({})", stats.plugins.len(), sources);
+    println!();
+
+    print_sizes(&stats);
+    println!();
+
+    println!("Licenses:");
+    let mut licenses: Vec<(String, usize)> = stats.count_by_license().into_iter().collect();
+    licenses.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then_with(|| a.cmp(b)));
+    for (license, count) in licenses {
+        println!("  {0: <20} {1}", license, count);
+    }
+    println!();
+
+    match stats.least_recently_updated() {
+        Some(oldest) => println!(
+            "Least recently updated: {}, {} {} (updated {})",
+            oldest.name,
+            oldest.plugin.title,
+            oldest.plugin.get_version(),
+            oldest.modify_date.as_deref().unwrap_or_default()
+        ),
+        None => println!(
+            "Least recently updated: unknown, run gdm outdated to fetch the update dates of the assets"
+        ),
+    }
+    Ok(())
+}
+
+/// Plugins from the largest to the smallest, and the total
+fn print_sizes(stats: &ProjectStats) {
+    println!("{0: <40} {1: >12}", "Plugin", "Size");
+    let mut plugins: Vec<_> = stats.plugins.iter().collect();
+    plugins.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+    for plugin in plugins {
+        let size = if plugin.size == 0 {
+            "not installed".to_string()
+        } else {
+            HumanBytes(plugin.size).to_string()
+        };
+        println!("{0: <40} {1: >12}", plugin.name, size);
+    }
+    println!(
+        "{0: <40} {1: >12}",
+        "Total",
+        HumanBytes(stats.total_size()).to_string()
+    );
+}
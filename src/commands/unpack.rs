@@ -0,0 +1,49 @@
+use gdm_core::config::{AppConfig, DefaultAppConfig};
+use gdm_core::error::{ErrorKind, GdmError};
+use gdm_core::services::{ArchiveCache, Bundle};
+use gdm_core::ui::UserInterface;
+
+use anyhow::{Result, bail};
+use clap::Args;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[derive(Args)]
+#[command(
+    about = "Restore gdm.json and the plugin archives of a bundle written by gdm pack, to install them with gdm install --frozen"
+)]
+pub struct UnpackArgs {
+    #[arg(help = "Bundle to restore, e.g. gdm unpack gdm-bundle.zip")]
+    bundle: PathBuf,
+}
+
+/// Doesn't require gdm.json, which is restored from the bundle into a fresh clone
+pub async fn handle(args: &UnpackArgs, ui: Arc<dyn UserInterface>) -> Result<()> {
+    let Some(archive_cache) = ArchiveCache::in_cache_dir() else {
+        bail!(
+            GdmError::new(
+                ErrorKind::NotFound,
+                "No cache directory to restore the archives of plugins to"
+            )
+            .with_hint("Set XDG_CACHE_HOME or HOME.")
+        );
+    };
+    let (config_file_path, contents) = Bundle::new(archive_cache).unpack(
+        &args.bundle,
+        DefaultAppConfig::default().get_config_file_path(),
+    )?;
+    if !contents.git_plugins.is_empty() {
+        ui.warning(&format!(
+            "Git plugins aren't in the bundle and are fetched from their repository: {}",
+            contents.git_plugins.join(", ")
+        ));
+    }
+    ui.success(&format!(
+        "Restored {} and {} plugin archives from {}",
+        config_file_path.display(),
+        contents.archives.len(),
+        args.bundle.display()
+    ));
+    ui.status("To install the plugins without downloading them, use: gdm install --frozen");
+    Ok(())
+}
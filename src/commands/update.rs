@@ -1,14 +1,76 @@
-use crate::services::{DefaultPluginService, PluginService};
+use crate::commands::install::import_resources;
+
+use gdm_core::models::UpdateLevel;
+use gdm_core::services::{DefaultPluginService, PluginService};
+use gdm_core::ui::UserInterface;
 
 use anyhow::Result;
 use clap::Args;
+use std::sync::Arc;
 
 #[derive(Args)]
-#[command(about = "Update all outdated plugins")]
-pub struct UpdateArgs {}
+#[command(about = "Update all outdated plugins, or only the given ones")]
+pub struct UpdateArgs {
+    #[arg(
+        help = "Names or aliases of the plugins to update, e.g. \"gut\". Updates all plugins if omitted"
+    )]
+    names: Vec<String>,
+    #[arg(
+        long,
+        help = "Include pre-release versions, e.g. \"9.2.0-rc1\", for all plugins"
+    )]
+    pre: bool,
+    #[arg(
+        long,
+        group = "level",
+        help = "Only take patch updates, e.g. 9.2.0 to 9.2.1"
+    )]
+    patch: bool,
+    #[arg(
+        long,
+        group = "level",
+        help = "Take patch and minor updates, e.g. 9.2.0 to 9.3.0. This is the default"
+    )]
+    minor: bool,
+    #[arg(
+        long,
+        group = "level",
+        help = "Also take major updates that may break compatibility, e.g. 9.2.0 to 10.0.0"
+    )]
+    major: bool,
+    #[arg(
+        long,
+        help = "Only warn when a plugin's license isn't allowed by project.licenses in gdm.json"
+    )]
+    no_verify_license: bool,
+    #[arg(
+        long,
+        help = "Run godot --headless --import afterwards to regenerate .import and .uid files. Needs Godot 4.2 or newer"
+    )]
+    import: bool,
+}
+
+impl UpdateArgs {
+    fn level(&self) -> UpdateLevel {
+        if self.patch {
+            UpdateLevel::Patch
+        } else if self.major {
+            UpdateLevel::Major
+        } else {
+            UpdateLevel::Minor
+        }
+    }
+}
 
-pub async fn handle() -> Result<()> {
-    let plugin_service = DefaultPluginService::default();
-    plugin_service.update_plugins().await?;
+pub async fn handle(args: &UpdateArgs, ui: Arc<dyn UserInterface>) -> Result<()> {
+    let plugin_service = DefaultPluginService::default()
+        .with_ui(ui.clone())
+        .with_verify_license(!args.no_verify_license);
+    plugin_service
+        .update_plugins(&args.names, args.pre, args.level())
+        .await?;
+    if args.import {
+        import_resources(ui.as_ref())?;
+    }
     Ok(())
 }
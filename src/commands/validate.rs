@@ -0,0 +1,84 @@
+use crate::terminal::{self, Annotation, AnnotationLevel, ReportFormat, porcelain};
+use gdm_core::config::{AppConfig, DefaultAppConfig, DefaultGdmConfig, GdmConfig};
+use gdm_core::error::{ErrorKind, GdmError};
+
+use anyhow::{Result, bail};
+use clap::Args;
+use regex::Regex;
+
+#[derive(Args)]
+#[command(about = "Check the configuration file for errors")]
+pub struct ValidateArgs {
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = ReportFormat::Text,
+        help = "Output format: text, or github for GitHub Actions annotations"
+    )]
+    format: ReportFormat,
+    #[arg(
+        long,
+        conflicts_with = "format",
+        help = "Print tab-separated records for scripts whose fields don't change between releases"
+    )]
+    porcelain: bool,
+}
+
+pub async fn handle(args: &ValidateArgs) -> Result<()> {
+    if args.format.delimiter().is_some() {
+        bail!(
+            GdmError::new(
+                ErrorKind::Usage,
+                "gdm validate doesn't print a table, so it can't use --format csv or tsv."
+            )
+            .with_hint("Use --format text or --format github.")
+        );
+    }
+    let config_file_path = DefaultAppConfig::default()
+        .get_config_file_path()
+        .to_path_buf();
+    let gdm_config = DefaultGdmConfig::default();
+    if let Err(e) = gdm_config.validate() {
+        let message = format!("{:#}", e);
+        // Parse errors end with e.g. "at line 4 column 7"
+        let line: Option<usize> = Regex::new(r"line (\d+)")?
+            .captures(&message)
+            .and_then(|captures| captures[1].parse().ok());
+        if args.porcelain {
+            let line = line.map(|line| line.to_string()).unwrap_or_default();
+            println!(
+                "{}",
+                porcelain::line(
+                    "invalid",
+                    &[
+                        config_file_path.display().to_string().as_str(),
+                        &line,
+                        &message
+                    ]
+                )
+            );
+        } else if args.format == ReportFormat::Github {
+            let annotation = Annotation {
+                level: AnnotationLevel::Error,
+                file: config_file_path.display().to_string(),
+                line,
+                title: "Invalid configuration".to_string(),
+                message,
+            };
+            println!("{}", annotation);
+        }
+        return Err(e);
+    }
+    if args.porcelain {
+        println!(
+            "{}",
+            porcelain::line("valid", &[config_file_path.display().to_string()])
+        );
+    } else if args.format == ReportFormat::Text {
+        println!(
+            "{}",
+            terminal::success(format!("{} is valid", config_file_path.display()))
+        );
+    }
+    Ok(())
+}
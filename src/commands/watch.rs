@@ -0,0 +1,104 @@
+use crate::terminal;
+use gdm_core::config::{AppConfig, DefaultAppConfig, DefaultGdmConfig, GdmConfig, GdmConfigFormat};
+use gdm_core::services::{DefaultFileService, DefaultPluginService, FileWatcher, PluginService};
+use gdm_core::ui::UserInterface;
+
+use anyhow::Result;
+use clap::Args;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Args)]
+#[command(
+    about = "Watch the configuration file and install plugins whenever it changes, until stopped with Ctrl+C"
+)]
+pub struct WatchArgs {
+    #[arg(
+        long,
+        default_value_t = 500,
+        help = "How often to check for changes, in milliseconds"
+    )]
+    interval: u64,
+    #[arg(
+        long,
+        help = "Skip plugins listed in dev_plugins, e.g. for export builds"
+    )]
+    no_dev: bool,
+}
+
+pub async fn handle(args: &WatchArgs, ui: Arc<dyn UserInterface>) -> Result<()> {
+    let config_file_path = DefaultAppConfig::default()
+        .get_config_file_path()
+        .to_path_buf();
+    let local_config_file_path = GdmConfigFormat::local_config_file_path(&config_file_path);
+    let paths: Vec<PathBuf> = vec![config_file_path, local_config_file_path];
+    let interval = Duration::from_millis(args.interval.max(50));
+
+    let mut watcher = FileWatcher::new(paths.clone());
+    install(args, ui.clone(), &mut watcher).await;
+    ui.status(&format!(
+        "Watching {} for changes. Press Ctrl+C to stop.",
+        paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(" and ")
+    ));
+
+    loop {
+        tokio::time::sleep(interval).await;
+        let mut changed = watcher.changed();
+        if changed.is_empty() {
+            continue;
+        }
+        // Wait until the editor or tool has finished writing
+        loop {
+            tokio::time::sleep(interval).await;
+            let still_changing = watcher.changed();
+            if still_changing.is_empty() {
+                break;
+            }
+            changed.extend(still_changing);
+        }
+        changed.sort();
+        changed.dedup();
+        ui.status(&format!(
+            "\n{} changed, installing plugins.",
+            changed
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+
+        install(args, ui.clone(), &mut watcher).await;
+    }
+}
+
+/// Installs the plugins, reporting errors instead of stopping, e.g. while the file is invalid mid-edit.
+/// Later changes are compared to the files as install read or wrote them, so it doesn't run again
+/// because of its own writes but edits saved while it ran aren't lost.
+async fn install(args: &WatchArgs, ui: Arc<dyn UserInterface>, watcher: &mut FileWatcher) {
+    DefaultFileService::clear_cache();
+    if let Err(e) = install_plugins(args, ui).await {
+        eprintln!("{} {:#}", terminal::error("error:"), e);
+    }
+    for path in watcher.paths() {
+        if let Some(content) = DefaultFileService::cached_content(&path) {
+            watcher.set_seen(&path, Some(content.into_bytes()));
+        }
+    }
+}
+
+/// Installs with the project settings of the configuration file as it is now, e.g. a changed addons_dir
+async fn install_plugins(args: &WatchArgs, ui: Arc<dyn UserInterface>) -> Result<()> {
+    let app_config = DefaultAppConfig::default();
+    let metadata =
+        DefaultGdmConfig::new(app_config.clone(), Arc::new(DefaultFileService)).load()?;
+    DefaultPluginService::for_app_config(app_config.with_project_settings(&metadata)?)
+        .with_ui(ui)
+        .install_plugins(&[], !args.no_dev, false)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use crate::terminal;
+use gdm_core::error::{ErrorKind, GdmError};
+use gdm_core::models::FolderRole;
+use gdm_core::services::{DefaultPluginService, PluginService};
+
+use anyhow::{Result, bail};
+use clap::Args;
+
+#[derive(Args)]
+#[command(about = "Explain which plugin installed an addon folder and what depends on it")]
+pub struct WhyArgs {
+    #[arg(help = "Name or path of the addon folder, e.g. \"gut\" or \"addons/gut\"")]
+    folder: String,
+}
+
+pub async fn handle(args: &WhyArgs) -> Result<()> {
+    let plugin_service = DefaultPluginService::default();
+    let report = plugin_service.explain_folder(&args.folder)?;
+    let folder = report.folder.display();
+
+    if !report.is_managed() {
+        if !report.exists {
+            bail!(
+                GdmError::new(
+                    ErrorKind::NotFound,
+                    format!(
+                        "{} doesn't exist and no plugin in the configuration file installs it.",
+                        folder
+                    )
+                )
+                .with_hint("Run gdm list to see the installed plugins.")
+            );
+        }
+        println!(
+            "{}",
+            terminal::warning(format!("{} isn't managed by gdm.", folder))
+        );
+        println!("No plugin in the configuration file installs it.");
+    }
+
+    for owner in &report.owners {
+        let relation = match owner.role {
+            FolderRole::Main => "the main folder of",
+            FolderRole::SubAsset => "a sub-asset of",
+        };
+        let mut plugin = format!(
+            "{}, {} {}",
+            owner.name,
+            owner.plugin.title,
+            owner.plugin.get_version()
+        );
+        if let Some(source) = &owner.plugin.source {
+            plugin = format!("{} from {}", plugin, source);
+        }
+        if owner.plugin.dev {
+            plugin = format!("{} (dev)", plugin);
+        }
+        println!("{} is {} plugin {}.", folder, relation, plugin);
+    }
+    if report.owners.len() > 1 {
+        println!(
+            "The folder is shared, removing one of these plugins deletes it for the others too."
+        );
+    }
+
+    if report.dependents.is_empty() {
+        println!("No other plugin depends on it.");
+    } else {
+        println!(
+            "Autoloads of {} use files in it.",
+            report.dependents.join(", ")
+        );
+    }
+
+    if report.is_managed() && !report.exists {
+        println!(
+            "{}",
+            terminal::warning("The folder is missing. Run gdm install to restore it.")
+        );
+    }
+    Ok(())
+}
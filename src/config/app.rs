@@ -1,20 +1,111 @@
+use crate::config::{DefaultGdmConfigMetadata, LicensePolicy};
+use crate::models::GitFetchOptions;
+use crate::services::DEFAULT_METADATA_TTL;
+
+use anyhow::{Result, bail};
 use serde_derive::Deserialize;
 
+use std::collections::BTreeMap;
+use std::fmt;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+static APP_CONFIG: OnceLock<DefaultAppConfig> = OnceLock::new();
+
+/// How installed plugins get into the project's addons folder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InstallMode {
+    /// Move a copy of the plugin into the project
+    #[default]
+    Copy,
+    /// Link the plugin folder to gdm's shared content store, with a junction on Windows.
+    /// Falls back to copying where links aren't supported.
+    Link,
+}
+
+impl fmt::Display for InstallMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            InstallMode::Copy => "copy",
+            InstallMode::Link => "link",
+        };
+        write!(f, "{}", name)
+    }
+}
 
-/// Application configuration settings
+impl FromStr for InstallMode {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<InstallMode> {
+        match value.to_lowercase().as_str() {
+            "copy" => Ok(InstallMode::Copy),
+            "link" => Ok(InstallMode::Link),
+            _ => bail!("Unknown install mode \"{}\", expected copy or link", value),
+        }
+    }
+}
+
+/// Application configuration settings. Every setting has a built-in default, so nothing has to
+/// be set in the environment.
 #[derive(Debug, Clone, Deserialize)]
 pub struct DefaultAppConfig {
-    /// API_BASE_URL environment variable
+    /// Asset Library API, set with `Gdm::builder().registry()`
     pub api_base_url: String,
-    /// CONFIG_FILE_PATH environment variable
+    /// gdm.json, or gdm.toml if the project uses it
     config_file_path: String,
-    /// CACHE_FOLDER_PATH environment variable
+    /// Staging folder for downloads, removed after installing
     cache_folder_path: String,
-    /// GODOT_PROJECT_FILE_PATH environment variable
+    /// project.godot, relative to the project directory
     godot_project_file_path: String,
-    /// ADDON_FOLDER_PATH environment variable
+    /// Default folder plugins are installed to
     addon_folder_path: String,
+    /// Folder the addons folders are resolved against, the working directory if not set
+    #[serde(default)]
+    project_dir: Option<String>,
+    /// --godot-version flag or GDM_GODOT_VERSION environment variable
+    godot_version: Option<String>,
+    /// --godot-binary flag or GDM_GODOT_BINARY environment variable
+    godot_binary_path: String,
+    /// addons_dir in gdm.json
+    project_addons_dir: Option<String>,
+    /// Lowest Godot version allowed by project.godot_version in gdm.json
+    project_godot_version: Option<String>,
+    /// project.licenses in gdm.json
+    license_policy: Option<LicensePolicy>,
+    /// project.credits in gdm.json
+    credits_file_path: Option<String>,
+    /// project.trusted_keys in gdm.json
+    #[serde(default)]
+    trusted_keys: Vec<String>,
+    /// --credential-helper flag or GDM_CREDENTIAL_HELPER environment variable
+    credential_helper: Option<String>,
+    /// --install-mode flag, GDM_INSTALL_MODE environment variable or install_mode in the global config
+    #[serde(default)]
+    install_mode: InstallMode,
+    /// git_fetch in the global config, for git plugins that don't set it in gdm.json
+    #[serde(default)]
+    git_fetch: GitFetchOptions,
+    /// headers in the global configuration, sent with requests to the Asset Library
+    headers: BTreeMap<String, String>,
+    /// git_url_rewrites in the global configuration, URL prefixes of git plugins replaced before connecting
+    #[serde(default)]
+    git_url_rewrites: BTreeMap<String, String>,
+    /// --metadata-ttl flag or GDM_METADATA_TTL environment variable, in seconds
+    metadata_ttl: u64,
+    /// Workspace manifest listing several projects
+    workspace_file_path: String,
+    /// Download folder shared by the projects of a workspace install
+    shared_cache_folder_path: Option<String>,
+    /// --yes flag, answers yes to confirmation prompts
+    assume_yes: bool,
+    /// --quiet flag, hides progress bars and status messages
+    quiet: bool,
+    /// --frozen flag or GDM_FROZEN environment variable, refuses to resolve or download anything
+    #[serde(default)]
+    frozen: bool,
 }
 
 impl DefaultAppConfig {
@@ -33,18 +124,237 @@ impl DefaultAppConfig {
             cache_folder_path: cache_folder_path.unwrap_or(".gdm".to_string()),
             godot_project_file_path: godot_project_file_path.unwrap_or("project.godot".to_string()),
             addon_folder_path: addon_folder_path.unwrap_or("addons".to_string()),
+            project_dir: None,
+            godot_version: None,
+            godot_binary_path: "godot".to_string(),
+            project_addons_dir: None,
+            project_godot_version: None,
+            license_policy: None,
+            credits_file_path: None,
+            trusted_keys: Vec::new(),
+            credential_helper: None,
+            install_mode: InstallMode::Copy,
+            git_fetch: GitFetchOptions::default(),
+            headers: BTreeMap::new(),
+            git_url_rewrites: BTreeMap::new(),
+            metadata_ttl: DEFAULT_METADATA_TTL.as_secs(),
+            workspace_file_path: "gdm-workspace.json".to_string(),
+            shared_cache_folder_path: None,
+            assume_yes: false,
+            frozen: false,
+            quiet: false,
+        }
+    }
+
+    /// Sets the configuration returned by `DefaultAppConfig::default()` for the rest of the run.
+    /// Fails when it was already set, so a second configuration can't be silently dropped.
+    pub fn init(app_config: DefaultAppConfig) -> Result<()> {
+        if APP_CONFIG.set(app_config).is_err() {
+            bail!("The app configuration was already initialized");
         }
+        Ok(())
+    }
+
+    /// Overrides the Godot version detected from project.godot
+    pub fn with_godot_version(mut self, godot_version: Option<String>) -> DefaultAppConfig {
+        self.godot_version = godot_version;
+        self
+    }
+
+    /// Resolves gdm.json, project.godot, the staging folder and the addons folders against
+    /// `project_dir` instead of the working directory
+    pub fn with_project_dir(mut self, project_dir: &Path) -> DefaultAppConfig {
+        let resolve = |path: &str| project_dir.join(path).to_string_lossy().to_string();
+        self.config_file_path = resolve(&self.config_file_path);
+        self.godot_project_file_path = resolve(&self.godot_project_file_path);
+        self.cache_folder_path = resolve(&self.cache_folder_path);
+        self.project_dir = Some(project_dir.to_string_lossy().to_string());
+        self
+    }
+
+    /// Sets the plugin config file, e.g. gdm.toml instead of gdm.json
+    pub fn with_config_file_path(mut self, config_file_path: &Path) -> DefaultAppConfig {
+        self.config_file_path = config_file_path.to_string_lossy().to_string();
+        self
+    }
+
+    /// Sets the Godot binary used to detect the engine version
+    pub fn with_godot_binary_path(mut self, godot_binary_path: Option<String>) -> DefaultAppConfig {
+        if let Some(path) = godot_binary_path {
+            self.godot_binary_path = path;
+        }
+        self
+    }
+
+    /// Sets the folder plugins are installed to, as declared in gdm.json
+    pub fn with_project_addons_dir(
+        mut self,
+        project_addons_dir: Option<String>,
+    ) -> DefaultAppConfig {
+        self.project_addons_dir = project_addons_dir;
+        self
+    }
+
+    /// Sets the Godot version used for Asset Library queries, as declared in gdm.json
+    pub fn with_project_godot_version(
+        mut self,
+        project_godot_version: Option<String>,
+    ) -> DefaultAppConfig {
+        self.project_godot_version = project_godot_version;
+        self
+    }
+
+    /// Sets the licenses `add` and `update` accept, as declared in gdm.json
+    pub fn with_license_policy(
+        mut self,
+        license_policy: Option<LicensePolicy>,
+    ) -> DefaultAppConfig {
+        self.license_policy = license_policy;
+        self
+    }
+
+    /// Sets the attribution file kept up to date as plugins change, as declared in gdm.json
+    pub fn with_credits_file_path(mut self, credits_file_path: Option<String>) -> DefaultAppConfig {
+        self.credits_file_path = credits_file_path;
+        self
+    }
+
+    /// Sets the keys git plugins with verify_signature must be signed with, as declared in gdm.json
+    pub fn with_trusted_keys(mut self, trusted_keys: Vec<String>) -> DefaultAppConfig {
+        self.trusted_keys = trusted_keys;
+        self
+    }
+
+    /// Applies the project settings of a loaded gdm.json: addons_dir, the Godot version range,
+    /// licenses, credits and trusted keys. The credits file is resolved against the project directory.
+    pub fn with_project_settings(
+        self,
+        metadata: &DefaultGdmConfigMetadata,
+    ) -> Result<DefaultAppConfig> {
+        let project = metadata.project.as_ref();
+        let project_godot_version = match project {
+            Some(project) => project.lowest_godot_version()?,
+            None => None,
+        };
+        let credits_file_path =
+            project
+                .and_then(|project| project.credits.as_ref())
+                .map(|credits| {
+                    self.get_project_dir()
+                        .join(credits)
+                        .to_string_lossy()
+                        .to_string()
+                });
+        Ok(self
+            .with_project_addons_dir(metadata.addons_dir.clone())
+            .with_project_godot_version(project_godot_version)
+            .with_license_policy(project.and_then(|project| project.licenses.clone()))
+            .with_credits_file_path(credits_file_path)
+            .with_trusted_keys(
+                project
+                    .map(|project| project.trusted_keys.clone())
+                    .unwrap_or_default(),
+            ))
+    }
+
+    /// Sets the command that prints tokens for the Asset Library and git hosts
+    pub fn with_credential_helper(mut self, credential_helper: Option<String>) -> DefaultAppConfig {
+        self.credential_helper = credential_helper;
+        self
+    }
+
+    /// Sets whether plugins are copied into projects or linked to the shared content store
+    pub fn with_install_mode(mut self, install_mode: InstallMode) -> DefaultAppConfig {
+        self.install_mode = install_mode;
+        self
+    }
+
+    /// Sets how much of the repositories of git plugins is fetched, unless a plugin sets it
+    pub fn with_git_fetch(mut self, git_fetch: GitFetchOptions) -> DefaultAppConfig {
+        self.git_fetch = git_fetch;
+        self
+    }
+
+    /// Sets URL prefixes of git plugins to replace, e.g. to fetch GitHub repositories from a mirror
+    pub fn with_git_url_rewrites(
+        mut self,
+        git_url_rewrites: BTreeMap<String, String>,
+    ) -> DefaultAppConfig {
+        self.git_url_rewrites = git_url_rewrites;
+        self
+    }
+
+    /// Sets extra headers sent to the Asset Library, e.g. an API key of a self-hosted registry
+    pub fn with_headers(mut self, headers: BTreeMap<String, String>) -> DefaultAppConfig {
+        self.headers = headers;
+        self
+    }
+
+    /// Sets how long Asset Library responses cached on disk are reused
+    pub fn with_metadata_ttl(mut self, metadata_ttl: Duration) -> DefaultAppConfig {
+        self.metadata_ttl = metadata_ttl.as_secs();
+        self
+    }
+
+    /// Sets the folder where downloaded assets are shared between the projects of a workspace
+    pub fn with_shared_cache_folder_path(
+        mut self,
+        shared_cache_folder_path: Option<String>,
+    ) -> DefaultAppConfig {
+        self.shared_cache_folder_path = shared_cache_folder_path;
+        self
+    }
+
+    /// Skips confirmation prompts for destructive operations
+    pub fn with_assume_yes(mut self, assume_yes: bool) -> DefaultAppConfig {
+        self.assume_yes = assume_yes;
+        self
+    }
+
+    /// Hides progress bars and status messages, printing only errors and results
+    pub fn with_quiet(mut self, quiet: bool) -> DefaultAppConfig {
+        self.quiet = quiet;
+        self
+    }
+
+    /// Installs only from gdm.json and the archive cache, failing instead of using the network
+    pub fn with_frozen(mut self, frozen: bool) -> DefaultAppConfig {
+        self.frozen = frozen;
+        self
     }
 }
 
+/// The configuration set with `DefaultAppConfig::init`, or the built-in defaults before that
 impl Default for DefaultAppConfig {
     fn default() -> Self {
+        if let Some(app_config) = APP_CONFIG.get() {
+            return app_config.clone();
+        }
         DefaultAppConfig {
             api_base_url: "https://godotengine.org/asset-library/api".to_string(),
             config_file_path: "gdm.json".to_string(),
             cache_folder_path: ".gdm".to_string(),
             godot_project_file_path: "project.godot".to_string(),
             addon_folder_path: "addons".to_string(),
+            project_dir: None,
+            godot_version: None,
+            godot_binary_path: "godot".to_string(),
+            project_addons_dir: None,
+            project_godot_version: None,
+            license_policy: None,
+            credits_file_path: None,
+            trusted_keys: Vec::new(),
+            credential_helper: None,
+            install_mode: InstallMode::Copy,
+            git_fetch: GitFetchOptions::default(),
+            headers: BTreeMap::new(),
+            git_url_rewrites: BTreeMap::new(),
+            metadata_ttl: DEFAULT_METADATA_TTL.as_secs(),
+            workspace_file_path: "gdm-workspace.json".to_string(),
+            shared_cache_folder_path: None,
+            assume_yes: false,
+            frozen: false,
+            quiet: false,
         }
     }
 }
@@ -66,6 +376,90 @@ impl AppConfig for DefaultAppConfig {
     fn get_addon_folder_path(&self) -> PathBuf {
         PathBuf::from(self.addon_folder_path.as_str())
     }
+
+    fn get_godot_version(&self) -> Option<String> {
+        self.godot_version.clone()
+    }
+
+    /// Empty unless set with `with_project_dir`, so joined paths stay relative to the working directory
+    fn get_project_dir(&self) -> PathBuf {
+        self.project_dir
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_default()
+    }
+
+    fn get_godot_binary_path(&self) -> &Path {
+        Path::new(&self.godot_binary_path)
+    }
+
+    /// Returns the folder plugins are installed to, relative to the project root
+    fn get_project_addons_dir(&self) -> PathBuf {
+        match &self.project_addons_dir {
+            Some(dir) => PathBuf::from(dir),
+            None => self.get_addon_folder_path(),
+        }
+    }
+
+    fn get_project_godot_version(&self) -> Option<String> {
+        self.project_godot_version.clone()
+    }
+
+    fn get_license_policy(&self) -> Option<LicensePolicy> {
+        self.license_policy.clone()
+    }
+
+    fn get_credits_file_path(&self) -> Option<PathBuf> {
+        self.credits_file_path.as_ref().map(PathBuf::from)
+    }
+
+    fn get_trusted_keys(&self) -> Vec<String> {
+        self.trusted_keys.clone()
+    }
+
+    fn get_credential_helper(&self) -> Option<String> {
+        self.credential_helper.clone()
+    }
+
+    fn get_install_mode(&self) -> InstallMode {
+        self.install_mode
+    }
+
+    fn get_git_fetch(&self) -> GitFetchOptions {
+        self.git_fetch
+    }
+
+    fn get_headers(&self) -> BTreeMap<String, String> {
+        self.headers.clone()
+    }
+
+    fn get_git_url_rewrites(&self) -> BTreeMap<String, String> {
+        self.git_url_rewrites.clone()
+    }
+
+    fn get_metadata_ttl(&self) -> Duration {
+        Duration::from_secs(self.metadata_ttl)
+    }
+
+    fn get_workspace_file_path(&self) -> &Path {
+        Path::new(&self.workspace_file_path)
+    }
+
+    fn get_shared_cache_folder_path(&self) -> Option<PathBuf> {
+        self.shared_cache_folder_path.as_ref().map(PathBuf::from)
+    }
+
+    fn get_assume_yes(&self) -> bool {
+        self.assume_yes
+    }
+
+    fn get_quiet(&self) -> bool {
+        self.quiet
+    }
+
+    fn get_frozen(&self) -> bool {
+        self.frozen
+    }
 }
 
 impl dyn AppConfig {
@@ -80,4 +474,23 @@ pub trait AppConfig: Send + Sync + 'static {
     fn get_config_file_path(&self) -> &Path;
     fn get_cache_folder_path(&self) -> &Path;
     fn get_addon_folder_path(&self) -> PathBuf;
+    fn get_godot_version(&self) -> Option<String>;
+    fn get_project_dir(&self) -> PathBuf;
+    fn get_godot_binary_path(&self) -> &Path;
+    fn get_project_addons_dir(&self) -> PathBuf;
+    fn get_project_godot_version(&self) -> Option<String>;
+    fn get_license_policy(&self) -> Option<LicensePolicy>;
+    fn get_credits_file_path(&self) -> Option<PathBuf>;
+    fn get_trusted_keys(&self) -> Vec<String>;
+    fn get_credential_helper(&self) -> Option<String>;
+    fn get_install_mode(&self) -> InstallMode;
+    fn get_git_fetch(&self) -> GitFetchOptions;
+    fn get_headers(&self) -> BTreeMap<String, String>;
+    fn get_git_url_rewrites(&self) -> BTreeMap<String, String>;
+    fn get_metadata_ttl(&self) -> Duration;
+    fn get_workspace_file_path(&self) -> &Path;
+    fn get_shared_cache_folder_path(&self) -> Option<PathBuf>;
+    fn get_assume_yes(&self) -> bool;
+    fn get_quiet(&self) -> bool;
+    fn get_frozen(&self) -> bool;
 }
@@ -1,21 +1,252 @@
+use crate::config::migration;
 use crate::config::{AppConfig, DefaultAppConfig};
+use crate::error::{ErrorKind, GdmError};
 use crate::models::{Plugin, PluginSource};
 use crate::services::{DefaultFileService, FileService};
+use crate::utils::Utils;
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, anyhow, bail};
+use semver::{Op, VersionReq};
 use serde_derive::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tracing::{debug, info};
 
+/// Plugins and dev plugins share one map, dev plugins are marked with `Plugin::dev`
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(try_from = "GdmConfigFile", into = "GdmConfigFile")]
 pub struct DefaultGdmConfigMetadata {
+    /// Schema reference for editors, e.g. the `gdm.schema.json` shipped with gdm
+    pub schema: Option<String>,
+    pub project: Option<GdmProjectMetadata>,
+    /// Folder plugins are installed to, relative to the project root. Defaults to `addons`.
+    pub addons_dir: Option<String>,
     pub plugins: BTreeMap<String, Plugin>,
 }
 
+/// Layout of gdm.json, with editor-only plugins in their own `dev_plugins` section
+#[derive(Serialize, Deserialize)]
+struct GdmConfigFile {
+    #[serde(rename = "$schema", default, skip_serializing_if = "Option::is_none")]
+    schema: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    format_version: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    project: Option<GdmProjectMetadata>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    addons_dir: Option<String>,
+    plugins: BTreeMap<String, Plugin>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    dev_plugins: BTreeMap<String, Plugin>,
+}
+
+impl TryFrom<GdmConfigFile> for DefaultGdmConfigMetadata {
+    type Error = String;
+
+    fn try_from(file: GdmConfigFile) -> std::result::Result<Self, Self::Error> {
+        if let Some(project) = &file.project {
+            project
+                .godot_version_req()
+                .map_err(|err| format!("{:#}", err))?;
+        }
+        let mut plugins = file.plugins;
+        for (key, mut plugin) in file.dev_plugins {
+            if plugins.contains_key(&key) {
+                return Err(format!(
+                    "Plugin {} is listed in both plugins and dev_plugins",
+                    key
+                ));
+            }
+            plugin.dev = true;
+            plugins.insert(key, plugin);
+        }
+
+        let mut names: HashSet<&str> = plugins.keys().map(String::as_str).collect();
+        for (key, plugin) in &plugins {
+            if let Some(alias) = &plugin.alias
+                && alias != key
+                && !names.insert(alias)
+            {
+                return Err(format!(
+                    "Alias {} of plugin {} is already used by another plugin",
+                    alias, key
+                ));
+            }
+        }
+
+        Ok(DefaultGdmConfigMetadata {
+            schema: file.schema,
+            project: file.project,
+            addons_dir: file.addons_dir,
+            plugins,
+        })
+    }
+}
+
+impl From<DefaultGdmConfigMetadata> for GdmConfigFile {
+    fn from(config: DefaultGdmConfigMetadata) -> Self {
+        let (dev_plugins, plugins) = config.plugins.into_iter().partition(|(_, p)| p.dev);
+        GdmConfigFile {
+            schema: config.schema,
+            format_version: Some(migration::CURRENT_FORMAT_VERSION),
+            project: config.project,
+            addons_dir: config.addons_dir,
+            plugins,
+            dev_plugins,
+        }
+    }
+}
+
+/// Project section of gdm.json:
+///
+/// ```json
+/// "project": { "name": "My Game", "godot_version": ">=4.3, <4.7" }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct GdmProjectMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Supported Godot versions as a semver range, e.g. `>=4.3, <4.7` or `~4.5`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub godot_version: Option<String>,
+    /// Licenses `add` and `update` accept, e.g. `{ "deny": ["GPLv3"] }`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub licenses: Option<LicensePolicy>,
+    /// Attribution file `add`, `update` and `remove` keep up to date, e.g. `CREDITS.md`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credits: Option<String>,
+    /// Keys git plugins with `verify_signature` must be signed with: GPG fingerprints of keys
+    /// in the local keyring, or SSH public keys, e.g. `ssh-ed25519 AAAA...`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trusted_keys: Vec<String>,
+}
+
+/// Licenses of Asset Library plugins the project accepts. Names are compared without case,
+/// e.g. `MIT` or `GPLv3` as listed in the Asset Library.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct LicensePolicy {
+    /// Only these licenses are accepted. Any license if empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    /// These licenses are refused, even if they're also allowed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+}
+
+impl LicensePolicy {
+    pub fn allows(&self, license: &str) -> bool {
+        let matches = |listed: &String| listed.eq_ignore_ascii_case(license.trim());
+        !self.deny.iter().any(matches) && (self.allow.is_empty() || self.allow.iter().any(matches))
+    }
+}
+
+impl GdmProjectMetadata {
+    pub fn godot_version_req(&self) -> Result<Option<VersionReq>> {
+        self.godot_version
+            .as_deref()
+            .map(|range| {
+                VersionReq::parse(range).with_context(|| {
+                    format!(
+                        "project.godot_version: invalid Godot version range \"{}\"",
+                        range
+                    )
+                })
+            })
+            .transpose()
+    }
+
+    /// Returns true if the declared range allows the Godot version, e.g. `4.5`.
+    /// Any version is allowed when the range isn't declared.
+    pub fn allows_godot_version(&self, godot_version: &str) -> Result<bool> {
+        Ok(match self.godot_version_req()? {
+            Some(req) => req.matches(&Utils::parse_semantic_version(godot_version)),
+            None => true,
+        })
+    }
+
+    /// Returns the lowest major.minor version the declared range allows, e.g. `4.3` for `>=4.3, <4.7`.
+    /// Asset Library queries use it so found assets work across the whole range.
+    pub fn lowest_godot_version(&self) -> Result<Option<String>> {
+        let Some(req) = self.godot_version_req()? else {
+            return Ok(None);
+        };
+        let lowest_version = req
+            .comparators
+            .iter()
+            .find(|comparator| !matches!(comparator.op, Op::Less | Op::LessEq))
+            .map(
+                |comparator| match (comparator.op, comparator.minor, comparator.patch) {
+                    (Op::Greater, None, _) => format!("{}.0", comparator.major + 1),
+                    (Op::Greater, Some(minor), None) => {
+                        format!("{}.{}", comparator.major, minor + 1)
+                    }
+                    (_, minor, _) => format!("{}.{}", comparator.major, minor.unwrap_or(0)),
+                },
+            );
+        Ok(lowest_version)
+    }
+}
+
 impl DefaultGdmConfigMetadata {
     pub fn new(plugins: BTreeMap<String, Plugin>) -> DefaultGdmConfigMetadata {
-        DefaultGdmConfigMetadata { plugins }
+        DefaultGdmConfigMetadata {
+            schema: None,
+            project: None,
+            addons_dir: None,
+            plugins,
+        }
+    }
+
+    fn with_plugins(&self, plugins: BTreeMap<String, Plugin>) -> DefaultGdmConfigMetadata {
+        DefaultGdmConfigMetadata {
+            plugins,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the configuration without dev plugins, and the dev plugins that were left out
+    pub fn without_dev_plugins(&self) -> (DefaultGdmConfigMetadata, Vec<Plugin>) {
+        let (dev_plugins, plugins): (BTreeMap<String, Plugin>, BTreeMap<String, Plugin>) =
+            self.plugins.clone().into_iter().partition(|(_, p)| p.dev);
+        (
+            self.with_plugins(plugins),
+            dev_plugins.into_values().collect(),
+        )
+    }
+
+    /// Replaces plugins with the entries of the same name in a local overlay, e.g. gdm.local.json,
+    /// and adds the plugins only listed there
+    pub fn with_local_overrides(
+        &self,
+        local: &DefaultGdmConfigMetadata,
+    ) -> DefaultGdmConfigMetadata {
+        let mut plugins = self.plugins.clone();
+        plugins.extend(local.plugins.clone());
+        self.with_plugins(plugins)
+    }
+
+    /// Reverts `with_local_overrides` before saving, so local pins and extra plugins
+    /// don't end up in the shared file. Plugins changed since loading are kept as they are.
+    pub fn without_local_overrides(
+        &self,
+        shared: &DefaultGdmConfigMetadata,
+        local: &DefaultGdmConfigMetadata,
+    ) -> DefaultGdmConfigMetadata {
+        let mut plugins = self.plugins.clone();
+        for (key, local_plugin) in &local.plugins {
+            let unchanged = plugins.get(key).is_some_and(|plugin| {
+                plugin.source == local_plugin.source && plugin.version == local_plugin.version
+            });
+            if !unchanged {
+                continue;
+            }
+            match shared.plugins.get(key) {
+                Some(shared_plugin) => plugins.insert(key.clone(), shared_plugin.clone()),
+                None => plugins.remove(key),
+            };
+        }
+        self.with_plugins(plugins)
     }
 }
 
@@ -40,8 +271,53 @@ impl GdmConfigMetadata for DefaultGdmConfigMetadata {
             .map(|(_, p)| p.clone())
     }
 
-    fn get_plugin_by_name(&self, name: &str) -> Option<Plugin> {
-        self.plugins.get(name).cloned()
+    /// Finds the plugin `name` refers to, see [`GdmConfigMetadata::find_plugins_by_name`].
+    /// Returns the key and the plugin, or `None` if no plugin or more than one matches.
+    fn get_plugin_by_name(&self, name: &str) -> Option<(String, Plugin)> {
+        let mut matches = self.find_plugins_by_name(name);
+        if matches.len() == 1 {
+            matches.pop()
+        } else {
+            None
+        }
+    }
+
+    fn find_plugins_by_name(&self, name: &str) -> Vec<(String, Plugin)> {
+        if let Some(plugin) = self.plugins.get(name) {
+            return vec![(name.to_string(), plugin.clone())];
+        }
+        if let Some((key, plugin)) = self
+            .plugins
+            .iter()
+            .find(|(_, plugin)| plugin.alias.as_deref() == Some(name))
+        {
+            return vec![(key.clone(), plugin.clone())];
+        }
+
+        let query = name.to_lowercase();
+        let names = |key: &str, plugin: &Plugin| {
+            [
+                Some(key),
+                plugin.alias.as_deref(),
+                Some(plugin.title.as_str()),
+            ]
+            .into_iter()
+            .flatten()
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+        };
+        let matching = |is_match: &dyn Fn(&str) -> bool| {
+            self.plugins
+                .iter()
+                .filter(|(key, plugin)| names(key, plugin).iter().any(|name| is_match(name)))
+                .map(|(key, plugin)| (key.clone(), plugin.clone()))
+                .collect::<Vec<_>>()
+        };
+        let exact = matching(&|name| name == query);
+        if !exact.is_empty() {
+            return exact;
+        }
+        matching(&|name| name.starts_with(&query))
     }
 
     fn remove_plugins(&self, plugins: HashSet<String>) -> DefaultGdmConfigMetadata {
@@ -51,17 +327,33 @@ impl GdmConfigMetadata for DefaultGdmConfigMetadata {
             info!("Removed plugin: {}", plugin_key);
         }
 
-        DefaultGdmConfigMetadata::new(_plugins)
+        self.with_plugins(_plugins)
+    }
+
+    fn enable_plugins(&self, plugin_keys: HashSet<String>) -> DefaultGdmConfigMetadata {
+        let mut _plugins = self.plugins.clone();
+        for plugin_key in plugin_keys {
+            if let Some(plugin) = _plugins.get_mut(&plugin_key) {
+                plugin.enabled = true;
+                info!("Enabled plugin: {}", plugin_key);
+            }
+        }
+
+        self.with_plugins(_plugins)
     }
 
     fn add_plugins(&self, plugins: &BTreeMap<String, Plugin>) -> DefaultGdmConfigMetadata {
         let mut _plugins = self.plugins.clone();
         for (key, plugin) in plugins {
-            _plugins.insert(key.clone(), plugin.clone());
+            let plugin = match _plugins.get(key) {
+                Some(existing) => plugin.clone().with_settings_from(existing),
+                None => plugin.clone(),
+            };
+            _plugins.insert(key.clone(), plugin);
             info!("Added/Updated plugin: {}", key);
         }
 
-        DefaultGdmConfigMetadata::new(_plugins)
+        self.with_plugins(_plugins)
     }
 
     fn get_plugins(&self, only_plugin_config: bool) -> BTreeMap<String, Plugin> {
@@ -79,12 +371,169 @@ impl GdmConfigMetadata for DefaultGdmConfigMetadata {
 
 pub trait GdmConfigMetadata {
     fn get_plugin_by_asset_id(&self, asset_id: &str) -> Option<Plugin>;
-    fn get_plugin_by_name(&self, name: &str) -> Option<Plugin>;
+    fn get_plugin_by_name(&self, name: &str) -> Option<(String, Plugin)>;
+    /// Plugins `name` refers to: the plugin with that key or alias, or else the plugins whose
+    /// key, alias or title is `name` ignoring case, or else the ones starting with it.
+    fn find_plugins_by_name(&self, name: &str) -> Vec<(String, Plugin)>;
     fn remove_plugins(&self, plugins: HashSet<String>) -> DefaultGdmConfigMetadata;
+    fn enable_plugins(&self, plugin_keys: HashSet<String>) -> DefaultGdmConfigMetadata;
     fn add_plugins(&self, plugins: &BTreeMap<String, Plugin>) -> DefaultGdmConfigMetadata;
     fn get_plugins(&self, only_plugin_config: bool) -> BTreeMap<String, Plugin>;
 }
 
+/// Serialization formats of the plugin config file, picked by its extension
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GdmConfigFormat {
+    Json,
+    Toml,
+}
+
+impl GdmConfigFormat {
+    pub fn from_path(config_file_path: &Path) -> GdmConfigFormat {
+        match config_file_path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => GdmConfigFormat::Toml,
+            _ => GdmConfigFormat::Json,
+        }
+    }
+
+    /// Parses the config, migrating older format versions in memory.
+    /// Errors name the offending field, e.g. `plugins.gut.version: invalid type: ...`
+    pub fn parse(&self, content: &str) -> Result<DefaultGdmConfigMetadata> {
+        // Parsing the original content keeps line numbers in errors when the layout didn't change
+        let mut manifest = self.parse_value(content)?;
+        if let Some(result) = migration::migrate(&mut manifest)?
+            && result.layout_changed
+        {
+            return Self::deserialize_with_path(manifest);
+        }
+        match self {
+            GdmConfigFormat::Json => {
+                let mut deserializer = serde_json::Deserializer::from_str(content);
+                let config = Self::deserialize_with_path(&mut deserializer)?;
+                deserializer.end()?;
+                Ok(config)
+            }
+            GdmConfigFormat::Toml => {
+                Self::deserialize_with_path(toml::Deserializer::parse(content)?)
+            }
+        }
+    }
+
+    /// Parses the config without mapping it to the model, e.g. to read its format version
+    pub fn parse_value(&self, content: &str) -> Result<serde_json::Value> {
+        match self {
+            GdmConfigFormat::Json => Ok(serde_json::from_str(content)?),
+            GdmConfigFormat::Toml => Ok(toml::from_str(content)?),
+        }
+    }
+
+    fn deserialize_with_path<'de, D>(deserializer: D) -> Result<DefaultGdmConfigMetadata>
+    where
+        D: serde::Deserializer<'de>,
+        D::Error: std::error::Error + Send + Sync + 'static,
+    {
+        serde_path_to_error::deserialize(deserializer).map_err(|err| {
+            let path = err.path().to_string();
+            let inner = err.into_inner();
+            if path == "." {
+                anyhow::Error::new(inner)
+            } else {
+                anyhow!("{}: {}", path, inner)
+            }
+        })
+    }
+
+    /// Serializes the config. For TOML, comments in the previous file content are kept
+    /// for the tables and keys that still exist.
+    pub fn serialize(
+        &self,
+        config: &DefaultGdmConfigMetadata,
+        previous_content: Option<&str>,
+    ) -> Result<String> {
+        match self {
+            GdmConfigFormat::Json => Ok(serde_json::to_string_pretty(config)?),
+            GdmConfigFormat::Toml => {
+                let mut document: toml_edit::DocumentMut =
+                    toml::to_string_pretty(config)?.parse()?;
+                if let Some(previous_document) = previous_content
+                    .and_then(|content| content.parse::<toml_edit::DocumentMut>().ok())
+                {
+                    Self::keep_comments(previous_document.as_table(), document.as_table_mut());
+                    document.set_trailing(previous_document.trailing().clone());
+                }
+                Ok(document.to_string())
+            }
+        }
+    }
+
+    fn keep_comments(previous_table: &toml_edit::Table, table: &mut toml_edit::Table) {
+        *table.decor_mut() = previous_table.decor().clone();
+
+        let keys: Vec<String> = table.iter().map(|(key, _)| key.to_string()).collect();
+        for key in keys {
+            let (Some(previous_key), Some(previous_item)) =
+                (previous_table.key(&key), previous_table.get(&key))
+            else {
+                continue;
+            };
+            // An inline table rewritten as a table header keeps its comments but not its spacing
+            let same_kind = matches!(
+                (table.get(&key), previous_item),
+                (Some(toml_edit::Item::Table(_)), toml_edit::Item::Table(_))
+                    | (Some(toml_edit::Item::Value(_)), toml_edit::Item::Value(_))
+            );
+            if !same_kind {
+                continue;
+            }
+            if let Some(mut table_key) = table.key_mut(&key) {
+                *table_key.leaf_decor_mut() = previous_key.leaf_decor().clone();
+            }
+            match (table.get_mut(&key), previous_item) {
+                (Some(toml_edit::Item::Table(table)), toml_edit::Item::Table(previous_table)) => {
+                    Self::keep_comments(previous_table, table);
+                }
+                (Some(toml_edit::Item::Value(value)), toml_edit::Item::Value(previous_value)) => {
+                    *value.decor_mut() = previous_value.decor().clone();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the local overlay of a config file, e.g. gdm.local.json for gdm.json
+    pub fn local_config_file_path(config_file_path: &Path) -> PathBuf {
+        match config_file_path.extension() {
+            Some(extension) => {
+                config_file_path.with_extension(format!("local.{}", extension.to_string_lossy()))
+            }
+            None => config_file_path.with_extension("local"),
+        }
+    }
+
+    /// Returns gdm.toml if the project uses it instead of gdm.json.
+    /// Both files can't exist at the same time.
+    pub fn resolve_config_file_path(
+        config_file_path: &Path,
+        file_service: &dyn FileService,
+    ) -> Result<PathBuf> {
+        let toml_file_path = config_file_path.with_extension("toml");
+        if toml_file_path == config_file_path || !file_service.file_exists(&toml_file_path)? {
+            return Ok(config_file_path.to_path_buf());
+        }
+        if file_service.file_exists(config_file_path)? {
+            bail!(GdmError::new(
+                ErrorKind::Conflict,
+                format!(
+                    "Found both {} and {}. Keep only one of them.",
+                    config_file_path.display(),
+                    toml_file_path.display()
+                )
+            ))
+        }
+        Ok(toml_file_path)
+    }
+}
+
 pub struct DefaultGdmConfig {
     pub app_config: DefaultAppConfig,
     pub file_service: Arc<dyn FileService + Send + Sync + 'static>,
@@ -100,6 +549,25 @@ impl Default for DefaultGdmConfig {
 }
 
 impl DefaultGdmConfig {
+    /// Where `migrate` keeps the original file, e.g. `gdm.json.v1.bak`
+    pub fn migration_backup_path(config_file_path: &Path, from_version: u64) -> PathBuf {
+        PathBuf::from(format!(
+            "{}.v{}.bak",
+            config_file_path.display(),
+            from_version
+        ))
+    }
+
+    /// Tells the user a config file was migrated from `from_version` and where the original is
+    pub fn migration_message(config_file_path: &Path, from_version: u64) -> String {
+        format!(
+            "Migrated {} to format version {}. The previous version was saved to {}",
+            config_file_path.display(),
+            migration::CURRENT_FORMAT_VERSION,
+            Self::migration_backup_path(config_file_path, from_version).display()
+        )
+    }
+
     #[allow(unused)]
     pub fn new(
         app_config: DefaultAppConfig,
@@ -110,6 +578,35 @@ impl DefaultGdmConfig {
             file_service,
         }
     }
+
+    /// Parses a config file, or returns None if it doesn't exist
+    fn read_config_file(
+        &self,
+        config_file_path: &Path,
+    ) -> Result<Option<DefaultGdmConfigMetadata>> {
+        if !self.file_service.file_exists(config_file_path)? {
+            return Ok(None);
+        }
+        let content = self.file_service.read_file_cached(config_file_path)?;
+        let config = GdmConfigFormat::from_path(config_file_path)
+            .parse(&content)
+            .map_err(|err| {
+                GdmError::new(
+                    ErrorKind::ConfigInvalid,
+                    format!(
+                        "Invalid plugin config file {}: {}",
+                        config_file_path.display(),
+                        err
+                    ),
+                )
+            })?;
+        Ok(Some(config))
+    }
+
+    fn read_local_config_file(&self) -> Result<Option<DefaultGdmConfigMetadata>> {
+        let config_file_path = self.app_config.get_config_file_path();
+        self.read_config_file(&GdmConfigFormat::local_config_file_path(config_file_path))
+    }
 }
 
 #[cfg_attr(test, mockall::automock)]
@@ -132,13 +629,18 @@ impl GdmConfig for DefaultGdmConfig {
         Ok(updated_plugin_config)
     }
 
+    /// Enables plugins in the config file, which `add_plugins` keeps disabled once disabled
+    fn enable_plugins(&self, plugin_keys: HashSet<String>) -> Result<DefaultGdmConfigMetadata> {
+        debug!("Enabling plugins: {:?}", plugin_keys);
+        let plugin_config = self.load()?;
+        let updated_plugin_config = plugin_config.enable_plugins(plugin_keys);
+        self.save(&updated_plugin_config)?;
+        Ok(updated_plugin_config)
+    }
+
     fn get_plugin_by_name(&self, name: &str) -> Option<(String, Plugin)> {
         let plugin_config = self.load().ok()?;
-        let plugin: Option<Plugin> = plugin_config.get_plugin_by_name(name);
-        if let Some(p) = plugin {
-            return Some((name.to_string(), p));
-        }
-        None
+        plugin_config.get_plugin_by_name(name)
     }
 
     fn get_plugin_by_asset_id(&self, asset_id: &str) -> Result<Option<Plugin>> {
@@ -160,32 +662,86 @@ impl GdmConfig for DefaultGdmConfig {
         Ok(!plugins.is_empty())
     }
 
+    /// Loads the config file merged with its local overlay, e.g. gdm.local.json
     fn load(&self) -> Result<DefaultGdmConfigMetadata> {
         let config_file_path = self.app_config.get_config_file_path();
 
-        if !self.file_service.file_exists(config_file_path)? {
-            return Ok(DefaultGdmConfigMetadata::default());
+        let config = self.read_config_file(config_file_path)?.unwrap_or_default();
+        match self.read_local_config_file()? {
+            Some(local_config) => {
+                debug!(
+                    "Applying local overrides for plugins: {:?}",
+                    local_config.plugins.keys()
+                );
+                Ok(config.with_local_overrides(&local_config))
+            }
+            None => Ok(config),
         }
+    }
+
+    /// Rewrites a config file with an older layout in the current format version,
+    /// keeping the original next to it, e.g. in `gdm.json.v1.bak`.
+    /// Files that only lack the version get it on the next save.
+    fn migrate(&self) -> Result<Option<u64>> {
+        let config_file_path = self.app_config.get_config_file_path();
+
+        let Some(config) = self.read_config_file(config_file_path)? else {
+            return Ok(None);
+        };
         let content = self.file_service.read_file_cached(config_file_path)?;
-        let config: DefaultGdmConfigMetadata =
-            serde_json::from_str(&content).with_context(|| {
+        let mut manifest = GdmConfigFormat::from_path(config_file_path).parse_value(&content)?;
+        let Some(from_version) = migration::migrate(&mut manifest)?
+            .filter(|result| result.layout_changed)
+            .map(|result| result.from_version)
+        else {
+            return Ok(None);
+        };
+
+        let backup_file_path = Self::migration_backup_path(config_file_path, from_version);
+        self.file_service.write_file(&backup_file_path, &content)?;
+        self.save(&config)?;
+        Ok(Some(from_version))
+    }
+
+    fn validate(&self) -> Result<DefaultGdmConfigMetadata> {
+        let config_file_path = self.app_config.get_config_file_path();
+
+        if !self.file_service.file_exists(config_file_path)? {
+            bail!(GdmError::new(
+                ErrorKind::ProjectFile,
                 format!(
-                    "Failed to parse plugin config file: {}",
+                    "No {} file found in the current directory.",
                     config_file_path.display()
                 )
-            })?;
-        Ok(config)
+            ))
+        }
+        self.load()
     }
 
     fn save(&self, config: &DefaultGdmConfigMetadata) -> Result<String> {
         let config_file_path = self.app_config.get_config_file_path();
 
-        let content = serde_json::to_string_pretty(config).with_context(|| {
-            format!(
-                "Failed to serialize configuration to JSON: {}",
-                config_file_path.display()
-            )
-        })?;
+        let config = &match self.read_local_config_file()? {
+            Some(local_config) => {
+                let shared_config = self.read_config_file(config_file_path)?.unwrap_or_default();
+                config.without_local_overrides(&shared_config, &local_config)
+            }
+            None => config.clone(),
+        };
+
+        let previous_content = if self.file_service.file_exists(config_file_path)? {
+            self.file_service.read_file_cached(config_file_path).ok()
+        } else {
+            None
+        };
+        let content = GdmConfigFormat::from_path(config_file_path)
+            .serialize(config, previous_content.as_deref())
+            .with_context(|| {
+                format!(
+                    "Failed to serialize configuration: {}",
+                    config_file_path.display()
+                )
+            })?;
 
         self.file_service.write_file(config_file_path, &content)?;
         info!(
@@ -198,13 +754,16 @@ impl GdmConfig for DefaultGdmConfig {
 
 pub trait GdmConfig {
     fn add_plugins(&self, plugins: &BTreeMap<String, Plugin>) -> Result<DefaultGdmConfigMetadata>;
+    fn enable_plugins(&self, plugin_keys: HashSet<String>) -> Result<DefaultGdmConfigMetadata>;
     fn get_plugin_by_asset_id(&self, asset_id: &str) -> Result<Option<Plugin>>;
     fn get_plugin_by_name(&self, name: &str) -> Option<(String, Plugin)>;
     fn get_plugins(&self) -> Result<BTreeMap<String, Plugin>>;
     fn has_installed_plugins(&self) -> Result<bool>;
     fn load(&self) -> Result<DefaultGdmConfigMetadata>;
+    fn migrate(&self) -> Result<Option<u64>>;
     fn remove_plugins(&self, plugin_keys: HashSet<String>) -> Result<DefaultGdmConfigMetadata>;
     fn save(&self, config: &DefaultGdmConfigMetadata) -> Result<String>;
+    fn validate(&self) -> Result<DefaultGdmConfigMetadata>;
 }
 
 #[cfg(test)]
@@ -301,6 +860,487 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_should_keep_autoload_when_replacing_plugins() {
+        let mut existing = Plugin::create_mock_plugin_1();
+        existing.autoload = BTreeMap::from([(
+            "Awesome".to_string(),
+            "*res://addons/awesome_plugin/awesome.gd".to_string(),
+        )]);
+        let plugin_config =
+            DefaultGdmConfigMetadata::new(BTreeMap::from([("plugin_1".to_string(), existing)]));
+        let new_plugins = BTreeMap::from([(
+            "plugin_1".to_string(),
+            Plugin::new_asset_store_plugin(
+                "54321".to_string(),
+                Some("addons/awesome_plugin/plugin.cfg".into()),
+                "Awesome Plugin".to_string(),
+                "1.8.0".to_string(),
+                "MIT".to_string(),
+                vec![],
+            ),
+        )]);
+
+        let updated_plugin_config = plugin_config.add_plugins(&new_plugins);
+        let plugin = updated_plugin_config.plugins.get("plugin_1").unwrap();
+
+        assert_eq!(plugin.version, "1.8.0");
+        assert_eq!(
+            plugin.autoload.get("Awesome"),
+            Some(&"*res://addons/awesome_plugin/awesome.gd".to_string())
+        );
+    }
+
+    #[test]
+    fn test_should_keep_addons_dir_when_adding_and_removing_plugins() {
+        let mut plugin_config = setup_test_plugin_config();
+        plugin_config.addons_dir = Some("addons/third_party".to_string());
+
+        let added = plugin_config.add_plugins(&BTreeMap::from([(
+            "plugin_3".to_string(),
+            Plugin::create_mock_plugin_1(),
+        )]));
+        assert_eq!(added.addons_dir, Some("addons/third_party".to_string()));
+
+        let removed = added.remove_plugins(HashSet::from(["plugin_3".to_string()]));
+        assert_eq!(removed.addons_dir, Some("addons/third_party".to_string()));
+    }
+
+    // dev_plugins
+
+    #[test]
+    fn test_should_parse_dev_plugins() {
+        let content = json!({
+            "plugins": {
+                "plugin_1": Plugin::create_mock_plugin_1(),
+            },
+            "dev_plugins": {
+                "plugin_2": Plugin::create_mock_plugin_2(),
+            }
+        });
+
+        let plugin_config: DefaultGdmConfigMetadata = serde_json::from_value(content).unwrap();
+
+        assert_eq!(plugin_config.plugins.len(), 2);
+        assert!(!plugin_config.plugins.get("plugin_1").unwrap().dev);
+        assert!(plugin_config.plugins.get("plugin_2").unwrap().dev);
+    }
+
+    #[test]
+    fn test_should_write_dev_plugins_to_own_section() {
+        let mut plugin_config = setup_test_plugin_config();
+        plugin_config.plugins.get_mut("plugin_2").unwrap().dev = true;
+
+        let content = serde_json::to_value(&plugin_config).unwrap();
+
+        assert!(content["plugins"].get("plugin_1").is_some());
+        assert!(content["plugins"].get("plugin_2").is_none());
+        assert!(content["dev_plugins"].get("plugin_2").is_some());
+    }
+
+    #[test]
+    fn test_should_not_write_empty_dev_plugins() {
+        let content = serde_json::to_value(setup_test_plugin_config()).unwrap();
+        assert!(content.get("dev_plugins").is_none());
+    }
+
+    #[test]
+    fn test_should_fail_when_plugin_is_in_both_sections() {
+        let content = json!({
+            "plugins": { "plugin_1": Plugin::create_mock_plugin_1() },
+            "dev_plugins": { "plugin_1": Plugin::create_mock_plugin_1() }
+        });
+
+        let result = serde_json::from_value::<DefaultGdmConfigMetadata>(content);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Plugin plugin_1 is listed in both plugins and dev_plugins")
+        );
+    }
+
+    #[test]
+    fn test_without_dev_plugins() {
+        let mut plugin_config = setup_test_plugin_config();
+        plugin_config.plugins.get_mut("plugin_2").unwrap().dev = true;
+
+        let (plugin_config, dev_plugins) = plugin_config.without_dev_plugins();
+
+        assert_eq!(
+            plugin_config.plugins.keys().collect::<Vec<_>>(),
+            vec!["plugin_1"]
+        );
+        assert_eq!(dev_plugins, vec![Plugin::create_mock_plugin_2()]);
+    }
+
+    // GdmConfigFormat
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            GdmConfigFormat::from_path(Path::new("gdm.toml")),
+            GdmConfigFormat::Toml
+        );
+        assert_eq!(
+            GdmConfigFormat::from_path(Path::new("gdm.json")),
+            GdmConfigFormat::Json
+        );
+    }
+
+    #[test]
+    fn test_toml_format_should_round_trip() {
+        let mut plugin_config = setup_test_plugin_config();
+        plugin_config.plugins.get_mut("plugin_2").unwrap().dev = true;
+
+        let content = GdmConfigFormat::Toml
+            .serialize(&plugin_config, None)
+            .unwrap();
+
+        assert!(content.contains("[dev_plugins.plugin_2]"));
+        assert_eq!(
+            GdmConfigFormat::Toml.parse(&content).unwrap(),
+            plugin_config
+        );
+    }
+
+    #[test]
+    fn test_toml_format_should_keep_comments() {
+        let plugin_config = setup_test_plugin_config();
+        let previous_content = GdmConfigFormat::Toml
+            .serialize(&plugin_config, None)
+            .unwrap()
+            .replacen(
+                "[plugins.plugin_1]",
+                "# Pinned for the demo\n[plugins.plugin_1]",
+                1,
+            )
+            .replacen(
+                "title = \"Awesome Plugin\"",
+                "title = \"Awesome Plugin\" # display name",
+                1,
+            );
+
+        let content = GdmConfigFormat::Toml
+            .serialize(&plugin_config, Some(&previous_content))
+            .unwrap();
+
+        assert!(content.contains("# Pinned for the demo\n[plugins.plugin_1]"));
+        assert!(content.contains("# display name"));
+        assert!(!content.contains("[plugins.plugin_1.source ]"));
+    }
+
+    #[test]
+    fn test_parse_error_should_name_the_field() {
+        let content = r#"{
+  "plugins": {
+    "gut": { "title": "Gut", "version": 9 }
+  }
+}"#;
+
+        let error = GdmConfigFormat::Json
+            .parse(content)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("plugins.gut.version: invalid type: integer `9`, expected a string")
+        );
+        assert!(error.contains("line 3"));
+    }
+
+    #[test]
+    fn test_toml_parse_error_should_name_the_field() {
+        let content = "[plugins.gut]\ntitle = \"Gut\"\nversion = 9\n";
+
+        let error = GdmConfigFormat::Toml
+            .parse(content)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("plugins.gut.version"));
+        assert!(error.contains("line 3"));
+    }
+
+    #[test]
+    fn test_parse_error_should_describe_invalid_source() {
+        let content = r#"{ "plugins": { "gut": { "source": { "id": "1709" }, "title": "Gut", "version": "9.5.0" } } }"#;
+
+        let error = GdmConfigFormat::Json
+            .parse(content)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("plugins.gut.source: expected an asset_id, or a url and reference"));
+    }
+
+    #[test]
+    fn test_should_keep_schema_reference() {
+        let content = r#"{ "$schema": "./gdm.schema.json", "plugins": {} }"#;
+
+        let plugin_config = GdmConfigFormat::Json.parse(content).unwrap();
+        assert_eq!(plugin_config.schema.as_deref(), Some("./gdm.schema.json"));
+
+        let content = GdmConfigFormat::Json
+            .serialize(&plugin_config, None)
+            .unwrap();
+        assert!(content.contains(r#""$schema": "./gdm.schema.json""#));
+    }
+
+    #[test]
+    fn test_schema_should_list_config_file_fields() {
+        let schema: serde_json::Value =
+            serde_json::from_str(include_str!("../../gdm.schema.json")).unwrap();
+        let mut plugin_config = setup_test_plugin_config();
+        plugin_config.schema = Some("gdm.schema.json".to_string());
+        plugin_config.project = Some(GdmProjectMetadata {
+            name: Some("My Game".to_string()),
+            godot_version: Some(">=4.3".to_string()),
+            licenses: Some(LicensePolicy {
+                allow: vec!["MIT".to_string()],
+                deny: vec![],
+            }),
+            credits: Some("CREDITS.md".to_string()),
+            trusted_keys: vec!["ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAI".to_string()],
+        });
+        plugin_config.addons_dir = Some("addons".to_string());
+        plugin_config.plugins.get_mut("plugin_2").unwrap().dev = true;
+        let plugin_1 = plugin_config.plugins.get_mut("plugin_1").unwrap();
+        plugin_1.godot_version = Some("4.2".to_string());
+        plugin_1.checksum = Some("sha256:abc".to_string());
+        plugin_1.alias = Some("awesome".to_string());
+        plugin_1.allow_prerelease = true;
+        plugin_1.channel = crate::models::UpdateChannel::Edge;
+        plugin_1.verify_signature = true;
+
+        let content = serde_json::to_value(&plugin_config).unwrap();
+
+        for key in content.as_object().unwrap().keys() {
+            assert!(schema["properties"].get(key).is_some(), "{} missing", key);
+        }
+        for key in content["project"].as_object().unwrap().keys() {
+            assert!(
+                schema["properties"]["project"]["properties"]
+                    .get(key)
+                    .is_some(),
+                "project.{} missing",
+                key
+            );
+        }
+        for key in content["plugins"]["plugin_1"].as_object().unwrap().keys() {
+            assert!(
+                schema["$defs"]["plugin"]["properties"].get(key).is_some(),
+                "{} missing",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn test_resolve_config_file_path_should_default_to_json() {
+        let mut mock_file_service = MockDefaultFileService::new();
+        mock_file_service
+            .expect_file_exists()
+            .with(eq(Path::new("gdm.toml")))
+            .returning(|_| Ok(false));
+
+        let path =
+            GdmConfigFormat::resolve_config_file_path(Path::new("gdm.json"), &mock_file_service)
+                .unwrap();
+        assert_eq!(path, PathBuf::from("gdm.json"));
+    }
+
+    #[test]
+    fn test_resolve_config_file_path_should_use_toml() {
+        let mut mock_file_service = MockDefaultFileService::new();
+        mock_file_service
+            .expect_file_exists()
+            .with(eq(Path::new("gdm.toml")))
+            .returning(|_| Ok(true));
+        mock_file_service
+            .expect_file_exists()
+            .with(eq(Path::new("gdm.json")))
+            .returning(|_| Ok(false));
+
+        let path =
+            GdmConfigFormat::resolve_config_file_path(Path::new("gdm.json"), &mock_file_service)
+                .unwrap();
+        assert_eq!(path, PathBuf::from("gdm.toml"));
+    }
+
+    #[test]
+    fn test_resolve_config_file_path_should_fail_with_both_files() {
+        let mut mock_file_service = MockDefaultFileService::new();
+        mock_file_service
+            .expect_file_exists()
+            .returning(|_| Ok(true));
+
+        let result =
+            GdmConfigFormat::resolve_config_file_path(Path::new("gdm.json"), &mock_file_service);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Found both gdm.json and gdm.toml")
+        );
+    }
+
+    #[test]
+    fn test_local_config_file_path() {
+        assert_eq!(
+            GdmConfigFormat::local_config_file_path(Path::new("gdm.json")),
+            PathBuf::from("gdm.local.json")
+        );
+        assert_eq!(
+            GdmConfigFormat::local_config_file_path(Path::new("game/gdm.toml")),
+            PathBuf::from("game/gdm.local.toml")
+        );
+    }
+
+    // local overrides
+
+    fn setup_local_overrides() -> DefaultGdmConfigMetadata {
+        let mut pinned_plugin = Plugin::create_mock_plugin_1();
+        pinned_plugin.version = "0.9.0".to_string();
+        DefaultGdmConfigMetadata::new(BTreeMap::from([
+            ("plugin_1".to_string(), pinned_plugin),
+            ("plugin_3".to_string(), Plugin::create_mock_plugin_3()),
+        ]))
+    }
+
+    #[test]
+    fn test_with_local_overrides_should_replace_and_add_plugins() {
+        let config = setup_test_plugin_config().with_local_overrides(&setup_local_overrides());
+
+        assert_eq!(config.plugins.len(), 3);
+        assert_eq!(config.plugins["plugin_1"].version, "0.9.0");
+        assert_eq!(config.plugins["plugin_2"], Plugin::create_mock_plugin_2());
+        assert_eq!(config.plugins["plugin_3"], Plugin::create_mock_plugin_3());
+    }
+
+    #[test]
+    fn test_without_local_overrides_should_restore_shared_plugins() {
+        let shared = setup_test_plugin_config();
+        let local = setup_local_overrides();
+
+        let config = shared
+            .with_local_overrides(&local)
+            .without_local_overrides(&shared, &local);
+
+        assert_eq!(config, shared);
+    }
+
+    #[test]
+    fn test_without_local_overrides_should_keep_changed_plugins() {
+        let shared = setup_test_plugin_config();
+        let local = setup_local_overrides();
+        let mut updated_plugin = Plugin::create_mock_plugin_1();
+        updated_plugin.version = "1.1.0".to_string();
+
+        let config = shared
+            .with_local_overrides(&local)
+            .add_plugins(&BTreeMap::from([("plugin_1".to_string(), updated_plugin)]))
+            .without_local_overrides(&shared, &local);
+
+        assert_eq!(config.plugins["plugin_1"].version, "1.1.0");
+        assert!(!config.plugins.contains_key("plugin_3"));
+    }
+
+    // project
+
+    fn setup_project(godot_version: &str) -> GdmProjectMetadata {
+        GdmProjectMetadata {
+            godot_version: Some(godot_version.to_string()),
+            ..GdmProjectMetadata::default()
+        }
+    }
+
+    #[test]
+    fn test_should_parse_project_section() {
+        let content = r#"{ "project": { "name": "My Game", "godot_version": ">=4.3, <4.7" }, "plugins": {} }"#;
+
+        let plugin_config = GdmConfigFormat::Json.parse(content).unwrap();
+        let project = plugin_config.project.clone().unwrap();
+        assert_eq!(project.name.as_deref(), Some("My Game"));
+        assert_eq!(project.godot_version.as_deref(), Some(">=4.3, <4.7"));
+
+        let content = GdmConfigFormat::Json
+            .serialize(&plugin_config, None)
+            .unwrap();
+        assert!(content.contains(r#""godot_version": ">=4.3, <4.7""#));
+    }
+
+    #[test]
+    fn test_license_policy_allows() {
+        let policy: LicensePolicy = serde_json::from_str(
+            r#"{ "allow": ["MIT", "Apache-2.0", "GPLv3"], "deny": ["gplv3"] }"#,
+        )
+        .unwrap();
+        assert!(policy.allows("MIT"));
+        assert!(policy.allows("apache-2.0"));
+        assert!(!policy.allows("GPLv3"));
+        assert!(!policy.allows("CC0"));
+
+        let policy = LicensePolicy {
+            allow: vec![],
+            deny: vec!["GPLv3".to_string()],
+        };
+        assert!(policy.allows("CC0"));
+        assert!(!policy.allows("GPLv3"));
+    }
+
+    #[test]
+    fn test_should_fail_on_invalid_godot_version_range() {
+        let content = r#"{ "project": { "godot_version": "four" }, "plugins": {} }"#;
+
+        let error = GdmConfigFormat::Json
+            .parse(content)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("project.godot_version: invalid Godot version range \"four\""));
+    }
+
+    #[test]
+    fn test_allows_godot_version() {
+        let project = setup_project(">=4.3, <4.7");
+        assert!(project.allows_godot_version("4.3").unwrap());
+        assert!(project.allows_godot_version("4.6").unwrap());
+        assert!(!project.allows_godot_version("4.7").unwrap());
+        assert!(!project.allows_godot_version("3.6").unwrap());
+        assert!(
+            GdmProjectMetadata::default()
+                .allows_godot_version("4.7")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_lowest_godot_version() {
+        let cases = [
+            (">=4.3, <4.7", Some("4.3")),
+            ("<4.7, >=4.2", Some("4.2")),
+            ("~4.5", Some("4.5")),
+            ("4", Some("4.0")),
+            (">4.3", Some("4.4")),
+            (">4", Some("5.0")),
+            ("<4.7", None),
+        ];
+        for (range, expected) in cases {
+            assert_eq!(
+                setup_project(range)
+                    .lowest_godot_version()
+                    .unwrap()
+                    .as_deref(),
+                expected,
+                "{}",
+                range
+            );
+        }
+        assert_eq!(
+            GdmProjectMetadata::default()
+                .lowest_godot_version()
+                .unwrap(),
+            None
+        );
+    }
+
     #[test]
     fn test_should_replace_old_plugins() {
         let plugin_config = setup_test_plugin_config();
@@ -492,7 +1532,8 @@ mod tests {
         let plugin_config = setup_test_plugin_config();
         let plugin_opt = plugin_config.get_plugin_by_name("plugin_1");
         assert!(plugin_opt.is_some());
-        let plugin = plugin_opt.unwrap();
+        let (key, plugin) = plugin_opt.unwrap();
+        assert_eq!(key, "plugin_1");
         assert_eq!(
             plugin.source,
             Some(PluginSource::AssetLibrary {
@@ -501,6 +1542,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_plugin_by_name_should_ignore_case() {
+        let plugin_config = setup_test_plugin_config();
+
+        let (key, _) = plugin_config.get_plugin_by_name("PLUGIN_1").unwrap();
+        assert_eq!(key, "plugin_1");
+        let (key, _) = plugin_config.get_plugin_by_name("awesome plugin").unwrap();
+        assert_eq!(key, "plugin_1");
+    }
+
+    #[test]
+    fn test_get_plugin_by_name_should_accept_unambiguous_prefix() {
+        let plugin_config = setup_test_plugin_config();
+
+        let (key, _) = plugin_config.get_plugin_by_name("awe").unwrap();
+        assert_eq!(key, "plugin_1");
+    }
+
+    #[test]
+    fn test_get_plugin_by_name_with_ambiguous_prefix_should_return_none() {
+        let plugin_config = setup_test_plugin_config();
+
+        assert!(plugin_config.get_plugin_by_name("plug").is_none());
+        assert_eq!(plugin_config.find_plugins_by_name("plug").len(), 2);
+    }
+
+    #[test]
+    fn test_get_plugin_by_name_should_find_plugin_by_alias() {
+        let mut plugin_config = setup_test_plugin_config();
+        plugin_config.plugins.get_mut("plugin_1").unwrap().alias = Some("awesome".to_string());
+
+        let (key, plugin) = plugin_config.get_plugin_by_name("awesome").unwrap();
+        assert_eq!(key, "plugin_1");
+        assert_eq!(plugin.title, "Awesome Plugin");
+        assert!(plugin_config.get_plugin_by_name("superb").is_none());
+    }
+
+    #[test]
+    fn test_should_fail_when_alias_is_used_twice() {
+        let content = json!({
+            "plugins": {
+                "plugin_1": { "title": "Awesome Plugin", "version": "1.0.0", "alias": "plugin_2" },
+                "plugin_2": { "title": "Super Plugin", "version": "2.1.3" }
+            }
+        });
+
+        let result = serde_json::from_value::<DefaultGdmConfigMetadata>(content);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Alias plugin_2 of plugin plugin_1 is already used by another plugin")
+        );
+    }
+
     // remove_installed_plugin
 
     #[test]
@@ -652,6 +1748,201 @@ mod tests {
         assert_eq!(config, expected_plugin_config);
     }
 
+    fn setup_gdm_config_with_local_overrides() -> (DefaultGdmConfig, RecordedWrites) {
+        let writes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded_writes = writes.clone();
+
+        let mut mock_file_service = MockDefaultFileService::new();
+        mock_file_service
+            .expect_file_exists()
+            .returning(|_| Ok(true));
+        mock_file_service
+            .expect_read_file_cached()
+            .returning(|path| {
+                let config = if path == Path::new("gdm.local.json") {
+                    setup_local_overrides()
+                } else {
+                    setup_test_plugin_config()
+                };
+                Ok(serde_json::to_string(&config).unwrap())
+            });
+        mock_file_service
+            .expect_write_file()
+            .returning(move |path, content| {
+                recorded_writes
+                    .lock()
+                    .unwrap()
+                    .push((path.to_path_buf(), content.to_string()));
+                Ok(())
+            });
+
+        let gdm_config =
+            DefaultGdmConfig::new(DefaultAppConfig::default(), Arc::new(mock_file_service));
+        (gdm_config, writes)
+    }
+
+    #[test]
+    fn test_load_should_apply_local_overrides() {
+        let (gdm_config, _) = setup_gdm_config_with_local_overrides();
+
+        let config = gdm_config.load().unwrap();
+        assert_eq!(config.plugins.len(), 3);
+        assert_eq!(config.plugins["plugin_1"].version, "0.9.0");
+    }
+
+    #[test]
+    fn test_save_should_not_write_local_overrides() {
+        let (gdm_config, writes) = setup_gdm_config_with_local_overrides();
+
+        let config = gdm_config.load().unwrap();
+        let config = config.remove_plugins(HashSet::from(["plugin_2".to_string()]));
+        gdm_config.save(&config).unwrap();
+
+        let writes = writes.lock().unwrap();
+        assert_eq!(writes.len(), 1);
+        assert_eq!(writes[0].0, PathBuf::from("gdm.json"));
+        let saved: DefaultGdmConfigMetadata = serde_json::from_str(&writes[0].1).unwrap();
+        assert_eq!(
+            saved.plugins,
+            BTreeMap::from([("plugin_1".to_string(), Plugin::create_mock_plugin_1())])
+        );
+    }
+
+    // migrate
+
+    type RecordedWrites = Arc<std::sync::Mutex<Vec<(PathBuf, String)>>>;
+
+    fn setup_gdm_config_for_migration(
+        content: &'static str,
+        expected_writes: usize,
+    ) -> (DefaultGdmConfig, RecordedWrites) {
+        let writes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded_writes = writes.clone();
+
+        let mut mock_file_service = MockDefaultFileService::new();
+        mock_file_service
+            .expect_file_exists()
+            .returning(|path| Ok(path == Path::new("gdm.json")));
+        mock_file_service
+            .expect_read_file_cached()
+            .returning(move |_| Ok(content.to_string()));
+        mock_file_service
+            .expect_write_file()
+            .times(expected_writes)
+            .returning(move |path, content| {
+                recorded_writes
+                    .lock()
+                    .unwrap()
+                    .push((path.to_path_buf(), content.to_string()));
+                Ok(())
+            });
+
+        let gdm_config =
+            DefaultGdmConfig::new(DefaultAppConfig::default(), Arc::new(mock_file_service));
+        (gdm_config, writes)
+    }
+
+    #[test]
+    fn test_load_should_migrate_legacy_layout_in_memory() {
+        let plugin_config_repository = DefaultGdmConfig::new(
+            DefaultAppConfig::new(
+                None,
+                Some(String::from("tests/mocks/gdm_test_1.json")),
+                None,
+                None,
+                None,
+            ),
+            Arc::new(DefaultFileService),
+        );
+        let config = plugin_config_repository.load().unwrap();
+        assert_eq!(
+            config.plugins.get("plugin_1").unwrap().source,
+            Some(PluginSource::AssetLibrary {
+                asset_id: "54321".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_migrate_should_back_up_and_rewrite_legacy_layout() {
+        const LEGACY_CONTENT: &str = r#"{ "plugins": { "gut": { "asset_id": "1709", "title": "Gut", "version": "9.1.0" } } }"#;
+        let (gdm_config, writes) = setup_gdm_config_for_migration(LEGACY_CONTENT, 2);
+
+        assert_eq!(gdm_config.migrate().unwrap(), Some(1));
+
+        let writes = writes.lock().unwrap();
+        assert_eq!(writes[0].0, PathBuf::from("gdm.json.v1.bak"));
+        assert_eq!(writes[0].1, LEGACY_CONTENT);
+        assert_eq!(writes[1].0, PathBuf::from("gdm.json"));
+        let saved: serde_json::Value = serde_json::from_str(&writes[1].1).unwrap();
+        assert_eq!(saved["format_version"], 2);
+        assert_eq!(saved["plugins"]["gut"]["source"]["asset_id"], "1709");
+        assert!(saved["plugins"]["gut"].get("asset_id").is_none());
+    }
+
+    #[test]
+    fn test_migrate_should_not_rewrite_when_only_version_is_missing() {
+        let (gdm_config, _writes) = setup_gdm_config_for_migration(
+            r#"{ "plugins": { "gut": { "source": { "asset_id": "1709" }, "title": "Gut", "version": "9.1.0" } } }"#,
+            0,
+        );
+
+        assert_eq!(gdm_config.migrate().unwrap(), None);
+    }
+
+    #[test]
+    fn test_migrate_should_fail_on_newer_format_version() {
+        let (gdm_config, _writes) =
+            setup_gdm_config_for_migration(r#"{ "format_version": 3, "plugins": {} }"#, 0);
+
+        let result = gdm_config.migrate();
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("format_version 3 is newer than the newest format this gdm supports")
+        );
+    }
+
+    // validate
+
+    #[test]
+    fn test_validate_should_fail_without_config_file() {
+        let plugin_config_repository = DefaultGdmConfig::new(
+            DefaultAppConfig::new(
+                None,
+                Some(String::from("tests/mocks/non_existent_file.json")),
+                None,
+                None,
+                None,
+            ),
+            Arc::new(DefaultFileService),
+        );
+        let result = plugin_config_repository.validate();
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("No tests/mocks/non_existent_file.json file found")
+        );
+    }
+
+    #[test]
+    fn test_validate_should_return_config() {
+        let plugin_config_repository = DefaultGdmConfig::new(
+            DefaultAppConfig::new(
+                None,
+                Some(String::from("tests/mocks/gdm.json")),
+                None,
+                None,
+                None,
+            ),
+            Arc::new(DefaultFileService),
+        );
+        let config = plugin_config_repository.validate().unwrap();
+        assert_eq!(config.plugins.len(), 2);
+    }
+
     #[test]
     fn test_get_plugins_should_return_correct_plugins() {
         let app_config = DefaultAppConfig::new(
@@ -690,6 +1981,10 @@ mod tests {
             .expect_file_exists()
             .with(eq(test_file_path))
             .returning(|_| Ok(true));
+        mock_file_service
+            .expect_file_exists()
+            .with(eq(Path::new("tests/mocks/gdm.local.json")))
+            .returning(|_| Ok(false));
         mock_file_service
             .expect_write_file()
             .returning(|_, _| Ok(()));
@@ -788,6 +2083,26 @@ mod tests {
         assert_eq!(updated_config.plugins, expected_plugins);
     }
 
+    #[test]
+    fn test_enable_plugins_should_enable_disabled_plugin_added_again() {
+        let plugin_config = DefaultGdmConfigMetadata::new(BTreeMap::from([(
+            "plugin_1".to_string(),
+            Plugin {
+                enabled: false,
+                ..Plugin::create_mock_plugin_1()
+            },
+        )]));
+
+        let updated_config = plugin_config
+            .enable_plugins(HashSet::from(["plugin_1".to_string()]))
+            .add_plugins(&BTreeMap::from([(
+                "plugin_1".to_string(),
+                Plugin::create_mock_plugin_1(),
+            )]));
+
+        assert!(updated_config.plugins["plugin_1"].enabled);
+    }
+
     #[test]
     fn test_remove_plugins_should_not_remove_anything_if_keys_do_not_exist() {
         let plugin_config_repository =
@@ -835,7 +2150,10 @@ mod tests {
         let result = plugin_config_repository.save(&plugin_config);
         assert!(result.is_ok());
 
-        assert_eq!(result.unwrap(), String::from("{\n  \"plugins\": {}\n}"));
+        assert_eq!(
+            result.unwrap(),
+            String::from("{\n  \"format_version\": 2,\n  \"plugins\": {}\n}")
+        );
     }
 
     #[test]
@@ -854,6 +2172,7 @@ mod tests {
         assert!(result.is_ok());
 
         let expected = json!({
+            "format_version": 2,
             "plugins": {
                 "plugin_1": {
                     "source": {
@@ -896,6 +2215,7 @@ mod tests {
         assert!(result.is_ok());
 
         let expected = json!({
+            "format_version": 2,
             "plugins": {
                 "plugin_1": {
                     "source": {
@@ -0,0 +1,191 @@
+use crate::config::InstallMode;
+use crate::error::{ErrorKind, GdmError};
+use crate::models::GitFetchOptions;
+use crate::services::DefaultHttpService;
+use crate::utils::Utils;
+
+use anyhow::{Context, Result, bail};
+use serde_derive::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Settings of the user that apply to every project, e.g. `~/.config/gdm/config.json`:
+///
+/// ```json
+/// {
+///   "credential_helper": "vault-token gdm",
+///   "cache_size": "2GB",
+///   "install_mode": "link",
+///   "git_fetch": { "depth": 50, "tags": true },
+///   "headers": { "X-Api-Key": "secret" },
+///   "git_url_rewrites": { "https://github.com/": "https://git.example.com/github/" },
+///   "config_backups": 50,
+///   "color": "never"
+/// }
+/// ```
+///
+/// Flags and environment variables take precedence over it.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct GlobalConfig {
+    /// Command that prints a token for a host, see --credential-helper
+    pub credential_helper: Option<String>,
+    /// Largest size of the cache directory, e.g. `2GB`, see --cache-size
+    pub cache_size: Option<String>,
+    /// Whether plugins are copied into projects or linked to the shared content store, see --install-mode
+    pub install_mode: Option<InstallMode>,
+    /// How much of the repositories of git plugins is fetched, unless gdm.json sets it for a plugin
+    #[serde(default)]
+    pub git_fetch: GitFetchOptions,
+    /// Extra headers sent with requests to the Asset Library, e.g. an API key
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// URL prefixes of git plugins and what to replace them with before connecting, like git's
+    /// `url.<base>.insteadOf`, e.g. to fetch from an internal mirror
+    #[serde(default)]
+    pub git_url_rewrites: BTreeMap<String, String>,
+    /// Backups of project.godot and gdm.json kept per project, 0 to take none, see
+    /// `gdm restore-config`
+    pub config_backups: Option<usize>,
+    /// When to color the output, see --color
+    pub color: Option<ColorChoice>,
+}
+
+/// When to color the output, set with --color, GDM_COLOR or `color` in the global config
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    /// Color terminals, unless NO_COLOR is set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Enables or disables colors on stdout and stderr for the rest of the run
+    pub fn apply(&self) {
+        let enabled = match self {
+            ColorChoice::Auto => return,
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        };
+        console::set_colors_enabled(enabled);
+        console::set_colors_enabled_stderr(enabled);
+    }
+}
+
+impl fmt::Display for ColorChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ColorChoice::Auto => "auto",
+            ColorChoice::Always => "always",
+            ColorChoice::Never => "never",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for ColorChoice {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<ColorChoice> {
+        match value.to_lowercase().as_str() {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => bail!(
+                "Unknown color choice \"{}\", expected auto, always or never",
+                value
+            ),
+        }
+    }
+}
+
+impl GlobalConfig {
+    /// `config.json` in [`Utils::config_dir`], None if the directory can't be determined
+    pub fn file_path() -> Option<PathBuf> {
+        Utils::config_dir().map(|dir| dir.join("config.json"))
+    }
+
+    /// The global configuration, or the defaults if there's no file
+    pub fn load() -> Result<GlobalConfig> {
+        match Self::file_path() {
+            Some(path) => Self::load_from(&path),
+            None => Ok(GlobalConfig::default()),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Result<GlobalConfig> {
+        if !path.exists() {
+            return Ok(GlobalConfig::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let config: GlobalConfig = match serde_json::from_str(&content) {
+            Ok(config) => config,
+            Err(e) => bail!(GdmError::new(
+                ErrorKind::ConfigInvalid,
+                format!("Invalid global configuration {}: {}", path.display(), e)
+            )),
+        };
+        DefaultHttpService::header_map(&config.headers)
+            .with_context(|| format!("Invalid global configuration {}", path.display()))?;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_from_should_read_file_or_default() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let path = dir.path().join("config.json");
+
+        assert_eq!(
+            GlobalConfig::load_from(&path).unwrap(),
+            GlobalConfig::default()
+        );
+
+        std::fs::write(&path, r#"{"credential_helper": "vault-token gdm"}"#).unwrap();
+        assert_eq!(
+            GlobalConfig::load_from(&path)
+                .unwrap()
+                .credential_helper
+                .as_deref(),
+            Some("vault-token gdm")
+        );
+
+        std::fs::write(&path, r#"{"color": "never"}"#).unwrap();
+        assert_eq!(
+            GlobalConfig::load_from(&path).unwrap().color,
+            Some(ColorChoice::Never)
+        );
+
+        std::fs::write(&path, r#"{"credentials": "vault-token gdm"}"#).unwrap();
+        assert!(GlobalConfig::load_from(&path).is_err());
+    }
+
+    #[test]
+    fn test_load_from_should_validate_headers() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let path = dir.path().join("config.json");
+
+        std::fs::write(&path, r#"{"headers": {"X-Api-Key": "secret"}}"#).unwrap();
+        assert_eq!(
+            GlobalConfig::load_from(&path).unwrap().headers,
+            BTreeMap::from([("X-Api-Key".to_string(), "secret".to_string())])
+        );
+
+        std::fs::write(&path, r#"{"headers": {"X Api Key": "secret"}}"#).unwrap();
+        let err = GlobalConfig::load_from(&path).unwrap_err();
+        assert_eq!(
+            GdmError::find(&err).map(|e| e.kind),
+            Some(ErrorKind::ConfigInvalid)
+        );
+    }
+}
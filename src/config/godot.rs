@@ -1,16 +1,21 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
-use anyhow::{Result, bail};
+use anyhow::{Context, Result, bail};
 use serde_derive::{Deserialize, Serialize};
 use tracing::{debug, error, info};
 
 use crate::config::{AppConfig, DefaultAppConfig};
-use crate::config::{DefaultGdmConfigMetadata, GdmConfigMetadata};
+use crate::config::{DefaultGdmConfigMetadata, GdmConfigMetadata, GdmProjectMetadata};
+use crate::error::{ErrorKind, GdmError};
 use crate::models::Plugin;
 use crate::services::{DefaultFileService, FileService};
+use crate::utils::Utils;
 
 // TODO: Rename all repositories to configs and rename internal structs accordingly
 
+const PACKED_STRING_ARRAY: &str = "PackedStringArray";
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct GodotProjectMetadata {
     config_version: usize,
@@ -37,11 +42,19 @@ impl GodotProjectMetadata {
         self.get_default_godot_version()
     }
 
+    /// Returns true if the Godot version was read from `config/features`
+    pub fn has_godot_version(&self) -> bool {
+        !self.godot_version.is_empty()
+    }
+
     fn get_default_godot_version(&self) -> Result<String> {
         match self.config_version {
             5 => Ok("4.5".to_string()),
             4 => Ok("3.6".to_string()),
-            _ => bail!("Unsupported config_version: {}", self.config_version),
+            _ => bail!(GdmError::new(
+                ErrorKind::ProjectFile,
+                format!("Unsupported config_version: {}", self.config_version)
+            )),
         }
     }
 }
@@ -55,6 +68,45 @@ impl Default for GodotProjectMetadata {
     }
 }
 
+/// Line endings and byte order mark of project.godot. Lines are read without them and written
+/// back with the ones the file had, so saving doesn't change every line on Windows checkouts.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct ProjectFileFormat {
+    crlf: bool,
+    bom: bool,
+}
+
+impl ProjectFileFormat {
+    const BOM: char = '\u{feff}';
+
+    /// Files with mixed line endings are written with the ones most of their lines use
+    fn detect(content: &str) -> ProjectFileFormat {
+        let line_breaks = content.matches('\n').count();
+        let crlf_line_breaks = content.matches("\r\n").count();
+        ProjectFileFormat {
+            crlf: crlf_line_breaks > 0 && crlf_line_breaks * 2 >= line_breaks,
+            bom: content.starts_with(Self::BOM),
+        }
+    }
+
+    fn lines(content: &str) -> Vec<String> {
+        content
+            .strip_prefix(Self::BOM)
+            .unwrap_or(content)
+            .split('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line).to_string())
+            .collect()
+    }
+
+    fn join(&self, lines: &[String]) -> String {
+        let content = lines.join(if self.crlf { "\r\n" } else { "\n" });
+        match self.bom {
+            true => format!("{}{}", Self::BOM, content),
+            false => content,
+        }
+    }
+}
+
 pub struct DefaultGodotConfig {
     pub file_service: Box<dyn FileService + Send + Sync + 'static>,
     pub app_config: DefaultAppConfig,
@@ -80,12 +132,197 @@ impl DefaultGodotConfig {
             app_config,
         }
     }
+
+    fn plugins_to_resource_paths(plugins: &[Plugin]) -> Vec<String> {
+        plugins
+            .iter()
+            .filter_map(|plugin| plugin.plugin_cfg_path.as_ref())
+            .map(|path| match path.starts_with("res://") {
+                true => path.clone(),
+                false => format!("res://{}", path),
+            })
+            .collect()
+    }
+
+    /// e.g. `PackedStringArray("a", "b")`, escaping quotes and backslashes like Godot
+    fn to_string_array(array_type: &str, paths: &[String]) -> String {
+        let paths = paths
+            .iter()
+            .map(|path| format!("\"{}\"", path.replace('\\', "\\\\").replace('"', "\\\"")))
+            .collect::<Vec<String>>()
+            .join(", ");
+        format!("{}({})", array_type, paths)
+    }
+
+    /// Parses `PackedStringArray("a", "b")` into its values. Values are read between their
+    /// quotes, so paths outside `addons`, or with commas or escaped quotes, are kept as they are.
+    /// Godot 3 writes `PoolStringArray` instead, which is read the same way.
+    fn parse_packed_string_array(value: &str) -> Vec<String> {
+        let mut values = Vec::new();
+        let mut chars = value.chars();
+        while chars.by_ref().any(|c| c == '"') {
+            let mut item = String::new();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => break,
+                    '\\' => item.extend(chars.next()),
+                    c => item.push(c),
+                }
+            }
+            if !item.is_empty() {
+                values.push(item);
+            }
+        }
+        values
+    }
+
+    /// The type of a string array value, e.g. `PoolStringArray` in Godot 3 projects
+    fn string_array_type(value: &str) -> &str {
+        match value.split_once('(') {
+            Some((array_type, _)) if !array_type.trim().is_empty() => array_type.trim(),
+            _ => PACKED_STRING_ARRAY,
+        }
+    }
+
+    /// Lines of Godot's output reporting errors, e.g. `ERROR: Failed loading resource: ...`
+    fn import_errors(output: &str) -> Vec<String> {
+        output
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with("ERROR:") || line.starts_with("SCRIPT ERROR:"))
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Points to the closest parent folder with project.godot, e.g. when gdm runs in `addons`
+    fn missing_project_file_error(&self) -> GdmError {
+        let godot_project_file_path = self.app_config.get_godot_project_file_path();
+        error!(
+            "No project.godot file found in the current directory: {}",
+            godot_project_file_path.display()
+        );
+        let error = GdmError::new(
+            ErrorKind::ProjectFile,
+            "No project.godot file found in the current directory",
+        );
+        match std::env::current_dir()
+            .ok()
+            .and_then(|dir| self.find_project_dir_above(&dir))
+        {
+            Some(project_dir) => error.with_hint(format!(
+                "Found {} in {}. Run gdm there, or pass --project {}.",
+                godot_project_file_path.display(),
+                project_dir.display(),
+                project_dir.display()
+            )),
+            None => error,
+        }
+    }
+
+    /// The closest folder above `dir` with project.godot
+    fn find_project_dir_above(&self, dir: &Path) -> Option<PathBuf> {
+        let godot_project_file_path = self.app_config.get_godot_project_file_path();
+        if godot_project_file_path.is_absolute() {
+            return None;
+        }
+        dir.ancestors()
+            .skip(1)
+            .find(|ancestor| {
+                self.file_service
+                    .file_exists(&ancestor.join(godot_project_file_path))
+                    .unwrap_or(false)
+            })
+            .map(Path::to_path_buf)
+    }
+
+    /// Index of the line after the section starting at `index`, i.e. the next section header or
+    /// the end of the file
+    fn section_end(contents: &[String], index: usize) -> usize {
+        contents
+            .iter()
+            .skip(index + 1)
+            .position(|line| line.starts_with("["))
+            .map(|i| i + index + 1)
+            .unwrap_or(contents.len())
+    }
+
+    /// Inserts `lines` after the last entry of the section starting at `index`, or after the
+    /// blank line following its header if it has none
+    fn insert_into_section(
+        contents: &mut Vec<String>,
+        index: usize,
+        section_end: usize,
+        lines: Vec<String>,
+    ) {
+        let last_entry = contents[index + 1..section_end]
+            .iter()
+            .rposition(|line| !line.trim().is_empty())
+            .map(|i| i + index + 1);
+        match last_entry {
+            Some(last_entry) => {
+                contents.splice(last_entry + 1..last_entry + 1, lines);
+            }
+            None if contents.get(index + 1).is_some_and(|line| line.is_empty())
+                && index + 1 < section_end =>
+            {
+                contents.splice(index + 2..index + 2, lines);
+            }
+            None => {
+                let mut lines = lines;
+                lines.insert(0, "".to_string());
+                contents.splice(index + 1..index + 1, lines);
+            }
+        }
+    }
+
+    /// Inserts a section before the first section that sorts after it alphabetically,
+    /// or appends it to the end of the file.
+    fn insert_section(contents: &mut Vec<String>, header: &str, section: Vec<String>) {
+        let section_index = contents.iter().position(|line| {
+            line.starts_with("[") && line.ends_with("]") && line.to_lowercase().as_str() > header
+        });
+
+        match section_index {
+            Some(index) => {
+                debug!(
+                    "Inserting {} section before section {}",
+                    header, contents[index]
+                );
+                contents.splice(index..index, section);
+            }
+            None => {
+                debug!("Appending {} section to the end of the file", header);
+                // The section starts on its own line if the file doesn't end with a newline
+                if contents.last().is_some_and(|line| !line.is_empty()) {
+                    contents.push("".to_string());
+                }
+                contents.extend(section);
+            }
+        }
+    }
 }
 
 #[cfg_attr(test, mockall::automock)]
 impl GodotConfig for DefaultGodotConfig {
     fn get_godot_version_from_project(&self) -> Result<String> {
+        if let Some(godot_version) = self.app_config.get_godot_version() {
+            info!("Using Godot version override: {}", godot_version);
+            return Ok(godot_version);
+        }
+        if let Some(godot_version) = self.app_config.get_project_godot_version() {
+            info!(
+                "Using Godot version declared in the project section: {}",
+                godot_version
+            );
+            return Ok(godot_version);
+        }
         let godot_config = self.load()?;
+        if !godot_config.has_godot_version() {
+            match self.get_godot_version_from_binary() {
+                Ok(godot_version) => return Ok(godot_version),
+                Err(e) => debug!("Couldn't detect Godot version from binary: {}", e),
+            }
+        }
         let godot_version = godot_config.get_godot_version()?;
         info!(
             "Retrieved Godot version from project: {}",
@@ -94,50 +331,176 @@ impl GodotConfig for DefaultGodotConfig {
         Ok(godot_version)
     }
 
-    fn plugins_to_packed_string_array(&self, plugins: Vec<Plugin>) -> String {
-        let plugin_paths = plugins
-            .iter()
-            .filter(|plugin| plugin.plugin_cfg_path.is_some())
-            .map(|plugin| format!("\"res://{}\"", plugin.plugin_cfg_path.as_ref().unwrap()))
-            .collect::<Vec<String>>()
-            .join(", ");
-        let packed_string_array = format!("PackedStringArray({})", plugin_paths);
-        info!(
-            "Converted plugins to PackedStringArray: {}",
-            packed_string_array
+    /// Returns a warning if project.godot uses a Godot version outside the range declared in gdm.json
+    fn check_godot_version_range(&self, project: &GdmProjectMetadata) -> Result<Option<String>> {
+        let Some(range) = &project.godot_version else {
+            return Ok(None);
+        };
+        let godot_config = self.load()?;
+        if !godot_config.has_godot_version() {
+            return Ok(None);
+        }
+        let godot_version = godot_config.get_godot_version()?;
+        if project.allows_godot_version(&godot_version)? {
+            return Ok(None);
+        }
+        Ok(Some(format!(
+            "Warning: project.godot uses Godot {}, which is outside the range {} declared in {}",
+            godot_version,
+            range,
+            self.app_config.get_config_file_path().display()
+        )))
+    }
+
+    fn get_godot_version_from_binary(&self) -> Result<String> {
+        let godot_binary_path = self.app_config.get_godot_binary_path();
+        debug!(
+            "Detecting Godot version with: {} --version",
+            godot_binary_path.display()
         );
-        packed_string_array
+        let output = std::process::Command::new(godot_binary_path)
+            .arg("--version")
+            .output()
+            .with_context(|| format!("Failed to run {}", godot_binary_path.display()))?;
+        if !output.status.success() {
+            bail!(
+                "{} --version exited with {}",
+                godot_binary_path.display(),
+                output.status
+            );
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let Some(godot_version) = Utils::parse_godot_version_output(&stdout) else {
+            bail!("Unrecognized Godot version output: {}", stdout.trim());
+        };
+        info!("Retrieved Godot version from binary: {}", godot_version);
+        Ok(godot_version)
+    }
+
+    /// Runs `godot --headless --import` in the project, so the `.import` and `.uid` files of the
+    /// installed plugins exist before the editor is opened. Fails if Godot exits with an error or
+    /// prints `ERROR:` lines, which it does for resources it can't import.
+    fn import_resources(&self) -> Result<()> {
+        let godot_binary_path = self.app_config.get_godot_binary_path();
+        let project_dir = match self.app_config.get_godot_project_file_path().parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        debug!(
+            "Importing resources with: {} --headless --path {} --import",
+            godot_binary_path.display(),
+            project_dir.display()
+        );
+        let output = std::process::Command::new(godot_binary_path)
+            .arg("--headless")
+            .arg("--path")
+            .arg(project_dir)
+            .arg("--import")
+            .output()
+            .map_err(|e| {
+                GdmError::new(
+                    ErrorKind::ImportFailed,
+                    format!("Failed to run {}: {}", godot_binary_path.display(), e),
+                )
+                .with_hint("Pass the Godot 4.2 or newer executable with --godot-binary or GDM_GODOT_BINARY.")
+            })?;
+        let output_text = format!(
+            "{}\n{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        debug!("Godot import output:\n{}", output_text.trim());
+        let errors = Self::import_errors(&output_text);
+        if !output.status.success() || !errors.is_empty() {
+            let details = match errors.is_empty() {
+                true => format!(
+                    "{} exited with {}",
+                    godot_binary_path.display(),
+                    output.status
+                ),
+                false => errors.join("\n"),
+            };
+            bail!(GdmError::new(
+                ErrorKind::ImportFailed,
+                format!(
+                    "Godot failed to import the project's resources:\n{}",
+                    details
+                )
+            ));
+        }
+        info!("Imported resources with {}", godot_binary_path.display());
+        Ok(())
+    }
+
+    fn get_enabled_plugins(&self) -> Result<Vec<String>> {
+        let contents = self.load_project_file()?;
+        let editor_plugins_index = contents
+            .iter()
+            .position(|line| line.starts_with("[editor_plugins]"));
+
+        let enabled_plugins = editor_plugins_index
+            .and_then(|index| {
+                contents
+                    .iter()
+                    .skip(index + 1)
+                    .take_while(|line| !line.starts_with("["))
+                    .find_map(|line| line.strip_prefix("enabled="))
+            })
+            .map(Self::parse_packed_string_array)
+            .unwrap_or_default();
+        Ok(enabled_plugins)
+    }
+
+    /// Returns the plugins enabled in project.godot that aren't listed in gdm.json
+    fn get_unmanaged_plugins(&self, gdm_config: &DefaultGdmConfigMetadata) -> Result<Vec<String>> {
+        let managed_paths: HashSet<String> = Self::plugins_to_resource_paths(
+            &gdm_config.plugins.values().cloned().collect::<Vec<_>>(),
+        )
+        .into_iter()
+        .collect();
+        let unmanaged_plugins = self
+            .get_enabled_plugins()?
+            .into_iter()
+            .filter(|path| !managed_paths.contains(path))
+            .collect();
+        Ok(unmanaged_plugins)
     }
 
     fn save(&self, gdm_config: DefaultGdmConfigMetadata) -> Result<()> {
+        self.save_with_removed(gdm_config, vec![])
+    }
+
+    /// Saves the project file and unregisters the autoloads of the removed plugins
+    fn save_with_removed(
+        &self,
+        gdm_config: DefaultGdmConfigMetadata,
+        removed_plugins: Vec<Plugin>,
+    ) -> Result<()> {
         let godot_project_file_path = self.app_config.get_godot_project_file_path();
         if !self.file_service.file_exists(godot_project_file_path)? {
-            error!(
-                "No project.godot file found in the current directory: {}",
-                godot_project_file_path.display()
-            );
-            bail!("No project.godot file found in the current directory");
+            bail!(self.missing_project_file_error());
         }
-        let lines = self.update_project_file(gdm_config)?;
+        let lines = self.update_project_file(gdm_config.clone(), &removed_plugins)?;
+        let lines = self.update_autoload_section(lines, &gdm_config, &removed_plugins)?;
         self.save_project_file(lines)
     }
 
     fn load(&self) -> Result<GodotProjectMetadata> {
         let godot_project_file_path = self.app_config.get_godot_project_file_path();
         if !self.file_service.file_exists(godot_project_file_path)? {
-            error!(
-                "No project.godot file found in the current directory: {}",
-                godot_project_file_path.display()
-            );
-            bail!("No project.godot file found in the current directory");
+            bail!(self.missing_project_file_error());
         }
         self.read_godot_project_file()
     }
 
     /// Updates the plugins in the Godot project file and returns the updated lines.
+    /// Enabled plugins not listed in gdm.json or in the removed plugins are preserved.
+    /// Only the `enabled` line changes, keeping the order of the plugins already in it, so other
+    /// lines, blank lines and comments stay as they are. The section is added or removed as a
+    /// whole when the first plugin is enabled or the last one is removed.
     ///
     /// godot.project plugin format:
-    /// ```
+    /// ```ini
     /// [editor_plugins]
     ///
     /// enabled=PackedStringArray("res://addons/gd_flow/plugin.cfg")
@@ -147,50 +510,109 @@ impl GodotConfig for DefaultGodotConfig {
     fn update_project_file(
         &self,
         gdm_config_metadata: DefaultGdmConfigMetadata,
+        removed_plugins: &[Plugin],
     ) -> Result<Vec<String>> {
         let plugin_config_plugins = gdm_config_metadata.get_plugins(true);
-        let _plugins = plugin_config_plugins
+        let enabled_plugins = plugin_config_plugins
             .values()
+            .filter(|plugin| plugin.enabled)
             .cloned()
             .collect::<Vec<Plugin>>();
+        let enabled_managed_paths = Self::plugins_to_resource_paths(&enabled_plugins);
 
         let mut contents = self.load_project_file()?;
 
-        if contents.last().unwrap() != "" {
-            contents.push("".to_string());
-        }
-
         let editor_plugins_index = contents
             .iter()
             .position(|line| line.starts_with("[editor_plugins]"));
+        let section_end = editor_plugins_index.map(|index| Self::section_end(&contents, index));
 
-        if _plugins.is_empty() {
-            // If there are no plugins, we need to remove the [editor_plugins] section if it exists.
-            if let Some(index) = editor_plugins_index {
-                info!("Removing [editor_plugins] section from Godot project file");
-                for _ in 0..4 {
-                    contents.remove(index);
-                }
+        let plugin_index = match (editor_plugins_index, section_end) {
+            (Some(index), Some(section_end)) => contents[index + 1..section_end]
+                .iter()
+                .position(|line| line.starts_with("enabled="))
+                .map(|i| i + index + 1),
+            _ => None,
+        };
+
+        let managed_paths: HashSet<String> = Self::plugins_to_resource_paths(
+            &gdm_config_metadata
+                .plugins
+                .values()
+                .chain(removed_plugins.iter())
+                .cloned()
+                .collect::<Vec<_>>(),
+        )
+        .into_iter()
+        .collect();
+        let current_paths: Vec<String> = plugin_index
+            .map(|index| Self::parse_packed_string_array(&contents[index]["enabled=".len()..]))
+            .unwrap_or_default();
+        let unmanaged_paths: Vec<&String> = current_paths
+            .iter()
+            .filter(|path| !managed_paths.contains(*path))
+            .collect();
+
+        if !unmanaged_paths.is_empty() {
+            info!("Preserving unmanaged editor plugins: {:?}", unmanaged_paths);
+        }
+
+        // Plugins that stay enabled keep their place, newly enabled ones are appended
+        let mut enabled_paths: Vec<String> = current_paths
+            .iter()
+            .filter(|path| !managed_paths.contains(*path) || enabled_managed_paths.contains(path))
+            .cloned()
+            .collect();
+        for path in enabled_managed_paths {
+            if !enabled_paths.contains(&path) {
+                enabled_paths.push(path);
             }
+        }
+
+        if plugin_index.is_some() && enabled_paths == current_paths {
+            debug!("Enabled editor plugins are unchanged");
             return Ok(contents);
         }
 
-        let plugin_index = match editor_plugins_index {
-            Some(index) => contents
+        if enabled_paths.is_empty() {
+            let (Some(index), Some(plugin_index)) = (editor_plugins_index, plugin_index) else {
+                return Ok(contents);
+            };
+            contents.remove(plugin_index);
+            let section_end = Self::section_end(&contents, index);
+            if contents[index + 1..section_end]
                 .iter()
-                .skip(index + 1)
-                .position(|line| line.starts_with("enabled="))
-                .map(|i| i + index + 1),
-            None => None,
-        };
+                .all(|line| line.trim().is_empty())
+            {
+                info!("Removing [editor_plugins] section from Godot project file");
+                contents.drain(index..section_end);
+            }
+            return Ok(contents);
+        }
+
+        // Keeps PoolStringArray in Godot 3 projects
+        let array_type = plugin_index
+            .map(|index| Self::string_array_type(&contents[index]["enabled=".len()..]))
+            .unwrap_or(PACKED_STRING_ARRAY);
+        let packed_string_array = Self::to_string_array(array_type, &enabled_paths);
+        info!(
+            "Converted plugins to PackedStringArray: {}",
+            packed_string_array
+        );
+        let enabled_line = format!("enabled={}", packed_string_array);
 
         if let Some(plugin_index) = plugin_index {
             debug!(
                 "Updating existing [editor_plugins] section with plugins: {:?}",
                 gdm_config_metadata.plugins.keys().cloned()
             );
-            contents[plugin_index] =
-                format!("enabled={}", self.plugins_to_packed_string_array(_plugins));
+            contents[plugin_index] = enabled_line;
+            return Ok(contents);
+        }
+
+        if let (Some(index), Some(section_end)) = (editor_plugins_index, section_end) {
+            debug!("Adding enabled plugins to the existing [editor_plugins] section");
+            Self::insert_into_section(&mut contents, index, section_end, vec![enabled_line]);
             return Ok(contents);
         }
 
@@ -199,38 +621,110 @@ impl GodotConfig for DefaultGodotConfig {
         let editor_plugins_section = vec![
             "[editor_plugins]".to_string(),
             "".to_string(),
-            format!("enabled={}", self.plugins_to_packed_string_array(_plugins)),
+            enabled_line,
             "".to_string(),
         ];
 
         // If [editor_plugins] section doesn't exists, we need to add it to the project file.
         // I _think_ it should be added alphabetically, but I'm not 100% sure.
-        for i in 0..contents.len() {
-            let line = &contents[i];
-            // Checks if the line is a section header and if it's alphabetically after [editor_plugins]
-            if line.starts_with("[")
-                && line.ends_with("]")
-                && line.to_lowercase().cmp(&"[editor_plugins]".to_string())
-                    == std::cmp::Ordering::Greater
-            {
-                debug!("Inserting [editor_plugins] section before section {}", line);
-                contents.splice(i..i, editor_plugins_section);
-                return Ok(contents);
-                // If we reach the end of the file, we need to add the section at the end.
-            } else if i == contents.len() - 1 {
-                debug!("Appending [editor_plugins] section to the end of the file");
-                contents.extend(editor_plugins_section);
+        Self::insert_section(&mut contents, "[editor_plugins]", editor_plugins_section);
+        Ok(contents)
+    }
+
+    /// Registers the autoloads declared by the plugins and removes the ones of removed plugins.
+    /// Autoloads not declared in gdm.json are left untouched, and lines of autoloads whose path
+    /// didn't change are kept as they are.
+    ///
+    /// godot.project autoload format:
+    /// ```ini
+    /// [autoload]
+    ///
+    /// ModLoader="*res://addons/mod_loader/mod_loader.gd"
+    ///
+    /// [<next section>]
+    /// ```
+    fn update_autoload_section(
+        &self,
+        contents: Vec<String>,
+        gdm_config: &DefaultGdmConfigMetadata,
+        removed_plugins: &[Plugin],
+    ) -> Result<Vec<String>> {
+        let mut contents = contents;
+        let mut autoloads: BTreeMap<String, String> = gdm_config
+            .plugins
+            .values()
+            .flat_map(|plugin| plugin.autoload.clone())
+            .collect();
+        let stale_autoloads: HashSet<String> = removed_plugins
+            .iter()
+            .flat_map(|plugin| plugin.autoload.keys().cloned())
+            .filter(|name| !autoloads.contains_key(name))
+            .collect();
+
+        let autoload_index = contents
+            .iter()
+            .position(|line| line.starts_with("[autoload]"));
+
+        let Some(index) = autoload_index else {
+            if autoloads.is_empty() {
                 return Ok(contents);
             }
+            info!("Adding [autoload] section to Godot project file");
+            let mut autoload_section = vec!["[autoload]".to_string(), "".to_string()];
+            autoload_section.extend(
+                autoloads
+                    .iter()
+                    .map(|(name, path)| format!("{}=\"{}\"", name, path)),
+            );
+            autoload_section.push("".to_string());
+            Self::insert_section(&mut contents, "[autoload]", autoload_section);
+            return Ok(contents);
+        };
+
+        let mut line_index = index + 1;
+        while line_index < Self::section_end(&contents, index) {
+            let line = &contents[line_index];
+            let Some((name, value)) = line.split_once('=') else {
+                line_index += 1;
+                continue;
+            };
+            let name = name.trim();
+            if stale_autoloads.contains(name) {
+                debug!("Removing autoload {} from Godot project file", name);
+                contents.remove(line_index);
+                continue;
+            }
+            if let Some(path) = autoloads.remove(name)
+                && value.trim().trim_matches('"') != path
+            {
+                debug!("Updating autoload {} in Godot project file", name);
+                contents[line_index] = format!("{}=\"{}\"", name, path);
+            }
+            line_index += 1;
         }
 
-        bail!("Failed to update plugins in Godot project file")
+        let section_end = Self::section_end(&contents, index);
+        if !autoloads.is_empty() {
+            debug!("Adding {} autoloads to [autoload] section", autoloads.len());
+            let entries = autoloads
+                .iter()
+                .map(|(name, path)| format!("{}=\"{}\"", name, path))
+                .collect();
+            Self::insert_into_section(&mut contents, index, section_end, entries);
+        } else if contents[index + 1..section_end]
+            .iter()
+            .all(|line| line.trim().is_empty())
+        {
+            info!("Removing [autoload] section from Godot project file");
+            contents.drain(index..section_end);
+        }
+        Ok(contents)
     }
 
     /// Parses project.godot file and gathers plugins, config_version, and godot_version
     ///
     /// godot.project sections of interest:
-    /// ```
+    /// ```ini
     /// config_version=5
     ///
     /// ...
@@ -292,11 +786,7 @@ impl GodotConfig for DefaultGodotConfig {
             .file_service
             .file_exists(self.app_config.get_godot_project_file_path())?;
         if !exists {
-            error!(
-                "No project.godot file found in the current directory: {}",
-                self.app_config.get_godot_project_file_path().display()
-            );
-            bail!("No project.godot file found in the current directory")
+            bail!(self.missing_project_file_error())
         }
         info!("Godot project file validated successfully");
         Ok(())
@@ -310,7 +800,7 @@ impl GodotConfig for DefaultGodotConfig {
         let file = self
             .file_service
             .read_file_cached(self.app_config.get_godot_project_file_path())?;
-        let lines = file.split('\n').map(|s| s.to_string()).collect::<Vec<_>>();
+        let lines = ProjectFileFormat::lines(&file);
         info!("Loaded Godot project file with {} lines", lines.len());
         Ok(lines)
     }
@@ -325,10 +815,18 @@ impl GodotConfig for DefaultGodotConfig {
                 "No project.godot file found in the current directory: {}",
                 godot_project_file_path.display()
             );
-            bail!("No project.godot file found in the current directory")
+            bail!(GdmError::new(
+                ErrorKind::ProjectFile,
+                "No project.godot file found in the current directory"
+            ))
         }
+        let format = self
+            .file_service
+            .read_file_cached(godot_project_file_path)
+            .map(|content| ProjectFileFormat::detect(&content))
+            .unwrap_or_default();
         self.file_service
-            .write_file(godot_project_file_path, &lines.join("\n"))?;
+            .write_file(godot_project_file_path, &format.join(&lines))?;
         info!(
             "Godot project file saved successfully: {}",
             godot_project_file_path.display()
@@ -338,11 +836,30 @@ impl GodotConfig for DefaultGodotConfig {
 }
 pub trait GodotConfig {
     fn get_godot_version_from_project(&self) -> Result<String>;
-    fn plugins_to_packed_string_array(&self, plugins: Vec<Plugin>) -> String;
+    fn get_godot_version_from_binary(&self) -> Result<String>;
+    fn import_resources(&self) -> Result<()>;
+    fn check_godot_version_range(&self, project: &GdmProjectMetadata) -> Result<Option<String>>;
     fn validate_project_file(&self) -> Result<()>;
     fn save(&self, gdm_config: DefaultGdmConfigMetadata) -> Result<()>;
+    fn save_with_removed(
+        &self,
+        gdm_config: DefaultGdmConfigMetadata,
+        removed_plugins: Vec<Plugin>,
+    ) -> Result<()>;
     fn load(&self) -> Result<GodotProjectMetadata>;
-    fn update_project_file(&self, gdm_config: DefaultGdmConfigMetadata) -> Result<Vec<String>>;
+    fn get_enabled_plugins(&self) -> Result<Vec<String>>;
+    fn get_unmanaged_plugins(&self, gdm_config: &DefaultGdmConfigMetadata) -> Result<Vec<String>>;
+    fn update_project_file(
+        &self,
+        gdm_config: DefaultGdmConfigMetadata,
+        removed_plugins: &[Plugin],
+    ) -> Result<Vec<String>>;
+    fn update_autoload_section(
+        &self,
+        contents: Vec<String>,
+        gdm_config: &DefaultGdmConfigMetadata,
+        removed_plugins: &[Plugin],
+    ) -> Result<Vec<String>>;
     fn read_godot_project_file(&self) -> Result<GodotProjectMetadata>;
     fn load_project_file(&self) -> Result<Vec<String>>;
     fn save_project_file(&self, lines: Vec<String>) -> Result<()>;
@@ -409,26 +926,191 @@ mod tests {
         );
     }
 
-    // plugins_to_packed_string_array
+    // get_godot_version_from_project
 
     #[test]
-    fn test_plugins_to_packed_string_array() {
+    fn test_get_godot_version_from_project_should_prefer_override() {
         let app_config = DefaultAppConfig::new(
             None,
             None,
             None,
-            Some(String::from(
-                "tests/mocks/project_with_plugins_and_version.godot",
-            )),
+            Some(String::from("tests/mocks/project_with_old_config.godot")),
             Some(String::from("tests/mocks/addons")),
+        )
+        .with_godot_version(Some("4.6".to_string()));
+
+        let repository = DefaultGodotConfig::new(Box::new(DefaultFileService), app_config);
+        assert_eq!(repository.get_godot_version_from_project().unwrap(), "4.6");
+    }
+
+    #[test]
+    fn test_get_godot_version_from_project_should_prefer_project_section() {
+        let app_config = DefaultAppConfig::new(
+            None,
+            None,
+            None,
+            Some(String::from("tests/mocks/project_without_plugins.godot")),
+            Some(String::from("tests/mocks/addons")),
+        )
+        .with_project_godot_version(Some("4.3".to_string()));
+
+        let repository = DefaultGodotConfig::new(Box::new(DefaultFileService), app_config);
+        assert_eq!(repository.get_godot_version_from_project().unwrap(), "4.3");
+    }
+
+    #[test]
+    fn test_get_godot_version_from_project_without_override() {
+        let app_config = DefaultAppConfig::new(
+            None,
+            None,
+            None,
+            Some(String::from("tests/mocks/project_with_old_config.godot")),
+            Some(String::from("tests/mocks/addons")),
+        )
+        .with_godot_binary_path(Some("non_existent_godot_binary".to_string()));
+
+        let repository = DefaultGodotConfig::new(Box::new(DefaultFileService), app_config);
+        assert_eq!(repository.get_godot_version_from_project().unwrap(), "3.6");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_get_godot_version_from_project_should_use_binary_when_features_are_missing() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = temp_dir::TempDir::new().unwrap();
+        let godot_binary_path = temp_dir.child("godot");
+        std::fs::write(
+            &godot_binary_path,
+            "#!/bin/sh\necho 4.2.2.stable.official.15073afe3\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&godot_binary_path, std::fs::Permissions::from_mode(0o755))
+            .unwrap();
+
+        let app_config = DefaultAppConfig::new(
+            None,
+            None,
+            None,
+            Some(String::from("tests/mocks/project_with_old_config.godot")),
+            Some(String::from("tests/mocks/addons")),
+        )
+        .with_godot_binary_path(Some(godot_binary_path.to_string_lossy().to_string()));
+
+        let repository = DefaultGodotConfig::new(Box::new(DefaultFileService), app_config);
+        assert_eq!(repository.get_godot_version_from_project().unwrap(), "4.2");
+    }
+
+    #[test]
+    fn test_import_errors_should_list_error_lines() {
+        let output = "Godot Engine v4.4.stable.official\n\
+            [   0% ] first_scan_filesystem\n\
+            ERROR: Failed loading resource: res://addons/gut/icon.png.\n   at: _load (core/io/resource_loader.cpp:284)\n\
+            SCRIPT ERROR: Parse Error: Identifier \"Foo\" not declared.\n\
+            WARNING: Unused variable\n";
+
+        assert_eq!(
+            DefaultGodotConfig::import_errors(output),
+            vec![
+                "ERROR: Failed loading resource: res://addons/gut/icon.png.".to_string(),
+                "SCRIPT ERROR: Parse Error: Identifier \"Foo\" not declared.".to_string(),
+            ]
         );
+    }
 
-        let mock_file_service = MockDefaultFileService::default();
-        let repository = DefaultGodotConfig::new(Box::new(mock_file_service), app_config);
-        let result = repository.plugins_to_packed_string_array(vec![
+    #[cfg(unix)]
+    #[test]
+    fn test_import_resources_should_fail_on_import_errors() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = temp_dir::TempDir::new().unwrap();
+        let godot_binary_path = temp_dir.child("godot");
+        std::fs::write(
+            &godot_binary_path,
+            "#!/bin/sh\n[ \"$1 $2 $4\" = \"--headless --path --import\" ] || exit 3\necho 'ERROR: Failed loading resource: res://icon.png.' >&2\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&godot_binary_path, std::fs::Permissions::from_mode(0o755))
+            .unwrap();
+        let app_config = DefaultAppConfig::default()
+            .with_godot_binary_path(Some(godot_binary_path.to_string_lossy().to_string()));
+        let repository = DefaultGodotConfig::new(Box::new(DefaultFileService), app_config);
+
+        let error = repository.import_resources().unwrap_err();
+
+        let gdm_error = GdmError::find(&error).unwrap();
+        assert_eq!(gdm_error.kind, ErrorKind::ImportFailed);
+        assert!(
+            error
+                .to_string()
+                .contains("ERROR: Failed loading resource: res://icon.png.")
+        );
+    }
+
+    // check_godot_version_range
+
+    fn setup_godot_config_for_range_check(godot_project_file_path: &str) -> DefaultGodotConfig {
+        let app_config = DefaultAppConfig::new(
+            None,
+            None,
+            None,
+            Some(String::from(godot_project_file_path)),
+            Some(String::from("tests/mocks/addons")),
+        );
+        DefaultGodotConfig::new(Box::new(DefaultFileService), app_config)
+    }
+
+    fn setup_project(godot_version: &str) -> GdmProjectMetadata {
+        GdmProjectMetadata {
+            godot_version: Some(godot_version.to_string()),
+            ..GdmProjectMetadata::default()
+        }
+    }
+
+    #[test]
+    fn test_check_godot_version_range_should_warn_outside_range() {
+        let repository =
+            setup_godot_config_for_range_check("tests/mocks/project_without_plugins.godot");
+        let warning = repository
+            .check_godot_version_range(&setup_project(">=4.6"))
+            .unwrap();
+        assert_eq!(
+            warning.as_deref(),
+            Some(
+                "Warning: project.godot uses Godot 4.5, which is outside the range >=4.6 declared in gdm.json"
+            )
+        );
+    }
+
+    #[test]
+    fn test_check_godot_version_range_should_not_warn_inside_range() {
+        let repository =
+            setup_godot_config_for_range_check("tests/mocks/project_without_plugins.godot");
+        let warning = repository
+            .check_godot_version_range(&setup_project(">=4.3, <4.7"))
+            .unwrap();
+        assert_eq!(warning, None);
+    }
+
+    #[test]
+    fn test_check_godot_version_range_should_not_warn_without_features() {
+        let repository =
+            setup_godot_config_for_range_check("tests/mocks/project_with_old_config.godot");
+        let warning = repository
+            .check_godot_version_range(&setup_project(">=4.6"))
+            .unwrap();
+        assert_eq!(warning, None);
+    }
+
+    // to_packed_string_array
+
+    #[test]
+    fn test_plugins_to_packed_string_array() {
+        let plugin_paths = DefaultGodotConfig::plugins_to_resource_paths(&[
             Plugin::create_mock_plugin_1(),
             Plugin::create_mock_plugin_2(),
         ]);
+        let result = DefaultGodotConfig::to_string_array(PACKED_STRING_ARRAY, &plugin_paths);
         assert_eq!(
             result,
             String::from(
@@ -437,6 +1119,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_packed_string_array() {
+        let result = DefaultGodotConfig::parse_packed_string_array(
+            "PackedStringArray(\"res://addons/a/plugin.cfg\", \"res://addons/b/plugin.cfg\")",
+        );
+        assert_eq!(
+            result,
+            vec![
+                "res://addons/a/plugin.cfg".to_string(),
+                "res://addons/b/plugin.cfg".to_string()
+            ]
+        );
+        assert!(DefaultGodotConfig::parse_packed_string_array("PackedStringArray()").is_empty());
+    }
+
+    #[test]
+    fn test_parse_packed_string_array_should_keep_paths_outside_addons() {
+        let result = DefaultGodotConfig::parse_packed_string_array(
+            "PackedStringArray(\"res://tools/level, editor/plugin.cfg\",\"res://addons/\\\"b\\\"/plugin.cfg\")",
+        );
+        assert_eq!(
+            result,
+            vec![
+                "res://tools/level, editor/plugin.cfg".to_string(),
+                "res://addons/\"b\"/plugin.cfg".to_string()
+            ]
+        );
+        assert_eq!(
+            DefaultGodotConfig::to_string_array(PACKED_STRING_ARRAY, &result),
+            "PackedStringArray(\"res://tools/level, editor/plugin.cfg\", \"res://addons/\\\"b\\\"/plugin.cfg\")"
+        );
+        assert_eq!(
+            DefaultGodotConfig::parse_packed_string_array(
+                "PoolStringArray( \"res://addons/a/plugin.cfg\" )"
+            ),
+            vec!["res://addons/a/plugin.cfg".to_string()]
+        );
+    }
+
     // read_godot_project_file
 
     #[test]
@@ -494,6 +1215,24 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_find_project_dir_above_should_return_closest_parent_with_project_file() {
+        let mut file_service = MockDefaultFileService::new();
+        file_service
+            .expect_file_exists()
+            .returning(|path| Ok(path == Path::new("/game/project.godot")));
+        let repository = DefaultGodotConfig::new(
+            Box::new(file_service),
+            DefaultAppConfig::new(None, None, None, None, None),
+        );
+
+        assert_eq!(
+            repository.find_project_dir_above(Path::new("/game/addons/gut")),
+            Some(PathBuf::from("/game"))
+        );
+        assert_eq!(repository.find_project_dir_above(Path::new("/other")), None);
+    }
+
     #[test]
     fn test_load_should_not_return_error_if_file_exists() {
         let app_config = DefaultAppConfig::new(
@@ -613,7 +1352,7 @@ renderer/rendering_method="gl_compatibility"
         plugins.insert("awesome_plugin".to_string(), Plugin::create_mock_plugin_1());
         let gdm_config = DefaultGdmConfigMetadata::new(plugins);
 
-        let result = repository.update_project_file(gdm_config);
+        let result = repository.update_project_file(gdm_config, &[]);
         assert!(result.is_ok());
         let lines = result.unwrap();
 
@@ -681,7 +1420,7 @@ enabled=PackedStringArray("res://addons/super_plugin/plugin.cfg")
         plugins.insert("super_plugin".to_string(), Plugin::create_mock_plugin_2());
         let gdm_config = DefaultGdmConfigMetadata::new(plugins);
 
-        let result = repository.update_project_file(gdm_config);
+        let result = repository.update_project_file(gdm_config, &[]);
         assert!(result.is_ok());
         let lines = result.unwrap();
 
@@ -719,7 +1458,10 @@ enabled=PackedStringArray("res://addons/super_plugin/plugin.cfg")
         plugins.insert("awesome_plugin".to_string(), Plugin::create_mock_plugin_1());
         let gdm_config = DefaultGdmConfigMetadata::new(plugins);
 
-        let result = repository.update_project_file(gdm_config);
+        let mut old_plugin = Plugin::create_mock_plugin_2();
+        old_plugin.plugin_cfg_path = Some("addons/old_plugin/plugin.cfg".to_string());
+
+        let result = repository.update_project_file(gdm_config, &[old_plugin]);
         assert!(result.is_ok());
         let lines = result.unwrap();
 
@@ -764,7 +1506,7 @@ enabled=PackedStringArray("res://addons/super_plugin/plugin.cfg")
         plugins.insert("some_library".to_string(), Plugin::create_mock_plugin_3());
         let gdm_config = DefaultGdmConfigMetadata::new(plugins);
 
-        let result = repository.update_project_file(gdm_config);
+        let result = repository.update_project_file(gdm_config, &[]);
         assert!(result.is_ok());
         let lines = result.unwrap();
 
@@ -806,7 +1548,10 @@ enabled=PackedStringArray("res://addons/super_plugin/plugin.cfg")
 
         let gdm_config = DefaultGdmConfigMetadata::new(BTreeMap::new());
 
-        let result = repository.update_project_file(gdm_config);
+        let mut test_plugin = Plugin::create_mock_plugin_1();
+        test_plugin.plugin_cfg_path = Some("addons/test_plugin/plugin.cfg".to_string());
+
+        let result = repository.update_project_file(gdm_config, &[test_plugin]);
         assert!(result.is_ok());
         let lines = result.unwrap();
 
@@ -816,17 +1561,31 @@ enabled=PackedStringArray("res://addons/super_plugin/plugin.cfg")
     }
 
     #[test]
-    fn test_update_project_file_should_add_empty_line_at_end_if_missing() {
-        use std::collections::BTreeMap;
+    fn test_update_project_file_should_not_add_empty_line_at_end_without_changes() {
+        let mut mock_file_service = MockDefaultFileService::default();
+        mock_file_service.expect_read_file_cached().returning(|_| {
+            Ok(String::from(
+                "config_version=5\n\
+                    [application]\n\
+                    config/name=\"Test\"",
+            ))
+        });
 
-        let app_config = DefaultAppConfig::new(
-            None,
-            None,
-            None,
-            Some(String::from("tests/mocks/project.godot")),
-            Some(String::from("addons")),
+        let repository =
+            DefaultGodotConfig::new(Box::new(mock_file_service), DefaultAppConfig::default());
+
+        let lines = repository
+            .update_project_file(DefaultGdmConfigMetadata::default(), &[])
+            .unwrap();
+
+        assert_eq!(
+            lines.join("\n"),
+            "config_version=5\n[application]\nconfig/name=\"Test\""
         );
+    }
 
+    #[test]
+    fn test_update_project_file_should_append_section_to_file_without_newline_at_end() {
         let mut mock_file_service = MockDefaultFileService::default();
         mock_file_service.expect_read_file_cached().returning(|_| {
             Ok(String::from(
@@ -836,16 +1595,386 @@ enabled=PackedStringArray("res://addons/super_plugin/plugin.cfg")
             ))
         });
 
-        let repository = DefaultGodotConfig::new(Box::new(mock_file_service), app_config);
+        let repository =
+            DefaultGodotConfig::new(Box::new(mock_file_service), DefaultAppConfig::default());
+        let gdm_config = DefaultGdmConfigMetadata::new(BTreeMap::from([(
+            "awesome_plugin".to_string(),
+            Plugin::create_mock_plugin_1(),
+        )]));
 
-        let gdm_config = DefaultGdmConfigMetadata::new(BTreeMap::new());
+        let lines = repository.update_project_file(gdm_config, &[]).unwrap();
 
-        let result = repository.update_project_file(gdm_config);
-        assert!(result.is_ok());
-        let lines = result.unwrap();
+        assert_eq!(
+            lines.join("\n"),
+            "config_version=5\n[application]\nconfig/name=\"Test\"\n\n[editor_plugins]\n\nenabled=PackedStringArray(\"res://addons/awesome_plugin/plugin.cfg\")\n"
+        );
+    }
+
+    #[test]
+    fn test_update_project_file_should_keep_unchanged_enabled_line() {
+        let content = "; Engine configuration file.\n\
+            config_version=5\n\
+            \n\
+            [editor_plugins]\n\
+            \n\
+            enabled=PackedStringArray( \"res://addons/manual_plugin/plugin.cfg\",\"res://addons/awesome_plugin/plugin.cfg\" )\n\
+            \n\
+            [rendering]\n";
+        let mut mock_file_service = MockDefaultFileService::default();
+        mock_file_service
+            .expect_read_file_cached()
+            .returning(move |_| Ok(String::from(content)));
+
+        let repository =
+            DefaultGodotConfig::new(Box::new(mock_file_service), DefaultAppConfig::default());
+        let gdm_config = DefaultGdmConfigMetadata::new(BTreeMap::from([(
+            "awesome_plugin".to_string(),
+            Plugin::create_mock_plugin_1(),
+        )]));
+
+        let lines = repository.update_project_file(gdm_config, &[]).unwrap();
+
+        assert_eq!(lines.join("\n"), content);
+    }
+
+    #[test]
+    fn test_update_project_file_should_only_change_enabled_line() {
+        let mut mock_file_service = MockDefaultFileService::default();
+        mock_file_service.expect_read_file_cached().returning(|_| {
+            Ok(String::from(
+                "config_version=5\n\
+                    \n\
+                    [application]\n\
+                    \n\
+                    config/name=\"Test\"\n\
+                    \n\
+                    \n\
+                    [editor_plugins]\n\
+                    \n\
+                    enabled=PackedStringArray(\"res://addons/super_plugin/plugin.cfg\", \"res://addons/manual_plugin/plugin.cfg\")\n\
+                    \n\
+                    [rendering]\n\
+                    renderer/rendering_method=\"gl_compatibility\"\n",
+            ))
+        });
+
+        let repository =
+            DefaultGodotConfig::new(Box::new(mock_file_service), DefaultAppConfig::default());
+        let gdm_config = DefaultGdmConfigMetadata::new(BTreeMap::from([
+            ("awesome_plugin".to_string(), Plugin::create_mock_plugin_1()),
+            ("super_plugin".to_string(), Plugin::create_mock_plugin_2()),
+        ]));
+
+        let lines = repository.update_project_file(gdm_config, &[]).unwrap();
+
+        assert_eq!(
+            lines.join("\n"),
+            "config_version=5\n\n[application]\n\nconfig/name=\"Test\"\n\n\n[editor_plugins]\n\n\
+            enabled=PackedStringArray(\"res://addons/super_plugin/plugin.cfg\", \"res://addons/manual_plugin/plugin.cfg\", \"res://addons/awesome_plugin/plugin.cfg\")\n\
+            \n[rendering]\nrenderer/rendering_method=\"gl_compatibility\"\n"
+        );
+    }
+
+    #[test]
+    fn test_update_project_file_should_keep_section_with_other_lines_when_no_plugins() {
+        let mut mock_file_service = MockDefaultFileService::default();
+        mock_file_service.expect_read_file_cached().returning(|_| {
+            Ok(String::from(
+                "[editor_plugins]\n\
+                    \n\
+                    ; Managed by gdm\n\
+                    enabled=PackedStringArray(\"res://addons/awesome_plugin/plugin.cfg\")\n\
+                    \n\
+                    [rendering]\n",
+            ))
+        });
+
+        let repository =
+            DefaultGodotConfig::new(Box::new(mock_file_service), DefaultAppConfig::default());
+
+        let lines = repository
+            .update_project_file(
+                DefaultGdmConfigMetadata::default(),
+                &[Plugin::create_mock_plugin_1()],
+            )
+            .unwrap();
+
+        assert_eq!(
+            lines.join("\n"),
+            "[editor_plugins]\n\n; Managed by gdm\n\n[rendering]\n"
+        );
+    }
+
+    #[test]
+    fn test_update_project_file_should_preserve_unmanaged_plugins() {
+        let mut mock_file_service = MockDefaultFileService::default();
+        mock_file_service.expect_read_file_cached().returning(|_| {
+            Ok(String::from(
+                "config_version=5\n\
+                    [editor_plugins]\n\
+                    \n\
+                    enabled=PackedStringArray(\"res://addons/manual_plugin/plugin.cfg\", \"res://addons/old_plugin/plugin.cfg\")\n\
+                    \n\
+                    [rendering]\n",
+            ))
+        });
+
+        let repository =
+            DefaultGodotConfig::new(Box::new(mock_file_service), DefaultAppConfig::default());
+
+        let mut removed_plugin = Plugin::create_mock_plugin_2();
+        removed_plugin.plugin_cfg_path = Some("addons/old_plugin/plugin.cfg".to_string());
+        let gdm_config = DefaultGdmConfigMetadata::new(BTreeMap::from([(
+            "awesome_plugin".to_string(),
+            Plugin::create_mock_plugin_1(),
+        )]));
+
+        let lines = repository
+            .update_project_file(gdm_config, &[removed_plugin])
+            .unwrap();
+
+        assert!(lines.contains(&String::from(
+            "enabled=PackedStringArray(\"res://addons/manual_plugin/plugin.cfg\", \"res://addons/awesome_plugin/plugin.cfg\")"
+        )));
+    }
+
+    #[test]
+    fn test_update_project_file_should_keep_plugins_outside_addons() {
+        let mut mock_file_service = MockDefaultFileService::default();
+        mock_file_service.expect_read_file_cached().returning(|_| {
+            Ok(String::from(
+                "config_version=4\n\
+                    [editor_plugins]\n\
+                    \n\
+                    enabled=PoolStringArray( \"res://tools/level_editor/plugin.cfg\" )\n\
+                    \n\
+                    [rendering]\n",
+            ))
+        });
+
+        let repository =
+            DefaultGodotConfig::new(Box::new(mock_file_service), DefaultAppConfig::default());
+        let plugin = Plugin::create_mock_plugin_1().relocate(Path::new("./tools/"));
+        let gdm_config =
+            DefaultGdmConfigMetadata::new(BTreeMap::from([("awesome_plugin".to_string(), plugin)]));
+
+        let lines = repository.update_project_file(gdm_config, &[]).unwrap();
+
+        assert!(lines.contains(&String::from(
+            "enabled=PoolStringArray(\"res://tools/level_editor/plugin.cfg\", \"res://tools/awesome_plugin/plugin.cfg\")"
+        )));
+    }
+
+    #[test]
+    fn test_update_project_file_should_keep_section_with_only_unmanaged_plugins() {
+        let mut mock_file_service = MockDefaultFileService::default();
+        mock_file_service.expect_read_file_cached().returning(|_| {
+            Ok(String::from(
+                "config_version=5\n\
+                    [editor_plugins]\n\
+                    \n\
+                    enabled=PackedStringArray(\"res://addons/manual_plugin/plugin.cfg\", \"res://addons/awesome_plugin/plugin.cfg\")\n\
+                    \n\
+                    [rendering]\n",
+            ))
+        });
+
+        let repository =
+            DefaultGodotConfig::new(Box::new(mock_file_service), DefaultAppConfig::default());
+
+        let lines = repository
+            .update_project_file(
+                DefaultGdmConfigMetadata::default(),
+                &[Plugin::create_mock_plugin_1()],
+            )
+            .unwrap();
+
+        assert!(lines.contains(&String::from(
+            "enabled=PackedStringArray(\"res://addons/manual_plugin/plugin.cfg\")"
+        )));
+    }
+
+    #[test]
+    fn test_update_project_file_should_not_enable_disabled_plugins() {
+        let mut mock_file_service = MockDefaultFileService::default();
+        mock_file_service.expect_read_file_cached().returning(|_| {
+            Ok(String::from(
+                "config_version=5\n\
+                    [editor_plugins]\n\
+                    \n\
+                    enabled=PackedStringArray(\"res://addons/awesome_plugin/plugin.cfg\", \"res://addons/super_plugin/plugin.cfg\")\n\
+                    \n\
+                    [rendering]\n",
+            ))
+        });
+
+        let repository =
+            DefaultGodotConfig::new(Box::new(mock_file_service), DefaultAppConfig::default());
+
+        let mut disabled_plugin = Plugin::create_mock_plugin_2();
+        disabled_plugin.enabled = false;
+        let gdm_config = DefaultGdmConfigMetadata::new(BTreeMap::from([
+            ("awesome_plugin".to_string(), Plugin::create_mock_plugin_1()),
+            ("super_plugin".to_string(), disabled_plugin),
+        ]));
+
+        let lines = repository.update_project_file(gdm_config, &[]).unwrap();
+
+        assert!(lines.contains(&String::from(
+            "enabled=PackedStringArray(\"res://addons/awesome_plugin/plugin.cfg\")"
+        )));
+    }
+
+    // get_unmanaged_plugins
+
+    #[test]
+    fn test_get_unmanaged_plugins_should_return_plugins_missing_from_gdm_config() {
+        let mut mock_file_service = MockDefaultFileService::default();
+        mock_file_service.expect_read_file_cached().returning(|_| {
+            Ok(String::from(
+                "[editor_plugins]\n\
+                    \n\
+                    enabled=PackedStringArray(\"res://addons/manual_plugin/plugin.cfg\", \"res://addons/awesome_plugin/plugin.cfg\")\n",
+            ))
+        });
+
+        let repository =
+            DefaultGodotConfig::new(Box::new(mock_file_service), DefaultAppConfig::default());
+        let gdm_config = DefaultGdmConfigMetadata::new(BTreeMap::from([(
+            "awesome_plugin".to_string(),
+            Plugin::create_mock_plugin_1(),
+        )]));
+
+        let result = repository.get_unmanaged_plugins(&gdm_config).unwrap();
+        assert_eq!(
+            result,
+            vec!["res://addons/manual_plugin/plugin.cfg".to_string()]
+        );
+    }
+
+    // update_autoload_section
+
+    fn setup_autoload_plugin(autoload: &[(&str, &str)]) -> Plugin {
+        let mut plugin = Plugin::create_mock_plugin_1();
+        plugin.autoload = autoload
+            .iter()
+            .map(|(name, path)| (name.to_string(), path.to_string()))
+            .collect();
+        plugin
+    }
+
+    fn to_lines(content: &str) -> Vec<String> {
+        content.split('\n').map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_update_autoload_section_should_add_section_when_it_is_missing() {
+        let repository = DefaultGodotConfig::new(
+            Box::new(MockDefaultFileService::default()),
+            DefaultAppConfig::default(),
+        );
+        let contents = to_lines(
+            "config_version=5\n\n[application]\n\nconfig/name=\"Test\"\n\n[rendering]\n\nrenderer/rendering_method=\"gl_compatibility\"\n",
+        );
+        let plugin =
+            setup_autoload_plugin(&[("ModLoader", "*res://addons/mod_loader/mod_loader.gd")]);
+        let gdm_config =
+            DefaultGdmConfigMetadata::new(BTreeMap::from([("mod_loader".to_string(), plugin)]));
+
+        let result = repository
+            .update_autoload_section(contents, &gdm_config, &[])
+            .unwrap();
+
+        assert_eq!(
+            result.join("\n"),
+            "config_version=5\n\n[application]\n\nconfig/name=\"Test\"\n\n[autoload]\n\nModLoader=\"*res://addons/mod_loader/mod_loader.gd\"\n\n[rendering]\n\nrenderer/rendering_method=\"gl_compatibility\"\n"
+        );
+    }
+
+    #[test]
+    fn test_update_autoload_section_should_keep_unmanaged_autoloads() {
+        let repository = DefaultGodotConfig::new(
+            Box::new(MockDefaultFileService::default()),
+            DefaultAppConfig::default(),
+        );
+        let contents = to_lines(
+            "[autoload]\n\nGame=\"*res://game.gd\"\nEvents=\"*res://addons/old_events.gd\"\n\n[rendering]\n",
+        );
+        let plugin = setup_autoload_plugin(&[("Events", "*res://addons/events/events.gd")]);
+        let gdm_config =
+            DefaultGdmConfigMetadata::new(BTreeMap::from([("events".to_string(), plugin)]));
+
+        let result = repository
+            .update_autoload_section(contents, &gdm_config, &[])
+            .unwrap();
+
+        assert_eq!(
+            result.join("\n"),
+            "[autoload]\n\nGame=\"*res://game.gd\"\nEvents=\"*res://addons/events/events.gd\"\n\n[rendering]\n"
+        );
+    }
+
+    #[test]
+    fn test_update_autoload_section_should_remove_autoloads_of_removed_plugins() {
+        let repository = DefaultGodotConfig::new(
+            Box::new(MockDefaultFileService::default()),
+            DefaultAppConfig::default(),
+        );
+        let contents = to_lines(
+            "[autoload]\n\nGame=\"*res://game.gd\"\nEvents=\"*res://addons/events/events.gd\"\n\n[rendering]\n",
+        );
+        let removed = setup_autoload_plugin(&[("Events", "*res://addons/events/events.gd")]);
+
+        let result = repository
+            .update_autoload_section(contents, &DefaultGdmConfigMetadata::default(), &[removed])
+            .unwrap();
+
+        assert_eq!(
+            result.join("\n"),
+            "[autoload]\n\nGame=\"*res://game.gd\"\n\n[rendering]\n"
+        );
+    }
+
+    #[test]
+    fn test_update_autoload_section_should_keep_unchanged_lines() {
+        let repository = DefaultGodotConfig::new(
+            Box::new(MockDefaultFileService::default()),
+            DefaultAppConfig::default(),
+        );
+        let content = "[autoload]\n\nEvents = \"*res://addons/events/events.gd\"\n\n; Game state\nGame=\"*res://game.gd\"\n\n[rendering]\n";
+        let plugin = setup_autoload_plugin(&[
+            ("Events", "*res://addons/events/events.gd"),
+            ("Sound", "*res://addons/events/sound.gd"),
+        ]);
+        let gdm_config =
+            DefaultGdmConfigMetadata::new(BTreeMap::from([("events".to_string(), plugin)]));
+
+        let result = repository
+            .update_autoload_section(to_lines(content), &gdm_config, &[])
+            .unwrap();
+
+        assert_eq!(
+            result.join("\n"),
+            "[autoload]\n\nEvents = \"*res://addons/events/events.gd\"\n\n; Game state\nGame=\"*res://game.gd\"\nSound=\"*res://addons/events/sound.gd\"\n\n[rendering]\n"
+        );
+    }
+
+    #[test]
+    fn test_update_autoload_section_should_remove_empty_section() {
+        let repository = DefaultGodotConfig::new(
+            Box::new(MockDefaultFileService::default()),
+            DefaultAppConfig::default(),
+        );
+        let contents = to_lines(
+            "[application]\n\n[autoload]\n\nEvents=\"*res://addons/events/events.gd\"\n\n[rendering]\n",
+        );
+        let removed = setup_autoload_plugin(&[("Events", "*res://addons/events/events.gd")]);
 
-        // Check that empty line was added
-        assert_eq!(lines.last().unwrap(), "");
+        let result = repository
+            .update_autoload_section(contents, &DefaultGdmConfigMetadata::default(), &[removed])
+            .unwrap();
+
+        assert_eq!(result.join("\n"), "[application]\n\n[rendering]\n");
     }
 
     // save_project_file
@@ -866,6 +1995,9 @@ enabled=PackedStringArray("res://addons/super_plugin/plugin.cfg")
         mock_file_service
             .expect_file_exists()
             .returning(|_| Ok(true));
+        mock_file_service
+            .expect_read_file_cached()
+            .returning(|_| Ok(String::from("line1\n")));
         mock_file_service
             .expect_write_file()
             .withf(|path: &Path, content: &str| {
@@ -886,6 +2018,53 @@ enabled=PackedStringArray("res://addons/super_plugin/plugin.cfg")
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_save_project_file_should_keep_crlf_and_bom() {
+        let mut mock_file_service = MockDefaultFileService::default();
+        mock_file_service
+            .expect_file_exists()
+            .returning(|_| Ok(true));
+        mock_file_service.expect_read_file_cached().returning(|_| {
+            Ok(String::from(
+                "\u{feff}config_version=5\r\n\r\n[rendering]\r\n",
+            ))
+        });
+        mock_file_service
+            .expect_write_file()
+            .withf(|_, content: &str| {
+                content == "\u{feff}config_version=5\r\n\r\n[editor_plugins]\r\n\r\n[rendering]\r\n"
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let repository =
+            DefaultGodotConfig::new(Box::new(mock_file_service), DefaultAppConfig::default());
+        let mut lines = repository.load_project_file().unwrap();
+        assert_eq!(lines[0], "config_version=5");
+        lines.splice(2..2, ["[editor_plugins]".to_string(), "".to_string()]);
+
+        repository.save_project_file(lines).unwrap();
+    }
+
+    #[test]
+    fn test_project_file_format_should_use_most_common_line_ending() {
+        assert_eq!(
+            ProjectFileFormat::detect("a\r\nb\r\nc\n"),
+            ProjectFileFormat {
+                crlf: true,
+                bom: false
+            }
+        );
+        assert_eq!(
+            ProjectFileFormat::detect("a\nb\nc\r\n"),
+            ProjectFileFormat::default()
+        );
+        assert_eq!(
+            ProjectFileFormat::lines("\u{feff}a\r\nb\n"),
+            vec!["a".to_string(), "b".to_string(), "".to_string()]
+        );
+    }
+
     #[test]
     fn test_save_project_file_should_return_error_when_file_not_found() {
         let app_config = DefaultAppConfig::new(
@@ -0,0 +1,196 @@
+use anyhow::{Result, bail};
+use serde_json::{Map, Value};
+use tracing::info;
+
+/// Layout version written to `format_version` in gdm.json
+pub const CURRENT_FORMAT_VERSION: u64 = 2;
+
+/// Files without `format_version` were written before versioning
+const UNVERSIONED_FORMAT_VERSION: u64 = 1;
+
+/// Upgrades the manifest by one version. Returns true if it changed anything besides the version.
+type Migration = fn(&mut Map<String, Value>) -> bool;
+
+/// Migrations in order, each upgrading the manifest from the version before it to the given version
+const MIGRATIONS: [(u64, Migration); 1] = [(2, move_asset_id_to_source)];
+
+#[derive(Debug, PartialEq)]
+pub struct MigrationResult {
+    pub from_version: u64,
+    /// False if only the format version changed, so the original file still parses the same
+    pub layout_changed: bool,
+}
+
+/// Returns the format version of a raw manifest
+pub fn format_version(manifest: &Value) -> Result<u64> {
+    match manifest.get("format_version") {
+        None => Ok(UNVERSIONED_FORMAT_VERSION),
+        Some(version) => match version.as_u64() {
+            Some(version) if version > CURRENT_FORMAT_VERSION => bail!(
+                "format_version {} is newer than the newest format this gdm supports ({}). Update gdm to use this file.",
+                version,
+                CURRENT_FORMAT_VERSION
+            ),
+            Some(version) if version >= UNVERSIONED_FORMAT_VERSION => Ok(version),
+            _ => bail!(
+                "format_version: expected a positive integer, found {}",
+                version
+            ),
+        },
+    }
+}
+
+/// Upgrades a raw manifest to the current format version.
+/// Returns None if it was already current.
+pub fn migrate(manifest: &mut Value) -> Result<Option<MigrationResult>> {
+    let from_version = format_version(manifest)?;
+    if from_version == CURRENT_FORMAT_VERSION {
+        return Ok(None);
+    }
+    let Some(manifest_object) = manifest.as_object_mut() else {
+        bail!("Expected the configuration file to contain an object")
+    };
+
+    let mut layout_changed = false;
+    for (version, migration) in MIGRATIONS {
+        if version > from_version {
+            info!("Migrating configuration file to format version {}", version);
+            layout_changed |= migration(manifest_object);
+        }
+    }
+    manifest_object.insert(
+        "format_version".to_string(),
+        Value::from(CURRENT_FORMAT_VERSION),
+    );
+    Ok(Some(MigrationResult {
+        from_version,
+        layout_changed,
+    }))
+}
+
+/// Version 1 stored `asset_id` directly on the plugin, before git sources existed:
+/// `{ "asset_id": "1709", "title": "Gut", "version": "9.1.0" }`
+fn move_asset_id_to_source(manifest: &mut Map<String, Value>) -> bool {
+    let mut changed = false;
+    for section in ["plugins", "dev_plugins"] {
+        let Some(plugins) = manifest.get_mut(section).and_then(Value::as_object_mut) else {
+            continue;
+        };
+        for plugin in plugins.values_mut().filter_map(Value::as_object_mut) {
+            if plugin.contains_key("source") {
+                continue;
+            }
+            if let Some(asset_id) = plugin.remove("asset_id") {
+                let mut source = Map::new();
+                source.insert("asset_id".to_string(), asset_id);
+                plugin.insert("source".to_string(), Value::Object(source));
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // format_version
+
+    #[test]
+    fn test_format_version_without_field_should_be_unversioned() {
+        assert_eq!(format_version(&json!({ "plugins": {} })).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_format_version_newer_than_supported_should_fail() {
+        let result = format_version(&json!({ "format_version": 99, "plugins": {} }));
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("format_version 99 is newer than the newest format this gdm supports")
+        );
+    }
+
+    #[test]
+    fn test_format_version_invalid_should_fail() {
+        let result = format_version(&json!({ "format_version": "two", "plugins": {} }));
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("format_version: expected a positive integer")
+        );
+    }
+
+    // migrate
+
+    #[test]
+    fn test_migrate_should_move_asset_id_to_source() {
+        let mut manifest = json!({
+            "plugins": {
+                "gut": { "asset_id": "1709", "title": "Gut", "version": "9.1.0" },
+                "git_plugin": {
+                    "source": { "url": "https://example.com/plugin.git", "reference": "main" },
+                    "title": "Git Plugin",
+                    "version": "1.0.0"
+                }
+            }
+        });
+
+        let result = migrate(&mut manifest).unwrap();
+
+        assert_eq!(
+            result,
+            Some(MigrationResult {
+                from_version: 1,
+                layout_changed: true
+            })
+        );
+        assert_eq!(
+            manifest,
+            json!({
+                "format_version": 2,
+                "plugins": {
+                    "gut": { "source": { "asset_id": "1709" }, "title": "Gut", "version": "9.1.0" },
+                    "git_plugin": {
+                        "source": { "url": "https://example.com/plugin.git", "reference": "main" },
+                        "title": "Git Plugin",
+                        "version": "1.0.0"
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_migrate_unversioned_current_layout_should_only_add_version() {
+        let mut manifest = json!({
+            "plugins": {
+                "gut": { "source": { "asset_id": "1709" }, "title": "Gut", "version": "9.1.0" }
+            }
+        });
+
+        let result = migrate(&mut manifest).unwrap();
+
+        assert_eq!(
+            result,
+            Some(MigrationResult {
+                from_version: 1,
+                layout_changed: false
+            })
+        );
+        assert_eq!(manifest["format_version"], 2);
+    }
+
+    #[test]
+    fn test_migrate_current_manifest_should_not_change() {
+        let mut manifest = json!({ "format_version": 2, "plugins": {} });
+        let original = manifest.clone();
+
+        assert_eq!(migrate(&mut manifest).unwrap(), None);
+        assert_eq!(manifest, original);
+    }
+}
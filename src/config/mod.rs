@@ -1,10 +1,18 @@
 mod app;
 mod gdm;
+mod global;
 mod godot;
+mod migration;
+mod workspace;
 
-pub use app::{AppConfig, DefaultAppConfig};
-pub use gdm::{DefaultGdmConfig, DefaultGdmConfigMetadata, GdmConfig, GdmConfigMetadata};
+pub use app::{AppConfig, DefaultAppConfig, InstallMode};
+pub use gdm::{
+    DefaultGdmConfig, DefaultGdmConfigMetadata, GdmConfig, GdmConfigFormat, GdmConfigMetadata,
+    GdmProjectMetadata, LicensePolicy,
+};
+pub use global::{ColorChoice, GlobalConfig};
 pub use godot::{DefaultGodotConfig, GodotConfig};
+pub use workspace::{DefaultWorkspaceConfig, DefaultWorkspaceConfigMetadata, WorkspaceConfig};
 
 #[cfg(test)]
 #[allow(unused)]
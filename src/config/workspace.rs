@@ -0,0 +1,305 @@
+use crate::config::{
+    AppConfig, DefaultAppConfig, DefaultGdmConfig, DefaultGdmConfigMetadata, GdmConfig,
+    GdmConfigFormat,
+};
+use crate::error::{ErrorKind, GdmError};
+use crate::models::Plugin;
+use crate::services::{DefaultFileService, FileService};
+
+use anyhow::{Result, bail};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::info;
+
+/// Workspace manifest, e.g. `gdm-workspace.json`:
+///
+/// ```json
+/// {
+///   "projects": ["game", "tools"],
+///   "plugins": { "gut": { "source": { "asset_id": "1709" }, "title": "Gut", "version": "9.5.0" } }
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct DefaultWorkspaceConfigMetadata {
+    /// Project directories relative to the workspace manifest
+    pub projects: Vec<String>,
+    /// Plugins shared by every project in the workspace
+    #[serde(default)]
+    pub plugins: BTreeMap<String, Plugin>,
+}
+
+impl DefaultWorkspaceConfigMetadata {
+    /// Adds the shared plugins to a project's configuration.
+    /// Plugins the project declares itself take precedence over the shared definitions.
+    pub fn merge_into(
+        &self,
+        project_config: &DefaultGdmConfigMetadata,
+    ) -> DefaultGdmConfigMetadata {
+        let mut merged_config = project_config.clone();
+        for (key, plugin) in &self.plugins {
+            merged_config
+                .plugins
+                .entry(key.clone())
+                .or_insert_with(|| plugin.clone());
+        }
+        merged_config
+    }
+}
+
+pub struct DefaultWorkspaceConfig {
+    pub app_config: DefaultAppConfig,
+    pub file_service: Arc<dyn FileService + Send + Sync + 'static>,
+}
+
+impl Default for DefaultWorkspaceConfig {
+    fn default() -> Self {
+        DefaultWorkspaceConfig {
+            app_config: DefaultAppConfig::default(),
+            file_service: Arc::new(DefaultFileService),
+        }
+    }
+}
+
+impl DefaultWorkspaceConfig {
+    #[allow(unused)]
+    pub fn new(
+        app_config: DefaultAppConfig,
+        file_service: Arc<dyn FileService + Send + Sync + 'static>,
+    ) -> Self {
+        DefaultWorkspaceConfig {
+            app_config,
+            file_service,
+        }
+    }
+}
+
+impl WorkspaceConfig for DefaultWorkspaceConfig {
+    fn load(&self) -> Result<DefaultWorkspaceConfigMetadata> {
+        let workspace_file_path = self.app_config.get_workspace_file_path();
+
+        if !self.file_service.file_exists(workspace_file_path)? {
+            bail!(GdmError::new(
+                ErrorKind::ProjectFile,
+                format!(
+                    "No {} file found in the current directory.",
+                    workspace_file_path.display()
+                )
+            ))
+        }
+        let content = self.file_service.read_file_cached(workspace_file_path)?;
+        let workspace: DefaultWorkspaceConfigMetadata =
+            serde_json::from_str(&content).map_err(|err| {
+                GdmError::new(
+                    ErrorKind::ConfigInvalid,
+                    format!(
+                        "Failed to parse workspace file {}: {}",
+                        workspace_file_path.display(),
+                        err
+                    ),
+                )
+            })?;
+        Ok(workspace)
+    }
+
+    fn get_project_config_file_path(&self, project_dir: &Path) -> Result<PathBuf> {
+        GdmConfigFormat::resolve_config_file_path(
+            &project_dir.join(self.app_config.get_config_file_path()),
+            self.file_service.as_ref(),
+        )
+    }
+
+    fn sync_project(
+        &self,
+        workspace: &DefaultWorkspaceConfigMetadata,
+        project_dir: &Path,
+    ) -> Result<DefaultGdmConfigMetadata> {
+        if !self.file_service.directory_exists(project_dir) {
+            bail!(GdmError::new(
+                ErrorKind::NotFound,
+                format!("Workspace project not found: {}", project_dir.display())
+            ))
+        }
+
+        let config_file_path = self.get_project_config_file_path(project_dir)?;
+        DefaultGdmConfig::new(
+            self.app_config
+                .clone()
+                .with_config_file_path(&config_file_path),
+            self.file_service.clone(),
+        )
+        .migrate()?;
+        let config_format = GdmConfigFormat::from_path(&config_file_path);
+        let previous_content = if self.file_service.file_exists(&config_file_path)? {
+            Some(self.file_service.read_file_cached(&config_file_path)?)
+        } else {
+            None
+        };
+        let project_config = match &previous_content {
+            Some(content) => config_format.parse(content).map_err(|err| {
+                GdmError::new(
+                    ErrorKind::ConfigInvalid,
+                    format!(
+                        "Invalid plugin config file {}: {}",
+                        config_file_path.display(),
+                        err
+                    ),
+                )
+            })?,
+            None => DefaultGdmConfigMetadata::default(),
+        };
+
+        let merged_config = workspace.merge_into(&project_config);
+        if merged_config != project_config {
+            let content = config_format.serialize(&merged_config, previous_content.as_deref())?;
+            self.file_service.write_file(&config_file_path, &content)?;
+            info!(
+                "Added shared plugins to {}: {:?}",
+                config_file_path.display(),
+                merged_config.plugins.keys()
+            );
+        }
+        Ok(merged_config)
+    }
+}
+
+pub trait WorkspaceConfig {
+    fn load(&self) -> Result<DefaultWorkspaceConfigMetadata>;
+    fn get_project_config_file_path(&self, project_dir: &Path) -> Result<PathBuf>;
+    fn sync_project(
+        &self,
+        workspace: &DefaultWorkspaceConfigMetadata,
+        project_dir: &Path,
+    ) -> Result<DefaultGdmConfigMetadata>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::predicate::*;
+
+    use crate::services::MockDefaultFileService;
+
+    fn setup_workspace() -> DefaultWorkspaceConfigMetadata {
+        DefaultWorkspaceConfigMetadata {
+            projects: vec!["game".to_string(), "tools".to_string()],
+            plugins: BTreeMap::from([
+                ("plugin_1".to_string(), Plugin::create_mock_plugin_1()),
+                ("plugin_2".to_string(), Plugin::create_mock_plugin_2()),
+            ]),
+        }
+    }
+
+    // merge_into
+
+    #[test]
+    fn test_merge_into_should_add_shared_plugins() {
+        let workspace = setup_workspace();
+        let merged_config = workspace.merge_into(&DefaultGdmConfigMetadata::default());
+        assert_eq!(merged_config.plugins, workspace.plugins);
+    }
+
+    #[test]
+    fn test_merge_into_should_keep_project_plugins() {
+        let workspace = setup_workspace();
+        let mut project_plugin = Plugin::create_mock_plugin_1();
+        project_plugin.version = "0.1.0".to_string();
+        let project_config = DefaultGdmConfigMetadata::new(BTreeMap::from([(
+            "plugin_1".to_string(),
+            project_plugin.clone(),
+        )]));
+
+        let merged_config = workspace.merge_into(&project_config);
+
+        assert_eq!(merged_config.plugins.len(), 2);
+        assert_eq!(merged_config.plugins.get("plugin_1"), Some(&project_plugin));
+    }
+
+    // load
+
+    #[test]
+    fn test_load_should_fail_without_workspace_file() {
+        let mut mock_file_service = MockDefaultFileService::new();
+        mock_file_service
+            .expect_file_exists()
+            .with(eq(Path::new("gdm-workspace.json")))
+            .returning(|_| Ok(false));
+
+        let workspace_config =
+            DefaultWorkspaceConfig::new(DefaultAppConfig::default(), Arc::new(mock_file_service));
+
+        let result = workspace_config.load();
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("No gdm-workspace.json file found")
+        );
+    }
+
+    #[test]
+    fn test_load_should_parse_workspace_file() {
+        let mut mock_file_service = MockDefaultFileService::new();
+        mock_file_service
+            .expect_file_exists()
+            .returning(|_| Ok(true));
+        mock_file_service
+            .expect_read_file_cached()
+            .returning(|_| Ok(r#"{ "projects": ["game", "tools"] }"#.to_string()));
+
+        let workspace_config =
+            DefaultWorkspaceConfig::new(DefaultAppConfig::default(), Arc::new(mock_file_service));
+
+        let workspace = workspace_config.load().unwrap();
+        assert_eq!(workspace.projects, vec!["game", "tools"]);
+        assert!(workspace.plugins.is_empty());
+    }
+
+    // sync_project
+
+    #[test]
+    fn test_sync_project_should_write_shared_plugins_to_project_config() {
+        let mut mock_file_service = MockDefaultFileService::new();
+        mock_file_service
+            .expect_directory_exists()
+            .with(eq(Path::new("game")))
+            .returning(|_| true);
+        mock_file_service
+            .expect_file_exists()
+            .with(eq(PathBuf::from("game/gdm.toml")))
+            .returning(|_| Ok(false));
+        mock_file_service
+            .expect_file_exists()
+            .with(eq(PathBuf::from("game/gdm.json")))
+            .returning(|_| Ok(false));
+        mock_file_service
+            .expect_write_file()
+            .with(eq(PathBuf::from("game/gdm.json")), always())
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let workspace_config =
+            DefaultWorkspaceConfig::new(DefaultAppConfig::default(), Arc::new(mock_file_service));
+
+        let merged_config = workspace_config
+            .sync_project(&setup_workspace(), Path::new("game"))
+            .unwrap();
+        assert_eq!(merged_config.plugins.len(), 2);
+    }
+
+    #[test]
+    fn test_sync_project_should_fail_for_missing_project() {
+        let mut mock_file_service = MockDefaultFileService::new();
+        mock_file_service
+            .expect_directory_exists()
+            .returning(|_| false);
+
+        let workspace_config =
+            DefaultWorkspaceConfig::new(DefaultAppConfig::default(), Arc::new(mock_file_service));
+
+        let result = workspace_config.sync_project(&setup_workspace(), Path::new("missing"));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,211 @@
+use std::fmt;
+
+/// Category of a `GdmError`, each with a stable code and exit code for scripts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorKind {
+    /// Invalid combination of command line arguments
+    Usage,
+    /// project.godot or gdm.json is missing or can't be used
+    ProjectFile,
+    /// gdm.json can't be parsed
+    ConfigInvalid,
+    /// Two files or entries contradict each other
+    Conflict,
+    /// The Asset Library or a git host couldn't be reached
+    Network,
+    /// A plugin, asset or version doesn't exist
+    NotFound,
+    /// A downloaded archive or repository doesn't contain an addon
+    ArchiveInvalid,
+    /// A downloaded artifact doesn't match the checksum in gdm.json
+    ChecksumMismatch,
+    /// A git plugin isn't signed by a key in project.trusted_keys
+    SignatureInvalid,
+    /// An installed plugin is affected by an advisory, see `gdm audit`
+    Advisory,
+    /// A plugin's license isn't accepted by project.licenses in gdm.json
+    LicensePolicy,
+    /// `gdm outdated --fail-on` found plugins with newer versions
+    Outdated,
+    /// Godot failed to import the project's resources after `--import`
+    ImportFailed,
+}
+
+impl ErrorKind {
+    /// Stable identifier of the error category, e.g. `GDM-201`
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::Usage => "GDM-101",
+            ErrorKind::ProjectFile => "GDM-102",
+            ErrorKind::ConfigInvalid => "GDM-103",
+            ErrorKind::Conflict => "GDM-104",
+            ErrorKind::Network => "GDM-201",
+            ErrorKind::NotFound => "GDM-202",
+            ErrorKind::ArchiveInvalid => "GDM-301",
+            ErrorKind::ChecksumMismatch => "GDM-302",
+            ErrorKind::SignatureInvalid => "GDM-303",
+            ErrorKind::Advisory => "GDM-401",
+            ErrorKind::LicensePolicy => "GDM-402",
+            ErrorKind::Outdated => "GDM-403",
+            ErrorKind::ImportFailed => "GDM-501",
+        }
+    }
+
+    /// Process exit code. 1 is left for errors without a category, 2 is used by clap for usage errors.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ErrorKind::Usage => 2,
+            ErrorKind::ProjectFile | ErrorKind::ConfigInvalid => 3,
+            ErrorKind::Conflict => 4,
+            ErrorKind::Network => 5,
+            ErrorKind::NotFound => 6,
+            ErrorKind::ArchiveInvalid
+            | ErrorKind::ChecksumMismatch
+            | ErrorKind::SignatureInvalid => 7,
+            ErrorKind::Advisory | ErrorKind::LicensePolicy | ErrorKind::Outdated => 8,
+            ErrorKind::ImportFailed => 9,
+        }
+    }
+
+    fn default_hint(&self) -> Option<&'static str> {
+        match self {
+            ErrorKind::Usage => Some("Run gdm <command> --help to see the accepted arguments."),
+            ErrorKind::ProjectFile => {
+                Some("Run gdm in the folder that contains project.godot and gdm.json.")
+            }
+            ErrorKind::ConfigInvalid => {
+                Some("Fix the field named above and run gdm validate to check the file.")
+            }
+            ErrorKind::Conflict => None,
+            ErrorKind::Network => Some("Check your internet connection and try again."),
+            ErrorKind::NotFound => Some("Check the name with gdm list or gdm search."),
+            ErrorKind::ArchiveInvalid => Some(
+                "The asset might not be packaged as a Godot addon. Try another version or report it to its author.",
+            ),
+            ErrorKind::ChecksumMismatch => None,
+            ErrorKind::SignatureInvalid => Some(
+                "Add the signing key to project.trusted_keys in gdm.json, or remove verify_signature from the plugin if it isn't signed.",
+            ),
+            ErrorKind::Advisory => {
+                Some("Follow the suggested actions, e.g. update or remove the affected plugins.")
+            }
+            ErrorKind::LicensePolicy => Some(
+                "Allow the license in project.licenses in gdm.json, or pass --no-verify-license to install it anyway.",
+            ),
+            ErrorKind::Outdated => Some("Update the plugins with gdm update."),
+            ErrorKind::ImportFailed => Some(
+                "Open the project in the Godot editor to see the errors. The plugins are installed, only importing failed.",
+            ),
+        }
+    }
+}
+
+/// Error shown at the CLI boundary with a code, exit code and remediation hint.
+/// Travels inside `anyhow::Error`, so `.context()` can still be added on the way up.
+#[derive(Debug)]
+pub struct GdmError {
+    pub kind: ErrorKind,
+    message: String,
+    hint: Option<String>,
+    /// Name of the plugin the error is about, for tools reading `--output-format json`
+    plugin: Option<String>,
+}
+
+impl GdmError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> GdmError {
+        GdmError {
+            kind,
+            message: message.into(),
+            hint: None,
+            plugin: None,
+        }
+    }
+
+    /// Replaces the default hint of the error kind
+    pub fn with_hint(mut self, hint: impl Into<String>) -> GdmError {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    pub fn hint(&self) -> Option<&str> {
+        self.hint.as_deref().or(self.kind.default_hint())
+    }
+
+    pub fn with_plugin(mut self, plugin: impl Into<String>) -> GdmError {
+        self.plugin = Some(plugin.into());
+        self
+    }
+
+    pub fn plugin(&self) -> Option<&str> {
+        self.plugin.as_deref()
+    }
+
+    /// Returns the outermost `GdmError` in the error chain
+    pub fn find(error: &anyhow::Error) -> Option<&GdmError> {
+        error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<GdmError>())
+    }
+}
+
+impl fmt::Display for GdmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for GdmError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::{Context, anyhow};
+
+    #[test]
+    fn test_codes_should_be_unique() {
+        let kinds = [
+            ErrorKind::Usage,
+            ErrorKind::ProjectFile,
+            ErrorKind::ConfigInvalid,
+            ErrorKind::Conflict,
+            ErrorKind::Network,
+            ErrorKind::NotFound,
+            ErrorKind::ArchiveInvalid,
+            ErrorKind::ChecksumMismatch,
+            ErrorKind::SignatureInvalid,
+            ErrorKind::Advisory,
+            ErrorKind::LicensePolicy,
+            ErrorKind::Outdated,
+            ErrorKind::ImportFailed,
+        ];
+        let codes: std::collections::HashSet<&str> = kinds.iter().map(ErrorKind::code).collect();
+        assert_eq!(codes.len(), kinds.len());
+    }
+
+    #[test]
+    fn test_hint_should_fall_back_to_kind_hint() {
+        let error = GdmError::new(ErrorKind::Network, "Failed to fetch data");
+        assert_eq!(
+            error.hint(),
+            Some("Check your internet connection and try again.")
+        );
+
+        let error = error.with_hint("Try again later.");
+        assert_eq!(error.hint(), Some("Try again later."));
+    }
+
+    #[test]
+    fn test_find_should_look_through_context() {
+        let error = Err::<(), _>(anyhow!(GdmError::new(
+            ErrorKind::NotFound,
+            "No asset found with ID '1'"
+        )))
+        .context("Failed to fetch latest plugins from Asset Store API")
+        .unwrap_err();
+
+        let gdm_error = GdmError::find(&error).unwrap();
+        assert_eq!(gdm_error.kind, ErrorKind::NotFound);
+        assert_eq!(gdm_error.to_string(), "No asset found with ID '1'");
+        assert!(GdmError::find(&anyhow!("Something went wrong")).is_none());
+    }
+}
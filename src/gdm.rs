@@ -0,0 +1,467 @@
+use crate::api::{AssetListResponse, AssetStoreAPI, DefaultAssetStoreAPI};
+use crate::config::{
+    AppConfig, DefaultAppConfig, DefaultGdmConfig, DefaultGodotConfig, GdmConfig, GdmConfigFormat,
+    GodotConfig,
+};
+use crate::error::{ErrorKind, GdmError};
+use crate::installers::{AssetLibraryInstaller, GitInstaller, PluginInstaller};
+use crate::models::{
+    AdvisoryFeed, AuditFinding, ChangeSummary, Credits, DependencyGraph, FolderReport,
+    OutdatedPlugin, Plugin, PluginListing, ProjectStats, UpdateLevel,
+};
+use crate::services::{
+    AddOptions, DefaultExtractService, DefaultFileService, DefaultGitService, DefaultHttpService,
+    DefaultInstallService, DefaultPluginService, FileService, PluginParser, PluginService,
+};
+use crate::ui::{SilentInterface, UserInterface};
+
+use anyhow::{Result, bail};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const DEFAULT_REGISTRY: &str = "https://godotengine.org/asset-library/api";
+
+/// Folder created in the cache directory for the downloads of an install, so removing it after
+/// installing leaves the rest of the directory alone
+const STAGING_FOLDER: &str = ".gdm";
+
+/// Manages the plugins of one Godot project, for embedding gdm in other tools:
+///
+/// ```no_run
+/// # async fn run() -> anyhow::Result<()> {
+/// let gdm = gdm_core::Gdm::builder()
+///     .project_dir("path/to/project")
+///     .build()?;
+/// let summary = gdm.add("Godot Unit Testing", None).await?;
+/// println!("{}", summary);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Unlike the `Default` services, nothing is read from the environment or from the
+/// configuration set with `DefaultAppConfig::init`.
+pub struct Gdm {
+    project_dir: PathBuf,
+    app_config: DefaultAppConfig,
+    ui: Arc<dyn UserInterface>,
+}
+
+/// Builds a [`Gdm`], see [`Gdm::builder`]
+#[derive(Default)]
+pub struct GdmBuilder {
+    project_dir: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
+    registry: Option<String>,
+    godot_version: Option<String>,
+    credential_helper: Option<String>,
+    headers: BTreeMap<String, String>,
+    ui: Option<Arc<dyn UserInterface>>,
+}
+
+impl GdmBuilder {
+    /// Folder with project.godot, defaults to the current directory
+    pub fn project_dir(mut self, project_dir: impl Into<PathBuf>) -> Self {
+        self.project_dir = Some(project_dir.into());
+        self
+    }
+
+    /// Folder for downloads and extracted archives, defaults to the project. They're staged in
+    /// a `.gdm` folder inside it, which is removed after plugins are installed.
+    pub fn cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(cache_dir.into());
+        self
+    }
+
+    /// Asset Library API URL, defaults to https://godotengine.org/asset-library/api
+    pub fn registry(mut self, registry: impl Into<String>) -> Self {
+        self.registry = Some(registry.into());
+        self
+    }
+
+    /// Godot version used instead of the one detected from project.godot
+    pub fn godot_version(mut self, godot_version: impl Into<String>) -> Self {
+        self.godot_version = Some(godot_version.into());
+        self
+    }
+
+    /// Command that prints a token for the Asset Library or a git host, run with the host
+    /// as the last argument
+    pub fn credential_helper(mut self, credential_helper: impl Into<String>) -> Self {
+        self.credential_helper = Some(credential_helper.into());
+        self
+    }
+
+    /// Extra header sent with requests to the registry, e.g. an API key
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Shows progress, status messages and prompts, see [`SilentInterface`] for the default
+    pub fn ui(mut self, ui: Arc<dyn UserInterface>) -> Self {
+        self.ui = Some(ui);
+        self
+    }
+
+    pub fn build(self) -> Result<Gdm> {
+        let project_dir = std::path::absolute(self.project_dir.unwrap_or_else(|| ".".into()))?;
+        if !DefaultFileService.directory_exists(&project_dir) {
+            bail!(GdmError::new(
+                ErrorKind::NotFound,
+                format!("Project directory not found: {}", project_dir.display())
+            ))
+        }
+        let cache_dir = match self.cache_dir {
+            Some(cache_dir) => Some(std::path::absolute(cache_dir)?.join(STAGING_FOLDER)),
+            None => None,
+        };
+        DefaultHttpService::header_map(&self.headers)?;
+        let registry = self
+            .registry
+            .map(|registry| registry.trim_end_matches('/').to_string())
+            .unwrap_or_else(|| DEFAULT_REGISTRY.to_string());
+
+        let app_config = DefaultAppConfig::new(
+            Some(registry),
+            None,
+            cache_dir.map(|dir| dir.to_string_lossy().to_string()),
+            None,
+            None,
+        )
+        .with_project_dir(&project_dir)
+        .with_godot_version(self.godot_version)
+        .with_credential_helper(self.credential_helper)
+        .with_headers(self.headers);
+
+        Ok(Gdm {
+            project_dir,
+            app_config,
+            ui: self.ui.unwrap_or_else(|| Arc::new(SilentInterface)),
+        })
+    }
+}
+
+impl Gdm {
+    pub fn builder() -> GdmBuilder {
+        GdmBuilder::default()
+    }
+
+    pub fn project_dir(&self) -> &Path {
+        &self.project_dir
+    }
+
+    pub fn app_config(&self) -> &DefaultAppConfig {
+        &self.app_config
+    }
+
+    /// Adds a plugin from the Asset Library by name, at the latest version unless `version` is given
+    pub async fn add(&self, name: &str, version: Option<&str>) -> Result<ChangeSummary> {
+        self.plugin_service()?
+            .add_plugin(
+                None,
+                Some(name.to_string()),
+                version.map(str::to_string),
+                None,
+                None,
+                AddOptions::default(),
+            )
+            .await
+    }
+
+    /// Adds a plugin from the Asset Library by asset ID
+    pub async fn add_by_asset_id(
+        &self,
+        asset_id: &str,
+        version: Option<&str>,
+    ) -> Result<ChangeSummary> {
+        self.plugin_service()?
+            .add_plugin(
+                Some(asset_id.to_string()),
+                None,
+                version.map(str::to_string),
+                None,
+                None,
+                AddOptions::default(),
+            )
+            .await
+    }
+
+    /// Adds a plugin from a git repository, at `reference` or the main branch
+    pub async fn add_git(&self, url: &str, reference: Option<&str>) -> Result<ChangeSummary> {
+        self.plugin_service()?
+            .add_plugin(
+                None,
+                None,
+                None,
+                Some(url.to_string()),
+                reference.map(str::to_string),
+                AddOptions::default(),
+            )
+            .await
+    }
+
+    /// Installs the named plugins from the configuration file, or all of them when `names` is empty,
+    /// returning them by name
+    pub async fn install(
+        &self,
+        names: &[String],
+        include_dev: bool,
+    ) -> Result<BTreeMap<String, Plugin>> {
+        self.plugin_service()?
+            .install_plugins(names, include_dev, false)
+            .await
+    }
+
+    /// Updates the named plugins, or all of them when `names` is empty, up to `level`,
+    /// returning the updated ones
+    pub async fn update(
+        &self,
+        names: &[String],
+        include_prerelease: bool,
+        level: UpdateLevel,
+    ) -> Result<BTreeMap<String, Plugin>> {
+        self.plugin_service()?
+            .update_plugins(names, include_prerelease, level)
+            .await
+    }
+
+    /// Installs an older version of an Asset Library plugin and pins it there
+    pub async fn downgrade(&self, name: &str, version: &str) -> Result<ChangeSummary> {
+        self.plugin_service()?.downgrade_plugin(name, version).await
+    }
+
+    pub async fn remove(&self, name: &str) -> Result<ChangeSummary> {
+        self.plugin_service()?.remove_plugin_by_name(name).await
+    }
+
+    pub async fn remove_by_asset_id(&self, asset_id: &str) -> Result<ChangeSummary> {
+        self.plugin_service()?
+            .remove_plugin_by_asset_id(asset_id)
+            .await
+    }
+
+    /// Searches the Asset Library for plugins supporting the project's Godot version
+    pub async fn search(&self, name: &str) -> Result<AssetListResponse> {
+        let godot_version = self.app_config.get_godot_version().unwrap_or_default();
+        self.plugin_service()?
+            .get_asset_list_response_by_name_or_version(name, &godot_version, None)
+            .await
+    }
+
+    pub async fn list(&self) -> Result<PluginListing> {
+        self.plugin_service()?.list_plugins()
+    }
+
+    /// `level` decides which updates are wanted and which are blocked, like for `update`
+    pub async fn outdated(
+        &self,
+        include_prerelease: bool,
+        level: UpdateLevel,
+    ) -> Result<Vec<OutdatedPlugin>> {
+        self.plugin_service()?
+            .check_outdated_plugins(include_prerelease, level)
+            .await
+    }
+
+    /// Matches the installed plugins against an advisory feed, e.g. one loaded with `AdvisoryFeedLoader`
+    pub async fn audit(&self, feed: &AdvisoryFeed) -> Result<Vec<AuditFinding>> {
+        self.plugin_service()?.audit_plugins(feed)
+    }
+
+    /// Attributions of the plugins in the project, formatted as Markdown by their `Display`
+    pub async fn credits(&self) -> Result<Credits> {
+        self.plugin_service()?.credits()
+    }
+
+    /// Disk size, source and license of the plugins in the project
+    pub async fn stats(&self) -> Result<ProjectStats> {
+        self.plugin_service()?.project_stats()
+    }
+
+    /// The plugins of the project and their sub-assets, rendered with `to_dot` or `to_mermaid`
+    pub async fn graph(&self) -> Result<DependencyGraph> {
+        self.plugin_service()?.dependency_graph()
+    }
+
+    /// Which plugins install an addon folder and which plugins depend on it, like `gdm why`
+    pub async fn why(&self, folder: &str) -> Result<FolderReport> {
+        self.plugin_service()?.explain_folder(folder)
+    }
+
+    /// Wires the services for the project like the gdm binary does, without global configuration
+    fn plugin_service(&self) -> Result<DefaultPluginService> {
+        let file_service = Arc::new(DefaultFileService);
+        let config_file_path = GdmConfigFormat::resolve_config_file_path(
+            self.app_config.get_config_file_path(),
+            file_service.as_ref(),
+        )?;
+        let app_config = self
+            .app_config
+            .clone()
+            .with_config_file_path(&config_file_path);
+
+        let gdm_config = DefaultGdmConfig::new(app_config.clone(), file_service.clone());
+        if let Some(from_version) = gdm_config.migrate()? {
+            self.ui.status(&DefaultGdmConfig::migration_message(
+                &config_file_path,
+                from_version,
+            ));
+        }
+        let metadata = gdm_config.load()?;
+        let app_config = app_config.with_project_settings(&metadata)?;
+
+        let godot_config =
+            DefaultGodotConfig::new(Box::new(DefaultFileService), app_config.clone());
+        godot_config.validate_project_file()?;
+        if let Some(project) = &metadata.project
+            && let Some(warning) = godot_config.check_godot_version_range(project)?
+        {
+            self.ui.warning(&warning);
+        }
+
+        let asset_store_api: Arc<dyn AssetStoreAPI + Send + Sync> =
+            Arc::new(DefaultAssetStoreAPI::new(
+                Arc::new(DefaultHttpService::new(&app_config)),
+                app_config.clone(),
+                file_service.clone(),
+            ));
+        let extract_service = Arc::new(DefaultExtractService::new(
+            Box::new(DefaultFileService),
+            app_config.clone(),
+        ));
+        let git_service = Arc::new(DefaultGitService {
+            app_config: app_config.clone(),
+        });
+        let installers: Vec<Box<dyn PluginInstaller>> = vec![
+            Box::new(AssetLibraryInstaller::new(
+                asset_store_api.clone(),
+                extract_service,
+                app_config.clone(),
+            )),
+            Box::new(GitInstaller::new(git_service.clone())),
+        ];
+        let install_service = Arc::new(DefaultInstallService::new(
+            file_service.clone(),
+            Box::new(app_config.clone()),
+            Arc::new(PluginParser::new(file_service.clone())),
+            installers,
+        ));
+
+        Ok(DefaultPluginService::new(
+            Box::new(godot_config),
+            Box::new(DefaultGdmConfig::new(
+                app_config.clone(),
+                file_service.clone(),
+            )),
+            app_config,
+            file_service,
+            asset_store_api,
+            install_service,
+        )
+        .with_git_service(git_service)
+        .with_ui(self.ui.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_should_use_defaults() {
+        let gdm = Gdm::builder().build().unwrap();
+        assert_eq!(gdm.project_dir(), std::env::current_dir().unwrap());
+        assert_eq!(gdm.app_config().api_base_url, DEFAULT_REGISTRY);
+        assert_eq!(
+            gdm.app_config().get_cache_folder_path(),
+            std::env::current_dir().unwrap().join(".gdm")
+        );
+        assert_eq!(gdm.app_config().get_godot_version(), None);
+    }
+
+    #[test]
+    fn test_build_should_apply_options() {
+        let cache_dir = temp_dir::TempDir::new().unwrap();
+        let gdm = Gdm::builder()
+            .project_dir("tests")
+            .cache_dir(cache_dir.path())
+            .registry("http://localhost:8080/api/")
+            .godot_version("4.5")
+            .build()
+            .unwrap();
+        assert_eq!(gdm.project_dir(), std::path::absolute("tests").unwrap());
+        assert_eq!(gdm.app_config().api_base_url, "http://localhost:8080/api");
+        assert_eq!(
+            gdm.app_config().get_cache_folder_path(),
+            cache_dir.path().join(".gdm")
+        );
+        assert_eq!(
+            gdm.app_config().get_config_file_path(),
+            std::path::absolute("tests/gdm.json").unwrap()
+        );
+        assert_eq!(gdm.app_config().get_godot_version(), Some("4.5".into()));
+    }
+
+    #[test]
+    fn test_plugin_service_should_check_git_plugins_with_builder_config() {
+        let project_dir = temp_dir::TempDir::new().unwrap();
+        std::fs::write(project_dir.child("project.godot"), "config_version=5\n").unwrap();
+        std::fs::write(project_dir.child("gdm.json"), r#"{"plugins": {}}"#).unwrap();
+        let gdm = Gdm::builder()
+            .project_dir(project_dir.path())
+            .credential_helper("vault-token gdm")
+            .build()
+            .unwrap();
+
+        let plugin_service = gdm.plugin_service().unwrap();
+        let git_config = plugin_service.git_service.app_config();
+
+        assert_eq!(
+            git_config.get_credential_helper(),
+            Some("vault-token gdm".to_string())
+        );
+        assert_eq!(
+            git_config.get_config_file_path(),
+            project_dir.path().join("gdm.json")
+        );
+    }
+
+    #[test]
+    fn test_plugin_service_should_apply_project_settings() {
+        let project_dir = temp_dir::TempDir::new().unwrap();
+        std::fs::write(project_dir.child("project.godot"), "config_version=5\n").unwrap();
+        std::fs::write(
+            project_dir.child("gdm.json"),
+            r#"{"project": {"credits": "CREDITS.md", "trusted_keys": ["ssh-ed25519 AAAA"]}, "plugins": {}}"#,
+        )
+        .unwrap();
+        let gdm = Gdm::builder()
+            .project_dir(project_dir.path())
+            .build()
+            .unwrap();
+
+        let app_config = gdm.plugin_service().unwrap().app_config;
+
+        assert_eq!(
+            app_config.get_trusted_keys(),
+            vec!["ssh-ed25519 AAAA".to_string()]
+        );
+        assert_eq!(
+            app_config.get_credits_file_path(),
+            Some(project_dir.path().join("CREDITS.md"))
+        );
+    }
+
+    #[test]
+    fn test_build_with_missing_project_dir_should_fail() {
+        let result = Gdm::builder().project_dir("tests/missing_project").build();
+        assert!(
+            result
+                .err()
+                .unwrap()
+                .to_string()
+                .contains("Project directory not found")
+        );
+    }
+}
@@ -1,19 +1,23 @@
 use crate::api::{Asset, AssetResponse, AssetStoreAPI};
 use crate::config::{AppConfig, DefaultAppConfig};
+use crate::error::{ErrorKind, GdmError};
 use crate::installers::PluginInstaller;
 use crate::models::{Plugin, PluginSource};
-use crate::services::{ExtractService, InstallService};
-use crate::ui::OperationManager;
+use crate::services::{ArchiveCache, ExtractService, InstallService};
+use crate::ui::timings::{self, Phase};
+use crate::ui::{Operation, ProgressReporter};
 
 use anyhow::Result;
 use async_trait::async_trait;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use tracing::debug;
 
 pub struct AssetLibraryInstaller {
     asset_store_api: Arc<dyn AssetStoreAPI + Send + Sync>,
     extract_service: Arc<dyn ExtractService + Send + Sync>,
     app_config: DefaultAppConfig,
+    archive_cache: Option<ArchiveCache>,
 }
 
 impl Default for AssetLibraryInstaller {
@@ -27,6 +31,7 @@ impl Default for AssetLibraryInstaller {
             asset_store_api,
             extract_service,
             app_config,
+            archive_cache: None,
         }
     }
 }
@@ -42,9 +47,77 @@ impl AssetLibraryInstaller {
             asset_store_api,
             extract_service,
             app_config,
+            archive_cache: None,
         }
     }
 
+    /// Keeps the archive of each installed plugin in `archive_cache`, and installs from it
+    /// when the asset was removed from the library or its download is gone
+    pub fn with_archive_cache(mut self, archive_cache: Option<ArchiveCache>) -> Self {
+        self.archive_cache = archive_cache;
+        self
+    }
+
+    /// The cached archive of the installed version, if `error` means the asset or its download is gone.
+    /// Otherwise returns `error`.
+    fn cached_archive_for(&self, plugin: &Plugin, error: anyhow::Error) -> Result<PathBuf> {
+        let is_gone = GdmError::find(&error).is_some_and(|e| e.kind == ErrorKind::NotFound);
+        match (&plugin.source, &self.archive_cache) {
+            (Some(PluginSource::AssetLibrary { asset_id }), Some(archive_cache)) if is_gone => {
+                archive_cache.get(asset_id, &plugin.version).ok_or(error)
+            }
+            _ => Err(error),
+        }
+    }
+
+    /// The cached archive of the version in gdm.json, which --frozen installs instead of
+    /// resolving and downloading it
+    fn frozen_archive(&self, plugin: &Plugin) -> Result<PathBuf> {
+        let archive = match (&plugin.source, &self.archive_cache) {
+            (Some(PluginSource::AssetLibrary { asset_id }), Some(archive_cache)) => {
+                archive_cache.get(asset_id, &plugin.version)
+            }
+            _ => None,
+        };
+        archive.ok_or_else(|| {
+            GdmError::new(
+                ErrorKind::NotFound,
+                format!(
+                    "{} {} isn't in the archive cache, and --frozen doesn't download it",
+                    plugin.title, plugin.version
+                ),
+            )
+            .with_plugin(&plugin.title)
+            .with_hint("Install it once without --frozen to cache its archive.")
+            .into()
+        })
+    }
+
+    /// Metadata of the installed version, for an asset that isn't in the library anymore
+    fn installed_asset(plugin: &Plugin) -> AssetResponse {
+        let asset_id = match &plugin.source {
+            Some(PluginSource::AssetLibrary { asset_id }) => asset_id.clone(),
+            _ => String::new(),
+        };
+        AssetResponse {
+            asset_id,
+            title: plugin.title.clone(),
+            version_string: plugin.version.clone(),
+            godot_version: plugin.godot_version.clone().unwrap_or_default(),
+            cost: plugin.license.clone().unwrap_or_default(),
+            ..AssetResponse::default()
+        }
+    }
+
+    /// Copies a cached archive into the staging folder, which is removed after installing
+    fn restore_cached_archive(&self, archive: &Path, asset: AssetResponse) -> Result<Asset> {
+        let cache_dir = self.app_config.get_cache_folder_path();
+        std::fs::create_dir_all(cache_dir)?;
+        let file_path = cache_dir.join(format!("{}-cached.zip", asset.asset_id));
+        std::fs::copy(archive, &file_path)?;
+        Ok(Asset::new(file_path, asset))
+    }
+
     async fn resolve_asset_metadata(&self, plugin: &Plugin) -> Result<AssetResponse> {
         if let Some(PluginSource::AssetLibrary { asset_id }) = &plugin.source {
             let api = self.asset_store_api.clone();
@@ -61,14 +134,81 @@ impl AssetLibraryInstaller {
         asset: &AssetResponse,
         index: usize,
         total: usize,
-        operation_manager: &OperationManager,
+        reporter: &dyn ProgressReporter,
     ) -> Result<Asset> {
-        let pb_task =
-            operation_manager.add_progress_bar(index, total, &asset.title, &asset.version)?;
+        let task = reporter.started(
+            &Operation::Install,
+            index,
+            total,
+            &asset.title,
+            &asset.version,
+        )?;
 
         let api = self.asset_store_api.clone();
 
-        api.download_asset(asset, pb_task).await
+        let downloaded = api.download_asset(asset, task.clone()).await?;
+        reporter.add_downloaded_bytes(task.position());
+        Ok(downloaded)
+    }
+
+    /// Resolves the version in gdm.json and downloads it, installing from the archive cache if
+    /// the asset or its download is gone
+    async fn download(
+        &self,
+        index: usize,
+        total: usize,
+        plugin: &Plugin,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<Asset> {
+        let subject = plugin.title.as_str();
+        let resolve_task = reporter.started(
+            &Operation::Resolve,
+            index,
+            total,
+            subject,
+            &plugin.get_version(),
+        )?;
+        let asset_metadata = timings::measure(
+            subject,
+            Phase::Metadata,
+            self.resolve_asset_metadata(plugin),
+        )
+        .await;
+        resolve_task.finished();
+
+        Ok(match asset_metadata {
+            Ok(asset_metadata) => {
+                let downloaded = timings::measure(
+                    subject,
+                    Phase::Download,
+                    self.download_asset_with_manager(&asset_metadata, index, total, reporter),
+                )
+                .await;
+                match downloaded {
+                    Ok(downloaded) => downloaded,
+                    Err(e) => {
+                        let archive = self.cached_archive_for(plugin, e)?;
+                        reporter.warning(&format!(
+                            "The download of {} {} is no longer available. Installing it from the cached archive {}",
+                            plugin.title,
+                            plugin.version,
+                            archive.display()
+                        ));
+                        self.restore_cached_archive(&archive, asset_metadata)?
+                    }
+                }
+            }
+            Err(e) => {
+                let archive = self.cached_archive_for(plugin, e)?;
+                reporter.warning(&format!(
+                    "{} is no longer in the Asset Library, it may have been removed or delisted. Installing {} from the cached archive {}",
+                    plugin.title,
+                    plugin.version,
+                    archive.display()
+                ));
+                self.restore_cached_archive(&archive, Self::installed_asset(plugin))?
+            }
+        })
     }
 
     async fn extract_to_cache_with_manager(
@@ -76,27 +216,33 @@ impl AssetLibraryInstaller {
         downloaded_asset: &Asset,
         index: usize,
         total: usize,
-        operation_manager: &OperationManager,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(String, PathBuf)> {
         let cache_dir = self.app_config.get_cache_folder_path();
 
         let asset_response = &downloaded_asset.asset_response;
         let asset_id = asset_response.asset_id.clone();
 
-        let pb_task = operation_manager.add_progress_bar(
+        let task = reporter.started(
+            &Operation::Install,
             index,
             total,
             &asset_response.title,
             &asset_response.version_string,
         )?;
 
+        task.message(&format!(
+            "Extracting: {} ({})",
+            asset_response.title, asset_response.version_string
+        ));
+
         let extract_service = self.extract_service.clone();
         let asset_cloned = downloaded_asset.clone();
 
         let tmp_dir = cache_dir.join(&asset_id);
 
         extract_service
-            .extract_asset_to_cache(&asset_cloned, &tmp_dir, pb_task)
+            .extract_asset_to_cache(&asset_cloned, &tmp_dir, task)
             .await
             .map(|path| (asset_id, path))
     }
@@ -114,17 +260,38 @@ impl PluginInstaller for AssetLibraryInstaller {
         total: usize,
         install_service: &dyn InstallService,
         plugin: &Plugin,
-        operation_manager: Arc<OperationManager>,
+        reporter: Arc<dyn ProgressReporter>,
     ) -> Result<(String, Plugin)> {
-        let asset_metadata = self.resolve_asset_metadata(plugin).await?;
+        let subject = plugin.title.as_str();
+        let requested_addons_dir = plugin.addons_dir.clone();
+        let downloaded_file = if self.app_config.get_frozen() {
+            let archive = self.frozen_archive(plugin)?;
+            self.restore_cached_archive(&archive, Self::installed_asset(plugin))?
+        } else {
+            self.download(index, total, plugin, reporter.as_ref())
+                .await?
+        };
 
-        let downloaded_file = self
-            .download_asset_with_manager(&asset_metadata, index, total, &operation_manager)
-            .await?;
+        let checksum =
+            install_service.verify_archive_checksum(plugin, &downloaded_file.file_path)?;
+        if let Some(archive_cache) = &self.archive_cache {
+            let asset = &downloaded_file.asset_response;
+            if let Err(e) = archive_cache.store(
+                &asset.asset_id,
+                &asset.version_string,
+                &downloaded_file.file_path,
+                &checksum,
+            ) {
+                debug!("Failed to cache archive: {:#}", e);
+            }
+        }
 
-        let path = self
-            .extract_to_cache_with_manager(&downloaded_file, index, total, &operation_manager)
-            .await?;
+        let path = timings::measure(
+            subject,
+            Phase::Extract,
+            self.extract_to_cache_with_manager(&downloaded_file, index, total, reporter.as_ref()),
+        )
+        .await?;
 
         let (asset_id, staging_dir) = path;
         let metadata = &downloaded_file.asset_response;
@@ -133,14 +300,21 @@ impl PluginInstaller for AssetLibraryInstaller {
             asset_id: asset_id.clone(),
         };
 
-        let (main_folder_name, mut plugin, folders_to_move) = install_service
+        let (main_folder_name, discovered_plugin, folders_to_move) = install_service
             .discover_and_analyze_plugins(&plugin_source, &staging_dir, &metadata.title)?;
 
-        install_service.install_from_cache(&staging_dir, &folders_to_move)?;
+        let addons_dir = install_service.get_addons_dir(plugin);
+        timings::measure_sync(subject, Phase::Move, || {
+            install_service.install_from_cache(&staging_dir, &folders_to_move, &addons_dir)
+        })?;
 
+        let mut plugin = discovered_plugin.relocate(&addons_dir);
+        plugin.addons_dir = requested_addons_dir;
         plugin.title = metadata.title.clone();
         plugin.version = metadata.version_string.clone();
         plugin.license = Some(metadata.cost.clone());
+        plugin.godot_version = Some(metadata.godot_version.clone()).filter(|v| !v.is_empty());
+        plugin.checksum = Some(checksum);
 
         Ok((main_folder_name, plugin))
     }
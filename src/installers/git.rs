@@ -1,12 +1,12 @@
 use crate::installers::PluginInstaller;
 use crate::models::{Plugin, PluginSource};
 use crate::services::{GitService, InstallService};
-use crate::ui::OperationManager;
+use crate::ui::timings::{self, Phase};
+use crate::ui::{Operation, ProgressReporter};
 
 use anyhow::Result;
 use async_trait::async_trait;
 use std::sync::Arc;
-use std::time::Duration;
 
 pub struct GitInstaller {
     git_service: Arc<dyn GitService + Send + Sync>,
@@ -37,8 +37,10 @@ impl PluginInstaller for GitInstaller {
         total: usize,
         install_service: &dyn InstallService,
         plugin: &Plugin,
-        operation_manager: Arc<OperationManager>,
+        reporter: Arc<dyn ProgressReporter>,
     ) -> Result<(String, Plugin)> {
+        let git_fetch = plugin.git_fetch;
+        let verify_signature = plugin.verify_signature;
         let plugin_source = match &plugin.source {
             Some(PluginSource::Git { url, reference }) => (url.clone(), reference.clone()),
             _ => {
@@ -50,18 +52,29 @@ impl PluginInstaller for GitInstaller {
         let url = &plugin_source.0;
         let reference = &plugin_source.1;
 
-        let pb = operation_manager.add_progress_bar(index, total, url, reference)?;
-
-        pb.enable_steady_tick(Duration::from_millis(100));
-
-        let (staging_dir, _) = tokio::task::spawn_blocking(move || {
-            let url = &plugin_source.0;
-            let reference = &plugin_source.1;
-            git_service.shallow_fetch_repository(url, Some(reference.clone()))
-        })
+        let task = reporter.started(&Operation::Install, index, total, url, reference)?;
+
+        let subject = url.clone();
+        let (staging_dir, tree_id, commit_id) = timings::measure(
+            &subject,
+            Phase::Download,
+            tokio::task::spawn_blocking(move || {
+                let url = &plugin_source.0;
+                let reference = &plugin_source.1;
+                git_service.shallow_fetch_repository(
+                    url,
+                    Some(reference.clone()),
+                    git_fetch,
+                    verify_signature,
+                )
+            }),
+        )
         .await??;
 
-        pb.finish_and_clear();
+        task.finished();
+
+        let checksum = format!("git-tree:{}", tree_id);
+        plugin.verify_checksum(&checksum)?;
 
         let repo_name = self
             .git_service
@@ -70,11 +83,19 @@ impl PluginInstaller for GitInstaller {
 
         let source = plugin.source.clone().unwrap();
 
-        let (folder_name, plugin, folders_to_move) =
+        let (folder_name, discovered_plugin, folders_to_move) =
             install_service.discover_and_analyze_plugins(&source, &staging_dir, &repo_name)?;
 
-        install_service.install_from_cache(&staging_dir, &folders_to_move)?;
+        let addons_dir = install_service.get_addons_dir(plugin);
+        timings::measure_sync(&subject, Phase::Move, || {
+            install_service.install_from_cache(&staging_dir, &folders_to_move, &addons_dir)
+        })?;
+
+        let mut installed_plugin = discovered_plugin.relocate(&addons_dir);
+        installed_plugin.addons_dir = plugin.addons_dir.clone();
+        installed_plugin.checksum = Some(checksum);
+        installed_plugin.commit = Some(commit_id);
 
-        Ok((folder_name, plugin))
+        Ok((folder_name, installed_plugin))
     }
 }
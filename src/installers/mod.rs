@@ -11,7 +11,7 @@ pub use git::GitInstaller;
 use crate::{
     models::{Plugin, PluginSource},
     services::InstallService,
-    ui::OperationManager,
+    ui::ProgressReporter,
 };
 
 #[async_trait]
@@ -24,6 +24,6 @@ pub trait PluginInstaller: Send + Sync {
         total: usize,
         install_service: &dyn InstallService,
         plugin: &Plugin,
-        operation_manager: Arc<OperationManager>,
+        reporter: Arc<dyn ProgressReporter>,
     ) -> Result<(String, Plugin)>;
 }
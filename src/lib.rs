@@ -0,0 +1,22 @@
+//! Plugin management for Godot projects, used by the `gdm` binary.
+//!
+//! The services don't print or exit. Progress, status messages and confirmation prompts go
+//! through [`ui::UserInterface`], set with `DefaultPluginService::with_ui`. Without one,
+//! nothing is shown and questions are answered yes.
+//!
+//! [`Gdm`] wires the services for one project without reading the environment, and returns
+//! typed results from add, install, update, remove and search.
+
+#![forbid(unsafe_code)]
+
+pub mod api;
+pub mod config;
+pub mod error;
+mod gdm;
+pub mod installers;
+pub mod models;
+pub mod services;
+pub mod ui;
+pub mod utils;
+
+pub use gdm::{Gdm, GdmBuilder};
@@ -1,32 +1,124 @@
 #![forbid(unsafe_code)]
 
-mod api;
 mod commands;
-mod config;
-mod installers;
-mod models;
-mod services;
-mod ui;
-mod utils;
+mod terminal;
 
 use crate::commands::Cli;
-use anyhow::Result;
+use crate::terminal::OutputFormat;
+use crate::terminal::redact::Redacting;
+use anyhow::{Context, Result};
 use clap::Parser;
+use gdm_core::config::GlobalConfig;
+use gdm_core::error::GdmError;
+use gdm_core::services::{DefaultInstallService, RELEASES_PAGE_URL, UpdateCheck};
+use gdm_core::ui::timings;
+use std::fs::OpenOptions;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::prelude::*;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
-    tracing_subscriber::fmt()
-        .with_max_level(cli.verbosity)
-        .init();
+    // An invalid global config is reported by the command, colored or not
+    cli.color_choice(&GlobalConfig::load().unwrap_or_default())
+        .apply();
+
+    let update_check = cli
+        .should_check_for_updates()
+        .then(UpdateCheck::new)
+        .flatten()
+        .map(UpdateCheck::spawn);
+
+    // Ctrl+C ends gdm without dropping the staging guard of an install in progress
+    tokio::spawn(async {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            DefaultInstallService::remove_interrupted_staging();
+            std::process::exit(130);
+        }
+    });
+
+    let result = match init_tracing(&cli) {
+        Ok(()) => commands::handle(&cli).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = &result {
+        tracing::debug!("Command failed: {:#}", e);
+    }
+    if cli.timings
+        && let Some(summary) = timings::summary()
+    {
+        eprintln!("\n{}", summary);
+    }
 
-    let result = commands::handle(&cli.command).await;
+    // Don't hold up the command for a slow release feed, the check is retried tomorrow
+    if let Some(update_check) = update_check
+        && let Ok(Ok(Some(version))) =
+            tokio::time::timeout(Duration::from_millis(500), update_check).await
+    {
+        eprintln!(
+            "\n{}",
+            terminal::hint(format!(
+                "gdm {} is available, you have {}. Download it from {}",
+                version,
+                env!("CARGO_PKG_VERSION"),
+                RELEASES_PAGE_URL
+            ))
+        );
+    }
 
     match result {
         Ok(_) => Ok(()),
-        Err(e) => {
-            eprintln!("{}", e);
-            std::process::exit(1);
+        Err(e) if cli.output_format == OutputFormat::Json => {
+            eprintln!("{}", terminal::error_json(&e));
+            std::process::exit(GdmError::find(&e).map_or(1, |e| e.kind.exit_code()));
         }
+        Err(e) => match GdmError::find(&e) {
+            Some(gdm_error) => {
+                eprintln!(
+                    "{} {}",
+                    terminal::error(format!("error[{}]:", gdm_error.kind.code())),
+                    e
+                );
+                if let Some(hint) = gdm_error.hint() {
+                    eprintln!("{}", terminal::hint(format!("hint: {}", hint)));
+                }
+                std::process::exit(gdm_error.kind.exit_code());
+            }
+            None => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        },
     }
 }
+
+/// Logs to the console at the -v level, and to --log-file at --log-level if given.
+/// Secrets like tokens and credentials in URLs are redacted from both.
+fn init_tracing(cli: &Cli) -> Result<()> {
+    let console_layer = tracing_subscriber::fmt::layer()
+        .with_writer(Redacting(std::io::stdout))
+        .with_filter(LevelFilter::from(cli.verbosity));
+    let file_layer = match &cli.log_file {
+        Some(path) => {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .with_context(|| format!("Failed to open log file {}", path.display()))?;
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .with_ansi(false)
+                    .with_writer(Redacting(Mutex::new(file)))
+                    .with_filter(LevelFilter::from_level(cli.log_level)),
+            )
+        }
+        None => None,
+    };
+    tracing_subscriber::registry()
+        .with(console_layer)
+        .with(file_layer)
+        .init();
+    Ok(())
+}
@@ -0,0 +1,218 @@
+use crate::models::{Plugin, PluginSource};
+use crate::utils::Utils;
+
+use anyhow::{Result, bail};
+use semver::VersionReq;
+use serde_derive::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// How urgently an advisory should be acted on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+            Severity::Critical => "critical",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Severity {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Severity> {
+        match value.to_lowercase().as_str() {
+            "low" => Ok(Severity::Low),
+            "medium" => Ok(Severity::Medium),
+            "high" => Ok(Severity::High),
+            "critical" => Ok(Severity::Critical),
+            _ => bail!(
+                "Unknown severity \"{}\", expected low, medium, high or critical",
+                value
+            ),
+        }
+    }
+}
+
+/// What is wrong with the affected versions
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdvisoryKind {
+    /// The asset contains malicious code
+    Malicious,
+    /// The asset isn't maintained anymore
+    Abandoned,
+    /// The versions don't work, e.g. they fail to load or corrupt project files
+    Broken,
+}
+
+impl fmt::Display for AdvisoryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AdvisoryKind::Malicious => "malicious",
+            AdvisoryKind::Abandoned => "abandoned",
+            AdvisoryKind::Broken => "broken",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// An entry of the advisory feed. Matches an Asset Library plugin by `asset_id`
+/// or a git plugin by `url`, and the installed version by `versions`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Advisory {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Semver requirement of the affected versions, e.g. `>=1.0.0, <1.2.1`. All versions if missing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub versions: Option<String>,
+    pub kind: AdvisoryKind,
+    pub severity: Severity,
+    pub summary: String,
+    /// What to do about it, e.g. "Update to 1.2.1"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+}
+
+impl Advisory {
+    /// Whether the installed plugin is the advisory's asset or repository, in an affected version.
+    /// An advisory with a `versions` requirement that can't be parsed matches all versions.
+    pub fn affects(&self, plugin: &Plugin) -> bool {
+        let is_same_source = match &plugin.source {
+            Some(PluginSource::AssetLibrary { asset_id }) => {
+                self.asset_id.as_ref() == Some(asset_id)
+            }
+            Some(PluginSource::Git { url, .. }) => self
+                .url
+                .as_deref()
+                .is_some_and(|advisory_url| Self::same_repository(advisory_url, url)),
+            None => false,
+        };
+        if !is_same_source {
+            return false;
+        }
+        match self.versions.as_deref().map(VersionReq::parse) {
+            Some(Ok(versions)) => versions.matches(&Utils::parse_semantic_version(&plugin.version)),
+            _ => true,
+        }
+    }
+
+    /// Compares repository URLs without a trailing slash or `.git`
+    fn same_repository(a: &str, b: &str) -> bool {
+        let normalize = |url: &str| {
+            url.trim_end_matches('/')
+                .trim_end_matches(".git")
+                .to_lowercase()
+        };
+        normalize(a) == normalize(b)
+    }
+}
+
+/// The community advisory feed, a JSON file with an `advisories` list
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AdvisoryFeed {
+    #[serde(default)]
+    pub advisories: Vec<Advisory>,
+}
+
+/// An advisory that affects an installed plugin
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditFinding {
+    /// Name of the plugin in gdm.json
+    pub name: String,
+    pub plugin: Plugin,
+    pub advisory: Advisory,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn advisory(asset_id: Option<&str>, url: Option<&str>, versions: Option<&str>) -> Advisory {
+        Advisory {
+            id: "GDM-ADV-0001".to_string(),
+            asset_id: asset_id.map(str::to_string),
+            url: url.map(str::to_string),
+            versions: versions.map(str::to_string),
+            kind: AdvisoryKind::Broken,
+            severity: Severity::High,
+            summary: "Corrupts project.godot".to_string(),
+            action: Some("Update to 1.2.1".to_string()),
+        }
+    }
+
+    fn asset_plugin(asset_id: &str, version: &str) -> Plugin {
+        Plugin::new_asset_store_plugin(
+            asset_id.to_string(),
+            None,
+            "Plugin".to_string(),
+            version.to_string(),
+            "MIT".to_string(),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_affects_should_match_asset_id_and_versions() {
+        let advisory = advisory(Some("1709"), None, Some(">=1.0.0, <1.2.1"));
+
+        assert!(advisory.affects(&asset_plugin("1709", "1.2.0")));
+        assert!(!advisory.affects(&asset_plugin("1709", "1.2.1")));
+        assert!(!advisory.affects(&asset_plugin("1710", "1.2.0")));
+    }
+
+    #[test]
+    fn test_affects_without_versions_should_match_all_versions() {
+        let advisory = advisory(Some("1709"), None, None);
+
+        assert!(advisory.affects(&asset_plugin("1709", "0.1.0")));
+        assert!(advisory.affects(&asset_plugin("1709", "9.0.0")));
+    }
+
+    #[test]
+    fn test_affects_should_match_git_url_without_suffix() {
+        let advisory = advisory(None, Some("https://github.com/bitwes/Gut.git"), None);
+        let mut plugin = asset_plugin("", "9.2.0");
+        plugin.source = Some(PluginSource::Git {
+            url: "https://github.com/bitwes/gut/".to_string(),
+            reference: "main".to_string(),
+        });
+
+        assert!(advisory.affects(&plugin));
+        assert!(!advisory.affects(&asset_plugin("1709", "9.2.0")));
+    }
+
+    #[test]
+    fn test_severity_should_parse_and_order() {
+        assert_eq!("High".parse::<Severity>().unwrap(), Severity::High);
+        assert!("urgent".parse::<Severity>().is_err());
+        assert!(Severity::Critical > Severity::Medium);
+    }
+
+    #[test]
+    fn test_feed_should_deserialize() {
+        let feed: AdvisoryFeed = serde_json::from_str(
+            r#"{"advisories": [{"id": "GDM-ADV-0001", "asset_id": "1709", "kind": "malicious", "severity": "critical", "summary": "Steals credentials"}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(feed.advisories.len(), 1);
+        assert_eq!(feed.advisories[0].kind, AdvisoryKind::Malicious);
+        assert_eq!(feed.advisories[0].versions, None);
+    }
+}
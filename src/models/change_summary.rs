@@ -0,0 +1,168 @@
+use crate::models::Plugin;
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// What a command changed in the project, printed at the end of add, install, update and remove
+#[derive(Debug, Default, PartialEq)]
+pub struct ChangeSummary {
+    /// Name and version of plugins that weren't in the configuration before
+    pub added: Vec<(String, String)>,
+    /// Name, old version and new version
+    pub updated: Vec<(String, String, String)>,
+    /// Name and version of plugins installed again at the version in the configuration
+    pub installed: Vec<(String, String)>,
+    /// Name and version of plugins whose installed files already match the configuration
+    pub up_to_date: Vec<(String, String)>,
+    pub removed: Vec<String>,
+    /// Name and the reason it was skipped
+    pub skipped: Vec<(String, String)>,
+    pub modified_files: Vec<PathBuf>,
+}
+
+impl ChangeSummary {
+    /// Sorts the installed plugins into added, updated and reinstalled ones
+    /// by comparing them to the configuration before the command
+    pub fn from_installed(
+        before: &BTreeMap<String, Plugin>,
+        installed: &BTreeMap<String, Plugin>,
+    ) -> ChangeSummary {
+        let mut summary = ChangeSummary::default();
+        for (name, plugin) in installed {
+            let version = plugin.get_version();
+            match before.get(name) {
+                Some(existing) if existing.get_version() != version => {
+                    summary
+                        .updated
+                        .push((name.clone(), existing.get_version(), version))
+                }
+                Some(_) => summary.installed.push((name.clone(), version)),
+                None => summary.added.push((name.clone(), version)),
+            }
+        }
+        summary
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.updated.is_empty()
+            && self.installed.is_empty()
+            && self.up_to_date.is_empty()
+            && self.removed.is_empty()
+            && self.skipped.is_empty()
+            && self.modified_files.is_empty()
+    }
+}
+
+impl fmt::Display for ChangeSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = Vec::new();
+        let mut push_line = |label: &str, entries: Vec<String>| {
+            if !entries.is_empty() {
+                lines.push(format!("{}: {}", label, entries.join(", ")));
+            }
+        };
+        push_line(
+            "Added",
+            self.added
+                .iter()
+                .map(|(name, version)| format!("{} {}", name, version))
+                .collect(),
+        );
+        push_line(
+            "Updated",
+            self.updated
+                .iter()
+                .map(|(name, old, new)| format!("{} {} → {}", name, old, new))
+                .collect(),
+        );
+        push_line(
+            "Installed",
+            self.installed
+                .iter()
+                .map(|(name, version)| format!("{} {}", name, version))
+                .collect(),
+        );
+        push_line(
+            "Up to date",
+            self.up_to_date
+                .iter()
+                .map(|(name, version)| format!("{} {}", name, version))
+                .collect(),
+        );
+        push_line("Removed", self.removed.clone());
+        push_line(
+            "Skipped",
+            self.skipped
+                .iter()
+                .map(|(name, reason)| format!("{} ({})", name, reason))
+                .collect(),
+        );
+        push_line(
+            "Modified",
+            self.modified_files
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect(),
+        );
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin_with_version(version: &str) -> Plugin {
+        Plugin {
+            version: version.to_string(),
+            ..Plugin::default()
+        }
+    }
+
+    #[test]
+    fn test_from_installed_should_sort_plugins_by_change() {
+        let before = BTreeMap::from([
+            ("gut".to_string(), plugin_with_version("9.2.0")),
+            ("beehave".to_string(), plugin_with_version("2.8.0")),
+        ]);
+        let installed = BTreeMap::from([
+            ("gut".to_string(), plugin_with_version("9.3.0")),
+            ("beehave".to_string(), plugin_with_version("2.8.0")),
+            ("limboai".to_string(), plugin_with_version("1.4.0")),
+        ]);
+
+        let summary = ChangeSummary::from_installed(&before, &installed);
+
+        assert_eq!(
+            summary.added,
+            vec![("limboai".to_string(), "1.4.0".to_string())]
+        );
+        assert_eq!(
+            summary.updated,
+            vec![("gut".to_string(), "9.2.0".to_string(), "9.3.0".to_string())]
+        );
+        assert_eq!(
+            summary.installed,
+            vec![("beehave".to_string(), "2.8.0".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_display_should_skip_empty_categories() {
+        let summary = ChangeSummary {
+            updated: vec![("gut".to_string(), "9.2.0".to_string(), "9.3.0".to_string())],
+            removed: vec!["beehave".to_string()],
+            modified_files: vec![PathBuf::from("gdm.json"), PathBuf::from("project.godot")],
+            ..ChangeSummary::default()
+        };
+
+        assert_eq!(
+            summary.to_string(),
+            "Updated: gut 9.2.0 → 9.3.0\nRemoved: beehave\nModified: gdm.json, project.godot"
+        );
+        assert!(!summary.is_empty());
+        assert!(ChangeSummary::default().is_empty());
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt;
+
+/// Attribution of one plugin in the credits file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Credit {
+    pub title: String,
+    /// `author` in the plugin's plugin.cfg
+    pub author: Option<String>,
+    pub version: String,
+    pub license: Option<String>,
+    /// Asset Library page or git repository of the plugin
+    pub source_url: Option<String>,
+}
+
+/// Attribution list of the managed plugins, written by `gdm credits` as Markdown
+#[derive(Debug, Default, PartialEq)]
+pub struct Credits {
+    pub credits: Vec<Credit>,
+}
+
+impl fmt::Display for Credits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# Credits")?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "This project uses the following third-party Godot plugins."
+        )?;
+        for credit in &self.credits {
+            writeln!(f)?;
+            writeln!(f, "## {}", credit.title)?;
+            writeln!(f)?;
+            if let Some(author) = &credit.author {
+                writeln!(f, "- Author: {}", author)?;
+            }
+            writeln!(f, "- Version: {}", credit.version)?;
+            if let Some(license) = &credit.license {
+                writeln!(f, "- License: {}", license)?;
+            }
+            if let Some(source_url) = &credit.source_url {
+                writeln!(f, "- Source: <{}>", source_url)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_should_list_known_attributions() {
+        let credits = Credits {
+            credits: vec![
+                Credit {
+                    title: "Gut".to_string(),
+                    author: Some("Butch Wesley".to_string()),
+                    version: "9.6.0".to_string(),
+                    license: Some("MIT".to_string()),
+                    source_url: Some(
+                        "https://godotengine.org/asset-library/asset/1709".to_string(),
+                    ),
+                },
+                Credit {
+                    title: "Dialogic".to_string(),
+                    version: "2.0.0".to_string(),
+                    ..Credit::default()
+                },
+            ],
+        };
+
+        let markdown = credits.to_string();
+
+        assert!(markdown.starts_with("# Credits\n"));
+        assert!(markdown.contains(
+            "## Gut\n\n- Author: Butch Wesley\n- Version: 9.6.0\n- License: MIT\n- Source: <https://godotengine.org/asset-library/asset/1709>\n"
+        ));
+        assert!(markdown.ends_with("## Dialogic\n\n- Version: 2.0.0\n"));
+    }
+}
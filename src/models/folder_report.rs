@@ -0,0 +1,39 @@
+use crate::models::Plugin;
+
+use std::path::PathBuf;
+
+/// How a plugin installs an addon folder
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FolderRole {
+    /// The folder named after the plugin, removed with it by `gdm remove`
+    Main,
+    /// A folder the plugin's asset installs next to the main folder, listed in `sub_assets`
+    SubAsset,
+}
+
+/// A plugin in the configuration file that installs the folder
+#[derive(Debug, Clone, PartialEq)]
+pub struct FolderOwner {
+    /// Name of the plugin in gdm.json
+    pub name: String,
+    pub plugin: Plugin,
+    pub role: FolderRole,
+}
+
+/// Why an addon folder is in the project, as reported by `gdm why`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FolderReport {
+    /// Path of the folder relative to the project root, e.g. `addons/gut`
+    pub folder: PathBuf,
+    pub exists: bool,
+    /// Plugins that install the folder. Several if assets bundle the same folder.
+    pub owners: Vec<FolderOwner>,
+    /// Other plugins with autoloads in the folder, which break if it's removed
+    pub dependents: Vec<String>,
+}
+
+impl FolderReport {
+    pub fn is_managed(&self) -> bool {
+        !self.owners.is_empty()
+    }
+}
@@ -0,0 +1,176 @@
+use crate::models::Plugin;
+
+use std::collections::BTreeMap;
+
+/// The project, the plugins it depends on and the sub-assets installed with each plugin,
+/// rendered as Graphviz DOT or a Mermaid flowchart
+#[derive(Debug, Default, PartialEq)]
+pub struct DependencyGraph {
+    /// project.name in gdm.json, or the name of the project folder
+    pub project: String,
+    pub plugins: Vec<GraphNode>,
+}
+
+/// A plugin of the dependency graph
+#[derive(Debug, Default, PartialEq)]
+pub struct GraphNode {
+    /// Name of the plugin in gdm.json
+    pub name: String,
+    pub version: String,
+    pub source: Option<String>,
+    pub dev: bool,
+    /// Addon folders installed with the plugin
+    pub sub_assets: Vec<String>,
+}
+
+impl DependencyGraph {
+    pub fn new(project: String, plugins: &BTreeMap<String, Plugin>) -> DependencyGraph {
+        let plugins = plugins
+            .iter()
+            .map(|(name, plugin)| GraphNode {
+                name: name.clone(),
+                version: plugin.get_version(),
+                source: plugin.source.as_ref().map(|source| source.to_string()),
+                dev: plugin.dev,
+                sub_assets: plugin.sub_assets.clone(),
+            })
+            .collect();
+        DependencyGraph { project, plugins }
+    }
+
+    /// Graphviz DOT, e.g. for `dot -Tsvg`
+    pub fn to_dot(&self) -> String {
+        let quote = |lines: &[String]| {
+            let lines: Vec<String> = lines
+                .iter()
+                .map(|line| line.replace('\\', "\\\\").replace('"', "\\\""))
+                .collect();
+            format!("\"{}\"", lines.join("\\n"))
+        };
+        let mut lines = vec![
+            "digraph gdm {".to_string(),
+            "  rankdir=LR;".to_string(),
+            format!(
+                "  project [label={}, shape=box];",
+                quote(std::slice::from_ref(&self.project))
+            ),
+        ];
+        for (index, plugin) in self.plugins.iter().enumerate() {
+            let id = format!("plugin_{}", index);
+            lines.push(format!(
+                "  {} [label={}];",
+                id,
+                quote(&Self::plugin_label(plugin))
+            ));
+            if plugin.dev {
+                lines.push(format!(
+                    "  project -> {} [style=dashed, label=\"dev\"];",
+                    id
+                ));
+            } else {
+                lines.push(format!("  project -> {};", id));
+            }
+            for (sub_index, sub_asset) in plugin.sub_assets.iter().enumerate() {
+                let sub_id = format!("{}_{}", id, sub_index);
+                lines.push(format!(
+                    "  {} [label={}, shape=folder];",
+                    sub_id,
+                    quote(std::slice::from_ref(sub_asset))
+                ));
+                lines.push(format!("  {} -> {};", id, sub_id));
+            }
+        }
+        lines.push("}".to_string());
+        lines.join("\n") + "\n"
+    }
+
+    /// Mermaid flowchart, e.g. for a fenced `mermaid` block in Markdown
+    pub fn to_mermaid(&self) -> String {
+        let quote = |value: &str| format!("\"{}\"", value.replace('"', "#quot;"));
+        let mut lines = vec![
+            "flowchart LR".to_string(),
+            format!("  project[{}]", quote(&self.project)),
+        ];
+        for (index, plugin) in self.plugins.iter().enumerate() {
+            let id = format!("plugin_{}", index);
+            lines.push(format!(
+                "  {}({})",
+                id,
+                quote(&Self::plugin_label(plugin).join("<br>"))
+            ));
+            if plugin.dev {
+                lines.push(format!("  project -.->|dev| {}", id));
+            } else {
+                lines.push(format!("  project --> {}", id));
+            }
+            for (sub_index, sub_asset) in plugin.sub_assets.iter().enumerate() {
+                let sub_id = format!("{}_{}", id, sub_index);
+                lines.push(format!("  {}[/{}/]", sub_id, quote(sub_asset)));
+                lines.push(format!("  {} --> {}", id, sub_id));
+            }
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /// Lines of a plugin's label, e.g. `gut 9.6.0` and `Asset Library (1709)`
+    fn plugin_label(plugin: &GraphNode) -> Vec<String> {
+        let mut label = vec![format!("{} {}", plugin.name, plugin.version)];
+        label.extend(plugin.source.clone());
+        label
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_graph() -> DependencyGraph {
+        let mut plugin = Plugin::create_mock_plugin_3();
+        plugin.dev = true;
+        DependencyGraph::new(
+            "My \"Game\"".to_string(),
+            &BTreeMap::from([
+                ("awesome_plugin".to_string(), Plugin::create_mock_plugin_1()),
+                ("some_library".to_string(), plugin),
+            ]),
+        )
+    }
+
+    #[test]
+    fn test_to_dot() {
+        assert_eq!(
+            setup_graph().to_dot(),
+            r#"digraph gdm {
+  rankdir=LR;
+  project [label="My \"Game\"", shape=box];
+  plugin_0 [label="awesome_plugin 1.0.0\nAsset Library (54321)"];
+  project -> plugin_0;
+  plugin_1 [label="some_library 3.3.3\nAsset Library (345678)"];
+  project -> plugin_1 [style=dashed, label="dev"];
+  plugin_1_0 [label="sub_asset1", shape=folder];
+  plugin_1 -> plugin_1_0;
+  plugin_1_1 [label="sub_asset2", shape=folder];
+  plugin_1 -> plugin_1_1;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_to_mermaid() {
+        assert_eq!(
+            setup_graph().to_mermaid(),
+            r#"flowchart LR
+  project["My #quot;Game#quot;"]
+  plugin_0("awesome_plugin 1.0.0<br>Asset Library (54321)")
+  project --> plugin_0
+  plugin_1("some_library 3.3.3<br>Asset Library (345678)")
+  project -.->|dev| plugin_1
+  plugin_1_0[/"sub_asset1"/]
+  plugin_1 --> plugin_1_0
+  plugin_1_1[/"sub_asset2"/]
+  plugin_1 --> plugin_1_1
+"#
+        );
+    }
+}
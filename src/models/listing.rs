@@ -0,0 +1,17 @@
+use crate::models::Plugin;
+
+use std::collections::BTreeMap;
+
+/// Plugins in the configuration file, and plugins enabled in project.godot that gdm doesn't manage
+#[derive(Debug, Default, PartialEq)]
+pub struct PluginListing {
+    pub plugins: BTreeMap<String, Plugin>,
+    /// `res://` paths of the unmanaged plugin.cfg files
+    pub unmanaged: Vec<String>,
+}
+
+impl PluginListing {
+    pub fn is_empty(&self) -> bool {
+        self.plugins.is_empty() && self.unmanaged.is_empty()
+    }
+}
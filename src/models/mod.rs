@@ -1,3 +1,19 @@
+mod advisory;
+mod change_summary;
+mod credits;
+mod folder_report;
+mod graph;
+mod listing;
+mod outdated;
 mod plugin;
+mod stats;
 
-pub use plugin::{Plugin, PluginSource};
+pub use advisory::{Advisory, AdvisoryFeed, AdvisoryKind, AuditFinding, Severity};
+pub use change_summary::ChangeSummary;
+pub use credits::{Credit, Credits};
+pub use folder_report::{FolderOwner, FolderReport, FolderRole};
+pub use graph::{DependencyGraph, GraphNode};
+pub use listing::PluginListing;
+pub use outdated::{OutdatedPlugin, UpdateLevel, UpdateStatus, VersionChange};
+pub use plugin::{GitFetchOptions, Plugin, PluginSource, UpdateChannel};
+pub use stats::{PluginStats, ProjectStats};
@@ -0,0 +1,238 @@
+use crate::models::Plugin;
+use crate::utils::Utils;
+
+use semver::Version;
+
+/// Whether a newer version of an installed plugin is available
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpdateStatus {
+    UpToDate,
+    UpdateAvailable,
+    /// The latest version is a pre-release, which `--pre` would include
+    PreRelease,
+    /// The asset was removed from the Asset Library, so the installed version is the latest
+    Delisted,
+    /// A newer version exists, but the plugin is pinned or the update is larger than the
+    /// update level allows, so `gdm update` leaves the plugin as it is
+    Blocked,
+}
+
+/// How far `gdm update` may move a plugin's version
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum UpdateLevel {
+    /// Only patch updates, e.g. 9.2.0 to 9.2.1
+    Patch,
+    /// Patch and minor updates, e.g. 9.2.0 to 9.3.0, but not 9.2.0 to 10.0.0
+    #[default]
+    Minor,
+    /// Any newer version, including breaking ones
+    Major,
+}
+
+impl UpdateLevel {
+    /// Whether moving from `current` to `latest` stays within the level. Below 1.0.0,
+    /// a minor update counts as a major one, since semver allows it to break compatibility.
+    pub fn allows(&self, current: &Version, latest: &Version) -> bool {
+        let is_breaking = if current.major == 0 {
+            latest.major != 0 || latest.minor != current.minor
+        } else {
+            latest.major != current.major
+        };
+        match self {
+            UpdateLevel::Patch => {
+                !is_breaking && latest.major == current.major && latest.minor == current.minor
+            }
+            UpdateLevel::Minor => !is_breaking,
+            UpdateLevel::Major => true,
+        }
+    }
+
+    /// Compares the versions of two plugins, see `allows`
+    pub fn allows_plugin(&self, current: &Plugin, latest: &Plugin) -> bool {
+        self.allows(
+            &Utils::parse_semantic_version(&current.version),
+            &Utils::parse_semantic_version(&latest.version),
+        )
+    }
+
+    /// Flag that allows a larger update than this level, e.g. `--major`
+    pub fn next_flag(&self) -> &'static str {
+        match self {
+            UpdateLevel::Patch => "--minor",
+            UpdateLevel::Minor | UpdateLevel::Major => "--major",
+        }
+    }
+}
+
+/// The most significant part of a version that changed, which hints at how risky an update is
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VersionChange {
+    Major,
+    Minor,
+    Patch,
+    /// Only the pre-release or build metadata changed, e.g. 9.2.0-rc1 to 9.2.0
+    PreRelease,
+}
+
+impl VersionChange {
+    /// None when the versions are equal
+    pub fn between(current: &Version, latest: &Version) -> Option<VersionChange> {
+        if latest.major != current.major {
+            Some(VersionChange::Major)
+        } else if latest.minor != current.minor {
+            Some(VersionChange::Minor)
+        } else if latest.patch != current.patch {
+            Some(VersionChange::Patch)
+        } else if latest != current {
+            Some(VersionChange::PreRelease)
+        } else {
+            None
+        }
+    }
+
+    /// Index of the dot-separated part of a version string where the change starts
+    pub fn component_index(&self) -> usize {
+        match self {
+            VersionChange::Major => 0,
+            VersionChange::Minor => 1,
+            VersionChange::Patch | VersionChange::PreRelease => 2,
+        }
+    }
+}
+
+/// An installed plugin, the version `gdm update` would install and the latest version
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutdatedPlugin {
+    pub current: Plugin,
+    /// The newest version the pin and update level allow, `current` if there's none
+    pub wanted: Plugin,
+    pub latest: Plugin,
+    pub status: UpdateStatus,
+}
+
+impl OutdatedPlugin {
+    /// `wanted` is the newest version the update level allows, None if it allows none
+    pub fn new(
+        current: Plugin,
+        wanted: Option<Plugin>,
+        latest: Plugin,
+        include_prerelease: bool,
+    ) -> OutdatedPlugin {
+        let wanted = wanted.filter(|_| !current.pinned);
+        let status = if current.is_updated_by(&latest, include_prerelease) {
+            if wanted.is_some() {
+                UpdateStatus::UpdateAvailable
+            } else {
+                UpdateStatus::Blocked
+            }
+        } else if latest > current {
+            UpdateStatus::PreRelease
+        } else {
+            UpdateStatus::UpToDate
+        };
+        let wanted = match wanted {
+            Some(wanted) if status == UpdateStatus::UpdateAvailable => wanted,
+            _ => current.clone(),
+        };
+        OutdatedPlugin {
+            current,
+            wanted,
+            latest,
+            status,
+        }
+    }
+
+    /// Which part of the version an update to `target`, i.e. `wanted` or `latest`, changes.
+    /// None for Git plugins following a branch.
+    pub fn change_to(&self, target: &Plugin) -> Option<VersionChange> {
+        if self.current.version.is_empty() || target.version.is_empty() {
+            return None;
+        }
+        VersionChange::between(
+            &Utils::parse_semantic_version(&self.current.version),
+            &Utils::parse_semantic_version(&target.version),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_level_allows() {
+        let current = Version::new(9, 2, 0);
+        let patch = Version::new(9, 2, 1);
+        let minor = Version::new(9, 3, 0);
+        let major = Version::new(10, 0, 0);
+
+        assert!(UpdateLevel::Patch.allows(&current, &patch));
+        assert!(!UpdateLevel::Patch.allows(&current, &minor));
+        assert!(UpdateLevel::Minor.allows(&current, &minor));
+        assert!(!UpdateLevel::Minor.allows(&current, &major));
+        assert!(UpdateLevel::Major.allows(&current, &major));
+    }
+
+    #[test]
+    fn test_update_level_should_treat_minor_below_1_0_as_breaking() {
+        let current = Version::new(0, 4, 2);
+
+        assert!(UpdateLevel::Minor.allows(&current, &Version::new(0, 4, 3)));
+        assert!(!UpdateLevel::Minor.allows(&current, &Version::new(0, 5, 0)));
+        assert!(UpdateLevel::Major.allows(&current, &Version::new(0, 5, 0)));
+    }
+
+    #[test]
+    fn test_version_change_between() {
+        let current = Version::new(9, 2, 0);
+
+        assert_eq!(
+            VersionChange::between(&current, &Version::new(10, 0, 0)),
+            Some(VersionChange::Major)
+        );
+        assert_eq!(
+            VersionChange::between(&current, &Version::new(9, 3, 0)),
+            Some(VersionChange::Minor)
+        );
+        assert_eq!(
+            VersionChange::between(&current, &Version::new(9, 2, 1)),
+            Some(VersionChange::Patch)
+        );
+        assert_eq!(
+            VersionChange::between(&Version::parse("9.2.0-rc1").unwrap(), &current),
+            Some(VersionChange::PreRelease)
+        );
+        assert_eq!(VersionChange::between(&current, &current), None);
+    }
+
+    #[test]
+    fn test_outdated_plugin_should_block_pinned_and_larger_updates() {
+        let current = Plugin::create_mock_plugin_1();
+        let mut minor = current.clone();
+        minor.version = "1.1.0".to_string();
+        let mut major = current.clone();
+        major.version = "2.0.0".to_string();
+
+        let available =
+            OutdatedPlugin::new(current.clone(), Some(minor.clone()), minor.clone(), false);
+        assert_eq!(available.status, UpdateStatus::UpdateAvailable);
+        assert_eq!(available.wanted, minor);
+
+        let below_major =
+            OutdatedPlugin::new(current.clone(), Some(minor.clone()), major.clone(), false);
+        assert_eq!(below_major.status, UpdateStatus::UpdateAvailable);
+        assert_eq!(below_major.wanted, minor);
+        assert_eq!(below_major.latest, major);
+
+        let blocked = OutdatedPlugin::new(current.clone(), None, major.clone(), false);
+        assert_eq!(blocked.status, UpdateStatus::Blocked);
+        assert_eq!(blocked.wanted, current);
+        assert_eq!(blocked.latest, major);
+
+        let mut pinned = current.clone();
+        pinned.pinned = true;
+        let blocked = OutdatedPlugin::new(pinned.clone(), Some(minor.clone()), minor, false);
+        assert_eq!(blocked.status, UpdateStatus::Blocked);
+        assert_eq!(blocked.wanted, pinned);
+    }
+}
@@ -1,15 +1,59 @@
+use anyhow::{Result, bail};
 use serde_derive::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::num::NonZeroU32;
+use std::path::{Component, Path, PathBuf};
 
+use crate::error::{ErrorKind, GdmError};
 use crate::{api::AssetResponse, utils::Utils};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-#[serde(untagged)]
+#[serde(untagged, expecting = "expected an asset_id, or a url and reference")]
 pub enum PluginSource {
     AssetLibrary { asset_id: String },      // Optionally store asset ID
     Git { url: String, reference: String }, // Optionally store git URL and ref
 }
 
+impl std::fmt::Display for PluginSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PluginSource::AssetLibrary { asset_id } => write!(f, "Asset Library ({})", asset_id),
+            PluginSource::Git { url, reference } => write!(f, "{} ({})", url, reference),
+        }
+    }
+}
+
+impl PluginSource {
+    /// Page of the plugin for a browser: its Asset Library page, or the git repository with
+    /// SSH URLs turned into HTTPS, e.g. `git@github.com:user/repo.git` becomes
+    /// `https://github.com/user/repo`
+    pub fn web_url(&self, api_base_url: &str) -> String {
+        match self {
+            PluginSource::AssetLibrary { asset_id } => format!(
+                "{}/asset/{}",
+                api_base_url.trim_end_matches("/api"),
+                asset_id
+            ),
+            PluginSource::Git { url, .. } => {
+                let url = url.trim_end_matches('/');
+                let url = url.strip_suffix(".git").unwrap_or(url);
+                if let Some(path) = url.strip_prefix("ssh://") {
+                    let path = path.split_once('@').map_or(path, |(_, path)| path);
+                    format!("https://{}", path)
+                } else if !url.contains("://")
+                    && let Some((host, path)) = url.split_once(':')
+                {
+                    let host = host.split_once('@').map_or(host, |(_, host)| host);
+                    format!("https://{}/{}", host, path)
+                } else {
+                    url.to_string()
+                }
+            }
+        }
+    }
+}
+
 impl PartialEq for PluginSource {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -32,7 +76,70 @@ impl PartialEq for PluginSource {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+/// Where `update` and `outdated` look for new versions of an Asset Library plugin
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    /// Versions approved by the Asset Library moderators
+    #[default]
+    Stable,
+    /// Also versions submitted to the Asset Library but not reviewed yet
+    Edge,
+}
+
+impl UpdateChannel {
+    fn is_stable(&self) -> bool {
+        *self == UpdateChannel::Stable
+    }
+}
+
+/// How much of a git plugin's repository is fetched. Set per plugin with `git_fetch` in
+/// gdm.json, or for every git plugin in the global config.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct GitFetchOptions {
+    /// Commits fetched from the tip of the ref, 1 if not set. A commit given as the ref must
+    /// be among them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<NonZeroU32>,
+    /// Also fetch the tags of the repository
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub tags: bool,
+    /// Fetch the whole history instead of `depth` commits
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub full_history: bool,
+}
+
+impl GitFetchOptions {
+    /// These options, with the ones that aren't set taken from `defaults`
+    pub fn or(self, defaults: GitFetchOptions) -> GitFetchOptions {
+        GitFetchOptions {
+            depth: self.depth.or(defaults.depth),
+            tags: self.tags || defaults.tags,
+            full_history: self.full_history || defaults.full_history,
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == GitFetchOptions::default()
+    }
+}
+
+impl fmt::Display for GitFetchOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.full_history {
+            write!(f, "full history")?;
+        } else {
+            write!(f, "depth {}", self.depth.map_or(1, NonZeroU32::get))?;
+        }
+        if self.tags {
+            write!(f, ", tags")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Plugin {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source: Option<PluginSource>,
@@ -41,10 +148,86 @@ pub struct Plugin {
     pub plugin_cfg_path: Option<String>,
     pub title: String,
     pub version: String,
+    /// Name accepted by commands in place of the plugin key, e.g. `gut` for `gut-9.1.0`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
     #[serde(default = "Vec::new")]
     pub sub_assets: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub license: Option<String>,
+    /// Godot version the asset targets in the Asset Library, e.g. `4.2`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub godot_version: Option<String>,
+    /// Autoload singletons registered in project.godot while the plugin is installed,
+    /// e.g. `"ModLoader": "*res://addons/mod_loader/mod_loader.gd"`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub autoload: BTreeMap<String, String>,
+    /// Folder the plugin is installed to, relative to the project root, e.g. `addons/third_party`.
+    /// Defaults to `addons_dir` in gdm.json.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub addons_dir: Option<String>,
+    /// Whether the plugin is enabled in project.godot. Disabled plugins stay installed.
+    #[serde(
+        default = "Plugin::default_enabled",
+        skip_serializing_if = "Plugin::is_enabled"
+    )]
+    pub enabled: bool,
+    /// Checksum of the installed artifact, verified on every install.
+    /// `sha256:<hex>` for Asset Library archives, `git-tree:<id>` for the addons tree of git plugins.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// Commit a git plugin was installed from. Pins the exact state of plugins that follow a branch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// `sha256:<hex>` of the files installed into the addons folder. `install` skips plugins
+    /// whose folders still match it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+    /// Whether `update` and `outdated` consider pre-release versions, e.g. `9.2.0-rc1`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_prerelease: bool,
+    /// Set by `downgrade`. `update` leaves pinned plugins at their version unless they're named.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+    #[serde(default, skip_serializing_if = "UpdateChannel::is_stable")]
+    pub channel: UpdateChannel,
+    /// How much of the repository of a git plugin is fetched
+    #[serde(default, skip_serializing_if = "GitFetchOptions::is_default")]
+    pub git_fetch: GitFetchOptions,
+    /// Whether a git plugin is only installed if its tag or commit is signed by a key in
+    /// `project.trusted_keys`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub verify_signature: bool,
+    /// Whether the plugin is listed under `dev_plugins` in gdm.json
+    #[serde(skip)]
+    pub dev: bool,
+}
+
+impl Default for Plugin {
+    fn default() -> Self {
+        Plugin {
+            source: None,
+            plugin_cfg_path: None,
+            title: String::new(),
+            version: String::new(),
+            alias: None,
+            sub_assets: Vec::new(),
+            license: None,
+            godot_version: None,
+            autoload: BTreeMap::new(),
+            addons_dir: None,
+            enabled: true,
+            checksum: None,
+            commit: None,
+            content_hash: None,
+            allow_prerelease: false,
+            pinned: false,
+            channel: UpdateChannel::Stable,
+            git_fetch: GitFetchOptions::default(),
+            verify_signature: false,
+            dev: false,
+        }
+    }
 }
 
 impl Eq for Plugin {}
@@ -67,7 +250,7 @@ impl PartialOrd for Plugin {
 
 impl From<AssetResponse> for Plugin {
     fn from(asset_response: AssetResponse) -> Self {
-        Plugin::new(
+        let mut plugin = Plugin::new(
             Some(PluginSource::AssetLibrary {
                 asset_id: asset_response.asset_id,
             }),
@@ -76,7 +259,9 @@ impl From<AssetResponse> for Plugin {
             asset_response.version_string,
             Some(asset_response.cost),
             Vec::new(),
-        )
+        );
+        plugin.godot_version = Some(asset_response.godot_version).filter(|v| !v.is_empty());
+        plugin
     }
 }
 
@@ -90,22 +275,80 @@ impl Plugin {
         sub_assets: Vec<String>,
     ) -> Plugin {
         // Convert PathBuf to Unix-style string path
-        let _plugin_cfg_path = plugin_cfg_path.map(|p| {
-            p.iter()
-                .map(|s| s.to_string_lossy())
-                .collect::<Vec<_>>()
-                .join("/")
-        });
+        let _plugin_cfg_path = plugin_cfg_path.map(|p| Self::to_unix_path(&p));
         Plugin {
             source,
             plugin_cfg_path: _plugin_cfg_path,
             title,
             version,
+            alias: None,
             license,
             sub_assets,
+            godot_version: None,
+            autoload: BTreeMap::new(),
+            addons_dir: None,
+            enabled: true,
+            checksum: None,
+            commit: None,
+            content_hash: None,
+            allow_prerelease: false,
+            pinned: false,
+            channel: UpdateChannel::Stable,
+            git_fetch: GitFetchOptions::default(),
+            verify_signature: false,
+            dev: false,
+        }
+    }
+
+    fn default_enabled() -> bool {
+        true
+    }
+
+    fn is_enabled(enabled: &bool) -> bool {
+        *enabled
+    }
+
+    fn to_unix_path(path: &Path) -> String {
+        path.iter()
+            .map(|s| s.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Returns the folder the plugin is installed to
+    pub fn get_addons_dir(&self, default_addons_dir: PathBuf) -> PathBuf {
+        match &self.addons_dir {
+            Some(dir) => PathBuf::from(dir),
+            None => default_addons_dir,
         }
     }
 
+    /// Points plugin_cfg_path from the staged `addons` folder to the folder the plugin was installed to.
+    /// `addons_dir` may be written as `./tools` or `res://tools`, the path is relative to the
+    /// project root either way, so it matches the `res://` path Godot enables the plugin with.
+    pub fn relocate(mut self, addons_dir: &Path) -> Plugin {
+        if let Some(relative_path) = self
+            .plugin_cfg_path
+            .as_ref()
+            .and_then(|path| path.strip_prefix("addons/"))
+        {
+            let addons_dir = addons_dir.to_string_lossy();
+            let addons_dir: PathBuf = Path::new(
+                addons_dir
+                    .strip_prefix("res://")
+                    .unwrap_or(addons_dir.as_ref()),
+            )
+            .components()
+            .filter(|component| matches!(component, Component::Normal(_)))
+            .collect();
+            self.plugin_cfg_path = Some(match addons_dir.as_os_str().is_empty() {
+                true => relative_path.to_string(),
+                false => format!("{}/{}", Self::to_unix_path(&addons_dir), relative_path),
+            });
+        }
+        self
+    }
+
     #[cfg(test)]
     pub fn new_asset_store_plugin(
         asset_id: String,
@@ -131,6 +374,143 @@ impl Plugin {
         self.version.to_string()
     }
 
+    /// Abbreviated commit a git plugin was installed from, e.g. `1a2b3c4`
+    pub fn short_commit(&self) -> Option<&str> {
+        self.commit
+            .as_deref()
+            .map(|commit| commit.get(..7).unwrap_or(commit))
+    }
+
+    /// Whether the version is a semver pre-release, e.g. `9.2.0-rc1`
+    pub fn is_prerelease(&self) -> bool {
+        !Utils::parse_semantic_version(&self.version).pre.is_empty()
+    }
+
+    /// Returns true if `latest` is newer and may replace this plugin.
+    /// Pre-releases only qualify with `allow_prerelease` or `include_prerelease`.
+    pub fn is_updated_by(&self, latest: &Plugin, include_prerelease: bool) -> bool {
+        latest > self && (include_prerelease || self.allow_prerelease || !latest.is_prerelease())
+    }
+
+    /// Returns a warning if the plugin targets a Godot version the project can't run it on:
+    /// another major version, or a newer minor version than the project
+    pub fn check_godot_compatibility(&self, project_godot_version: &str) -> Option<String> {
+        let godot_version = self.godot_version.as_deref()?;
+        let plugin_version = Utils::parse_semantic_version(godot_version);
+        let project_version = Utils::parse_semantic_version(project_godot_version);
+        let is_compatible = plugin_version.major == project_version.major
+            && plugin_version.minor <= project_version.minor;
+        if is_compatible {
+            return None;
+        }
+        Some(format!(
+            "Warning: plugin {} targets Godot {}, project is {}",
+            self.title, godot_version, project_godot_version
+        ))
+    }
+
+    /// Fails if the plugin has a recorded checksum that doesn't match the downloaded artifact
+    pub fn verify_checksum(&self, checksum: &str) -> Result<()> {
+        match &self.checksum {
+            Some(expected) if expected != checksum => bail!(
+                GdmError::new(
+                    ErrorKind::ChecksumMismatch,
+                    format!(
+                        "Checksum mismatch for {} {}: gdm.json has {} but the downloaded artifact has {}. \
+                        The upstream artifact changed for the same version.",
+                        self.title,
+                        self.get_version(),
+                        expected,
+                        checksum
+                    )
+                )
+                .with_plugin(&self.title)
+                .with_hint(
+                    "If you trust the new artifact, remove the checksum from gdm.json and run install again."
+                )
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fails unless gdm.json records both the checksum of the artifact and the content hash of
+    /// the installed files, which `install --locked` verifies
+    pub fn require_integrity_hashes(&self) -> Result<()> {
+        let missing = match (&self.checksum, &self.content_hash) {
+            (Some(_), Some(_)) => return Ok(()),
+            (None, Some(_)) => "checksum",
+            (Some(_), None) => "content_hash",
+            (None, None) => "checksum and content_hash",
+        };
+        bail!(
+            GdmError::new(
+                ErrorKind::ChecksumMismatch,
+                format!(
+                    "{} {} has no {} in gdm.json to verify it against.",
+                    self.title,
+                    self.get_version(),
+                    missing
+                )
+            )
+            .with_plugin(&self.title)
+            .with_hint(
+                "Run gdm install without --locked or --frozen once to record them, and commit gdm.json."
+            )
+        )
+    }
+
+    /// Fails if the plugin has a recorded content hash that doesn't match the installed files
+    pub fn verify_content_hash(&self, content_hash: Option<&str>) -> Result<()> {
+        match &self.content_hash {
+            Some(expected) if Some(expected.as_str()) != content_hash => bail!(
+                GdmError::new(
+                    ErrorKind::ChecksumMismatch,
+                    format!(
+                        "Content hash mismatch for {} {}: gdm.json has {} but the installed files have {}. \
+                        The files changed after the artifact was downloaded.",
+                        self.title,
+                        self.get_version(),
+                        expected,
+                        content_hash.unwrap_or("none")
+                    )
+                )
+                .with_plugin(&self.title)
+                .with_hint(
+                    "If you trust the new files, run gdm install without --locked or --frozen to record their content hash."
+                )
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Carries over settings the user declared in gdm.json, which installers can't discover
+    pub fn with_settings_from(mut self, existing: &Plugin) -> Plugin {
+        if self.autoload.is_empty() {
+            self.autoload = existing.autoload.clone();
+        }
+        if self.addons_dir.is_none() {
+            self.addons_dir = existing.addons_dir.clone();
+        }
+        if self.alias.is_none() {
+            self.alias = existing.alias.clone();
+        }
+        self.enabled = self.enabled && existing.enabled;
+        self.allow_prerelease = self.allow_prerelease || existing.allow_prerelease;
+        // A pin holds a version, so it's dropped once the plugin moves to another one
+        if self.version == existing.version {
+            self.pinned = self.pinned || existing.pinned;
+        }
+        if self.channel.is_stable() {
+            self.channel = existing.channel;
+        }
+        if self.git_fetch.is_default() {
+            self.git_fetch = existing.git_fetch;
+        }
+        self.verify_signature = self.verify_signature || existing.verify_signature;
+        self.dev = self.dev || existing.dev;
+        self
+    }
+
     #[cfg(test)]
     pub fn create_mock_plugin_1() -> Plugin {
         Plugin::new(
@@ -178,6 +558,34 @@ impl Plugin {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_web_url() {
+        let api_base_url = "https://godotengine.org/asset-library/api";
+        let git = |url: &str| PluginSource::Git {
+            url: url.to_string(),
+            reference: "main".to_string(),
+        };
+        assert_eq!(
+            PluginSource::AssetLibrary {
+                asset_id: "1709".to_string()
+            }
+            .web_url(api_base_url),
+            "https://godotengine.org/asset-library/asset/1709"
+        );
+        assert_eq!(
+            git("https://github.com/bitwes/Gut.git").web_url(api_base_url),
+            "https://github.com/bitwes/Gut"
+        );
+        assert_eq!(
+            git("git@github.com:bitwes/Gut.git").web_url(api_base_url),
+            "https://github.com/bitwes/Gut"
+        );
+        assert_eq!(
+            git("ssh://git@gitlab.com/group/repo.git").web_url(api_base_url),
+            "https://gitlab.com/group/repo"
+        );
+    }
+
     fn setup_test_plugin() -> Plugin {
         Plugin::new(
             Some(PluginSource::AssetLibrary {
@@ -279,6 +687,25 @@ mod tests {
         assert!(plugin_2_new > plugin_2_old);
     }
 
+    #[test]
+    fn test_is_updated_by_should_skip_pre_release_unless_allowed() {
+        let mut current = Plugin::create_mock_plugin_1();
+        let mut latest = Plugin::create_mock_plugin_1();
+        latest.version = "1.1.0-rc1".to_string();
+
+        assert!(latest.is_prerelease());
+        assert!(!current.is_updated_by(&latest, false));
+        assert!(current.is_updated_by(&latest, true));
+
+        current.allow_prerelease = true;
+        assert!(current.is_updated_by(&latest, false));
+
+        latest.version = "1.1.0".to_string();
+        current.allow_prerelease = false;
+        assert!(!latest.is_prerelease());
+        assert!(current.is_updated_by(&latest, false));
+    }
+
     #[test]
     fn test_plugin_partial_ord_semver_pre_release_versions() {
         let plugin_pre = Plugin::new_asset_store_plugin(
@@ -501,4 +928,320 @@ mod tests {
         assert_eq!(deserialized.sub_assets, vec!["subX".to_string()]);
         // plugin_cfg_path is None by default
     }
+
+    #[test]
+    fn test_plugin_source_display() {
+        let asset_source = PluginSource::AssetLibrary {
+            asset_id: "1709".to_string(),
+        };
+        let git_source = PluginSource::Git {
+            url: "https://github.com/user/repo.git".to_string(),
+            reference: "main".to_string(),
+        };
+        assert_eq!(asset_source.to_string(), "Asset Library (1709)");
+        assert_eq!(
+            git_source.to_string(),
+            "https://github.com/user/repo.git (main)"
+        );
+    }
+
+    #[test]
+    fn test_plugin_deserialize_autoload_from_json() {
+        let json = r#"{
+            "source": {
+                "asset_id": "456"
+            },
+            "title": "Mod Loader",
+            "version": "1.0.0",
+            "autoload": {
+                "ModLoader": "*res://addons/mod_loader/mod_loader.gd"
+            }
+        }"#;
+        let plugin: Plugin = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            plugin.autoload,
+            BTreeMap::from([(
+                "ModLoader".to_string(),
+                "*res://addons/mod_loader/mod_loader.gd".to_string()
+            )])
+        );
+    }
+
+    #[test]
+    fn test_plugin_serialize_skips_empty_autoload() {
+        let plugin = Plugin::create_mock_plugin_1();
+        let json = serde_json::to_string(&plugin).unwrap();
+        assert!(!json.contains("autoload"));
+    }
+
+    #[test]
+    fn test_plugin_enabled_defaults_to_true() {
+        let json = r#"{
+            "title": "Plugin",
+            "version": "1.0.0"
+        }"#;
+        let plugin: Plugin = serde_json::from_str(json).unwrap();
+        assert!(plugin.enabled);
+        assert!(!serde_json::to_string(&plugin).unwrap().contains("enabled"));
+    }
+
+    #[test]
+    fn test_plugin_serialize_disabled() {
+        let mut plugin = Plugin::create_mock_plugin_1();
+        plugin.enabled = false;
+        let json = serde_json::to_string(&plugin).unwrap();
+        assert!(json.contains("\"enabled\":false"));
+    }
+
+    #[test]
+    fn test_relocate_plugin_cfg_path_to_custom_addons_dir() {
+        let plugin = Plugin::create_mock_plugin_1().relocate(Path::new("addons/third_party"));
+        assert_eq!(
+            plugin.plugin_cfg_path,
+            Some("addons/third_party/awesome_plugin/plugin.cfg".to_string())
+        );
+    }
+
+    #[test]
+    fn test_relocate_plugin_cfg_path_should_normalize_addons_dir() {
+        for addons_dir in ["./tools/", "res://tools", "tools"] {
+            let plugin = Plugin::create_mock_plugin_1().relocate(Path::new(addons_dir));
+            assert_eq!(
+                plugin.plugin_cfg_path,
+                Some("tools/awesome_plugin/plugin.cfg".to_string())
+            );
+        }
+        let plugin = Plugin::create_mock_plugin_1().relocate(Path::new("."));
+        assert_eq!(
+            plugin.plugin_cfg_path,
+            Some("awesome_plugin/plugin.cfg".to_string())
+        );
+    }
+
+    #[test]
+    fn test_relocate_plugin_cfg_path_to_default_addons_dir() {
+        let plugin = Plugin::create_mock_plugin_1().relocate(Path::new("addons"));
+        assert_eq!(
+            plugin.plugin_cfg_path,
+            Some("addons/awesome_plugin/plugin.cfg".to_string())
+        );
+    }
+
+    #[test]
+    fn test_get_addons_dir() {
+        let mut plugin = Plugin::create_mock_plugin_1();
+        assert_eq!(
+            plugin.get_addons_dir(PathBuf::from("addons")),
+            PathBuf::from("addons")
+        );
+
+        plugin.addons_dir = Some("addons/third_party".to_string());
+        assert_eq!(
+            plugin.get_addons_dir(PathBuf::from("addons")),
+            PathBuf::from("addons/third_party")
+        );
+    }
+
+    #[test]
+    fn test_verify_checksum_without_recorded_checksum() {
+        let plugin = Plugin::create_mock_plugin_1();
+        assert!(plugin.verify_checksum("sha256:abc").is_ok());
+    }
+
+    #[test]
+    fn test_verify_checksum_with_matching_checksum() {
+        let mut plugin = Plugin::create_mock_plugin_1();
+        plugin.checksum = Some("sha256:abc".to_string());
+        assert!(plugin.verify_checksum("sha256:abc").is_ok());
+    }
+
+    #[test]
+    fn test_require_integrity_hashes_should_name_missing_hashes() {
+        let mut plugin = Plugin::create_mock_plugin_1();
+        plugin.checksum = Some("sha256:abc".to_string());
+
+        let result = plugin.require_integrity_hashes();
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("has no content_hash")
+        );
+
+        plugin.content_hash = Some("sha256:def".to_string());
+        assert!(plugin.require_integrity_hashes().is_ok());
+    }
+
+    #[test]
+    fn test_verify_content_hash_with_changed_files() {
+        let mut plugin = Plugin::create_mock_plugin_1();
+        plugin.content_hash = Some("sha256:abc".to_string());
+
+        assert!(plugin.verify_content_hash(Some("sha256:abc")).is_ok());
+        let error = plugin.verify_content_hash(Some("sha256:def")).unwrap_err();
+        assert_eq!(
+            GdmError::find(&error).unwrap().kind,
+            ErrorKind::ChecksumMismatch
+        );
+    }
+
+    #[test]
+    fn test_verify_checksum_with_changed_artifact() {
+        let mut plugin = Plugin::create_mock_plugin_1();
+        plugin.checksum = Some("sha256:abc".to_string());
+
+        let result = plugin.verify_checksum("sha256:def");
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Checksum mismatch for Awesome Plugin 1.0.0")
+        );
+    }
+
+    #[test]
+    fn test_from_asset_response_should_keep_godot_version() {
+        let asset_response = AssetResponse {
+            asset_id: "1709".to_string(),
+            godot_version: "4.2".to_string(),
+            ..AssetResponse::default()
+        };
+        let plugin = Plugin::from(asset_response);
+        assert_eq!(plugin.godot_version.as_deref(), Some("4.2"));
+
+        let plugin = Plugin::from(AssetResponse::default());
+        assert_eq!(plugin.godot_version, None);
+    }
+
+    #[test]
+    fn test_check_godot_compatibility() {
+        let mut plugin = Plugin::create_mock_plugin_1();
+        assert_eq!(plugin.check_godot_compatibility("4.5"), None);
+
+        plugin.godot_version = Some("4.2".to_string());
+        assert_eq!(plugin.check_godot_compatibility("4.5"), None);
+        assert_eq!(plugin.check_godot_compatibility("4.2"), None);
+        assert_eq!(
+            plugin.check_godot_compatibility("4.1").as_deref(),
+            Some("Warning: plugin Awesome Plugin targets Godot 4.2, project is 4.1")
+        );
+        assert_eq!(
+            plugin.check_godot_compatibility("3.6").as_deref(),
+            Some("Warning: plugin Awesome Plugin targets Godot 4.2, project is 3.6")
+        );
+    }
+
+    #[test]
+    fn test_with_settings_from_keeps_channel() {
+        let mut existing = Plugin::create_mock_plugin_1();
+        existing.channel = UpdateChannel::Edge;
+
+        let plugin = Plugin::create_mock_plugin_1().with_settings_from(&existing);
+        assert_eq!(plugin.channel, UpdateChannel::Edge);
+    }
+
+    #[test]
+    fn test_channel_should_default_to_stable() {
+        let plugin: Plugin =
+            serde_json::from_str(r#"{ "title": "Gut", "version": "9.1.0" }"#).unwrap();
+        assert_eq!(plugin.channel, UpdateChannel::Stable);
+        assert!(!serde_json::to_string(&plugin).unwrap().contains("channel"));
+
+        let plugin: Plugin =
+            serde_json::from_str(r#"{ "title": "Gut", "version": "9.1.0", "channel": "edge" }"#)
+                .unwrap();
+        assert_eq!(plugin.channel, UpdateChannel::Edge);
+    }
+
+    #[test]
+    fn test_git_fetch_should_fall_back_to_defaults() {
+        let plugin: Plugin = serde_json::from_str(
+            r#"{ "title": "Gut", "version": "9.1.0", "git_fetch": { "depth": 50 } }"#,
+        )
+        .unwrap();
+        let defaults = GitFetchOptions {
+            depth: NonZeroU32::new(10),
+            tags: true,
+            full_history: false,
+        };
+
+        let options = plugin.git_fetch.or(defaults);
+        assert_eq!(options.depth, NonZeroU32::new(50));
+        assert!(options.tags);
+        assert_eq!(options.to_string(), "depth 50, tags");
+        assert_eq!(GitFetchOptions::default().to_string(), "depth 1");
+
+        assert!(
+            serde_json::from_str::<Plugin>(
+                r#"{ "title": "Gut", "version": "9.1.0", "git_fetch": { "depth": 0 } }"#,
+            )
+            .is_err()
+        );
+        let plugin = Plugin::create_mock_plugin_1();
+        assert!(
+            !serde_json::to_string(&plugin)
+                .unwrap()
+                .contains("git_fetch")
+        );
+    }
+
+    #[test]
+    fn test_with_settings_from_keeps_alias() {
+        let mut existing = Plugin::create_mock_plugin_1();
+        existing.alias = Some("awesome".to_string());
+
+        let plugin = Plugin::create_mock_plugin_1().with_settings_from(&existing);
+        assert_eq!(plugin.alias.as_deref(), Some("awesome"));
+    }
+
+    #[test]
+    fn test_with_settings_from_keeps_dev_plugin() {
+        let mut existing = Plugin::create_mock_plugin_1();
+        existing.dev = true;
+
+        let plugin = Plugin::create_mock_plugin_1().with_settings_from(&existing);
+        assert!(plugin.dev);
+    }
+
+    #[test]
+    fn test_with_settings_from_keeps_disabled_state() {
+        let mut existing = Plugin::create_mock_plugin_1();
+        existing.enabled = false;
+
+        let plugin = Plugin::create_mock_plugin_1().with_settings_from(&existing);
+        assert!(!plugin.enabled);
+    }
+
+    #[test]
+    fn test_with_settings_from_keeps_new_plugin_disabled() {
+        let mut plugin = Plugin::create_mock_plugin_1();
+        plugin.enabled = false;
+
+        let plugin = plugin.with_settings_from(&Plugin::create_mock_plugin_1());
+        assert!(!plugin.enabled);
+    }
+
+    #[test]
+    fn test_with_settings_from_keeps_existing_autoload() {
+        let mut existing = Plugin::create_mock_plugin_1();
+        existing.autoload =
+            BTreeMap::from([("Events".to_string(), "*res://addons/events.gd".to_string())]);
+
+        let plugin = Plugin::create_mock_plugin_1().with_settings_from(&existing);
+        assert_eq!(plugin.autoload, existing.autoload);
+    }
+
+    #[test]
+    fn test_with_settings_from_keeps_pin_only_for_same_version() {
+        let mut existing = Plugin::create_mock_plugin_1();
+        existing.pinned = true;
+
+        let reinstalled = Plugin::create_mock_plugin_1().with_settings_from(&existing);
+        let mut updated = Plugin::create_mock_plugin_1();
+        updated.version = "1.1.0".to_string();
+        let updated = updated.with_settings_from(&existing);
+
+        assert!(reinstalled.pinned);
+        assert!(!updated.pinned);
+    }
 }
@@ -0,0 +1,120 @@
+use crate::models::{Plugin, PluginSource};
+
+use std::collections::BTreeMap;
+
+/// Disk size and last Asset Library update of one plugin in the configuration file
+#[derive(Debug, Clone, PartialEq)]
+pub struct PluginStats {
+    /// Name of the plugin in gdm.json
+    pub name: String,
+    pub plugin: Plugin,
+    /// Bytes of the plugin's addon folders, 0 if it isn't installed
+    pub size: u64,
+    /// `modify_date` of the asset in the Asset Library, e.g. `2024-05-01 12:00:00`.
+    /// None for git plugins and assets gdm hasn't seen in a search or install yet.
+    pub modify_date: Option<String>,
+}
+
+/// Summary of the managed plugins, as printed by `gdm stats`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProjectStats {
+    pub plugins: Vec<PluginStats>,
+}
+
+impl ProjectStats {
+    pub fn total_size(&self) -> u64 {
+        self.plugins.iter().map(|stats| stats.size).sum()
+    }
+
+    /// Number of plugins from the Asset Library, from git and without a source
+    pub fn count_by_source(&self) -> BTreeMap<&'static str, usize> {
+        self.count_by(|stats| match stats.plugin.source {
+            Some(PluginSource::AssetLibrary { .. }) => "Asset Library",
+            Some(PluginSource::Git { .. }) => "Git",
+            None => "Unknown",
+        })
+    }
+
+    /// Number of plugins per license, `Unknown` for plugins without one, e.g. from git
+    pub fn count_by_license(&self) -> BTreeMap<String, usize> {
+        self.count_by(|stats| {
+            stats
+                .plugin
+                .license
+                .clone()
+                .filter(|license| !license.is_empty())
+                .unwrap_or_else(|| "Unknown".to_string())
+        })
+    }
+
+    /// The plugin whose asset was updated longest ago in the Asset Library
+    pub fn least_recently_updated(&self) -> Option<&PluginStats> {
+        self.plugins
+            .iter()
+            .filter(|stats| stats.modify_date.is_some())
+            .min_by(|a, b| a.modify_date.cmp(&b.modify_date))
+    }
+
+    fn count_by<K: Ord>(&self, key: impl Fn(&PluginStats) -> K) -> BTreeMap<K, usize> {
+        let mut counts = BTreeMap::new();
+        for stats in &self.plugins {
+            *counts.entry(key(stats)).or_default() += 1;
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(name: &str, plugin: Plugin, size: u64, modify_date: Option<&str>) -> PluginStats {
+        PluginStats {
+            name: name.to_string(),
+            plugin,
+            size,
+            modify_date: modify_date.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_project_stats_should_aggregate_plugins() {
+        let mut git_plugin = Plugin::create_mock_plugin_3();
+        git_plugin.source = Some(PluginSource::Git {
+            url: "https://github.com/user/repo.git".to_string(),
+            reference: "main".to_string(),
+        });
+        git_plugin.license = None;
+        let project_stats = ProjectStats {
+            plugins: vec![
+                stats(
+                    "awesome_plugin",
+                    Plugin::create_mock_plugin_1(),
+                    1024,
+                    Some("2024-05-01 12:00:00"),
+                ),
+                stats(
+                    "super_plugin",
+                    Plugin::create_mock_plugin_2(),
+                    2048,
+                    Some("2021-03-04 08:00:00"),
+                ),
+                stats("repo", git_plugin, 512, None),
+            ],
+        };
+
+        assert_eq!(project_stats.total_size(), 3584);
+        assert_eq!(
+            project_stats.count_by_source(),
+            BTreeMap::from([("Asset Library", 2), ("Git", 1)])
+        );
+        assert_eq!(
+            project_stats.count_by_license(),
+            BTreeMap::from([("MIT".to_string(), 2), ("Unknown".to_string(), 1)])
+        );
+        assert_eq!(
+            project_stats.least_recently_updated().unwrap().name,
+            "super_plugin"
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use crate::error::{ErrorKind, GdmError};
+use crate::models::AdvisoryFeed;
+use crate::services::{DefaultHttpService, HttpService};
+
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::debug;
+
+/// Feed `gdm audit` checks by default, maintained in the gdm repository
+pub const DEFAULT_ADVISORY_FEED_URL: &str =
+    "https://raw.githubusercontent.com/k0psutin/gdm/main/advisories.json";
+
+/// Loads the advisory feed from a URL, or from a file, e.g. a mirror kept next to the project
+pub struct AdvisoryFeedLoader {
+    http_service: Arc<dyn HttpService + Send + Sync>,
+}
+
+impl Default for AdvisoryFeedLoader {
+    fn default() -> Self {
+        AdvisoryFeedLoader::new(Arc::new(DefaultHttpService::default()))
+    }
+}
+
+impl AdvisoryFeedLoader {
+    pub fn new(http_service: Arc<dyn HttpService + Send + Sync>) -> AdvisoryFeedLoader {
+        AdvisoryFeedLoader { http_service }
+    }
+
+    /// `location` is an http(s) URL or a path to a JSON file
+    pub async fn load(&self, location: &str) -> Result<AdvisoryFeed> {
+        let data = if location.starts_with("http://") || location.starts_with("https://") {
+            debug!("Fetching advisory feed: {}", location);
+            match self.http_service.get(location.to_string(), [].into()).await {
+                Ok(data) => data,
+                Err(e) => {
+                    debug!("Failed to fetch advisory feed: {:#}", e);
+                    bail!(
+                        GdmError::new(
+                            ErrorKind::Network,
+                            format!("Failed to fetch the advisory feed from {}", location)
+                        )
+                        .with_hint("Check the URL given with --feed or GDM_ADVISORY_FEED.")
+                    )
+                }
+            }
+        } else {
+            let path = Path::new(location);
+            let content = std::fs::read_to_string(path).map_err(|_| {
+                GdmError::new(
+                    ErrorKind::NotFound,
+                    format!("Advisory feed {} doesn't exist", path.display()),
+                )
+                .with_hint("Pass an http(s) URL or the path of a JSON file with --feed.")
+            })?;
+            serde_json::from_str::<serde_json::Value>(&content)
+                .with_context(|| format!("Failed to parse advisory feed {}", path.display()))?
+        };
+        serde_json::from_value(data)
+            .with_context(|| format!("Failed to parse advisory feed {}", location))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::MockDefaultHttpService;
+
+    #[tokio::test]
+    async fn test_load_should_fetch_url() {
+        let mut http_service = MockDefaultHttpService::default();
+        http_service
+            .expect_get()
+            .withf(|url, _| url == "https://example.com/advisories.json")
+            .returning(|_, _| {
+                Ok(serde_json::json!({
+                    "advisories": [{
+                        "id": "GDM-ADV-0001",
+                        "asset_id": "1709",
+                        "kind": "abandoned",
+                        "severity": "low",
+                        "summary": "No longer maintained"
+                    }]
+                }))
+            });
+
+        let feed = AdvisoryFeedLoader::new(Arc::new(http_service))
+            .load("https://example.com/advisories.json")
+            .await
+            .unwrap();
+        assert_eq!(feed.advisories.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_load_should_read_file() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let path = dir.path().join("advisories.json");
+        std::fs::write(&path, r#"{"advisories": []}"#).unwrap();
+
+        let feed = AdvisoryFeedLoader::new(Arc::new(MockDefaultHttpService::default()))
+            .load(&path.to_string_lossy())
+            .await
+            .unwrap();
+        assert!(feed.advisories.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_load_missing_file_should_return_err() {
+        let result = AdvisoryFeedLoader::new(Arc::new(MockDefaultHttpService::default()))
+            .load("missing-advisories.json")
+            .await;
+
+        let error = result.unwrap_err();
+        assert_eq!(GdmError::find(&error).unwrap().kind, ErrorKind::NotFound);
+    }
+}
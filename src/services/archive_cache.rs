@@ -0,0 +1,308 @@
+use crate::services::{DefaultFileService, FileService};
+use crate::ui::ProgressTask;
+use crate::utils::Utils;
+
+use anyhow::{Context, Result};
+use rayon::prelude::*;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tracing::debug;
+
+/// The last downloaded archive of each Asset Library plugin, kept after installing so a plugin
+/// whose asset was removed from the library, or whose download is gone, can still be installed
+#[derive(Debug, Clone)]
+pub struct ArchiveCache {
+    dir: PathBuf,
+}
+
+/// What `gdm cache verify` found for a cached archive
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArchiveStatus {
+    /// A readable zip matching the checksum recorded when it was cached
+    Valid,
+    /// A readable zip cached before checksums were recorded next to archives
+    Unverified,
+    /// An archive that would fail to install, with the reason
+    Corrupted(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifiedArchive {
+    pub path: PathBuf,
+    pub status: ArchiveStatus,
+}
+
+impl ArchiveCache {
+    pub fn new(dir: impl Into<PathBuf>) -> ArchiveCache {
+        ArchiveCache { dir: dir.into() }
+    }
+
+    /// Cache in the `archives` folder of [`Utils::cache_dir`], None if it can't be determined
+    pub fn in_cache_dir() -> Option<ArchiveCache> {
+        Some(ArchiveCache::new(Utils::cache_dir()?.join("archives")))
+    }
+
+    /// The archive stored for the version of the asset, if there is one.
+    /// Marks it as recently used, so [`CacheBudget`](crate::services::CacheBudget) evicts it last.
+    pub fn get(&self, asset_id: &str, version: &str) -> Option<PathBuf> {
+        let path = self.path(asset_id, version);
+        if !path.is_file() {
+            return None;
+        }
+        if let Err(e) = std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(SystemTime::now()))
+        {
+            debug!("Failed to mark cached archive as used: {}", e);
+        }
+        Some(path)
+    }
+
+    /// Copies `archive` into the cache with its checksum, e.g. `sha256:...`, and removes the
+    /// archives of other versions of the asset
+    pub fn store(
+        &self,
+        asset_id: &str,
+        version: &str,
+        archive: &Path,
+        checksum: &str,
+    ) -> Result<()> {
+        std::fs::create_dir_all(&self.dir).with_context(|| {
+            format!(
+                "Failed to create archive cache folder: {}",
+                self.dir.display()
+            )
+        })?;
+        let path = self.path(asset_id, version);
+        let prefix = format!("{}-", asset_id);
+        for entry in std::fs::read_dir(&self.dir)?.flatten() {
+            let other = entry.path();
+            if other != path && entry.file_name().to_string_lossy().starts_with(&prefix) {
+                debug!("Removing cached archive: {}", other.display());
+                std::fs::remove_file(&other)?;
+            }
+        }
+        // Copied next to it first, so an interrupted copy doesn't leave a truncated archive
+        let partial_path = path.with_extension("zip.part");
+        std::fs::copy(archive, &partial_path)
+            .and_then(|_| std::fs::rename(&partial_path, &path))
+            .with_context(|| format!("Failed to cache archive: {}", path.display()))?;
+        std::fs::write(Self::checksum_path(&path), checksum)
+            .with_context(|| format!("Failed to record checksum of {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Checks that every cached archive is a readable zip matching its recorded checksum,
+    /// advancing `progress` by one per archive
+    pub fn verify(&self, progress: &dyn ProgressTask) -> Result<Vec<VerifiedArchive>> {
+        if !self.dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(&self.dir)
+            .with_context(|| format!("Failed to read archive cache: {}", self.dir.display()))?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && !Self::is_checksum_path(path))
+            .collect();
+        paths.sort();
+        progress.set_length(paths.len() as u64);
+        // Archives are read and hashed in parallel, each counting towards the progress when done
+        let statuses: Vec<_> = paths
+            .par_iter()
+            .map(|path| {
+                let status = Self::verify_archive(path);
+                progress.bytes(1);
+                status
+            })
+            .collect();
+        progress.finished();
+        Ok(paths
+            .into_iter()
+            .zip(statuses)
+            .map(|(path, status)| VerifiedArchive { path, status })
+            .collect())
+    }
+
+    /// Removes a cached archive with its recorded checksum
+    pub fn remove(&self, path: &Path) -> Result<()> {
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to remove cached archive: {}", path.display()))?;
+        let checksum_path = Self::checksum_path(path);
+        if checksum_path.exists() {
+            std::fs::remove_file(&checksum_path)?;
+        }
+        Ok(())
+    }
+
+    /// e.g. `1709-9.2.0.zip.sha256` for `1709-9.2.0.zip`
+    pub(crate) fn checksum_path(path: &Path) -> PathBuf {
+        let mut checksum_path = OsString::from(path.as_os_str());
+        checksum_path.push(".sha256");
+        PathBuf::from(checksum_path)
+    }
+
+    pub(crate) fn is_checksum_path(path: &Path) -> bool {
+        path.extension()
+            .is_some_and(|extension| extension == "sha256")
+    }
+
+    fn verify_archive(path: &Path) -> ArchiveStatus {
+        if path.extension().is_none_or(|extension| extension != "zip") {
+            return ArchiveStatus::Corrupted(
+                "not a cached archive, e.g. an interrupted copy".to_string(),
+            );
+        }
+        if let Err(e) = Self::read_zip(path) {
+            return ArchiveStatus::Corrupted(format!("unreadable zip: {:#}", e));
+        }
+        let Ok(expected) = std::fs::read_to_string(Self::checksum_path(path)) else {
+            return ArchiveStatus::Unverified;
+        };
+        let actual = match DefaultFileService.sha256_file(path) {
+            Ok(hash) => format!("sha256:{}", hash),
+            Err(e) => return ArchiveStatus::Corrupted(format!("{:#}", e)),
+        };
+        if actual != expected.trim() {
+            return ArchiveStatus::Corrupted(format!(
+                "checksum mismatch, expected {} but got {}",
+                expected.trim(),
+                actual
+            ));
+        }
+        ArchiveStatus::Valid
+    }
+
+    /// Reads every entry, so damaged data fails its CRC check
+    fn read_zip(path: &Path) -> Result<()> {
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(path)?)?;
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index)?;
+            std::io::copy(&mut entry, &mut std::io::sink())
+                .with_context(|| format!("Failed to read {}", entry.name()))?;
+        }
+        Ok(())
+    }
+
+    fn path(&self, asset_id: &str, version: &str) -> PathBuf {
+        self.dir.join(Self::file_name(asset_id, version))
+    }
+
+    /// e.g. `1709-9.2.0.zip`. Characters that can't be used in file names are replaced.
+    pub(crate) fn file_name(asset_id: &str, version: &str) -> String {
+        let version: String = version
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '+') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        format!("{}-{}.zip", asset_id, version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::NoopTask;
+
+    fn write_archive(dir: &Path, content: &str) -> PathBuf {
+        let path = dir.join("download.zip");
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_store_and_get() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let cache = ArchiveCache::new(dir.path().join("archives"));
+        let archive = write_archive(dir.path(), "zip");
+
+        cache
+            .store("1709", "9.2.0", &archive, "sha256:abc")
+            .unwrap();
+
+        let cached = cache.get("1709", "9.2.0").unwrap();
+        assert_eq!(std::fs::read_to_string(cached).unwrap(), "zip");
+        assert_eq!(cache.get("1709", "9.1.0"), None);
+        assert_eq!(cache.get("170", "9.2.0"), None);
+    }
+
+    #[test]
+    fn test_store_should_replace_other_versions_of_the_asset() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let cache = ArchiveCache::new(dir.path().join("archives"));
+        let archive = write_archive(dir.path(), "zip");
+
+        cache
+            .store("1709", "9.1.0", &archive, "sha256:abc")
+            .unwrap();
+        cache
+            .store("17090", "1.0.0", &archive, "sha256:abc")
+            .unwrap();
+        cache
+            .store("1709", "9.2.0", &archive, "sha256:abc")
+            .unwrap();
+
+        assert_eq!(cache.get("1709", "9.1.0"), None);
+        assert!(!dir.path().join("archives/1709-9.1.0.zip.sha256").exists());
+        assert!(cache.get("1709", "9.2.0").is_some());
+        assert!(cache.get("17090", "1.0.0").is_some());
+    }
+
+    fn write_zip(path: &Path) {
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(path).unwrap());
+        zip.start_file(
+            "addons/plugin/plugin.cfg",
+            zip::write::SimpleFileOptions::default(),
+        )
+        .unwrap();
+        std::io::Write::write_all(&mut zip, b"[plugin]").unwrap();
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn test_verify_should_find_corrupted_archives() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let cache = ArchiveCache::new(dir.path().join("archives"));
+        let archive = dir.path().join("download.zip");
+        write_zip(&archive);
+        let checksum = format!(
+            "sha256:{}",
+            DefaultFileService.sha256_file(&archive).unwrap()
+        );
+        cache.store("1", "1.0.0", &archive, &checksum).unwrap();
+        cache.store("2", "1.0.0", &archive, "sha256:abc").unwrap();
+        cache.store("3", "1.0.0", &archive, &checksum).unwrap();
+        std::fs::remove_file(dir.path().join("archives/3-1.0.0.zip.sha256")).unwrap();
+        std::fs::write(dir.path().join("archives/4-1.0.0.zip"), "not a zip").unwrap();
+
+        let progress = NoopTask::default();
+        let statuses: Vec<ArchiveStatus> = cache
+            .verify(&progress)
+            .unwrap()
+            .into_iter()
+            .map(|archive| archive.status)
+            .collect();
+
+        assert_eq!(statuses[0], ArchiveStatus::Valid);
+        assert!(
+            matches!(&statuses[1], ArchiveStatus::Corrupted(reason) if reason.starts_with("checksum mismatch"))
+        );
+        assert_eq!(statuses[2], ArchiveStatus::Unverified);
+        assert!(
+            matches!(&statuses[3], ArchiveStatus::Corrupted(reason) if reason.starts_with("unreadable zip"))
+        );
+        assert_eq!(statuses.len(), 4);
+        assert_eq!(progress.position(), 4);
+
+        cache
+            .remove(&dir.path().join("archives/2-1.0.0.zip"))
+            .unwrap();
+        assert!(!dir.path().join("archives/2-1.0.0.zip.sha256").exists());
+    }
+}
@@ -0,0 +1,175 @@
+use crate::api::AssetListItem;
+use crate::utils::Utils;
+
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Lowest [`Utils::fuzzy_match_score`] of a title for `gdm search --offline` to list it
+const MIN_OFFLINE_SCORE: f64 = 0.85;
+
+/// Assets indexed by registry URL, then asset ID
+type Index = BTreeMap<String, BTreeMap<String, AssetListItem>>;
+
+/// Assets seen in searches and installs, kept in the cache directory so `gdm search --offline`
+/// can find them without the Asset Library. Unlike `metadata`, entries don't expire and
+/// aren't evicted, each asset is only updated when it's seen again.
+#[derive(Debug, Clone)]
+pub struct AssetIndex {
+    path: PathBuf,
+    registry: String,
+    /// Installs record assets concurrently
+    lock: Arc<Mutex<()>>,
+}
+
+impl AssetIndex {
+    pub fn new(path: impl Into<PathBuf>, registry: impl Into<String>) -> AssetIndex {
+        AssetIndex {
+            path: path.into(),
+            registry: registry.into(),
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// `asset-index.json` in [`Utils::cache_dir`], None if it can't be determined
+    pub fn in_cache_dir(registry: &str) -> Option<AssetIndex> {
+        Some(AssetIndex::new(
+            Utils::cache_dir()?.join("asset-index.json"),
+            registry,
+        ))
+    }
+
+    /// Adds or updates assets of the registry. Details the new entry doesn't have, e.g. the
+    /// author of an asset seen by an install, are kept from the previous one.
+    pub fn record(&self, assets: &[AssetListItem]) -> Result<()> {
+        if assets.is_empty() {
+            return Ok(());
+        }
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut index = self.load();
+        let registry_assets = index.entry(self.registry.clone()).or_default();
+        for asset in assets {
+            let mut asset = asset.clone();
+            if let Some(previous) = registry_assets.get(&asset.asset_id) {
+                for (field, previous) in [
+                    (&mut asset.author, &previous.author),
+                    (&mut asset.category, &previous.category),
+                    (&mut asset.support_level, &previous.support_level),
+                ] {
+                    if field.is_empty() {
+                        field.clone_from(previous);
+                    }
+                }
+            }
+            registry_assets.insert(asset.asset_id.clone(), asset);
+        }
+        self.save(&index)
+    }
+
+    /// The recorded asset with the ID in the registry
+    pub fn get(&self, asset_id: &str) -> Option<AssetListItem> {
+        self.load().remove(&self.registry)?.remove(asset_id)
+    }
+
+    /// Known assets of the registry whose titles match `query`, closest first
+    pub fn search(&self, query: &str) -> Vec<AssetListItem> {
+        let mut matches: Vec<(f64, AssetListItem)> = self
+            .load()
+            .remove(&self.registry)
+            .unwrap_or_default()
+            .into_values()
+            .map(|asset| (Utils::fuzzy_match_score(query, &asset.title), asset))
+            .filter(|(score, _)| *score >= MIN_OFFLINE_SCORE)
+            .collect();
+        matches.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+        matches.into_iter().map(|(_, asset)| asset).collect()
+    }
+
+    /// A missing or unreadable index is empty
+    fn load(&self) -> Index {
+        std::fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Writes a temporary file and renames it, so other runs never read a partial index
+    fn save(&self, index: &Index) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create folder: {}", parent.display()))?;
+        }
+        let temp_path = self
+            .path
+            .with_extension(format!("{}.tmp", std::process::id()));
+        std::fs::write(&temp_path, serde_json::to_string(index)?)
+            .with_context(|| format!("Failed to write asset index: {}", temp_path.display()))?;
+        std::fs::rename(&temp_path, &self.path)
+            .with_context(|| format!("Failed to write asset index: {}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(asset_id: &str, title: &str, author: &str) -> AssetListItem {
+        AssetListItem {
+            asset_id: asset_id.to_string(),
+            title: title.to_string(),
+            author: author.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_search_should_return_recorded_matches_of_registry() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let path = dir.path().join("asset-index.json");
+        let index = AssetIndex::new(&path, "https://godotengine.org/asset-library/api");
+        index
+            .record(&[
+                asset("1709", "Gut - Godot Unit Testing", "bitwes"),
+                asset("3023", "Dialogue Manager", "nathanhoad"),
+                asset("2045", "Simple Dialog", "someone"),
+            ])
+            .unwrap();
+        AssetIndex::new(&path, "https://assets.example.com/api")
+            .record(&[asset("1", "Dialogue Nodes", "example")])
+            .unwrap();
+
+        let titles = |assets: Vec<AssetListItem>| -> Vec<String> {
+            assets.into_iter().map(|asset| asset.title).collect()
+        };
+        assert_eq!(
+            titles(index.search("dialog")),
+            vec!["Dialogue Manager", "Simple Dialog"]
+        );
+        assert_eq!(
+            titles(index.search("godot unit tests")),
+            vec!["Gut - Godot Unit Testing"]
+        );
+        assert!(index.search("beehave").is_empty());
+    }
+
+    #[test]
+    fn test_record_should_keep_details_missing_from_new_entry() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let index = AssetIndex::new(dir.path().join("asset-index.json"), "registry");
+        index
+            .record(&[asset("1709", "Gut - Godot Unit Testing", "bitwes")])
+            .unwrap();
+
+        let mut installed = asset("1709", "Gut - Godot Unit Testing", "");
+        installed.version_string = "9.2.0".to_string();
+        index.record(&[installed]).unwrap();
+
+        let found = index.search("gut");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].author, "bitwes");
+        assert_eq!(found[0].version_string, "9.2.0");
+        assert_eq!(index.get("1709").unwrap().version_string, "9.2.0");
+        assert!(index.get("1").is_none());
+    }
+}
@@ -0,0 +1,338 @@
+use crate::config::GdmConfigFormat;
+use crate::error::{ErrorKind, GdmError};
+use crate::models::{Plugin, PluginSource};
+use crate::services::{ArchiveCache, DefaultFileService, FileService};
+
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+
+/// Folder of the Asset Library archives in a bundle
+const ARCHIVES_DIR: &str = "archives";
+
+/// A zip written by `gdm pack` with the plugin config file and the cached archive of every Asset
+/// Library plugin it lists, named as in the archive cache. `gdm unpack` restores them, so
+/// `gdm install --frozen` can install the plugins without the Asset Library, e.g. years later or
+/// on an air-gapped machine. Git plugins aren't bundled and are fetched from their repository.
+pub struct Bundle {
+    archive_cache: ArchiveCache,
+}
+
+/// What a bundle holds, by plugin name
+#[derive(Debug, Default, PartialEq)]
+pub struct BundleContents {
+    /// Asset Library plugins with their archive
+    pub archives: Vec<String>,
+    /// Git plugins, which are left out
+    pub git_plugins: Vec<String>,
+}
+
+impl Bundle {
+    pub fn new(archive_cache: ArchiveCache) -> Bundle {
+        Bundle { archive_cache }
+    }
+
+    /// Writes `config_file_path` and the cached archives of its plugins to `output`. Fails if an
+    /// archive isn't cached or doesn't match the checksum in the config file.
+    pub fn pack(&self, config_file_path: &Path, output: &Path) -> Result<BundleContents> {
+        let config_name = Self::config_name(config_file_path)?;
+        let content = fs::read_to_string(config_file_path).with_context(|| {
+            format!(
+                "Failed to read the plugin config file {}",
+                config_file_path.display()
+            )
+        })?;
+        let config = GdmConfigFormat::from_path(config_file_path).parse(&content)?;
+
+        let mut contents = BundleContents::default();
+        let mut archives = Vec::new();
+        for (name, plugin) in &config.plugins {
+            let Some(asset_id) = Self::asset_id(plugin) else {
+                contents.git_plugins.push(name.clone());
+                continue;
+            };
+            let Some(archive) = self.archive_cache.get(asset_id, &plugin.version) else {
+                bail!(
+                    GdmError::new(
+                        ErrorKind::NotFound,
+                        format!(
+                            "No cached archive of {} {} to pack",
+                            plugin.title, plugin.version
+                        )
+                    )
+                    .with_plugin(name)
+                    .with_hint(format!(
+                        "Remove the plugin's folder in the addons folder and run gdm install {} to download it again.",
+                        name
+                    ))
+                );
+            };
+            Self::verify_archive(plugin, &archive)?;
+            archives.push(archive);
+            contents.archives.push(name.clone());
+        }
+
+        if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let file = fs::File::create(output)
+            .with_context(|| format!("Failed to create bundle {}", output.display()))?;
+        let mut zip = zip::ZipWriter::new(file);
+        zip.start_file(config_name, SimpleFileOptions::default())?;
+        zip.write_all(content.as_bytes())?;
+        for archive in &archives {
+            // Archives are compressed already
+            let options =
+                SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+            zip.start_file(Self::entry_name(archive)?, options)?;
+            io::copy(&mut fs::File::open(archive)?, &mut zip)?;
+        }
+        zip.finish()?;
+        Ok(contents)
+    }
+
+    /// Restores the config file of `bundle` next to `config_file_path` and its archives to the
+    /// archive cache, returning the path of the config file. An existing config file must be the
+    /// same as the bundled one.
+    pub fn unpack(
+        &self,
+        bundle: &Path,
+        config_file_path: &Path,
+    ) -> Result<(PathBuf, BundleContents)> {
+        let file = fs::File::open(bundle)
+            .with_context(|| format!("Failed to open bundle {}", bundle.display()))?;
+        let mut zip = zip::ZipArchive::new(file).map_err(|e| {
+            GdmError::new(
+                ErrorKind::ArchiveInvalid,
+                format!("{} isn't a gdm bundle: {}", bundle.display(), e),
+            )
+            .with_hint("Create bundles with gdm pack.")
+        })?;
+
+        // A bundle of a project using gdm.toml can be unpacked where gdm.json is the default
+        let Some((config_file_path, mut entry)) = [
+            config_file_path.to_path_buf(),
+            config_file_path.with_extension("toml"),
+            config_file_path.with_extension("json"),
+        ]
+        .into_iter()
+        .find_map(|path| {
+            let index = zip.index_for_name(&Self::config_name(&path).ok()?)?;
+            Some((path, index))
+        })
+        .map(|(path, index)| zip.by_index(index).map(|entry| (path, entry)))
+        .transpose()?
+        else {
+            bail!(
+                GdmError::new(
+                    ErrorKind::ArchiveInvalid,
+                    format!(
+                        "{} has no plugin config file {}",
+                        bundle.display(),
+                        config_file_path.display()
+                    )
+                )
+                .with_hint("Create bundles with gdm pack.")
+            );
+        };
+        let mut content = String::new();
+        entry.read_to_string(&mut content)?;
+        drop(entry);
+        let config = GdmConfigFormat::from_path(&config_file_path).parse(&content)?;
+        if config_file_path.exists() && fs::read_to_string(&config_file_path)? != content {
+            bail!(
+                GdmError::new(
+                    ErrorKind::Conflict,
+                    format!(
+                        "{} differs from the one in {}",
+                        config_file_path.display(),
+                        bundle.display()
+                    )
+                )
+                .with_hint("Unpack the bundle in a fresh clone, or remove the file to replace it.")
+            );
+        }
+
+        let mut contents = BundleContents::default();
+        let staging = tempfile_path(bundle);
+        for (name, plugin) in &config.plugins {
+            let Some(asset_id) = Self::asset_id(plugin) else {
+                contents.git_plugins.push(name.clone());
+                continue;
+            };
+            let entry_name = format!(
+                "{}/{}",
+                ARCHIVES_DIR,
+                ArchiveCache::file_name(asset_id, &plugin.version)
+            );
+            let Ok(mut entry) = zip.by_name(&entry_name) else {
+                bail!(
+                    GdmError::new(
+                        ErrorKind::ArchiveInvalid,
+                        format!(
+                            "{} has no archive of {} {}",
+                            bundle.display(),
+                            plugin.title,
+                            plugin.version
+                        )
+                    )
+                    .with_plugin(name)
+                );
+            };
+            io::copy(&mut entry, &mut fs::File::create(&staging)?)?;
+            drop(entry);
+            let checksum = Self::verify_archive(plugin, &staging);
+            let stored = checksum.and_then(|checksum| {
+                self.archive_cache
+                    .store(asset_id, &plugin.version, &staging, &checksum)
+            });
+            fs::remove_file(&staging)?;
+            stored?;
+            contents.archives.push(name.clone());
+        }
+
+        if !config_file_path.exists() {
+            fs::write(&config_file_path, &content)?;
+        }
+        Ok((config_file_path, contents))
+    }
+
+    fn asset_id(plugin: &Plugin) -> Option<&str> {
+        match &plugin.source {
+            Some(PluginSource::AssetLibrary { asset_id }) => Some(asset_id),
+            _ => None,
+        }
+    }
+
+    /// Checks `archive` against the checksum of the plugin and returns its checksum
+    fn verify_archive(plugin: &Plugin, archive: &Path) -> Result<String> {
+        let checksum = format!("sha256:{}", DefaultFileService.sha256_file(archive)?);
+        plugin.verify_checksum(&checksum)?;
+        Ok(checksum)
+    }
+
+    fn config_name(config_file_path: &Path) -> Result<String> {
+        config_file_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .context("The plugin config file has no name")
+    }
+
+    fn entry_name(archive: &Path) -> Result<String> {
+        let file_name = archive
+            .file_name()
+            .context("Cached archive without a name")?
+            .to_string_lossy();
+        Ok(format!("{}/{}", ARCHIVES_DIR, file_name))
+    }
+}
+
+/// File next to the bundle each archive is extracted to before it's stored in the cache
+fn tempfile_path(bundle: &Path) -> PathBuf {
+    let mut name = bundle.as_os_str().to_owned();
+    name.push(".part");
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARCHIVE: &[u8] = b"PK\x05\x06\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
+
+    fn write_config(dir: &Path, checksum: &str) -> PathBuf {
+        let config_file_path = dir.join("gdm.json");
+        fs::write(
+            &config_file_path,
+            format!(
+                r#"{{
+  "plugins": {{
+    "gut": {{ "source": {{ "asset_id": "1709" }}, "title": "Gut", "version": "9.3.0", "checksum": "{}" }},
+    "beehave": {{ "source": {{ "url": "https://github.com/bitbrain/beehave.git", "reference": "v2.9.0" }}, "title": "Beehave", "version": "2.9.0" }}
+  }}
+}}"#,
+                checksum
+            ),
+        )
+        .unwrap();
+        config_file_path
+    }
+
+    fn archive_checksum(dir: &Path) -> String {
+        let archive = dir.join("archive.zip");
+        fs::write(&archive, ARCHIVE).unwrap();
+        format!(
+            "sha256:{}",
+            DefaultFileService.sha256_file(&archive).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_pack_and_unpack_should_restore_config_and_archives() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let checksum = archive_checksum(dir.path());
+        let config_file_path = write_config(dir.path(), &checksum);
+        let archive_cache = ArchiveCache::new(dir.path().join("cache"));
+        archive_cache
+            .store("1709", "9.3.0", &dir.path().join("archive.zip"), &checksum)
+            .unwrap();
+        let bundle_path = dir.path().join("bundle.zip");
+
+        let packed = Bundle::new(archive_cache)
+            .pack(&config_file_path, &bundle_path)
+            .unwrap();
+        assert_eq!(packed.archives, vec!["gut".to_string()]);
+        assert_eq!(packed.git_plugins, vec!["beehave".to_string()]);
+
+        let clone = dir.path().join("clone");
+        fs::create_dir(&clone).unwrap();
+        let fresh_cache = ArchiveCache::new(dir.path().join("fresh-cache"));
+        let (unpacked_config, unpacked) = Bundle::new(fresh_cache.clone())
+            .unpack(&bundle_path, &clone.join("gdm.json"))
+            .unwrap();
+        assert_eq!(unpacked_config, clone.join("gdm.json"));
+        assert_eq!(unpacked, packed);
+        assert_eq!(
+            fs::read_to_string(clone.join("gdm.json")).unwrap(),
+            fs::read_to_string(&config_file_path).unwrap()
+        );
+        let archive = fresh_cache.get("1709", "9.3.0").unwrap();
+        assert_eq!(fs::read(archive).unwrap(), ARCHIVE);
+    }
+
+    #[test]
+    fn test_pack_without_cached_archive_should_fail() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let config_file_path = write_config(dir.path(), "sha256:abc");
+        let bundle = Bundle::new(ArchiveCache::new(dir.path().join("cache")));
+
+        let error = bundle
+            .pack(&config_file_path, &dir.path().join("bundle.zip"))
+            .unwrap_err();
+        assert_eq!(GdmError::find(&error).unwrap().kind, ErrorKind::NotFound);
+        assert!(!dir.path().join("bundle.zip").exists());
+    }
+
+    #[test]
+    fn test_unpack_should_refuse_different_config_file() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let checksum = archive_checksum(dir.path());
+        let config_file_path = write_config(dir.path(), &checksum);
+        let archive_cache = ArchiveCache::new(dir.path().join("cache"));
+        archive_cache
+            .store("1709", "9.3.0", &dir.path().join("archive.zip"), &checksum)
+            .unwrap();
+        let bundle_path = dir.path().join("bundle.zip");
+        Bundle::new(archive_cache.clone())
+            .pack(&config_file_path, &bundle_path)
+            .unwrap();
+
+        fs::write(&config_file_path, r#"{ "plugins": {} }"#).unwrap();
+        let error = Bundle::new(archive_cache)
+            .unpack(&bundle_path, &config_file_path)
+            .unwrap_err();
+        assert_eq!(GdmError::find(&error).unwrap().kind, ErrorKind::Conflict);
+    }
+}
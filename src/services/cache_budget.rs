@@ -0,0 +1,188 @@
+use crate::error::{ErrorKind, GdmError};
+use crate::services::ArchiveCache;
+use crate::utils::Utils;
+
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tracing::debug;
+
+/// Largest size of the cache directory unless --cache-size is given
+pub const DEFAULT_CACHE_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// Folders of the cache directory that are evicted, others like update-check.json are kept
+const EVICTED_FOLDERS: [&str; 2] = ["archives", "metadata"];
+
+/// A file removed from the cache to keep it within its size budget
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvictedEntry {
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+/// Keeps the cached archives and Asset Library responses below a maximum size by removing the
+/// least recently used files first. Using a cached archive updates its modification time.
+#[derive(Debug, Clone)]
+pub struct CacheBudget {
+    dir: PathBuf,
+    max_size: u64,
+}
+
+impl CacheBudget {
+    pub fn new(dir: impl Into<PathBuf>, max_size: u64) -> CacheBudget {
+        CacheBudget {
+            dir: dir.into(),
+            max_size,
+        }
+    }
+
+    /// Budget for [`Utils::cache_dir`], None if it can't be determined
+    pub fn in_cache_dir(max_size: u64) -> Option<CacheBudget> {
+        Some(CacheBudget::new(Utils::cache_dir()?, max_size))
+    }
+
+    /// Parses sizes like `2GB`, `500MB`, `512KiB` or `1048576`. Units are powers of 1024.
+    pub fn parse_size(value: &str) -> Result<u64> {
+        let value = value.trim();
+        let split = value
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(value.len());
+        let (number, unit) = value.split_at(split);
+        let multiplier: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+            "" | "B" => 1,
+            "K" | "KB" | "KIB" => 1024,
+            "M" | "MB" | "MIB" => 1024 * 1024,
+            "G" | "GB" | "GIB" => 1024 * 1024 * 1024,
+            _ => bail!(Self::invalid_size_error(value)),
+        };
+        match number.parse::<f64>() {
+            Ok(number) if number >= 0.0 => Ok((number * multiplier as f64) as u64),
+            _ => bail!(Self::invalid_size_error(value)),
+        }
+    }
+
+    /// Removes the least recently used files until the cache fits the budget
+    pub fn evict(&self) -> Result<Vec<EvictedEntry>> {
+        let mut files = Vec::new();
+        for folder in EVICTED_FOLDERS {
+            Self::collect_files(&self.dir.join(folder), &mut files)?;
+        }
+        let mut total_size: u64 = files.iter().map(|(_, size, _)| size).sum();
+        if total_size <= self.max_size {
+            return Ok(Vec::new());
+        }
+        files.sort_by_key(|(_, _, modified)| *modified);
+
+        let mut evicted = Vec::new();
+        for (path, size, _) in files {
+            if total_size <= self.max_size {
+                break;
+            }
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to evict cached file: {}", path.display()))?;
+            let checksum_path = ArchiveCache::checksum_path(&path);
+            if checksum_path.exists() {
+                std::fs::remove_file(&checksum_path)?;
+            }
+            debug!(
+                "Evicted from the cache: {} ({} bytes)",
+                path.display(),
+                size
+            );
+            total_size -= size;
+            evicted.push(EvictedEntry { path, size });
+        }
+        Ok(evicted)
+    }
+
+    fn collect_files(dir: &Path, files: &mut Vec<(PathBuf, u64, SystemTime)>) -> Result<()> {
+        if !dir.is_dir() {
+            return Ok(());
+        }
+        for entry in std::fs::read_dir(dir)?.flatten() {
+            let metadata = entry.metadata()?;
+            if metadata.is_dir() {
+                Self::collect_files(&entry.path(), files)?;
+            } else if !ArchiveCache::is_checksum_path(&entry.path()) {
+                // Checksums are removed with their archive
+                let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                files.push((entry.path(), metadata.len(), modified));
+            }
+        }
+        Ok(())
+    }
+
+    fn invalid_size_error(value: &str) -> GdmError {
+        GdmError::new(ErrorKind::Usage, format!("Invalid cache size: {}", value))
+            .with_hint("Use a size like 500MB or 2GB.")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn write_file(path: &Path, size: usize, age: u64) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![0u8; size]).unwrap();
+        let modified = SystemTime::now() - Duration::from_secs(age);
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(CacheBudget::parse_size("1048576").unwrap(), 1048576);
+        assert_eq!(CacheBudget::parse_size("512KiB").unwrap(), 512 * 1024);
+        assert_eq!(
+            CacheBudget::parse_size("1.5 GB").unwrap(),
+            1536 * 1024 * 1024
+        );
+        assert_eq!(CacheBudget::parse_size("500mb").unwrap(), 500 * 1024 * 1024);
+        assert!(CacheBudget::parse_size("lots").is_err());
+        assert!(CacheBudget::parse_size("2TB").is_err());
+    }
+
+    #[test]
+    fn test_evict_should_remove_least_recently_used_files() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let oldest = dir.path().join("archives/1709-9.1.0.zip");
+        let older = dir.path().join("metadata/abc.json");
+        let newest = dir.path().join("archives/1234-1.0.0.zip");
+        write_file(&oldest, 100, 300);
+        write_file(&ArchiveCache::checksum_path(&oldest), 71, 0);
+        write_file(&older, 100, 200);
+        write_file(&newest, 100, 100);
+        write_file(&dir.path().join("update-check.json"), 1000, 400);
+
+        let evicted = CacheBudget::new(dir.path(), 150).evict().unwrap();
+
+        assert_eq!(
+            evicted,
+            vec![
+                EvictedEntry {
+                    path: oldest.clone(),
+                    size: 100
+                },
+                EvictedEntry {
+                    path: older.clone(),
+                    size: 100
+                },
+            ]
+        );
+        assert!(!ArchiveCache::checksum_path(&oldest).exists());
+        assert!(newest.exists());
+        assert!(dir.path().join("update-check.json").exists());
+        assert!(
+            CacheBudget::new(dir.path(), 150)
+                .evict()
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
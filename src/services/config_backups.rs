@@ -0,0 +1,335 @@
+use crate::error::{ErrorKind, GdmError};
+use crate::utils::Utils;
+
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::debug;
+
+/// Backups kept per project unless the global config sets `config_backups`
+pub const DEFAULT_CONFIG_BACKUPS: usize = 20;
+
+/// Copies of project.godot and gdm.json taken before commands change them, so configuration
+/// changes can be rolled back with `gdm restore-config` without touching plugin files. Each
+/// project has its own folder of backups, named by when they were taken.
+#[derive(Debug, Clone)]
+pub struct ConfigBackups {
+    dir: PathBuf,
+    retention: usize,
+}
+
+/// A backup and the names of the files in it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigBackup {
+    /// When the backup was taken in UTC, e.g. `20261015T142233.123Z`
+    pub id: String,
+    pub files: Vec<String>,
+}
+
+impl ConfigBackups {
+    pub fn new(dir: impl Into<PathBuf>, retention: usize) -> ConfigBackups {
+        ConfigBackups {
+            dir: dir.into(),
+            retention,
+        }
+    }
+
+    /// Backups of `project_dir` in the `backups` folder of [`Utils::cache_dir`], in a folder
+    /// named by the SHA-256 of the project's path. None if the folder can't be determined or
+    /// `retention` is zero.
+    pub fn in_cache_dir(project_dir: &Path, retention: usize) -> Option<ConfigBackups> {
+        if retention == 0 {
+            return None;
+        }
+        let project_dir = project_dir
+            .canonicalize()
+            .unwrap_or_else(|_| project_dir.to_path_buf());
+        let digest = ring::digest::digest(
+            &ring::digest::SHA256,
+            project_dir.to_string_lossy().as_bytes(),
+        );
+        let name = digest
+            .as_ref()
+            .iter()
+            .take(8)
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+        Some(ConfigBackups::new(
+            Utils::cache_dir()?.join("backups").join(name),
+            retention,
+        ))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Copies the existing `files` into a new backup and removes the oldest backups beyond the
+    /// retention. None if none of the files exist or they're the same as in the latest backup.
+    pub fn backup(&self, files: &[&Path]) -> Result<Option<ConfigBackup>> {
+        let mut contents = Vec::new();
+        for file in files {
+            if !file.is_file() {
+                continue;
+            }
+            let name = file
+                .file_name()
+                .with_context(|| format!("Can't back up {}", file.display()))?
+                .to_string_lossy()
+                .to_string();
+            let content = std::fs::read(file)
+                .with_context(|| format!("Failed to read {} to back it up", file.display()))?;
+            contents.push((name, content));
+        }
+        contents.sort();
+        if contents.is_empty() || self.is_latest(&contents)? {
+            return Ok(None);
+        }
+
+        let id = self.unique_id(SystemTime::now())?;
+        let backup_dir = self.dir.join(&id);
+        std::fs::create_dir_all(&backup_dir)
+            .with_context(|| format!("Failed to create backup folder: {}", backup_dir.display()))?;
+        for (name, content) in &contents {
+            std::fs::write(backup_dir.join(name), content)
+                .with_context(|| format!("Failed to back up {}", name))?;
+        }
+        debug!("Backed up configuration to {}", backup_dir.display());
+        self.prune()?;
+        Ok(Some(ConfigBackup {
+            id,
+            files: contents.into_iter().map(|(name, _)| name).collect(),
+        }))
+    }
+
+    /// Backups of the project, newest first
+    pub fn list(&self) -> Result<Vec<ConfigBackup>> {
+        if !self.dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut backups = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)
+            .with_context(|| format!("Failed to read backups: {}", self.dir.display()))?
+            .flatten()
+        {
+            if !entry.path().is_dir() {
+                continue;
+            }
+            let mut files: Vec<String> = std::fs::read_dir(entry.path())?
+                .flatten()
+                .map(|file| file.file_name().to_string_lossy().to_string())
+                .collect();
+            files.sort();
+            backups.push(ConfigBackup {
+                id: entry.file_name().to_string_lossy().to_string(),
+                files,
+            });
+        }
+        // Ids are timestamps, so they sort by age
+        backups.sort_by(|a, b| b.id.cmp(&a.id));
+        Ok(backups)
+    }
+
+    /// Copies the files of backup `id` into `project_dir`. The files it replaces are backed up
+    /// first so restoring can be undone, that backup is returned second.
+    pub fn restore(
+        &self,
+        id: &str,
+        project_dir: &Path,
+    ) -> Result<(ConfigBackup, Option<ConfigBackup>)> {
+        let Some(backup) = self.list()?.into_iter().find(|backup| backup.id == id) else {
+            bail!(
+                GdmError::new(
+                    ErrorKind::NotFound,
+                    format!("No configuration backup {}", id)
+                )
+                .with_hint("List the backups of this project with gdm restore-config --list.")
+            );
+        };
+        // Read before backing up the current files, which may remove the oldest backups
+        let mut contents = Vec::new();
+        for name in &backup.files {
+            let path = self.dir.join(id).join(name);
+            let content = std::fs::read(&path)
+                .with_context(|| format!("Failed to read backup {}", path.display()))?;
+            contents.push((project_dir.join(name), content));
+        }
+        let targets: Vec<&Path> = contents.iter().map(|(path, _)| path.as_path()).collect();
+        let previous = self.backup(&targets)?;
+        for (target, content) in &contents {
+            std::fs::write(target, content)
+                .with_context(|| format!("Failed to restore {}", target.display()))?;
+        }
+        Ok((backup, previous))
+    }
+
+    fn is_latest(&self, contents: &[(String, Vec<u8>)]) -> Result<bool> {
+        let Some(latest) = self.list()?.into_iter().next() else {
+            return Ok(false);
+        };
+        if latest.files.len() != contents.len() {
+            return Ok(false);
+        }
+        Ok(contents.iter().all(|(name, content)| {
+            std::fs::read(self.dir.join(&latest.id).join(name)).is_ok_and(|c| &c == content)
+        }))
+    }
+
+    fn prune(&self) -> Result<()> {
+        for backup in self.list()?.into_iter().skip(self.retention) {
+            let path = self.dir.join(&backup.id);
+            debug!("Removing old configuration backup: {}", path.display());
+            std::fs::remove_dir_all(&path)
+                .with_context(|| format!("Failed to remove backup: {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// The timestamp of `time`, with a suffix if a backup was already taken in the same
+    /// millisecond, so the new backup sorts after it
+    fn unique_id(&self, time: SystemTime) -> Result<String> {
+        let id = Self::timestamp(time);
+        let latest = self.list()?.into_iter().next().map(|backup| backup.id);
+        let mut unique_id = id.clone();
+        let mut suffix = 1;
+        while self.dir.join(&unique_id).exists()
+            || latest
+                .as_ref()
+                .is_some_and(|latest| latest.starts_with(&id) && *latest >= unique_id)
+        {
+            unique_id = format!("{}-{:03}", id, suffix);
+            suffix += 1;
+        }
+        Ok(unique_id)
+    }
+
+    /// e.g. `20261015T142233.123Z`, which can be used as a folder name on every platform
+    fn timestamp(time: SystemTime) -> String {
+        let duration = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let seconds = duration.as_secs();
+        let (year, month, day) = Self::civil_date(seconds / 86400);
+        let time_of_day = seconds % 86400;
+        format!(
+            "{:04}{:02}{:02}T{:02}{:02}{:02}.{:03}Z",
+            year,
+            month,
+            day,
+            time_of_day / 3600,
+            time_of_day % 3600 / 60,
+            time_of_day % 60,
+            duration.subsec_millis()
+        )
+    }
+
+    /// Year, month and day of a number of days since 1970-01-01, after Howard Hinnant's
+    /// `civil_from_days`
+    fn civil_date(days: u64) -> (u64, u64, u64) {
+        let days = days + 719468;
+        let era = days / 146097;
+        let day_of_era = days % 146097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        };
+        let year = year_of_era + era * 400 + u64::from(month <= 2);
+        (year, month, day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn write_project(dir: &Path, gdm_json: &str) -> (PathBuf, PathBuf) {
+        let project_godot = dir.join("project.godot");
+        let gdm_json_path = dir.join("gdm.json");
+        std::fs::write(&project_godot, "config_version=5\n").unwrap();
+        std::fs::write(&gdm_json_path, gdm_json).unwrap();
+        (project_godot, gdm_json_path)
+    }
+
+    #[test]
+    fn test_timestamp() {
+        let time = UNIX_EPOCH + Duration::from_millis(1_792_074_153_123);
+        assert_eq!(ConfigBackups::timestamp(time), "20261015T142233.123Z");
+        assert_eq!(ConfigBackups::timestamp(UNIX_EPOCH), "19700101T000000.000Z");
+        let leap_day = UNIX_EPOCH + Duration::from_secs(1_709_164_800);
+        assert_eq!(ConfigBackups::timestamp(leap_day), "20240229T000000.000Z");
+    }
+
+    #[test]
+    fn test_backup_should_skip_unchanged_files() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let (project_godot, gdm_json) = write_project(dir.path(), "{}");
+        let backups = ConfigBackups::new(dir.path().join("backups"), 5);
+
+        let backup = backups.backup(&[&project_godot, &gdm_json]).unwrap();
+        assert_eq!(
+            backup.unwrap().files,
+            vec!["gdm.json".to_string(), "project.godot".to_string()]
+        );
+        assert_eq!(backups.backup(&[&project_godot, &gdm_json]).unwrap(), None);
+
+        std::fs::write(&gdm_json, r#"{ "plugins": {} }"#).unwrap();
+        assert!(
+            backups
+                .backup(&[&project_godot, &gdm_json])
+                .unwrap()
+                .is_some()
+        );
+        assert_eq!(backups.list().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_backup_should_remove_backups_beyond_retention() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let (_, gdm_json) = write_project(dir.path(), "{}");
+        let backups = ConfigBackups::new(dir.path().join("backups"), 2);
+
+        for version in 0..4 {
+            std::fs::write(&gdm_json, format!(r#"{{ "version": {} }}"#, version)).unwrap();
+            backups.backup(&[&gdm_json]).unwrap();
+        }
+
+        let list = backups.list().unwrap();
+        assert_eq!(list.len(), 2);
+        let latest = std::fs::read_to_string(backups.dir().join(&list[0].id).join("gdm.json"));
+        assert_eq!(latest.unwrap(), r#"{ "version": 3 }"#);
+    }
+
+    #[test]
+    fn test_restore_should_copy_files_back() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let (project_godot, gdm_json) = write_project(dir.path(), "{}");
+        let backups = ConfigBackups::new(dir.path().join("backups"), 5);
+        let backup = backups
+            .backup(&[&project_godot, &gdm_json])
+            .unwrap()
+            .unwrap();
+
+        std::fs::write(&gdm_json, "broken").unwrap();
+        std::fs::write(&project_godot, "broken").unwrap();
+        let (restored, previous) = backups.restore(&backup.id, dir.path()).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&gdm_json).unwrap(), "{}");
+        assert_eq!(
+            std::fs::read_to_string(&project_godot).unwrap(),
+            "config_version=5\n"
+        );
+        assert_eq!(restored, backup);
+        let previous = backups.dir().join(previous.unwrap().id).join("gdm.json");
+        assert_eq!(std::fs::read_to_string(previous).unwrap(), "broken");
+        let error = backups.restore("19700101T000000.000Z", dir.path());
+        assert_eq!(
+            GdmError::find(&error.unwrap_err()).unwrap().kind,
+            ErrorKind::NotFound
+        );
+    }
+}
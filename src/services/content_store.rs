@@ -0,0 +1,219 @@
+use crate::services::{DefaultFileService, FileService};
+use crate::ui::NoopTask;
+use crate::utils::Utils;
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Plugin folders shared by all projects of the user, each stored once in a folder named after
+/// the hash of its content. With `--install-mode link`, projects link their plugin folders here
+/// instead of keeping a copy.
+/// The `.uid` and `.import` files the Godot editor writes into a linked folder end up in the
+/// stored folder, so they're shared by the projects linking it. They're not part of the hash,
+/// so the folder keeps its name and still matches the recorded `content_hash`.
+#[derive(Debug, Clone)]
+pub struct ContentStore {
+    dir: PathBuf,
+}
+
+impl ContentStore {
+    pub fn new(dir: impl Into<PathBuf>) -> ContentStore {
+        ContentStore { dir: dir.into() }
+    }
+
+    /// Store in the `store` folder of [`Utils::cache_dir`], None if it can't be determined
+    pub fn in_cache_dir() -> Option<ContentStore> {
+        Some(ContentStore::new(Utils::cache_dir()?.join("store")))
+    }
+
+    /// Moves the plugin folder `src` into the store, unless a folder with the same content is
+    /// already there, and links `dest` to the stored folder. Copies the stored folder to `dest`
+    /// instead where links aren't supported, e.g. on file systems without symlinks, cloning its
+    /// files where the file system can.
+    /// Returns whether `dest` is a link.
+    pub fn install(&self, src: &Path, dest: &Path) -> Result<bool> {
+        let stored = self.store(src)?;
+        Self::remove_link(dest)?;
+        match Self::link(&stored, dest) {
+            Ok(()) => {
+                debug!("Linked {} to {}", dest.display(), stored.display());
+                Ok(true)
+            }
+            Err(e) => {
+                debug!(
+                    "Failed to link {}, copying it instead: {:#}",
+                    dest.display(),
+                    e
+                );
+                DefaultFileService.copy_dir(&stored, dest)?;
+                Ok(false)
+            }
+        }
+    }
+
+    /// The stored folder with the content of `src`, moved or copied there if it's not stored yet
+    fn store(&self, src: &Path) -> Result<PathBuf> {
+        let hash = DefaultFileService.sha256_dir(src, &NoopTask::default())?;
+        let stored = self.dir.join(&hash);
+        if stored.is_dir() {
+            return Ok(stored);
+        }
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create content store: {}", self.dir.display()))?;
+        // Moved or copied next to it first, so an interrupted copy is never linked
+        let partial = self.dir.join(format!("{}.part", hash));
+        if partial.exists() {
+            std::fs::remove_dir_all(&partial)?;
+        }
+        DefaultFileService.move_dir(src, &partial)?;
+        if let Err(e) = std::fs::rename(&partial, &stored) {
+            // Another gdm stored the same content in the meantime
+            if !stored.is_dir() {
+                return Err(e).with_context(|| {
+                    format!("Failed to add {} to the content store", src.display())
+                });
+            }
+            std::fs::remove_dir_all(&partial)?;
+        }
+        Ok(stored)
+    }
+
+    /// Removes `path` if it's a link, e.g. one to a stored folder that was deleted since
+    fn remove_link(path: &Path) -> Result<()> {
+        if std::fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink()) {
+            std::fs::remove_file(path)
+                .or_else(|_| std::fs::remove_dir(path))
+                .with_context(|| format!("Failed to remove link: {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    fn link(stored: &Path, dest: &Path) -> Result<()> {
+        std::os::unix::fs::symlink(stored, dest)?;
+        Ok(())
+    }
+
+    /// Creates a junction, which unlike a directory symlink doesn't need administrator rights
+    /// or developer mode
+    #[cfg(windows)]
+    fn link(stored: &Path, dest: &Path) -> Result<()> {
+        let status = std::process::Command::new("cmd")
+            .arg("/C")
+            .arg("mklink")
+            .arg("/J")
+            .arg(dest)
+            .arg(stored)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()?;
+        if !status.success() {
+            anyhow::bail!("mklink /J exited with {}", status);
+        }
+        Ok(())
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    fn link(_stored: &Path, _dest: &Path) -> Result<()> {
+        anyhow::bail!("Links aren't supported on this platform")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_plugin(dir: &Path) {
+        std::fs::create_dir_all(dir.join("icons")).unwrap();
+        std::fs::write(dir.join("plugin.cfg"), "[plugin]\nname=\"Gut\"\n").unwrap();
+        std::fs::write(dir.join("icons/icon.svg"), "<svg/>").unwrap();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_install_should_link_to_stored_folder() {
+        let temp_dir = temp_dir::TempDir::new().unwrap();
+        let store = ContentStore::new(temp_dir.path().join("store"));
+        let src = temp_dir.path().join("staging/addons/gut");
+        let dest = temp_dir.path().join("project/addons/gut");
+        write_plugin(&src);
+        std::fs::create_dir_all(dest.parent().unwrap()).unwrap();
+
+        let linked = store.install(&src, &dest).unwrap();
+
+        assert!(linked);
+        assert!(!src.exists());
+        assert!(dest.symlink_metadata().unwrap().file_type().is_symlink());
+        assert_eq!(
+            std::fs::read_to_string(dest.join("icons/icon.svg")).unwrap(),
+            "<svg/>"
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_install_should_store_same_content_once() {
+        let temp_dir = temp_dir::TempDir::new().unwrap();
+        let store = ContentStore::new(temp_dir.path().join("store"));
+        for project in ["game", "tools"] {
+            let src = temp_dir.path().join(project).join("staging/gut");
+            let dest = temp_dir.path().join(project).join("gut");
+            write_plugin(&src);
+            store.install(&src, &dest).unwrap();
+        }
+
+        let stored = std::fs::read_dir(temp_dir.path().join("store"))
+            .unwrap()
+            .count();
+        assert_eq!(stored, 1);
+        assert_eq!(
+            std::fs::read_link(temp_dir.path().join("game/gut")).unwrap(),
+            std::fs::read_link(temp_dir.path().join("tools/gut")).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_install_should_keep_stored_folder_after_editor_writes_into_link() {
+        let temp_dir = temp_dir::TempDir::new().unwrap();
+        let store = ContentStore::new(temp_dir.path().join("store"));
+        let game = temp_dir.path().join("game/gut");
+        write_plugin(&temp_dir.path().join("game/staging/gut"));
+        store
+            .install(&temp_dir.path().join("game/staging/gut"), &game)
+            .unwrap();
+        let stored = std::fs::read_link(&game).unwrap();
+
+        // What the Godot editor writes when it opens the project
+        std::fs::write(game.join("plugin.cfg.uid"), "uid://b1").unwrap();
+        std::fs::write(game.join("icons/icon.svg.import"), "[remap]").unwrap();
+
+        let tools = temp_dir.path().join("tools/gut");
+        write_plugin(&temp_dir.path().join("tools/staging/gut"));
+        store
+            .install(&temp_dir.path().join("tools/staging/gut"), &tools)
+            .unwrap();
+        assert_eq!(std::fs::read_link(&tools).unwrap(), stored);
+        assert_eq!(
+            DefaultFileService
+                .sha256_dir(&game, &NoopTask::default())
+                .unwrap(),
+            stored.file_name().unwrap().to_string_lossy()
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_install_should_replace_dangling_link() {
+        let temp_dir = temp_dir::TempDir::new().unwrap();
+        let store = ContentStore::new(temp_dir.path().join("store"));
+        let src = temp_dir.path().join("staging/gut");
+        let dest = temp_dir.path().join("gut");
+        write_plugin(&src);
+        std::os::unix::fs::symlink(temp_dir.path().join("removed"), &dest).unwrap();
+
+        assert!(store.install(&src, &dest).unwrap());
+        assert!(dest.join("plugin.cfg").is_file());
+    }
+}
@@ -0,0 +1,131 @@
+use crate::config::{AppConfig, DefaultAppConfig};
+use crate::error::{ErrorKind, GdmError};
+
+use anyhow::{Result, bail};
+use std::collections::HashMap;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+/// Runs the command given with --credential-helper or GDM_CREDENTIAL_HELPER to get a token for a
+/// host, e.g. `my-secrets gdm-token` runs `my-secrets gdm-token github.com` and reads the token
+/// from its output. Tokens are asked once per host and run.
+#[derive(Debug, Clone)]
+pub struct CredentialHelper {
+    command: String,
+    tokens: Arc<Mutex<HashMap<String, Option<String>>>>,
+}
+
+impl CredentialHelper {
+    pub fn new(command: impl Into<String>) -> CredentialHelper {
+        CredentialHelper {
+            command: command.into(),
+            tokens: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// The helper of the current configuration, None if it's not set
+    pub fn from_config(app_config: &DefaultAppConfig) -> Option<CredentialHelper> {
+        app_config
+            .get_credential_helper()
+            .map(CredentialHelper::new)
+    }
+
+    /// Token for `host`, None if the helper doesn't print one
+    pub fn token(&self, host: &str) -> Result<Option<String>> {
+        let mut tokens = self.tokens.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(token) = tokens.get(host) {
+            return Ok(token.clone());
+        }
+        let token = self.run(host)?;
+        tokens.insert(host.to_string(), token.clone());
+        Ok(token)
+    }
+
+    fn run(&self, host: &str) -> Result<Option<String>> {
+        let mut parts = self.command.split_whitespace();
+        let Some(program) = parts.next() else {
+            bail!(Self::error("The credential helper is empty".to_string()));
+        };
+        debug!("Asking the credential helper for a token for {}", host);
+        let output = Command::new(program)
+            .args(parts)
+            .arg(host)
+            .output()
+            .map_err(|e| {
+                Self::error(format!(
+                    "Failed to run credential helper {}: {}",
+                    program, e
+                ))
+            })?;
+        if !output.status.success() {
+            bail!(Self::error(format!(
+                "Credential helper {} exited with {} for {}: {}",
+                program,
+                output.status,
+                host,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        let token = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        if token.is_empty() {
+            debug!("The credential helper has no token for {}", host);
+            return Ok(None);
+        }
+        Ok(Some(token))
+    }
+
+    fn error(message: String) -> GdmError {
+        GdmError::new(ErrorKind::ConfigInvalid, message).with_hint(
+            "Check the command given with --credential-helper or GDM_CREDENTIAL_HELPER. It's run with the host as the last argument and prints the token, or nothing for hosts it doesn't know.",
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_token_should_return_first_line_of_helper_output() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let script = dir.path().join("helper.sh");
+        let calls = dir.path().join("calls");
+        std::fs::write(
+            &script,
+            format!(
+                "echo \"$1\" >> {}\nif [ \"$1\" = github.com ]; then echo secret; fi\n",
+                calls.display()
+            ),
+        )
+        .unwrap();
+        let helper = CredentialHelper::new(format!("sh {}", script.display()));
+
+        assert_eq!(
+            helper.token("github.com").unwrap(),
+            Some("secret".to_string())
+        );
+        assert_eq!(
+            helper.token("github.com").unwrap(),
+            Some("secret".to_string())
+        );
+        assert_eq!(helper.token("gitlab.com").unwrap(), None);
+        assert_eq!(
+            std::fs::read_to_string(calls).unwrap(),
+            "github.com\ngitlab.com\n"
+        );
+    }
+
+    #[test]
+    fn test_token_with_missing_helper_should_return_err() {
+        let helper = CredentialHelper::new("gdm-missing-credential-helper");
+
+        assert!(helper.token("github.com").is_err());
+    }
+}
@@ -1,13 +1,16 @@
 use crate::config::{AppConfig, DefaultAppConfig};
+use crate::error::{ErrorKind, GdmError};
+use crate::ui::ProgressTask;
 use anyhow::{Context, Result};
-use indicatif::ProgressBar;
 use std::fs;
 use std::io;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use crate::api::Asset;
 use crate::services::{DefaultFileService, FileService};
+use crate::utils::Utils;
 
 pub struct DefaultExtractService {
     pub file_service: Box<dyn FileService + Send + Sync + 'static>,
@@ -55,6 +58,54 @@ impl DefaultExtractService {
             }
         }
     }
+
+    /// Whether any file of the archive would be extracted into a plugin folder
+    /// under `destination`, rather than straight into it
+    fn has_plugin_folder<R: io::Read + io::Seek>(
+        archive: &mut zip::ZipArchive<R>,
+        addons_folder_path: &Path,
+        destination: &Path,
+    ) -> Result<bool> {
+        for i in 0..archive.len() {
+            let file = archive.by_index(i)?;
+            if file.is_dir() {
+                continue;
+            }
+            let outpath = Self::create_extract_path(
+                addons_folder_path.to_path_buf(),
+                destination.to_path_buf(),
+                file.enclosed_name(),
+            );
+            if outpath.is_some_and(|path| {
+                path.strip_prefix(destination)
+                    .is_ok_and(|path| path.components().count() > 1)
+            }) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Whether all entries of the archive are inside one root folder
+    fn is_wrapped<R: io::Read + io::Seek>(archive: &mut zip::ZipArchive<R>) -> Result<bool> {
+        let mut root = None;
+        for i in 0..archive.len() {
+            let file = archive.by_index(i)?;
+            let Some(path) = file.enclosed_name() else {
+                continue;
+            };
+            let first = path.iter().next().map(|first| first.to_os_string());
+            if !file.is_dir() && path.components().count() < 2 {
+                return Ok(false);
+            }
+            match &root {
+                None => root = first,
+                Some(root) if Some(root) != first.as_ref() => return Ok(false),
+                _ => {}
+            }
+        }
+        Ok(root.is_some())
+    }
 }
 
 impl Default for DefaultExtractService {
@@ -73,7 +124,8 @@ impl ExtractService for DefaultExtractService {
         &self,
         file_path: &Path,
         destination: &Path,
-        pb_task: ProgressBar,
+        root_folder: Option<String>,
+        pb_task: Arc<dyn ProgressTask>,
     ) -> Result<()> {
         let file_path = file_path.to_path_buf();
         let destination = destination.to_path_buf();
@@ -82,21 +134,45 @@ impl ExtractService for DefaultExtractService {
             let file = fs::File::open(&file_path)
                 .with_context(|| format!("Failed to open zip file: {:?}", file_path))?;
 
-            let mut archive = zip::ZipArchive::new(file)?;
+            let mut archive = zip::ZipArchive::new(file).map_err(|err| {
+                GdmError::new(
+                    ErrorKind::ArchiveInvalid,
+                    format!("Failed to read zip file {}: {}", file_path.display(), err),
+                )
+            })?;
 
             pb_task.set_length(archive.len() as u64);
 
+            let root_folder = match root_folder {
+                Some(folder)
+                    if !Self::has_plugin_folder(
+                        &mut archive,
+                        &addons_folder_path,
+                        &destination,
+                    )? =>
+                {
+                    Some((destination.join(folder), Self::is_wrapped(&mut archive)?))
+                }
+                _ => None,
+            };
+
             for i in 0..archive.len() {
                 let mut file = archive.by_index(i)?;
-                pb_task.set_position(i as u64);
-
-                let outpath = match Self::create_extract_path(
-                    addons_folder_path.clone(),
-                    destination.to_path_buf(),
-                    file.enclosed_name(),
-                ) {
-                    Some(path) => path,
-                    None => continue,
+                pb_task.bytes(1);
+
+                let outpath = match (&root_folder, file.enclosed_name()) {
+                    (Some((root_folder, true)), Some(path)) => {
+                        root_folder.join(path.iter().skip(1).collect::<PathBuf>())
+                    }
+                    (Some((root_folder, false)), Some(path)) => root_folder.join(path),
+                    (_, path) => match Self::create_extract_path(
+                        addons_folder_path.clone(),
+                        destination.to_path_buf(),
+                        path,
+                    ) {
+                        Some(path) => path,
+                        None => continue,
+                    },
                 };
 
                 if !file.is_dir() && outpath.is_dir() {
@@ -124,7 +200,7 @@ impl ExtractService for DefaultExtractService {
                     }
                 }
             }
-            pb_task.finish_and_clear();
+            pb_task.finished();
             Ok(())
         })
         .await??;
@@ -137,18 +213,24 @@ impl ExtractService for DefaultExtractService {
         &self,
         asset: &Asset,
         staging_dir: &Path,
-        pb_task: ProgressBar,
+        pb_task: Arc<dyn ProgressTask>,
     ) -> Result<PathBuf> {
         // Create addons subdirectory in staging
         let staging_addons_dir = staging_dir.join("addons");
         self.file_service.create_directory(&staging_addons_dir)?;
 
         // Extract directly to staging/addons/
-        self.extract_zip_file(&asset.file_path, &staging_addons_dir, pb_task)
-            .await?;
-
-        // Clean up the zip file
-        self.file_service.remove_file(&asset.file_path)?;
+        // The zip is kept, other plugins of the same download may still extract it. It's removed
+        // with the staging folder after installing.
+        let root_folder = Utils::sanitize_folder_name(&asset.asset_response.title)
+            .unwrap_or_else(|| format!("asset_{}", asset.asset_response.asset_id));
+        self.extract_zip_file(
+            &asset.file_path,
+            &staging_addons_dir,
+            Some(root_folder),
+            pb_task,
+        )
+        .await?;
 
         Ok(staging_dir.to_path_buf())
     }
@@ -157,11 +239,14 @@ impl ExtractService for DefaultExtractService {
 #[cfg_attr(test, mockall::automock)]
 #[async_trait::async_trait]
 pub trait ExtractService: Send + Sync + 'static {
+    /// Extracts the plugin folders of a zip file into `destination`. An archive with its files
+    /// right in its root folder, without a plugin folder, is extracted into `root_folder`.
     async fn extract_zip_file(
         &self,
         file_path: &Path,
         destination: &Path,
-        pb_task: ProgressBar,
+        root_folder: Option<String>,
+        pb_task: Arc<dyn ProgressTask>,
     ) -> Result<()>;
 
     /// Extract asset to staging directory instead of directly to addons
@@ -169,7 +254,7 @@ pub trait ExtractService: Send + Sync + 'static {
         &self,
         asset: &Asset,
         staging_dir: &Path,
-        pb_task: ProgressBar,
+        pb_task: Arc<dyn ProgressTask>,
     ) -> Result<PathBuf>;
 }
 
@@ -196,6 +281,7 @@ mod tests {
         }
     }
     use crate::services::MockDefaultFileService;
+    use crate::ui::NoopTask;
 
     use super::*;
     use serial_test::serial;
@@ -206,11 +292,12 @@ mod tests {
     #[serial]
     async fn test_extract_zip_file_with_addons_folder() {
         let extract = DefaultExtractService::default();
-        let pb_task = ProgressBar::new(5000000);
+        let pb_task = Arc::new(NoopTask::default());
         let result = extract
             .extract_zip_file(
                 Path::new("tests/mocks/zip_files/test_with_addons_folder.zip"),
                 Path::new("tests/addons"),
+                None,
                 pb_task,
             )
             .await;
@@ -222,13 +309,14 @@ mod tests {
     #[serial]
     async fn test_extract_zip_file_with_extra_addons_files() {
         let extract = DefaultExtractService::default();
-        let pb_task = ProgressBar::new(5000000);
+        let pb_task = Arc::new(NoopTask::default());
         let result = extract
             .extract_zip_file(
                 Path::new(
                     "tests/mocks/zip_files/test_with_addons_folder_with_extra_addons_files.zip",
                 ),
                 Path::new("tests/addons"),
+                None,
                 pb_task,
             )
             .await;
@@ -240,16 +328,57 @@ mod tests {
     #[serial]
     async fn test_extract_zip_file_with_root_files() {
         let extract = DefaultExtractService::default();
-        let pb_task = ProgressBar::new(5000000);
+        let pb_task = Arc::new(NoopTask::default());
         let result = extract
             .extract_zip_file(
                 Path::new("tests/mocks/zip_files/test_with_addons_folder_with_root_files.zip"),
                 Path::new("tests/addons"),
+                None,
+                pb_task,
+            )
+            .await;
+        fs::remove_dir_all("tests/addons").unwrap();
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_extract_zip_file_without_plugin_folder_should_use_root_folder() {
+        let extract = DefaultExtractService::default();
+        let pb_task = Arc::new(NoopTask::default());
+        let result = extract
+            .extract_zip_file(
+                Path::new("tests/mocks/zip_files/test_without_root_folder.zip"),
+                Path::new("tests/addons"),
+                Some("Test_Plugin".to_string()),
+                pb_task,
+            )
+            .await;
+        let extracted = Path::new("tests/addons/Test_Plugin/test.txt").is_file();
+        fs::remove_dir_all("tests/addons").unwrap();
+        assert!(result.is_ok());
+        assert!(extracted);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_extract_zip_file_with_plugin_folder_should_ignore_root_folder() {
+        let extract = DefaultExtractService::default();
+        let pb_task = Arc::new(NoopTask::default());
+        let result = extract
+            .extract_zip_file(
+                Path::new("tests/mocks/zip_files/test_with_addons_folder.zip"),
+                Path::new("tests/addons"),
+                Some("Test_Plugin".to_string()),
                 pb_task,
             )
             .await;
+        let extracted = Path::new("tests/addons/some_plugin/plugin.cfg").is_file();
+        let root_folder = Path::new("tests/addons/Test_Plugin").exists();
         fs::remove_dir_all("tests/addons").unwrap();
         assert!(result.is_ok());
+        assert!(extracted);
+        assert!(!root_folder);
     }
 
     // create_extract_path
@@ -359,7 +488,7 @@ mod tests {
             .withf(move |_asset, dir, _pb| dir == staging_dir_clone.as_path())
             .returning(|_asset, dir, _pb| Ok(dir.to_path_buf()));
 
-        let pb_task = ProgressBar::new(100);
+        let pb_task = Arc::new(NoopTask::default());
         let result = mock_extract
             .extract_asset_to_cache(&asset, &staging_dir, pb_task)
             .await;
@@ -384,7 +513,7 @@ mod tests {
             DefaultExtractService::new(Box::new(mock_file_service), DefaultAppConfig::default());
 
         let staging_dir = PathBuf::from("staging_test");
-        let pb_task = ProgressBar::new(100);
+        let pb_task = Arc::new(NoopTask::default());
         let asset = make_mock_asset("test.zip", "TestPlugin");
 
         // This will fail at extract_zip_file (opening the archive) but we've verified create_directory is called
@@ -396,7 +525,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_extract_asset_to_staging_removes_zip() {
+    async fn test_extract_asset_to_staging_keeps_zip() {
         let mut mock_extract = MockDefaultExtractService::new();
         let staging_dir = PathBuf::from("staging_test");
         let asset = make_mock_asset("test.zip", "TestPlugin");
@@ -407,13 +536,13 @@ mod tests {
             .times(1)
             .returning(|_asset, dir, _pb| Ok(dir.to_path_buf()));
 
-        let pb_task = ProgressBar::new(100);
+        let pb_task = Arc::new(NoopTask::default());
         let result = mock_extract
             .extract_asset_to_cache(&asset, &staging_dir, pb_task)
             .await;
 
         assert!(result.is_ok());
-        // The real implementation calls remove_file on the zip after extraction
-        // This is verified by the mock expectation being satisfied
+        // The real implementation leaves the zip to the staging folder cleanup,
+        // so plugins sharing a download can extract it too
     }
 }
@@ -1,8 +1,12 @@
+use crate::ui::ProgressTask;
+
 use anyhow::{Context, Result};
 use bytes::Bytes;
+use rayon::prelude::*;
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     fs::{self},
+    io::Read,
     path::{Path, PathBuf},
     sync::{Mutex, OnceLock},
 };
@@ -40,7 +44,6 @@ impl Cache for DefaultCache {
             .insert(key.to_string(), value.to_string());
     }
 
-    #[cfg(test)]
     fn clear(&self) {
         self.cache.lock().unwrap().clear();
     }
@@ -50,25 +53,69 @@ pub trait Cache {
     fn has_key(&self, key: &str) -> bool;
     fn get(&self, key: &str) -> Option<String>;
     fn insert(&self, key: &str, value: &str);
-    #[cfg(test)]
-    #[allow(dead_code)]
     fn clear(&self);
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct DefaultFileService;
 
+/// Files whose content changed during this run
+static MODIFIED_FILES: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+impl DefaultFileService {
+    /// Files written with different content during this run, e.g. for the end-of-run summary
+    pub fn modified_files() -> BTreeSet<PathBuf> {
+        MODIFIED_FILES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Returns which of `paths` were modified and forgets them, so the next command
+    /// in the same process only reports its own changes
+    pub fn take_modified_files(paths: &[&Path]) -> Vec<PathBuf> {
+        let mut modified_files = MODIFIED_FILES.lock().unwrap_or_else(|e| e.into_inner());
+        paths
+            .iter()
+            .filter(|path| modified_files.remove(**path))
+            .map(|path| path.to_path_buf())
+            .collect()
+    }
+
+    /// Whether the Godot editor writes the file itself, e.g. `main.gd.uid` or `icon.svg.import`
+    fn is_editor_generated(path: &Path) -> bool {
+        matches!(
+            path.extension().and_then(|extension| extension.to_str()),
+            Some("uid" | "import")
+        )
+    }
+
+    /// Content of a file as gdm last read or wrote it since `clear_cache`, without reading it again
+    pub fn cached_content(file_path: &Path) -> Option<String> {
+        DefaultCache::new().get(&Self::cache_key(file_path))
+    }
+
+    /// Forgets the cached file contents, so files changed outside gdm are read again
+    pub fn clear_cache() {
+        DefaultCache::new().clear();
+    }
+
+    /// Cache key of a file, absolute so that relative paths of different projects don't collide
+    fn cache_key(file_path: &Path) -> String {
+        std::path::absolute(file_path)
+            .unwrap_or_else(|_| file_path.to_path_buf())
+            .to_string_lossy()
+            .to_string()
+    }
+}
+
 #[cfg_attr(test, mockall::automock)]
 #[async_trait::async_trait]
 impl FileService for DefaultFileService {
     fn read_file_cached(&self, file_path: &Path) -> Result<String> {
         debug!("Reading file with cache: {}", file_path.display());
         let cache = DefaultCache::new();
-        let path = file_path
-            .to_path_buf()
-            .into_os_string()
-            .into_string()
-            .unwrap();
+        let path = Self::cache_key(file_path);
         if cache.has_key(&path) {
             debug!("Cache hit for key: {}", path);
             return Ok(cache.get(&path).unwrap().clone());
@@ -89,8 +136,16 @@ impl FileService for DefaultFileService {
 
     fn write_file(&self, file_path: &Path, content: &str) -> Result<()> {
         debug!("Writing file: {}", file_path.display());
+        let changed = fs::read_to_string(file_path).map_or(true, |previous| previous != content);
         std::fs::write(file_path, content)
             .with_context(|| format!("Failed to write file: {}", file_path.display()))?;
+        DefaultCache::new().insert(&Self::cache_key(file_path), content);
+        if changed {
+            MODIFIED_FILES
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(file_path.to_path_buf());
+        }
         Ok(())
     }
 
@@ -172,6 +227,168 @@ impl FileService for DefaultFileService {
         fs::read_dir(dir_path)
             .with_context(|| format!("Failed to read directory: {}", dir_path.display()))
     }
+
+    /// Returns the SHA-256 of a file as a lowercase hex string
+    fn sha256_file(&self, file_path: &Path) -> Result<String> {
+        debug!("Hashing file: {}", file_path.display());
+        let mut file = fs::File::open(file_path)
+            .with_context(|| format!("Failed to open file: {}", file_path.display()))?;
+        let mut context = ring::digest::Context::new(&ring::digest::SHA256);
+        let mut buffer = [0; 8192];
+        loop {
+            let read = file.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            context.update(&buffer[..read]);
+        }
+        Ok(context
+            .finish()
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect())
+    }
+
+    /// Returns the SHA-256 over the relative paths and contents of all files in a directory,
+    /// visited in sorted order so it's the same on every platform. `progress` is lengthened by
+    /// the number of files and advanced by one per hashed file.
+    /// The `.uid` and `.import` files the Godot editor writes next to scripts and resources are
+    /// left out, so opening the project doesn't change the hash.
+    fn sha256_dir(&self, dir_path: &Path, progress: &dyn ProgressTask) -> Result<String> {
+        debug!("Hashing directory: {}", dir_path.display());
+        let mut files = Vec::new();
+        let mut pending = vec![dir_path.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            for entry in self.read_dir(&dir)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else if !Self::is_editor_generated(&path) {
+                    files.push(path);
+                }
+            }
+        }
+        let mut relative_files = files
+            .into_iter()
+            .map(|path| {
+                let relative_path = path
+                    .strip_prefix(dir_path)?
+                    .iter()
+                    .map(|part| part.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                anyhow::Ok((relative_path, path))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        relative_files.sort();
+
+        // Addons can have thousands of files, so they're hashed in parallel
+        progress.set_length(progress.position() + relative_files.len() as u64);
+        let hashes: Vec<_> = relative_files
+            .par_iter()
+            .map(|(_, path)| {
+                let hash = self.sha256_file(path);
+                progress.bytes(1);
+                hash
+            })
+            .collect();
+        let mut context = ring::digest::Context::new(&ring::digest::SHA256);
+        for ((relative_path, _), hash) in relative_files.iter().zip(hashes) {
+            context.update(relative_path.as_bytes());
+            context.update(&[0]);
+            context.update(hash?.as_bytes());
+            context.update(b"\n");
+        }
+        Ok(context
+            .finish()
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect())
+    }
+
+    /// Copies a directory and its subdirectories, cloning the files where the file system
+    /// supports it, see [`FileService::clone_file`]
+    fn copy_dir(&self, from: &Path, to: &Path) -> Result<()> {
+        debug!("Copying directory {} to {}", from.display(), to.display());
+        let mut pending = vec![(from.to_path_buf(), to.to_path_buf())];
+        while let Some((from, to)) = pending.pop() {
+            fs::create_dir_all(&to)
+                .with_context(|| format!("Failed to create directory: {}", to.display()))?;
+            for entry in self.read_dir(&from)? {
+                let entry = entry?;
+                let target = to.join(entry.file_name());
+                if entry.path().is_dir() {
+                    pending.push((entry.path(), target));
+                } else {
+                    self.clone_file(&entry.path(), &target)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Copies a file as a clone sharing its data, which takes no extra space until either copy is
+    /// edited. Linux clones with FICLONE on Btrfs and XFS, and `std::fs::copy` clones with
+    /// clonefile on APFS. Elsewhere, or across file systems, the file is copied.
+    fn clone_file(&self, from: &Path, to: &Path) -> Result<()> {
+        #[cfg(target_os = "linux")]
+        {
+            let source = fs::File::open(from)
+                .with_context(|| format!("Failed to open file: {}", from.display()))?;
+            let target = fs::File::create(to)
+                .with_context(|| format!("Failed to create file: {}", to.display()))?;
+            if rustix::fs::ioctl_ficlone(&target, &source).is_ok() {
+                target.set_permissions(source.metadata()?.permissions())?;
+                return Ok(());
+            }
+        }
+        fs::copy(from, to)
+            .with_context(|| format!("Failed to copy {} to {}", from.display(), to.display()))?;
+        Ok(())
+    }
+
+    /// Moves a directory, copying it when it's moved to another file system
+    fn move_dir(&self, from: &Path, to: &Path) -> Result<()> {
+        match fs::rename(from, to) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+                debug!(
+                    "{} and {} are on different file systems, copying instead",
+                    from.display(),
+                    to.display()
+                );
+                self.copy_dir(from, to)?;
+                self.remove_dir_all(from)?;
+            }
+            Err(e) => {
+                return Err(e).with_context(|| {
+                    format!("Failed to move {} to {}", from.display(), to.display())
+                });
+            }
+        }
+        info!("Moved {} to {}", from.display(), to.display());
+        Ok(())
+    }
+
+    /// Total size in bytes of the files in a directory and its subdirectories
+    fn dir_size(&self, dir_path: &Path) -> Result<u64> {
+        let mut size = 0;
+        let mut pending = vec![dir_path.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            for entry in self.read_dir(&dir)? {
+                let entry = entry?;
+                let metadata = entry.metadata()?;
+                if metadata.is_dir() {
+                    pending.push(entry.path());
+                } else {
+                    size += metadata.len();
+                }
+            }
+        }
+        Ok(size)
+    }
 }
 
 #[async_trait::async_trait]
@@ -188,11 +405,18 @@ pub trait FileService: Send + Sync + 'static {
     fn find_plugin_cfg_file_greedy(&self, dir: &Path) -> Result<Option<PathBuf>>;
     fn rename(&self, from: &Path, to: &Path) -> Result<()>;
     fn read_dir(&self, dir_path: &Path) -> Result<fs::ReadDir>;
+    fn sha256_file(&self, file_path: &Path) -> Result<String>;
+    fn sha256_dir(&self, dir_path: &Path, progress: &dyn ProgressTask) -> Result<String>;
+    fn dir_size(&self, dir_path: &Path) -> Result<u64>;
+    fn copy_dir(&self, from: &Path, to: &Path) -> Result<()>;
+    fn clone_file(&self, from: &Path, to: &Path) -> Result<()>;
+    fn move_dir(&self, from: &Path, to: &Path) -> Result<()>;
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ui::NoopTask;
     use serial_test::serial;
 
     // Tests for DefaultCache
@@ -249,6 +473,193 @@ mod tests {
         std::fs::remove_file(test_file_path).unwrap();
     }
 
+    #[test]
+    #[serial]
+    fn test_write_file_should_record_only_changed_files() {
+        let file_service = DefaultFileService;
+        let changed_path = Path::new("tests/mocks/test_write_changed.txt");
+        let unchanged_path = Path::new("tests/mocks/test_write_unchanged.txt");
+        std::fs::write(unchanged_path, "Same content").unwrap();
+
+        file_service
+            .write_file(changed_path, "New content")
+            .unwrap();
+        file_service
+            .write_file(unchanged_path, "Same content")
+            .unwrap();
+
+        let modified_files = DefaultFileService::modified_files();
+        assert!(modified_files.contains(changed_path));
+        assert!(!modified_files.contains(unchanged_path));
+
+        std::fs::remove_file(changed_path).unwrap();
+        std::fs::remove_file(unchanged_path).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_write_file_should_update_read_cache() {
+        let file_service = DefaultFileService;
+        let test_file_path = Path::new("tests/mocks/test_write_cached.txt");
+        file_service.write_file(test_file_path, "First").unwrap();
+        assert_eq!(
+            file_service.read_file_cached(test_file_path).unwrap(),
+            "First"
+        );
+
+        file_service.write_file(test_file_path, "Second").unwrap();
+        assert_eq!(
+            file_service.read_file_cached(test_file_path).unwrap(),
+            "Second"
+        );
+
+        std::fs::remove_file(test_file_path).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_take_modified_files_should_forget_taken_files() {
+        let file_service = DefaultFileService;
+        let test_file_path = Path::new("tests/mocks/test_take_modified.txt");
+        let _ = std::fs::remove_file(test_file_path);
+        file_service.write_file(test_file_path, "Content").unwrap();
+
+        let taken = DefaultFileService::take_modified_files(&[test_file_path]);
+        assert_eq!(taken, vec![test_file_path.to_path_buf()]);
+        assert!(DefaultFileService::take_modified_files(&[test_file_path]).is_empty());
+
+        std::fs::remove_file(test_file_path).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_sha256_dir_should_change_with_content() {
+        let file_service = DefaultFileService;
+        let dir = temp_dir::TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("scripts")).unwrap();
+        std::fs::write(dir.path().join("plugin.cfg"), "version=\"1.0\"").unwrap();
+        std::fs::write(dir.path().join("scripts/main.gd"), "extends Node").unwrap();
+
+        let progress = NoopTask::default();
+        let first = file_service.sha256_dir(dir.path(), &progress).unwrap();
+        assert_eq!(progress.position(), 2);
+        assert_eq!(
+            file_service.sha256_dir(dir.path(), &progress).unwrap(),
+            first
+        );
+
+        std::fs::write(dir.path().join("scripts/main.gd"), "extends Node2D").unwrap();
+        assert_ne!(
+            file_service.sha256_dir(dir.path(), &progress).unwrap(),
+            first
+        );
+    }
+
+    #[test]
+    fn test_sha256_dir_should_ignore_editor_generated_files() {
+        let file_service = DefaultFileService;
+        let dir = temp_dir::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("main.gd"), "extends Node").unwrap();
+        let progress = NoopTask::default();
+        let first = file_service.sha256_dir(dir.path(), &progress).unwrap();
+
+        std::fs::write(dir.path().join("main.gd.uid"), "uid://b1").unwrap();
+        std::fs::write(dir.path().join("icon.svg.import"), "[remap]").unwrap();
+
+        assert_eq!(
+            file_service.sha256_dir(dir.path(), &progress).unwrap(),
+            first
+        );
+    }
+
+    #[test]
+    fn test_dir_size_should_include_subdirectories() {
+        let file_service = DefaultFileService;
+        let dir = temp_dir::TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("scripts")).unwrap();
+        std::fs::write(dir.path().join("plugin.cfg"), "12345").unwrap();
+        std::fs::write(dir.path().join("scripts/main.gd"), "1234567890").unwrap();
+
+        assert_eq!(file_service.dir_size(dir.path()).unwrap(), 15);
+    }
+
+    #[test]
+    fn test_copy_dir_should_copy_subdirectories() {
+        let file_service = DefaultFileService;
+        let dir = temp_dir::TempDir::new().unwrap();
+        let from = dir.path().join("staging/gut");
+        let to = dir.path().join("addons/gut");
+        std::fs::create_dir_all(from.join("scripts")).unwrap();
+        std::fs::write(from.join("plugin.cfg"), "[plugin]").unwrap();
+        std::fs::write(from.join("scripts/main.gd"), "extends Node").unwrap();
+
+        file_service.copy_dir(&from, &to).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(to.join("scripts/main.gd")).unwrap(),
+            "extends Node"
+        );
+        assert_eq!(
+            file_service
+                .sha256_dir(&from, &NoopTask::default())
+                .unwrap(),
+            file_service.sha256_dir(&to, &NoopTask::default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_move_dir_should_remove_source() {
+        let file_service = DefaultFileService;
+        let dir = temp_dir::TempDir::new().unwrap();
+        let from = dir.path().join("staging/gut");
+        let to = dir.path().join("gut");
+        std::fs::create_dir_all(&from).unwrap();
+        std::fs::write(from.join("plugin.cfg"), "[plugin]").unwrap();
+
+        file_service.move_dir(&from, &to).unwrap();
+
+        assert!(!from.exists());
+        assert!(to.join("plugin.cfg").is_file());
+    }
+
+    #[test]
+    fn test_move_dir_onto_existing_folder_should_keep_source() {
+        let file_service = DefaultFileService;
+        let dir = temp_dir::TempDir::new().unwrap();
+        let from = dir.path().join("staging/gut");
+        let to = dir.path().join("gut");
+        std::fs::create_dir_all(&from).unwrap();
+        std::fs::write(from.join("plugin.cfg"), "[plugin]").unwrap();
+        std::fs::create_dir_all(&to).unwrap();
+        std::fs::write(to.join("local.gd"), "extends Node").unwrap();
+
+        assert!(file_service.move_dir(&from, &to).is_err());
+
+        assert!(from.join("plugin.cfg").is_file());
+        assert!(!to.join("plugin.cfg").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_clone_file_should_keep_content_and_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let file_service = DefaultFileService;
+        let dir = temp_dir::TempDir::new().unwrap();
+        let from = dir.path().join("tool.sh");
+        let to = dir.path().join("copy.sh");
+        std::fs::write(&from, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&from, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        file_service.clone_file(&from, &to).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&to).unwrap(), "#!/bin/sh\n");
+        assert_eq!(
+            std::fs::metadata(&to).unwrap().permissions().mode() & 0o777,
+            0o755
+        );
+    }
+
     // Tests for new rename and read_dir methods
 
     #[test]
@@ -297,6 +708,22 @@ mod tests {
         std::fs::remove_dir_all(dest_dir).unwrap();
     }
 
+    #[test]
+    #[serial]
+    fn test_sha256_file() {
+        let file_service = DefaultFileService;
+        let file_path = Path::new("tests/mocks/test_sha256.txt");
+        std::fs::write(file_path, "hello").unwrap();
+
+        let result = file_service.sha256_file(file_path);
+
+        std::fs::remove_file(file_path).unwrap();
+        assert_eq!(
+            result.unwrap(),
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+    }
+
     #[test]
     #[serial]
     fn test_rename_nonexistent_source_fails() {
@@ -0,0 +1,89 @@
+use std::path::{Path, PathBuf};
+
+/// Polls files for changes to their content, e.g. gdm.json for `gdm watch`.
+/// Missing files are watched too and count as changed when they're created or deleted.
+pub struct FileWatcher {
+    files: Vec<(PathBuf, Option<Vec<u8>>)>,
+}
+
+impl FileWatcher {
+    pub fn new(paths: Vec<PathBuf>) -> FileWatcher {
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let content = Self::read(&path);
+                (path, content)
+            })
+            .collect();
+        FileWatcher { files }
+    }
+
+    /// Files whose content differs from the last call, or from when the watcher was created
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (path, content) in &mut self.files {
+            let current = Self::read(path);
+            if current != *content {
+                *content = current;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+
+    /// The watched files
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.files.iter().map(|(path, _)| path.clone()).collect()
+    }
+
+    /// Compares `path` to `content` from now on, e.g. to what gdm wrote to it itself
+    pub fn set_seen(&mut self, path: &Path, content: Option<Vec<u8>>) {
+        if let Some((_, seen)) = self.files.iter_mut().find(|(file, _)| file == path) {
+            *seen = content;
+        }
+    }
+
+    fn read(path: &Path) -> Option<Vec<u8>> {
+        std::fs::read(path).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_should_report_modified_created_and_deleted_files() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let config = dir.path().join("gdm.json");
+        let local = dir.path().join("gdm.local.json");
+        std::fs::write(&config, "{}").unwrap();
+        let mut watcher = FileWatcher::new(vec![config.clone(), local.clone()]);
+
+        assert!(watcher.changed().is_empty());
+
+        std::fs::write(&config, "{ \"plugins\": {} }").unwrap();
+        std::fs::write(&local, "{}").unwrap();
+        assert_eq!(watcher.changed(), vec![config.clone(), local.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        std::fs::write(&config, "{ \"plugins\": {} }").unwrap();
+        std::fs::remove_file(&local).unwrap();
+        assert_eq!(watcher.changed(), vec![local]);
+    }
+
+    #[test]
+    fn test_set_seen_should_report_only_changes_after_the_seen_content() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let config = dir.path().join("gdm.json");
+        std::fs::write(&config, "{}").unwrap();
+        let mut watcher = FileWatcher::new(vec![config.clone()]);
+
+        std::fs::write(&config, "{ \"plugins\": {} }").unwrap();
+        watcher.set_seen(&config, Some(b"{ \"plugins\": {} }".to_vec()));
+        assert!(watcher.changed().is_empty());
+
+        watcher.set_seen(&config, Some(b"{}".to_vec()));
+        assert_eq!(watcher.changed(), vec![config]);
+    }
+}
@@ -3,12 +3,21 @@ use anyhow::{Context, bail};
 use gix::bstr::BString;
 use gix::bstr::ByteSlice;
 use gix::object::{Kind, tree};
+use gix::odb::pack;
+use gix::protocol::fetch::{self, Shallow, negotiate, refmap};
+use gix::protocol::transport::Service;
+use gix::protocol::transport::client::blocking_io;
 use gix::remote;
+use std::borrow::Cow;
 use std::fs;
 use std::num::NonZeroU32;
 use std::path::{Path, PathBuf};
+use tracing::debug;
 
 use crate::config::{AppConfig, DefaultAppConfig};
+use crate::error::{ErrorKind, GdmError};
+use crate::models::GitFetchOptions;
+use crate::services::{CredentialHelper, DefaultHttpService, SignatureVerifier};
 
 #[derive(Default)]
 pub struct DefaultGitService {
@@ -17,11 +26,17 @@ pub struct DefaultGitService {
 
 #[cfg_attr(test, mockall::automock)]
 pub trait GitService: Send + Sync + 'static {
+    /// Fetches `repo_ref` with as much history as `git_fetch` asks for, falling back to the global
+    /// settings, and extracts its addons folder. With `verify_signature`, the tag or commit must be
+    /// signed by a key in project.trusted_keys.
+    /// Returns the extracted repository, the id of the addons tree and the commit id.
     fn shallow_fetch_repository(
         &self,
         repo_url: &str,
         repo_ref: Option<String>,
-    ) -> Result<(PathBuf, usize)>;
+        git_fetch: GitFetchOptions,
+        verify_signature: bool,
+    ) -> Result<(PathBuf, String, String)>;
     fn extract_tree<'a>(
         &self,
         repo: &gix::Repository,
@@ -30,6 +45,12 @@ pub trait GitService: Send + Sync + 'static {
         file_count: &mut usize,
     ) -> Result<()>;
     fn extract_repo_name_from_src(&self, src: &Path) -> Result<String>;
+    /// Branches and tags of a remote repository as `(full ref name, commit id)`, without fetching any objects
+    fn list_remote_refs(&self, repo_url: &str) -> Result<Vec<(String, String)>>;
+    /// Id of the addons tree at `repo_ref`, fetching only the latest commit and extracting nothing
+    fn fetch_addons_tree_id(&self, repo_url: &str, repo_ref: &str) -> Result<String>;
+    /// Configuration repositories are fetched with, e.g. the credential helper and URL rewrites
+    fn app_config(&self) -> &DefaultAppConfig;
 }
 
 #[cfg_attr(test, mockall::automock)]
@@ -38,59 +59,32 @@ impl GitService for DefaultGitService {
         &self,
         repo_url: &str,
         repo_ref: Option<String>,
-    ) -> Result<(PathBuf, usize)> {
+        git_fetch: GitFetchOptions,
+        verify_signature: bool,
+    ) -> Result<(PathBuf, String, String)> {
         let target_ref = repo_ref.unwrap_or("main".into());
-        let cache_folder = self.app_config.get_cache_folder_path();
         let addon_folder = self.app_config.get_addon_folder_path();
-
-        let url = gix::url::parse(repo_url.into())?;
-        let repo_name = url.path.to_path().unwrap().file_stem().unwrap();
-        let dst = cache_folder.join(repo_name);
-
-        if dst.exists() {
-            fs::remove_dir_all(&dst)?;
+        let git_fetch = git_fetch.or(self.app_config.get_git_fetch());
+        let (repo, dst, tip) = self.fetch_addons(repo_url, &target_ref, &git_fetch, true)?;
+        if verify_signature {
+            self.verify_signature(&repo, &dst, tip, repo_url, &target_ref)?;
         }
-        fs::create_dir_all(&dst)?;
-
-        let mut repo = gix::init(&dst)?;
-
-        // Set a generic fallback committer to avoid errors when no user identity is configured
-        // This is required by gitoxide when updating references during fetch operations
-        repo.committer_or_set_generic_fallback()?;
-
-        let mut remote = repo.remote_at(url)?;
 
-        remote.replace_refspecs(
-            std::iter::once(BString::from(format!("{}:{}", target_ref, target_ref))),
-            remote::Direction::Fetch,
-        )?;
-
-        let connection = remote.connect(remote::Direction::Fetch)?;
-        let prepare_fetch = connection
-            .prepare_fetch(gix::progress::Discard, remote::ref_map::Options::default())?;
-
-        let _outcome = prepare_fetch
-            .with_shallow(remote::fetch::Shallow::DepthAtRemote(
-                NonZeroU32::new(1).unwrap(),
-            ))
-            .receive(gix::progress::Discard, &gix::interrupt::IS_INTERRUPTED)?;
-
-        let mut reference = repo.find_reference(&target_ref)?;
-        let commit = reference.peel_to_commit()?;
+        let commit = repo.find_object(tip)?.peel_to_commit()?;
+        let commit_id = commit.id.to_string();
         let tree = commit.tree()?;
         let dst_addons_path = dst.join("addons");
         let mut file_count = 0;
-        if let Some(addons_entry) = tree.find_entry(addon_folder.to_str().unwrap()) {
+        let tree_id = if let Some(addons_entry) = tree.find_entry(addon_folder.to_str().unwrap()) {
             let addons_tree = repo.find_object(addons_entry.oid())?.into_tree();
             self.extract_tree(&repo, &addons_tree, &dst_addons_path, &mut file_count)?;
+            addons_entry.oid().to_string()
         } else {
-            bail!(format!(
-                "Warning: No '{:?}' folder found in this commit.",
-                addon_folder
-            ));
-        }
+            bail!(Self::missing_addons_folder_error(&addon_folder));
+        };
+        debug!("Extracted {} files from {}", file_count, repo_url);
 
-        Ok((dst, file_count))
+        Ok((dst, tree_id, commit_id))
     }
 
     fn extract_tree<'a>(
@@ -134,4 +128,664 @@ impl GitService for DefaultGitService {
             .map(|s| s.to_string())
             .context("Failed to convert main plugin folder to string")
     }
+
+    fn list_remote_refs(&self, repo_url: &str) -> Result<Vec<(String, String)>> {
+        let url = self.parse_url(repo_url)?;
+        // Connecting to a remote needs a repository, even if nothing is fetched into it
+        let dst = self
+            .app_config
+            .get_cache_folder_path()
+            .join(format!("{}.refs", Self::repo_name(&url)?));
+        if dst.exists() {
+            fs::remove_dir_all(&dst)?;
+        }
+        let repo = gix::init_bare(&dst)?;
+        let credentials = self.credentials(&url)?;
+        let mut remote = repo.remote_at(url)?;
+        remote.replace_refspecs(
+            ["+refs/heads/*:refs/heads/*", "+refs/tags/*:refs/tags/*"],
+            remote::Direction::Fetch,
+        )?;
+
+        let mut connection = remote.connect(remote::Direction::Fetch)?;
+        if let Some(credentials) = credentials {
+            connection = connection.with_credentials(credentials);
+        }
+        let (ref_map, _handshake) =
+            connection.ref_map(gix::progress::Discard, remote::ref_map::Options::default())?;
+        fs::remove_dir_all(&dst)?;
+
+        let refs = ref_map
+            .remote_refs
+            .iter()
+            .filter_map(|remote_ref| match remote_ref.unpack() {
+                (name, Some(target), peeled) => {
+                    Some((name.to_string(), peeled.unwrap_or(target).to_string()))
+                }
+                _ => None,
+            })
+            .collect();
+        Ok(refs)
+    }
+
+    fn fetch_addons_tree_id(&self, repo_url: &str, repo_ref: &str) -> Result<String> {
+        let addon_folder = self.app_config.get_addon_folder_path();
+        let (repo, dst, tip) =
+            self.fetch_addons(repo_url, repo_ref, &GitFetchOptions::default(), false)?;
+
+        let tree_id = {
+            let tree = repo.find_object(tip)?.peel_to_commit()?.tree()?;
+            match tree.find_entry(addon_folder.to_str().unwrap()) {
+                Some(addons_entry) => addons_entry.oid().to_string(),
+                None => bail!(Self::missing_addons_folder_error(&addon_folder)),
+            }
+        };
+        drop(repo);
+        fs::remove_dir_all(&dst)?;
+        Ok(tree_id)
+    }
+
+    fn app_config(&self) -> &DefaultAppConfig {
+        &self.app_config
+    }
+}
+
+impl DefaultGitService {
+    /// Fetches `target_ref` with just the objects under the addons folder where the server
+    /// supports partial clone filters, and whole commits otherwise. Blobs are left out unless
+    /// `with_blobs` is set. Returns the repository, its folder and the id of the fetched tag or commit.
+    fn fetch_addons(
+        &self,
+        repo_url: &str,
+        target_ref: &str,
+        git_fetch: &GitFetchOptions,
+        with_blobs: bool,
+    ) -> Result<(gix::Repository, PathBuf, gix::ObjectId)> {
+        match self.partial_fetch_reference(repo_url, target_ref, git_fetch, with_blobs) {
+            Ok(Some(fetched)) => return Ok(fetched),
+            // Fetching everything wouldn't find a missing ref or addons folder either
+            Err(e) if GdmError::find(&e).is_some() => return Err(e),
+            Ok(None) => debug!(
+                "{} doesn't support partial clone filters, fetching the whole commit",
+                repo_url
+            ),
+            Err(e) => debug!(
+                "Partial fetch from {} failed, fetching the whole commit: {}",
+                repo_url, e
+            ),
+        }
+        let (repo, dst) = self.fetch_reference(repo_url, target_ref, git_fetch)?;
+        let tip = repo
+            .find_reference(target_ref)
+            .ok()
+            .and_then(|mut reference| reference.follow_to_object().ok())
+            .map(|id| id.detach());
+        let tip = Self::resolve_tip(&repo, repo_url, target_ref, tip, git_fetch)?;
+        Ok((repo, dst, tip))
+    }
+
+    /// Fetches the commits of `target_ref` and their trees without blobs, then the blobs of the
+    /// addons folder, so that large repositories don't download files outside of it.
+    /// None if the server can't filter objects.
+    #[allow(clippy::result_large_err)] // The error type is gix's
+    fn partial_fetch_reference(
+        &self,
+        repo_url: &str,
+        target_ref: &str,
+        git_fetch: &GitFetchOptions,
+        with_blobs: bool,
+    ) -> Result<Option<(gix::Repository, PathBuf, gix::ObjectId)>> {
+        let addon_folder = self.app_config.get_addon_folder_path();
+        let url = self.parse_url(repo_url)?;
+        let (repo, dst) = self.init_repository(&url)?;
+
+        let authenticate: gix::remote::AuthenticateFn<'static> = match self.credentials(&url)? {
+            Some(credentials) => Box::new(credentials),
+            None => {
+                let (mut cascade, _action, prompt_options) =
+                    repo.config_snapshot().credential_helpers(url.clone())?;
+                Box::new(move |action| cascade.invoke(action, prompt_options.clone()))
+            }
+        };
+        let mut transport = blocking_io::connect::connect(url, Default::default())?;
+        let mut handshake = gix::protocol::handshake(
+            &mut transport,
+            Service::UploadPack,
+            authenticate,
+            Vec::new(),
+            &mut gix::progress::Discard,
+        )?;
+
+        let refspecs = Self::refspecs(target_ref, git_fetch)
+            .iter()
+            .map(|refspec| {
+                gix::refspec::parse(
+                    refspec.as_str().into(),
+                    gix::refspec::parse::Operation::Fetch,
+                )
+                .map(|refspec| refspec.to_owned())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let ref_map = handshake
+            .prepare_lsrefs_or_extract_refmap(
+                Self::user_agent(),
+                true,
+                refmap::init::Context {
+                    fetch_refspecs: refspecs,
+                    extra_refspecs: Vec::new(),
+                },
+            )?
+            .fetch_blocking(gix::progress::Discard, &mut transport, false)?;
+        let mut wants: Vec<gix::ObjectId> = Vec::new();
+        for id in ref_map
+            .mappings
+            .iter()
+            .filter_map(|mapping| mapping.remote.as_id())
+        {
+            if !wants.iter().any(|want| *want == id) {
+                wants.push(id.to_owned());
+            }
+        }
+        if wants.is_empty() {
+            bail!(Self::missing_ref_error(repo_url, target_ref, git_fetch));
+        }
+        let tip = ref_map
+            .mappings
+            .iter()
+            .find(|mapping| mapping.spec_index == refmap::SpecIndex::ExplicitInRemote(0))
+            .and_then(|mapping| mapping.remote.as_id())
+            .map(|id| id.to_owned());
+
+        let shallow = Self::shallow(git_fetch);
+        if !Self::fetch_objects(
+            &repo,
+            &mut handshake,
+            &mut transport,
+            &wants,
+            Some("blob:none"),
+            &shallow,
+        )? {
+            return Ok(None);
+        }
+
+        let tip = Self::resolve_tip(&repo, repo_url, target_ref, tip, git_fetch)?;
+        if with_blobs {
+            let tree = repo.find_object(tip)?.peel_to_commit()?.tree()?;
+            let Some(addons_entry) = tree.find_entry(addon_folder.to_str().unwrap()) else {
+                bail!(Self::missing_addons_folder_error(&addon_folder));
+            };
+            let mut blob_ids = Vec::new();
+            Self::collect_blob_ids(&repo, &addons_entry.oid().to_owned(), &mut blob_ids)?;
+            if !blob_ids.is_empty()
+                && !Self::fetch_objects(
+                    &repo,
+                    &mut handshake,
+                    &mut transport,
+                    &blob_ids,
+                    None,
+                    &Shallow::NoChange,
+                )?
+            {
+                return Ok(None);
+            }
+            debug!(
+                "Fetched {} blobs of the addons folder from {}",
+                blob_ids.len(),
+                repo_url
+            );
+        }
+        gix::protocol::indicate_end_of_interaction(&mut transport, false).ok();
+
+        Ok(Some((repo, dst, tip)))
+    }
+
+    /// Fetches `wants` into the repository as a pack, applying the partial clone `filter`.
+    /// False if the server doesn't support filters.
+    fn fetch_objects(
+        repo: &gix::Repository,
+        handshake: &mut gix::protocol::Handshake,
+        transport: &mut Box<dyn blocking_io::Transport + Send>,
+        wants: &[gix::ObjectId],
+        filter: Option<&str>,
+        shallow: &Shallow,
+    ) -> Result<bool> {
+        let pack_dir = repo.objects.store_ref().path().join("pack");
+        let mut negotiate = WantObjects { wants, filter };
+        let outcome = gix::protocol::fetch(
+            &mut negotiate,
+            |reader, progress, should_interrupt| -> Result<bool, pack::bundle::write::Error> {
+                pack::Bundle::write_to_directory(
+                    reader,
+                    Some(&pack_dir),
+                    progress,
+                    should_interrupt,
+                    Some(repo.objects.clone()),
+                    pack::bundle::write::Options {
+                        thread_limit: None,
+                        index_version: pack::index::Version::default(),
+                        iteration_mode: pack::data::input::Mode::Verify,
+                        object_hash: repo.object_hash(),
+                    },
+                )?;
+                Ok(true)
+            },
+            gix::progress::Discard,
+            &gix::interrupt::IS_INTERRUPTED,
+            fetch::Context {
+                handshake,
+                transport,
+                user_agent: Self::user_agent(),
+                trace_packetlines: false,
+            },
+            fetch::Options {
+                shallow_file: repo.shallow_file(),
+                shallow,
+                tags: fetch::Tags::None,
+                reject_shallow_remote: false,
+            },
+        )?;
+        Ok(outcome.is_some())
+    }
+
+    /// Ids of the files in `tree_id` and its subtrees
+    fn collect_blob_ids(
+        repo: &gix::Repository,
+        tree_id: &gix::ObjectId,
+        blob_ids: &mut Vec<gix::ObjectId>,
+    ) -> Result<()> {
+        let tree = repo.find_tree(*tree_id)?;
+        for entry in tree.iter() {
+            let entry = entry?;
+            match entry.kind() {
+                tree::EntryKind::Blob | tree::EntryKind::BlobExecutable => {
+                    blob_ids.push(entry.oid().to_owned())
+                }
+                tree::EntryKind::Tree => {
+                    Self::collect_blob_ids(repo, &entry.oid().to_owned(), blob_ids)?
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses `repo_url` after replacing the longest prefix in the git URL rewrites of the
+    /// global config, as git does with `url.<base>.insteadOf`. Every fetch starts here, so it
+    /// also refuses to connect with --frozen.
+    fn parse_url(&self, repo_url: &str) -> Result<gix::Url> {
+        if self.app_config.get_frozen() {
+            bail!(DefaultHttpService::frozen_error(repo_url));
+        }
+        let rewrites = self.app_config.get_git_url_rewrites();
+        let rewritten = rewrites
+            .iter()
+            .filter(|(prefix, _)| repo_url.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(prefix, replacement)| format!("{}{}", replacement, &repo_url[prefix.len()..]));
+        let url = match rewritten {
+            Some(rewritten) => {
+                debug!("Rewrote {} to {}", repo_url, rewritten);
+                gix::url::parse(rewritten.as_str().into())
+                    .with_context(|| format!("Invalid rewritten git URL {}", rewritten))?
+            }
+            None => gix::url::parse(repo_url.into())?,
+        };
+        Ok(url)
+    }
+
+    /// Refspecs of `target_ref` and, with `tags`, of all tags. Abbreviated commit hashes aren't
+    /// advertised by servers, so they're looked up in the fetched history of the remote HEAD.
+    fn refspecs(target_ref: &str, git_fetch: &GitFetchOptions) -> Vec<String> {
+        let mut refspecs = if Self::is_commit_hash(target_ref) {
+            // Also matches a branch or tag named like a hash, and full hashes are sent directly
+            let mut refspecs = vec![target_ref.to_string()];
+            if target_ref.len() < 40 {
+                refspecs.push("+HEAD:refs/remotes/origin/HEAD".to_string());
+            }
+            refspecs
+        } else {
+            vec![format!("{}:{}", target_ref, target_ref)]
+        };
+        if git_fetch.tags {
+            refspecs.push("+refs/tags/*:refs/tags/*".to_string());
+        }
+        refspecs
+    }
+
+    fn shallow(git_fetch: &GitFetchOptions) -> Shallow {
+        if git_fetch.full_history {
+            Shallow::NoChange
+        } else {
+            Shallow::DepthAtRemote(git_fetch.depth.unwrap_or(NonZeroU32::MIN))
+        }
+    }
+
+    fn is_commit_hash(reference: &str) -> bool {
+        (4..=40).contains(&reference.len()) && reference.chars().all(|c| c.is_ascii_hexdigit())
+    }
+
+    /// The fetched `tip` of `target_ref`, or the commit with that hash in the fetched history
+    fn resolve_tip(
+        repo: &gix::Repository,
+        repo_url: &str,
+        target_ref: &str,
+        tip: Option<gix::ObjectId>,
+        git_fetch: &GitFetchOptions,
+    ) -> Result<gix::ObjectId> {
+        if let Some(tip) = tip {
+            return Ok(tip);
+        }
+        if Self::is_commit_hash(target_ref)
+            && let Some(Ok(id)) = repo
+                .objects
+                .lookup_prefix(gix::hash::Prefix::from_hex(target_ref)?, None)?
+        {
+            return Ok(repo.find_object(id)?.peel_to_commit()?.id);
+        }
+        bail!(Self::missing_ref_error(repo_url, target_ref, git_fetch))
+    }
+
+    fn missing_ref_error(
+        repo_url: &str,
+        target_ref: &str,
+        git_fetch: &GitFetchOptions,
+    ) -> GdmError {
+        let error = GdmError::new(
+            ErrorKind::NotFound,
+            format!(
+                "No branch, tag or commit '{}' found in {}",
+                target_ref, repo_url
+            ),
+        );
+        if !Self::is_commit_hash(target_ref) {
+            error.with_hint(format!(
+                "Check the branches and tags with git ls-remote {}",
+                repo_url
+            ))
+        } else if git_fetch.full_history {
+            error
+        } else {
+            let fetched = match git_fetch.depth.map_or(1, NonZeroU32::get) {
+                1 => "Only the latest commit is fetched".to_string(),
+                depth => format!("Only the last {} commits are fetched", depth),
+            };
+            error.with_hint(format!(
+                "{}. Add the plugin again with --depth or --full-history, or set git_fetch for it in gdm.json.",
+                fetched
+            ))
+        }
+    }
+
+    /// Checks the signature of `tip` against project.trusted_keys. A signed tag is verified
+    /// itself, otherwise the commit it points to must be signed.
+    fn verify_signature(
+        &self,
+        repo: &gix::Repository,
+        dst: &Path,
+        tip: gix::ObjectId,
+        repo_url: &str,
+        target_ref: &str,
+    ) -> Result<()> {
+        let verifier = SignatureVerifier::from_config(&self.app_config);
+        let object = repo.find_object(tip)?;
+        if object.kind == Kind::Tag
+            && let Some((payload, signature)) = SignatureVerifier::split_signed_tag(&object.data)
+        {
+            let subject = format!("Tag {} of {}", target_ref, repo_url);
+            return verifier.verify(signature, payload, dst, &subject);
+        }
+
+        let commit = object.peel_to_commit()?;
+        let subject = format!("Commit {} of {}", commit.id().shorten_or_id(), repo_url);
+        match commit.signature()? {
+            Some((signature, signed_data)) => {
+                verifier.verify(&signature, &signed_data.to_bstring(), dst, &subject)
+            }
+            None => bail!(
+                GdmError::new(
+                    ErrorKind::SignatureInvalid,
+                    format!("{} isn't signed", subject)
+                )
+                .with_hint(
+                    "Ask the author to sign their releases, or remove verify_signature from the plugin in gdm.json."
+                )
+            ),
+        }
+    }
+
+    fn user_agent() -> (&'static str, Option<Cow<'static, str>>) {
+        ("agent", Some(gix::env::agent().into()))
+    }
+
+    /// Creates an empty repository in the cache folder, replacing any previous one
+    fn init_repository(&self, url: &gix::Url) -> Result<(gix::Repository, PathBuf)> {
+        let dst = self
+            .app_config
+            .get_cache_folder_path()
+            .join(Self::repo_name(url)?);
+        if dst.exists() {
+            fs::remove_dir_all(&dst)?;
+        }
+        fs::create_dir_all(&dst)?;
+
+        let mut repo = gix::init(&dst)?;
+
+        // Set a generic fallback committer to avoid errors when no user identity is configured
+        // This is required by gitoxide when updating references during fetch operations
+        repo.committer_or_set_generic_fallback()?;
+        Ok((repo, dst))
+    }
+
+    /// Fetches the latest commit of `target_ref` into a fresh repository in the cache folder
+    fn fetch_reference(
+        &self,
+        repo_url: &str,
+        target_ref: &str,
+        git_fetch: &GitFetchOptions,
+    ) -> Result<(gix::Repository, PathBuf)> {
+        let url = self.parse_url(repo_url)?;
+        let (repo, dst) = self.init_repository(&url)?;
+
+        let credentials = self.credentials(&url)?;
+        let mut remote = repo.remote_at(url)?;
+
+        remote.replace_refspecs(
+            Self::refspecs(target_ref, git_fetch)
+                .into_iter()
+                .map(BString::from),
+            remote::Direction::Fetch,
+        )?;
+
+        let mut connection = remote.connect(remote::Direction::Fetch)?;
+        if let Some(credentials) = credentials {
+            connection = connection.with_credentials(credentials);
+        }
+        let prepare_fetch = connection
+            .prepare_fetch(gix::progress::Discard, remote::ref_map::Options::default())?;
+
+        let _outcome = prepare_fetch
+            .with_shallow(Self::shallow(git_fetch))
+            .receive(gix::progress::Discard, &gix::interrupt::IS_INTERRUPTED)?;
+
+        Ok((repo, dst))
+    }
+
+    /// Authenticates HTTP(S) remotes with the credential helper's token instead of git's own
+    /// credential helpers. None if no helper is configured or it has no token for the host.
+    #[allow(clippy::result_large_err)] // The error type is gix's
+    fn credentials(
+        &self,
+        url: &gix::Url,
+    ) -> Result<
+        Option<
+            impl FnMut(gix::credentials::helper::Action) -> gix::credentials::protocol::Result + use<>,
+        >,
+    > {
+        if !matches!(url.scheme, gix::url::Scheme::Http | gix::url::Scheme::Https) {
+            return Ok(None);
+        }
+        let (Some(credential_helper), Some(host)) =
+            (CredentialHelper::from_config(&self.app_config), url.host())
+        else {
+            return Ok(None);
+        };
+        let Some(token) = credential_helper.token(host)? else {
+            return Ok(None);
+        };
+        // GitHub, GitLab and Gitea accept tokens as the password of any user
+        let username = url.user().unwrap_or("x-access-token").to_string();
+        Ok(Some(move |action| match action {
+            gix::credentials::helper::Action::Get(context) => {
+                Ok(Some(gix::credentials::protocol::Outcome {
+                    identity: gix::sec::identity::Account {
+                        username: username.clone(),
+                        password: token.clone(),
+                        oauth_refresh_token: None,
+                    },
+                    next: context.into(),
+                }))
+            }
+            _ => Ok(None),
+        }))
+    }
+
+    fn repo_name(url: &gix::Url) -> Result<String> {
+        url.path
+            .to_path()?
+            .file_stem()
+            .map(|name| name.to_string_lossy().to_string())
+            .context("No repository name found in the URL")
+    }
+
+    fn missing_addons_folder_error(addon_folder: &Path) -> GdmError {
+        GdmError::new(
+            ErrorKind::ArchiveInvalid,
+            format!(
+                "Warning: No '{:?}' folder found in this commit.",
+                addon_folder
+            ),
+        )
+    }
+}
+
+/// Negotiates a fetch of the given objects into an empty repository, which has nothing to offer
+struct WantObjects<'a> {
+    wants: &'a [gix::ObjectId],
+    filter: Option<&'a str>,
+}
+
+impl fetch::Negotiate for WantObjects<'_> {
+    fn mark_complete_and_common_ref(&mut self) -> Result<negotiate::Action, negotiate::Error> {
+        Ok(negotiate::Action::MustNegotiate {
+            remote_ref_target_known: Vec::new(),
+        })
+    }
+
+    fn add_wants(&mut self, arguments: &mut fetch::Arguments, _known: &[bool]) -> bool {
+        if let Some(filter) = self.filter {
+            if !arguments.can_use_filter() {
+                return false;
+            }
+            arguments.filter(filter);
+        }
+        for id in self.wants {
+            arguments.want(id);
+        }
+        true
+    }
+
+    fn one_round(
+        &mut self,
+        _state: &mut negotiate::one_round::State,
+        _arguments: &mut fetch::Arguments,
+        _previous_response: Option<&fetch::Response>,
+    ) -> Result<(negotiate::Round, bool), negotiate::Error> {
+        let round = negotiate::Round {
+            haves_sent: 0,
+            in_vain: 0,
+            haves_to_send: 0,
+            previous_response_had_at_least_one_in_common: false,
+        };
+        Ok((round, true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_refspecs_should_look_up_abbreviated_commits_in_head() {
+        let tags = GitFetchOptions {
+            tags: true,
+            ..GitFetchOptions::default()
+        };
+
+        assert_eq!(
+            DefaultGitService::refspecs("main", &GitFetchOptions::default()),
+            vec!["main:main"]
+        );
+        assert_eq!(
+            DefaultGitService::refspecs("a1b2c3d", &tags),
+            vec![
+                "a1b2c3d",
+                "+HEAD:refs/remotes/origin/HEAD",
+                "+refs/tags/*:refs/tags/*"
+            ]
+        );
+        let commit = "d05b5317f25df257222dc2047516f8759245a43a";
+        assert_eq!(
+            DefaultGitService::refspecs(commit, &GitFetchOptions::default()),
+            vec![commit]
+        );
+    }
+
+    #[test]
+    fn test_parse_url_should_apply_longest_matching_rewrite() {
+        let git_service = DefaultGitService {
+            app_config: DefaultAppConfig::default().with_git_url_rewrites(BTreeMap::from([
+                (
+                    "https://github.com/".to_string(),
+                    "https://mirror.example.com/github/".to_string(),
+                ),
+                (
+                    "https://github.com/internal/".to_string(),
+                    "ssh://git@git.example.com/".to_string(),
+                ),
+            ])),
+        };
+
+        let url = git_service
+            .parse_url("https://github.com/bitwes/Gut.git")
+            .unwrap();
+        assert_eq!(
+            url.to_bstring(),
+            "https://mirror.example.com/github/bitwes/Gut.git"
+        );
+        let url = git_service
+            .parse_url("https://github.com/internal/tools.git")
+            .unwrap();
+        assert_eq!(url.to_bstring(), "ssh://git@git.example.com/tools.git");
+        let url = git_service
+            .parse_url("https://gitlab.com/user/repo.git")
+            .unwrap();
+        assert_eq!(url.to_bstring(), "https://gitlab.com/user/repo.git");
+    }
+
+    #[test]
+    fn test_missing_commit_should_hint_at_depth() {
+        let error = DefaultGitService::missing_ref_error(
+            "https://github.com/user/repo.git",
+            "a1b2c3d",
+            &GitFetchOptions::default(),
+        );
+        assert_eq!(error.kind, ErrorKind::NotFound);
+        assert!(
+            error
+                .hint()
+                .unwrap()
+                .starts_with("Only the latest commit is fetched.")
+        );
+    }
 }
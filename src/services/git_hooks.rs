@@ -0,0 +1,291 @@
+use crate::error::{ErrorKind, GdmError};
+
+use anyhow::{Context, Result, bail};
+use gix::bstr::ByteSlice;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+const BLOCK_START: &str = "# >>> gdm >>>";
+const BLOCK_END: &str = "# <<< gdm <<<";
+
+/// Git hooks `gdm hook install` writes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GitHook {
+    /// Validates the configuration file before committing
+    PreCommit,
+    /// Installs the plugins after pulling, so changes of teammates are picked up
+    PostMerge,
+}
+
+impl GitHook {
+    pub const ALL: [GitHook; 2] = [GitHook::PreCommit, GitHook::PostMerge];
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            GitHook::PreCommit => "pre-commit",
+            GitHook::PostMerge => "post-merge",
+        }
+    }
+
+    fn command(&self) -> &'static str {
+        match self {
+            GitHook::PreCommit => "validate",
+            GitHook::PostMerge => "install",
+        }
+    }
+}
+
+/// What `install` or `uninstall` did to a hook file
+#[derive(Debug, Clone, PartialEq)]
+pub enum HookChange {
+    Created(PathBuf),
+    /// gdm's block was added to or removed from a hook that also runs other commands
+    Updated(PathBuf),
+    Removed(PathBuf),
+    Unchanged(PathBuf),
+}
+
+/// Adds gdm's commands to the git hooks of the repository the project is in. The commands are
+/// kept in a marked block, so existing hooks keep working and the block can be removed again.
+pub struct GitHooks {
+    hooks_dir: PathBuf,
+    /// Project folder relative to the repository root, passed to gdm with --project
+    project_dir: PathBuf,
+}
+
+impl GitHooks {
+    pub fn new(hooks_dir: impl Into<PathBuf>, project_dir: impl Into<PathBuf>) -> GitHooks {
+        GitHooks {
+            hooks_dir: hooks_dir.into(),
+            project_dir: project_dir.into(),
+        }
+    }
+
+    /// Finds the hooks folder of the repository `project_dir` is in, respecting core.hooksPath
+    pub fn discover(project_dir: &Path) -> Result<GitHooks> {
+        let project_dir = std::path::absolute(project_dir)?;
+        let repo = gix::discover(&project_dir).map_err(|e| {
+            debug!("Failed to discover git repository: {}", e);
+            GdmError::new(
+                ErrorKind::ProjectFile,
+                format!("{} isn't in a git repository", project_dir.display()),
+            )
+            .with_hint("Run git init first, or run the command inside the repository.")
+        })?;
+        let Some(workdir) = repo.workdir() else {
+            bail!(GdmError::new(
+                ErrorKind::ProjectFile,
+                "Git hooks can't be installed in a bare repository",
+            ));
+        };
+        let workdir = std::path::absolute(workdir)?;
+        let hooks_dir = match repo.config_snapshot().string("core.hooksPath") {
+            Some(path) => workdir.join(path.to_path_lossy()),
+            None => repo.common_dir().join("hooks"),
+        };
+        let relative_project_dir = project_dir
+            .strip_prefix(&workdir)
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        Ok(GitHooks::new(hooks_dir, relative_project_dir))
+    }
+
+    pub fn install(&self) -> Result<Vec<HookChange>> {
+        std::fs::create_dir_all(&self.hooks_dir).with_context(|| {
+            format!(
+                "Failed to create git hooks folder: {}",
+                self.hooks_dir.display()
+            )
+        })?;
+        GitHook::ALL
+            .iter()
+            .map(|hook| self.install_hook(*hook))
+            .collect()
+    }
+
+    pub fn uninstall(&self) -> Result<Vec<HookChange>> {
+        GitHook::ALL
+            .iter()
+            .map(|hook| self.uninstall_hook(*hook))
+            .collect()
+    }
+
+    fn install_hook(&self, hook: GitHook) -> Result<HookChange> {
+        let path = self.hooks_dir.join(hook.file_name());
+        let block = self.block(hook);
+        let Some(existing) = Self::read_hook(&path)? else {
+            Self::write_hook(&path, &format!("#!/bin/sh\n\n{}", block))?;
+            return Ok(HookChange::Created(path));
+        };
+
+        let (shebang, rest) = match existing.split_once('\n') {
+            Some((first, rest)) if first.starts_with("#!") => (Some(first), rest),
+            _ if existing.starts_with("#!") => (Some(existing.as_str()), ""),
+            _ => (None, existing.as_str()),
+        };
+        if shebang.is_some_and(|shebang| !shebang.trim_end().ends_with("sh")) {
+            bail!(
+                GdmError::new(
+                    ErrorKind::Conflict,
+                    format!("{} isn't a shell script", path.display())
+                )
+                .with_hint(format!(
+                    "Run gdm {} from the hook yourself.",
+                    hook.command()
+                ))
+            );
+        }
+        // Run first, so hooks that end with exit or exec still run gdm
+        let rest = Self::without_block(rest);
+        let rest = rest.trim_start_matches('\n');
+        let mut content = format!("{}\n\n{}", shebang.unwrap_or("#!/bin/sh"), block);
+        if !rest.is_empty() {
+            content = format!("{}\n{}", content, rest);
+        }
+        if content == existing {
+            return Ok(HookChange::Unchanged(path));
+        }
+        Self::write_hook(&path, &content)?;
+        Ok(HookChange::Updated(path))
+    }
+
+    fn uninstall_hook(&self, hook: GitHook) -> Result<HookChange> {
+        let path = self.hooks_dir.join(hook.file_name());
+        let Some(existing) = Self::read_hook(&path)? else {
+            return Ok(HookChange::Unchanged(path));
+        };
+        if !existing.contains(BLOCK_START) {
+            return Ok(HookChange::Unchanged(path));
+        }
+        let content = Self::without_block(&existing);
+        let is_empty = content
+            .lines()
+            .all(|line| line.trim().is_empty() || line.starts_with("#!"));
+        if is_empty {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove git hook: {}", path.display()))?;
+            return Ok(HookChange::Removed(path));
+        }
+        Self::write_hook(&path, &content)?;
+        Ok(HookChange::Updated(path))
+    }
+
+    /// e.g. `gdm --project "game" install`, skipped when gdm isn't on PATH
+    fn block(&self, hook: GitHook) -> String {
+        let project = if self.project_dir.as_os_str().is_empty() {
+            String::new()
+        } else {
+            let project_dir: Vec<_> = self
+                .project_dir
+                .iter()
+                .map(|part| part.to_string_lossy())
+                .collect();
+            format!("--project \"{}\" ", project_dir.join("/"))
+        };
+        format!(
+            "{start}\n# Added by gdm hook install, remove with gdm hook uninstall\nif command -v gdm >/dev/null 2>&1; then\n  gdm {project}{command} || exit $?\nfi\n{end}\n",
+            start = BLOCK_START,
+            end = BLOCK_END,
+            project = project,
+            command = hook.command(),
+        )
+    }
+
+    /// The hook without gdm's block and the blank lines around it
+    fn without_block(content: &str) -> String {
+        let (Some(start), Some(end)) = (content.find(BLOCK_START), content.find(BLOCK_END)) else {
+            return content.to_string();
+        };
+        let before = content[..start].trim_end_matches('\n');
+        let after = content[end + BLOCK_END.len()..].trim_start_matches('\n');
+        if before.is_empty() {
+            after.to_string()
+        } else {
+            format!("{}\n{}", before, after)
+        }
+    }
+
+    fn read_hook(path: &Path) -> Result<Option<String>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read git hook: {}", path.display()))?;
+        Ok(Some(content))
+    }
+
+    fn write_hook(path: &Path, content: &str) -> Result<()> {
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write git hook: {}", path.display()))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_hooks(dir: &temp_dir::TempDir) -> GitHooks {
+        GitHooks::new(dir.path().join("hooks"), "game")
+    }
+
+    fn read(dir: &temp_dir::TempDir, hook: &str) -> String {
+        std::fs::read_to_string(dir.path().join("hooks").join(hook)).unwrap()
+    }
+
+    #[test]
+    fn test_install_should_create_hooks() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let hooks = setup_hooks(&dir);
+
+        let changes = hooks.install().unwrap();
+
+        assert!(matches!(changes[0], HookChange::Created(_)));
+        let post_merge = read(&dir, "post-merge");
+        assert!(post_merge.starts_with("#!/bin/sh\n\n# >>> gdm >>>\n"));
+        assert!(post_merge.contains("  gdm --project \"game\" install || exit $?\n"));
+        assert!(read(&dir, "pre-commit").contains("gdm --project \"game\" validate"));
+        assert!(matches!(
+            hooks.install().unwrap()[0],
+            HookChange::Unchanged(_)
+        ));
+    }
+
+    #[test]
+    fn test_install_and_uninstall_should_keep_existing_hook() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let hooks = setup_hooks(&dir);
+        std::fs::create_dir_all(dir.path().join("hooks")).unwrap();
+        let existing = "#!/usr/bin/env bash\nnpm test\nexit 0\n";
+        std::fs::write(dir.path().join("hooks/pre-commit"), existing).unwrap();
+
+        hooks.install().unwrap();
+        let pre_commit = read(&dir, "pre-commit");
+        assert!(pre_commit.starts_with("#!/usr/bin/env bash\n\n# >>> gdm >>>\n"));
+        assert!(pre_commit.ends_with("# <<< gdm <<<\n\nnpm test\nexit 0\n"));
+
+        let changes = hooks.uninstall().unwrap();
+        assert!(matches!(changes[0], HookChange::Updated(_)));
+        assert!(matches!(changes[1], HookChange::Removed(_)));
+        assert_eq!(read(&dir, "pre-commit"), existing);
+        assert!(!dir.path().join("hooks/post-merge").exists());
+    }
+
+    #[test]
+    fn test_install_into_non_shell_hook_should_return_err() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("hooks")).unwrap();
+        std::fs::write(
+            dir.path().join("hooks/pre-commit"),
+            "#!/usr/bin/env python3\nprint()\n",
+        )
+        .unwrap();
+
+        assert!(setup_hooks(&dir).install().is_err());
+    }
+}
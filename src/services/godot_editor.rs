@@ -0,0 +1,252 @@
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// A running Godot editor that has a project open. Godot doesn't write a lock file, so editors
+/// are found from the command lines of running processes: a process whose executable is named
+/// like Godot, opened with `--path <project>`, `<project>/project.godot` or, on Linux, started in
+/// the project folder. Headless runs and the project manager don't count. Processes are only
+/// listed on Linux and macOS, elsewhere no editor is ever found.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GodotEditor {
+    pub pid: u32,
+    pub command: String,
+}
+
+/// A process and the folder it was started in, if known
+struct Process {
+    pid: u32,
+    args: Vec<String>,
+    cwd: Option<PathBuf>,
+}
+
+impl GodotEditor {
+    /// The first editor with `project_dir` open, None if there is none
+    pub fn find_running(project_dir: &Path) -> Option<GodotEditor> {
+        let project_dir = canonicalize(project_dir);
+        let own_pid = std::process::id();
+        Self::processes()
+            .into_iter()
+            .filter(|process| process.pid != own_pid)
+            .find(|process| {
+                Self::opens_project(&process.args, process.cwd.as_deref(), &project_dir)
+            })
+            .map(|process| {
+                debug!(
+                    "Found a Godot editor of {}: {:?}",
+                    project_dir.display(),
+                    process.args
+                );
+                GodotEditor {
+                    pid: process.pid,
+                    command: process.args.join(" "),
+                }
+            })
+    }
+
+    /// Whether a process with `args` started in `cwd` is an editor of `project_dir`
+    fn opens_project(args: &[String], cwd: Option<&Path>, project_dir: &Path) -> bool {
+        let Some(program) = args.first() else {
+            return false;
+        };
+        let name = Path::new(program)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if !name.starts_with("godot") {
+            return false;
+        }
+        let args = &args[1..];
+        if args.iter().any(|arg| {
+            matches!(
+                arg.as_str(),
+                "--headless" | "-p" | "--project-manager" | "--help" | "--version"
+            )
+        }) {
+            return false;
+        }
+
+        let resolve = |path: &str| match cwd {
+            Some(cwd) => canonicalize(&cwd.join(path)),
+            None => canonicalize(Path::new(path)),
+        };
+        let mut opened = None;
+        for (index, arg) in args.iter().enumerate() {
+            if arg == "--path" {
+                opened = args.get(index + 1).map(|path| resolve(path));
+            } else if let Some(path) = arg.strip_prefix("--path=") {
+                opened = Some(resolve(path));
+            } else if arg.ends_with("project.godot") {
+                opened = Path::new(arg)
+                    .parent()
+                    .map(|dir| resolve(&dir.to_string_lossy()));
+            }
+        }
+        match opened {
+            Some(dir) => dir == project_dir,
+            // Without a path Godot opens the project in its working directory
+            None => cwd.is_some_and(|cwd| canonicalize(cwd) == project_dir),
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn processes() -> Vec<Process> {
+        let Ok(entries) = std::fs::read_dir("/proc") else {
+            return Vec::new();
+        };
+        entries
+            .flatten()
+            .filter_map(|entry| {
+                let pid = entry.file_name().to_str()?.parse().ok()?;
+                let cmdline = std::fs::read(entry.path().join("cmdline")).ok()?;
+                let args = cmdline
+                    .split(|byte| *byte == 0)
+                    .filter(|arg| !arg.is_empty())
+                    .map(|arg| String::from_utf8_lossy(arg).to_string())
+                    .collect();
+                Some(Process {
+                    pid,
+                    args,
+                    cwd: std::fs::read_link(entry.path().join("cwd")).ok(),
+                })
+            })
+            .collect()
+    }
+
+    #[cfg(target_os = "macos")]
+    fn processes() -> Vec<Process> {
+        let Ok(output) = std::process::Command::new("ps")
+            .args(["-axww", "-o", "pid=,args="])
+            .output()
+        else {
+            return Vec::new();
+        };
+        // ps separates arguments with spaces, so paths with spaces are split
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.split_whitespace();
+                let pid = parts.next()?.parse().ok()?;
+                Some(Process {
+                    pid,
+                    args: parts.map(str::to_string).collect(),
+                    cwd: None,
+                })
+            })
+            .collect()
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    fn processes() -> Vec<Process> {
+        Vec::new()
+    }
+}
+
+fn canonicalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(command: &str) -> Vec<String> {
+        command.split(' ').map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_opens_project_should_match_path_argument() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let project = dir.path().to_string_lossy().to_string();
+
+        assert!(GodotEditor::opens_project(
+            &args(&format!("/usr/bin/godot --editor --path {}", project)),
+            None,
+            dir.path()
+        ));
+        assert!(GodotEditor::opens_project(
+            &args(&format!(
+                "Godot_v4.4-stable_linux.x86_64 {}/project.godot",
+                project
+            )),
+            None,
+            dir.path()
+        ));
+        assert!(GodotEditor::opens_project(
+            &args("godot4 -e --path ."),
+            Some(dir.path()),
+            dir.path()
+        ));
+        assert!(!GodotEditor::opens_project(
+            &args("godot -e --path /somewhere/else"),
+            Some(dir.path()),
+            dir.path()
+        ));
+    }
+
+    #[test]
+    fn test_opens_project_should_use_working_directory_without_path() {
+        let dir = temp_dir::TempDir::new().unwrap();
+
+        assert!(GodotEditor::opens_project(
+            &args("godot -e"),
+            Some(dir.path()),
+            dir.path()
+        ));
+        assert!(!GodotEditor::opens_project(
+            &args("godot -e"),
+            None,
+            dir.path()
+        ));
+    }
+
+    #[test]
+    fn test_opens_project_should_skip_other_processes() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let cwd = Some(dir.path());
+
+        assert!(!GodotEditor::opens_project(
+            &args("vim project.godot"),
+            cwd,
+            dir.path()
+        ));
+        assert!(!GodotEditor::opens_project(
+            &args("godot --headless --import"),
+            cwd,
+            dir.path()
+        ));
+        assert!(!GodotEditor::opens_project(
+            &args("godot --project-manager"),
+            cwd,
+            dir.path()
+        ));
+        assert!(!GodotEditor::opens_project(&[], cwd, dir.path()));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_find_running_should_find_editor_process() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let godot = dir.child("godot");
+        std::os::unix::fs::symlink("/bin/sh", &godot).unwrap();
+        let mut editor = std::process::Command::new(&godot)
+            .args(["-c", "sleep 30; true", "--editor", "--path"])
+            .arg(dir.path())
+            .spawn()
+            .unwrap();
+
+        // The child may not have exec'd yet, so its command line is read until it shows up
+        let mut found = None;
+        for _ in 0..50 {
+            found = GodotEditor::find_running(dir.path());
+            if found.is_some() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+        editor.kill().unwrap();
+        editor.wait().unwrap();
+
+        assert_eq!(found.unwrap().pid, editor.id());
+        assert_eq!(GodotEditor::find_running(dir.path()), None);
+    }
+}
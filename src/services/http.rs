@@ -1,23 +1,135 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::config::{AppConfig, DefaultAppConfig};
+use crate::error::{ErrorKind, GdmError};
+use crate::services::CredentialHelper;
 
 use anyhow::{Result, bail};
-use reqwest::Response;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Response, StatusCode};
 use serde_json::Value;
 use tracing::{error, info};
 use url::Url;
 
+/// Sent with every request, e.g. `gdm/1.2.2 (+https://github.com/k0psutin/gdm)`
+pub const USER_AGENT: &str = concat!(
+    "gdm/",
+    env!("CARGO_PKG_VERSION"),
+    " (+",
+    env!("CARGO_PKG_REPOSITORY"),
+    ")"
+);
+
 #[derive(Debug, Clone)]
-pub struct DefaultHttpService {}
+pub struct DefaultHttpService {
+    credential_helper: Option<CredentialHelper>,
+    /// Host of the Asset Library, the only one `headers` are sent to
+    registry_host: Option<String>,
+    headers: HeaderMap,
+    /// --frozen, every request fails
+    frozen: bool,
+}
 
 impl DefaultHttpService {
-    pub fn new() -> DefaultHttpService {
-        DefaultHttpService {}
+    /// Takes the credential helper, Asset Library headers and --frozen from `app_config`
+    pub fn new(app_config: &DefaultAppConfig) -> DefaultHttpService {
+        // Headers of the global configuration are validated when it's loaded
+        let headers = Self::header_map(&app_config.get_headers()).unwrap_or_default();
+        DefaultHttpService {
+            credential_helper: CredentialHelper::from_config(app_config),
+            registry_host: Url::parse(&app_config.api_base_url)
+                .ok()
+                .and_then(|url| url.host_str().map(str::to_string)),
+            headers,
+            frozen: app_config.get_frozen(),
+        }
+    }
+
+    /// Sends `headers` with requests to the host of `registry`, e.g. an API key a self-hosted
+    /// Asset Library requires. Downloads from other hosts don't get them.
+    pub fn with_headers(
+        mut self,
+        registry: &str,
+        headers: &BTreeMap<String, String>,
+    ) -> Result<DefaultHttpService> {
+        self.registry_host = Url::parse(registry)?.host_str().map(str::to_string);
+        self.headers = Self::header_map(headers)?;
+        Ok(self)
+    }
+
+    /// Converts header names and values, failing on ones that can't be sent
+    pub fn header_map(headers: &BTreeMap<String, String>) -> Result<HeaderMap> {
+        let mut header_map = HeaderMap::new();
+        for (name, value) in headers {
+            let header_name = HeaderName::from_bytes(name.as_bytes());
+            let header_value = HeaderValue::from_str(value);
+            match (header_name, header_value) {
+                (Ok(header_name), Ok(mut header_value)) => {
+                    header_value.set_sensitive(true);
+                    header_map.insert(header_name, header_value);
+                }
+                _ => bail!(
+                    GdmError::new(ErrorKind::ConfigInvalid, format!("Invalid header: {}", name))
+                        .with_hint("Header names can't contain spaces or colons, and values must be visible ASCII.")
+                ),
+            }
+        }
+        Ok(header_map)
+    }
+
+    /// Uses this helper instead of the one of the configuration
+    pub fn with_credential_helper(
+        mut self,
+        credential_helper: Option<CredentialHelper>,
+    ) -> DefaultHttpService {
+        self.credential_helper = credential_helper;
+        self
+    }
+
+    /// GET request with the credential helper's token for the host, if it has one, and the
+    /// configured headers for the Asset Library host.
+    /// reqwest drops the token when a download redirects to another host.
+    fn request(&self, url: &Url) -> Result<reqwest::RequestBuilder> {
+        if self.frozen {
+            bail!(Self::frozen_error(url.as_str()));
+        }
+        let client = reqwest::Client::builder().user_agent(USER_AGENT).build()?;
+        let mut request = client.get(url.as_str());
+        if self.registry_host.is_some() && url.host_str() == self.registry_host.as_deref() {
+            request = request.headers(self.headers.clone());
+        }
+        let token = match (&self.credential_helper, url.host_str()) {
+            (Some(credential_helper), Some(host)) => credential_helper.token(host)?,
+            _ => None,
+        };
+        Ok(match token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        })
+    }
+
+    /// Error for a request made with --frozen, which must not use the network
+    pub fn frozen_error(url: &str) -> GdmError {
+        GdmError::new(
+            ErrorKind::Usage,
+            format!("Refusing to connect to {} with --frozen", url),
+        )
+        .with_hint("Run without --frozen to resolve and download plugins, then commit gdm.json.")
+    }
+
+    fn status_error(status: StatusCode) -> GdmError {
+        let kind = if status == StatusCode::NOT_FOUND {
+            ErrorKind::NotFound
+        } else {
+            ErrorKind::Network
+        };
+        GdmError::new(kind, status.to_string())
     }
 }
 
 impl Default for DefaultHttpService {
     fn default() -> Self {
-        DefaultHttpService::new()
+        DefaultHttpService::new(&DefaultAppConfig::default())
     }
 }
 
@@ -26,13 +138,13 @@ impl Default for DefaultHttpService {
 impl HttpService for DefaultHttpService {
     async fn get(&self, url: String, params: HashMap<String, String>) -> Result<Value> {
         let _url = Url::parse_with_params(&url, params)?;
-        match reqwest::get(_url.as_str()).await {
+        match self.request(&_url)?.send().await {
             Ok(response) => {
                 let status = response.status();
                 info!("[GET] {} [{}]", _url, status.as_u16());
 
                 if !status.is_success() {
-                    bail!(status);
+                    bail!(Self::status_error(status));
                 }
 
                 let data = response.json().await?;
@@ -43,7 +155,10 @@ impl HttpService for DefaultHttpService {
                     Some(status) => error!("[GET] {} [{}] - Error: {}", _url, status, e),
                     None => error!("[GET] {} - Error: {}", _url, e),
                 }
-                bail!("Failed to fetch data: {}", e)
+                bail!(GdmError::new(
+                    ErrorKind::Network,
+                    format!("Failed to fetch data: {}", e)
+                ))
             }
         }
     }
@@ -51,13 +166,13 @@ impl HttpService for DefaultHttpService {
     async fn get_file(&self, url: String) -> Result<Response> {
         let _url = Url::parse(&url)?;
 
-        match reqwest::get(_url.as_str()).await {
+        match self.request(&_url)?.send().await {
             Ok(response) => {
                 let status = response.status();
                 info!("[GET] {} [{}]", _url, status.as_u16());
 
                 if !status.is_success() {
-                    bail!(status);
+                    bail!(Self::status_error(status));
                 }
 
                 Ok(response)
@@ -67,7 +182,10 @@ impl HttpService for DefaultHttpService {
                     Some(status) => error!("[GET] {} [{}] - Error: {}", _url, status, e),
                     None => error!("[GET] {} - Error: {}", _url, e),
                 }
-                bail!("Failed to fetch file: {}", e)
+                bail!(GdmError::new(
+                    ErrorKind::Network,
+                    format!("Failed to fetch file: {}", e)
+                ))
             }
         }
     }
@@ -79,3 +197,52 @@ pub trait HttpService: Send + Sync {
 
     async fn get_file(&self, url: String) -> Result<Response>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers() -> BTreeMap<String, String> {
+        BTreeMap::from([("X-Api-Key".to_string(), "secret".to_string())])
+    }
+
+    #[test]
+    fn test_request_should_send_headers_only_to_registry_host() {
+        let http_service = DefaultHttpService::default()
+            .with_headers("https://assets.example.com/api", &headers())
+            .unwrap();
+
+        let request = http_service
+            .request(&Url::parse("https://assets.example.com/api/asset").unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(request.headers()["x-api-key"], "secret");
+
+        let request = http_service
+            .request(&Url::parse("https://github.com/user/repo/archive/main.zip").unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(request.headers().get("x-api-key").is_none());
+    }
+
+    #[test]
+    fn test_request_should_fail_when_frozen() {
+        let http_service = DefaultHttpService::new(&DefaultAppConfig::default().with_frozen(true));
+
+        let error = http_service
+            .request(&Url::parse("https://assets.example.com/api/asset").unwrap())
+            .unwrap_err();
+        assert_eq!(GdmError::find(&error).unwrap().kind, ErrorKind::Usage);
+    }
+
+    #[test]
+    fn test_header_map_should_reject_invalid_headers() {
+        assert!(DefaultHttpService::header_map(&headers()).is_ok());
+        let invalid_name = BTreeMap::from([("X: Key".to_string(), "secret".to_string())]);
+        assert!(DefaultHttpService::header_map(&invalid_name).is_err());
+        let invalid_value = BTreeMap::from([("X-Api-Key".to_string(), "line\nbreak".to_string())]);
+        assert!(DefaultHttpService::header_map(&invalid_value).is_err());
+    }
+}
@@ -2,14 +2,63 @@ use anyhow::{Result, bail};
 use async_trait::async_trait;
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tracing::debug;
 
-use crate::config::{AppConfig, DefaultAppConfig};
+use crate::api::{AssetStoreAPI, DefaultAssetStoreAPI};
+use crate::config::{AppConfig, DefaultAppConfig, InstallMode};
+use crate::error::{ErrorKind, GdmError};
 use crate::installers::{AssetLibraryInstaller, GitInstaller, PluginInstaller};
 use crate::models::{Plugin, PluginSource};
-use crate::services::{DefaultFileService, FileService, PluginParser};
-use crate::ui::OperationManager;
+use crate::services::{
+    ArchiveCache, ContentStore, DefaultExtractService, DefaultFileService, DefaultGitService,
+    FileService, PluginParser,
+};
+use crate::ui::{NoopTask, ProgressReporter, ProgressTask};
+use crate::utils::Utils;
+
+/// Staging folder of the install in progress, removed by
+/// [`DefaultInstallService::remove_interrupted_staging`] when gdm is interrupted
+static ACTIVE_STAGING: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Removes the staging folder when dropped, so failed, panicking and cancelled installs don't
+/// leave downloads behind
+struct StagingGuard<'a> {
+    install_service: Option<&'a DefaultInstallService>,
+}
+
+impl<'a> StagingGuard<'a> {
+    fn new(install_service: &'a DefaultInstallService) -> StagingGuard<'a> {
+        let dir = install_service.app_config.get_cache_folder_path();
+        *ACTIVE_STAGING.lock().unwrap_or_else(|e| e.into_inner()) = std::path::absolute(dir).ok();
+        StagingGuard {
+            install_service: Some(install_service),
+        }
+    }
+
+    /// Removes the staging folder after a successful install, returning the error if that fails
+    fn finish(mut self) -> Result<()> {
+        match self.install_service.take() {
+            Some(install_service) => Self::cleanup(install_service),
+            None => Ok(()),
+        }
+    }
+
+    fn cleanup(install_service: &DefaultInstallService) -> Result<()> {
+        *ACTIVE_STAGING.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        install_service.cleanup_cache()
+    }
+}
+
+impl Drop for StagingGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(install_service) = self.install_service.take()
+            && let Err(e) = Self::cleanup(install_service)
+        {
+            debug!("Failed to clean up the staging folder: {:#}", e);
+        }
+    }
+}
 
 /// Service for managing staged plugin installations
 /// Provides a unified workflow for all installer types
@@ -18,22 +67,68 @@ pub struct DefaultInstallService {
     app_config: Box<dyn AppConfig>,
     parser: Arc<PluginParser>,
     installers: Vec<Box<dyn PluginInstaller>>,
+    /// Plugin folders are linked to this store instead of moved into the project
+    content_store: Option<ContentStore>,
 }
 
 impl Default for DefaultInstallService {
     fn default() -> Self {
+        Self::with_asset_store_api(
+            Arc::new(DefaultAssetStoreAPI::default()),
+            DefaultAppConfig::default(),
+        )
+    }
+}
+
+impl DefaultInstallService {
+    /// Installs Asset Library plugins through `asset_store_api`, sharing the responses it already
+    /// fetched, into the addons folder and with the install mode of `app_config`
+    pub fn with_asset_store_api(
+        asset_store_api: Arc<dyn AssetStoreAPI + Send + Sync>,
+        app_config: DefaultAppConfig,
+    ) -> Self {
         let file_service = Arc::new(DefaultFileService);
-        let app_config = Box::new(DefaultAppConfig::default());
         let parser = Arc::new(PluginParser::new(file_service.clone()));
-        let asset_installer = AssetLibraryInstaller::default();
-        let git_installer = GitInstaller::default();
+        let asset_installer = AssetLibraryInstaller::new(
+            asset_store_api,
+            Arc::new(DefaultExtractService::new(
+                Box::new(DefaultFileService),
+                app_config.clone(),
+            )),
+            app_config.clone(),
+        )
+        .with_archive_cache(ArchiveCache::in_cache_dir());
+        let git_installer = GitInstaller::new(Arc::new(DefaultGitService {
+            app_config: app_config.clone(),
+        }));
         let installers: Vec<Box<dyn PluginInstaller>> =
             vec![Box::new(asset_installer), Box::new(git_installer)];
-        Self::new(file_service, app_config, parser, installers)
+        let content_store = ContentStore::in_cache_dir()
+            .filter(|_| app_config.get_install_mode() == InstallMode::Link);
+        Self::new(file_service, Box::new(app_config), parser, installers)
+            .with_content_store(content_store)
+    }
+
+    /// Removes the staging folder of the install in progress. For Ctrl+C handlers, which end the
+    /// process without dropping [`StagingGuard`].
+    pub fn remove_interrupted_staging() {
+        let staging = ACTIVE_STAGING
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        if let Some(dir) = staging
+            && dir.exists()
+        {
+            match std::fs::remove_dir_all(&dir) {
+                Ok(()) => debug!(
+                    "Removed staging folder of interrupted install: {}",
+                    dir.display()
+                ),
+                Err(e) => debug!("Failed to remove staging folder {}: {}", dir.display(), e),
+            }
+        }
     }
-}
 
-impl DefaultInstallService {
     pub fn new(
         file_service: Arc<dyn FileService + Send + Sync>,
         app_config: Box<dyn AppConfig>,
@@ -45,8 +140,15 @@ impl DefaultInstallService {
             app_config,
             parser,
             installers,
+            content_store: None,
         }
     }
+
+    /// Links plugin folders to `content_store` instead of moving them into the project
+    pub fn with_content_store(mut self, content_store: Option<ContentStore>) -> Self {
+        self.content_store = content_store;
+        self
+    }
 }
 
 #[cfg_attr(test, mockall::automock)]
@@ -61,7 +163,10 @@ impl InstallService for DefaultInstallService {
         let addons_dir = cache_dir.join("addons");
 
         if !self.file_service.directory_exists(&addons_dir) {
-            bail!("No 'addons' directory found at: {}", cache_dir.display());
+            bail!(GdmError::new(
+                ErrorKind::ArchiveInvalid,
+                format!("No 'addons' directory found at: {}", cache_dir.display())
+            ));
         }
 
         let addon_folders: Vec<PathBuf> = self
@@ -81,7 +186,10 @@ impl InstallService for DefaultInstallService {
             .collect();
 
         if addon_folders.is_empty() {
-            bail!("No folders found inside {}/addons", cache_dir.display());
+            bail!(GdmError::new(
+                ErrorKind::ArchiveInvalid,
+                format!("No folders found inside {}/addons", cache_dir.display())
+            ));
         }
 
         let parsed_plugins = self.parser.create_plugins_from_addon_folders_with_base(
@@ -114,18 +222,26 @@ impl InstallService for DefaultInstallService {
         Ok((main_plugin_folder, plugin, addon_folders))
     }
 
+    fn get_addons_dir(&self, plugin: &Plugin) -> PathBuf {
+        plugin.get_addons_dir(self.app_config.get_project_addons_dir())
+    }
+
     fn install_from_cache(
         &self,
         cache_dir: &Path,
         addon_folders: &[PathBuf],
+        project_addons_dir: &Path,
     ) -> Result<Vec<PathBuf>> {
-        let project_addons_dir = self.app_config.get_addon_folder_path();
         let staging_addons_dir = cache_dir.join("addons");
         let mut installed_paths = Vec::new();
 
         for folder in addon_folders {
             let src = staging_addons_dir.join(folder);
-            let dest = project_addons_dir.join(folder);
+            let dest = self
+                .app_config
+                .get_project_dir()
+                .join(project_addons_dir)
+                .join(folder);
 
             if self.file_service.directory_exists(&dest) {
                 debug!("Removing existing installation: {}", dest.display());
@@ -138,7 +254,12 @@ impl InstallService for DefaultInstallService {
                 self.file_service.create_directory(parent)?;
             }
 
-            self.file_service.rename(&src, &dest)?;
+            match &self.content_store {
+                Some(content_store) => {
+                    content_store.install(&src, &dest)?;
+                }
+                None => self.file_service.move_dir(&src, &dest)?,
+            }
 
             installed_paths.push(dest);
         }
@@ -146,6 +267,43 @@ impl InstallService for DefaultInstallService {
         Ok(installed_paths)
     }
 
+    fn verify_archive_checksum(&self, plugin: &Plugin, archive_path: &Path) -> Result<String> {
+        let checksum = format!("sha256:{}", self.file_service.sha256_file(archive_path)?);
+        plugin.verify_checksum(&checksum)?;
+        Ok(checksum)
+    }
+
+    fn installed_content_hash(
+        &self,
+        name: &str,
+        plugin: &Plugin,
+        progress: &dyn ProgressTask,
+    ) -> Result<Option<String>> {
+        let addons_dir = self
+            .app_config
+            .get_project_dir()
+            .join(self.get_addons_dir(plugin));
+        let folders = std::iter::once(name).chain(plugin.sub_assets.iter().map(String::as_str));
+        let mut context = ring::digest::Context::new(&ring::digest::SHA256);
+        for folder in folders {
+            let path = Utils::plugin_name_to_addon_folder_path(&addons_dir, Path::new(folder));
+            if !self.file_service.directory_exists(&path) {
+                return Ok(None);
+            }
+            context.update(folder.as_bytes());
+            context.update(&[0]);
+            context.update(self.file_service.sha256_dir(&path, progress)?.as_bytes());
+            context.update(b"\n");
+        }
+        let hash = context
+            .finish()
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+        Ok(Some(format!("sha256:{}", hash)))
+    }
+
     fn cleanup_cache(&self) -> Result<()> {
         let dir = self.app_config.get_cache_folder_path();
         if self.file_service.directory_exists(dir) {
@@ -158,9 +316,13 @@ impl InstallService for DefaultInstallService {
     async fn install(
         &self,
         plugins: &[Plugin],
-        operation_manager: Arc<OperationManager>,
+        reporter: Arc<dyn ProgressReporter>,
     ) -> Result<BTreeMap<String, Plugin>> {
+        // A staging folder left by a crashed run could mix old files into this install
+        self.cleanup_cache()?;
+        let staging = StagingGuard::new(self);
         let mut installed_plugins = Vec::new();
+        reporter.set_total_plugins(plugins.len());
 
         for (idx, plugin) in plugins.iter().enumerate() {
             let installer = self
@@ -169,15 +331,23 @@ impl InstallService for DefaultInstallService {
                 .find(|inst| inst.can_handle(plugin.source.clone()));
 
             if let Some(installer) = installer {
-                let future =
-                    installer.install(idx, plugins.len(), self, plugin, operation_manager.clone());
+                let reporter = reporter.clone();
+                let future = async move {
+                    let (name, mut installed) = installer
+                        .install(idx, plugins.len(), self, plugin, reporter.clone())
+                        .await?;
+                    installed.content_hash =
+                        self.installed_content_hash(&name, &installed, &NoopTask::default())?;
+                    reporter.plugin_installed();
+                    anyhow::Ok((name, installed))
+                };
                 installed_plugins.push(future);
             }
         }
 
         let results = futures::future::try_join_all(installed_plugins).await?;
 
-        self.cleanup_cache()?;
+        staging.finish()?;
 
         let installed_plugins: BTreeMap<String, Plugin> = results.into_iter().collect();
 
@@ -195,18 +365,33 @@ pub trait InstallService: Send + Sync {
         main_plugin_name: &str,
     ) -> Result<(String, Plugin, Vec<PathBuf>)>;
 
+    fn get_addons_dir(&self, plugin: &Plugin) -> PathBuf;
+
     fn install_from_cache(
         &self,
         asset_dir: &Path,
         addon_folders: &[PathBuf],
+        project_addons_dir: &Path,
     ) -> Result<Vec<PathBuf>>;
 
+    /// Hashes a downloaded archive and checks it against the checksum recorded for the plugin
+    fn verify_archive_checksum(&self, plugin: &Plugin, archive_path: &Path) -> Result<String>;
+
+    /// Hash of the plugin's folder and sub-asset folders in the addons folder,
+    /// None if any of them is missing. `progress` advances by one per hashed file.
+    fn installed_content_hash(
+        &self,
+        name: &str,
+        plugin: &Plugin,
+        progress: &dyn ProgressTask,
+    ) -> Result<Option<String>>;
+
     fn cleanup_cache(&self) -> Result<()>;
 
     async fn install(
         &self,
         plugins: &[Plugin],
-        operation_manager: Arc<OperationManager>,
+        reporter: Arc<dyn ProgressReporter>,
     ) -> Result<BTreeMap<String, Plugin>>;
 }
 
@@ -260,7 +445,7 @@ mod tests {
             _total: usize,
             _install_service: &dyn InstallService,
             plugin: &Plugin,
-            _operation_manager: Arc<OperationManager>,
+            _reporter: Arc<dyn ProgressReporter>,
         ) -> Result<(String, Plugin)> {
             if self.should_fail {
                 return Err(anyhow!(
@@ -290,6 +475,7 @@ mod tests {
             version: version.to_string(),
             sub_assets: vec![],
             license: Some("MIT".to_string()),
+            ..Plugin::default()
         }
     }
 
@@ -405,17 +591,15 @@ mod tests {
         fn test_install_from_cache_creates_parent_directory_if_missing() {
             let mut mock_file_service = MockDefaultFileService::new();
             let mut mock_app_config = MockDefaultAppConfig::new();
+            mock_app_config
+                .expect_get_project_dir()
+                .return_const(PathBuf::new());
 
             let project_addons = PathBuf::from("/project/addons");
             let cache_dir = PathBuf::from("/cache");
             let staging_addons = cache_dir.join("addons");
             let addon_folder = PathBuf::from("test_addon");
 
-            let project_addons_clone = project_addons.clone();
-            mock_app_config
-                .expect_get_addon_folder_path()
-                .returning(move || project_addons_clone.clone());
-
             let src = staging_addons.join(&addon_folder);
             let dest = project_addons.join(&addon_folder);
             let parent = dest.parent().unwrap().to_path_buf();
@@ -441,9 +625,9 @@ mod tests {
                 .times(1)
                 .returning(|_| Ok(()));
 
-            // Rename succeeds
+            // Move succeeds
             mock_file_service
-                .expect_rename()
+                .expect_move_dir()
                 .with(
                     mockall::predicate::eq(src.clone()),
                     mockall::predicate::eq(dest.clone()),
@@ -459,7 +643,11 @@ mod tests {
                 vec![],
             );
 
-            let result = service.install_from_cache(&cache_dir, slice::from_ref(&addon_folder));
+            let result = service.install_from_cache(
+                &cache_dir,
+                slice::from_ref(&addon_folder),
+                &project_addons,
+            );
 
             assert!(result.is_ok());
             let installed = result.unwrap();
@@ -467,21 +655,54 @@ mod tests {
             assert_eq!(installed[0], dest);
         }
 
+        #[test]
+        #[cfg(unix)]
+        fn test_install_from_cache_with_content_store_links_folder() {
+            let temp_dir = temp_dir::TempDir::new().unwrap();
+            let cache_dir = temp_dir.path().join("cache");
+            let project_addons = temp_dir.path().join("project/addons");
+            let addon_folder = PathBuf::from("test_addon");
+            let src = cache_dir.join("addons").join(&addon_folder);
+            std::fs::create_dir_all(&src).unwrap();
+            std::fs::write(src.join("plugin.cfg"), "[plugin]\n").unwrap();
+
+            let mut mock_app_config = MockDefaultAppConfig::new();
+            mock_app_config
+                .expect_get_project_dir()
+                .return_const(PathBuf::new());
+
+            let file_service = Arc::new(DefaultFileService);
+            let service = DefaultInstallService::new(
+                file_service.clone(),
+                Box::new(mock_app_config),
+                Arc::new(PluginParser::new(file_service)),
+                vec![],
+            )
+            .with_content_store(Some(ContentStore::new(temp_dir.path().join("store"))));
+
+            let installed = service
+                .install_from_cache(&cache_dir, slice::from_ref(&addon_folder), &project_addons)
+                .unwrap();
+
+            let dest = project_addons.join(&addon_folder);
+            assert_eq!(installed, vec![dest.clone()]);
+            assert!(dest.symlink_metadata().unwrap().file_type().is_symlink());
+            assert!(dest.join("plugin.cfg").is_file());
+        }
+
         #[test]
         fn test_install_from_cache_removes_existing_installation() {
             let mut mock_file_service = MockDefaultFileService::new();
             let mut mock_app_config = MockDefaultAppConfig::new();
+            mock_app_config
+                .expect_get_project_dir()
+                .return_const(PathBuf::new());
 
             let project_addons = PathBuf::from("/project/addons");
             let cache_dir = PathBuf::from("/cache");
             let staging_addons = cache_dir.join("addons");
             let addon_folder = PathBuf::from("test_addon");
 
-            let project_addons_clone = project_addons.clone();
-            mock_app_config
-                .expect_get_addon_folder_path()
-                .returning(move || project_addons_clone.clone());
-
             let src = staging_addons.join(&addon_folder);
             let dest = project_addons.join(&addon_folder);
             let parent = dest.parent().unwrap().to_path_buf();
@@ -507,9 +728,9 @@ mod tests {
                 .times(1)
                 .returning(|_| true);
 
-            // Rename succeeds
+            // Move succeeds
             mock_file_service
-                .expect_rename()
+                .expect_move_dir()
                 .with(
                     mockall::predicate::eq(src.clone()),
                     mockall::predicate::eq(dest.clone()),
@@ -525,7 +746,11 @@ mod tests {
                 vec![],
             );
 
-            let result = service.install_from_cache(&cache_dir, slice::from_ref(&addon_folder));
+            let result = service.install_from_cache(
+                &cache_dir,
+                slice::from_ref(&addon_folder),
+                &project_addons,
+            );
 
             assert!(result.is_ok());
         }
@@ -534,17 +759,15 @@ mod tests {
         fn test_install_from_cache_handles_rename_failure() {
             let mut mock_file_service = MockDefaultFileService::new();
             let mut mock_app_config = MockDefaultAppConfig::new();
+            mock_app_config
+                .expect_get_project_dir()
+                .return_const(PathBuf::new());
 
             let project_addons = PathBuf::from("/project/addons");
             let cache_dir = PathBuf::from("/cache");
             let staging_addons = cache_dir.join("addons");
             let addon_folder = PathBuf::from("test_addon");
 
-            let project_addons_clone = project_addons.clone();
-            mock_app_config
-                .expect_get_addon_folder_path()
-                .returning(move || project_addons_clone.clone());
-
             let src = staging_addons.join(&addon_folder);
             let dest = project_addons.join(&addon_folder);
             let parent = dest.parent().unwrap().to_path_buf();
@@ -561,9 +784,9 @@ mod tests {
                 .times(1)
                 .returning(|_| true);
 
-            // Rename fails
+            // Move fails
             mock_file_service
-                .expect_rename()
+                .expect_move_dir()
                 .with(
                     mockall::predicate::eq(src.clone()),
                     mockall::predicate::eq(dest.clone()),
@@ -579,7 +802,11 @@ mod tests {
                 vec![],
             );
 
-            let result = service.install_from_cache(&cache_dir, slice::from_ref(&addon_folder));
+            let result = service.install_from_cache(
+                &cache_dir,
+                slice::from_ref(&addon_folder),
+                &project_addons,
+            );
 
             assert!(result.is_err());
             assert!(result.unwrap_err().to_string().contains("Failed to move"));
@@ -589,6 +816,9 @@ mod tests {
         fn test_install_from_cache_handles_multiple_addons() {
             let mut mock_file_service = MockDefaultFileService::new();
             let mut mock_app_config = MockDefaultAppConfig::new();
+            mock_app_config
+                .expect_get_project_dir()
+                .return_const(PathBuf::new());
 
             let project_addons = PathBuf::from("/project/addons");
             let cache_dir = PathBuf::from("/cache");
@@ -599,11 +829,6 @@ mod tests {
                 PathBuf::from("addon3"),
             ];
 
-            let project_addons_clone = project_addons.clone();
-            mock_app_config
-                .expect_get_addon_folder_path()
-                .returning(move || project_addons_clone.clone());
-
             for addon_folder in &addon_folders {
                 let src = staging_addons.join(addon_folder);
                 let dest = project_addons.join(addon_folder);
@@ -622,7 +847,7 @@ mod tests {
                     .returning(|_| true);
 
                 mock_file_service
-                    .expect_rename()
+                    .expect_move_dir()
                     .with(
                         mockall::predicate::eq(src.clone()),
                         mockall::predicate::eq(dest.clone()),
@@ -639,7 +864,7 @@ mod tests {
                 vec![],
             );
 
-            let result = service.install_from_cache(&cache_dir, &addon_folders);
+            let result = service.install_from_cache(&cache_dir, &addon_folders, &project_addons);
 
             assert!(result.is_ok());
             let installed = result.unwrap();
@@ -649,15 +874,9 @@ mod tests {
         #[test]
         fn test_install_from_cache_with_empty_addon_list() {
             let mock_file_service = MockDefaultFileService::new();
-            let mut mock_app_config = MockDefaultAppConfig::new();
+            let mock_app_config = MockDefaultAppConfig::new();
             let cache_dir = PathBuf::from("/cache");
 
-            // Even with empty addon list, get_addon_folder_path is called once
-            mock_app_config
-                .expect_get_addon_folder_path()
-                .times(1)
-                .returning(|| PathBuf::from("/project/addons"));
-
             let parser = Arc::new(PluginParser::new(Arc::new(MockDefaultFileService::new())));
             let service = DefaultInstallService::new(
                 Arc::new(mock_file_service),
@@ -666,7 +885,7 @@ mod tests {
                 vec![],
             );
 
-            let result = service.install_from_cache(&cache_dir, &[]);
+            let result = service.install_from_cache(&cache_dir, &[], Path::new("/project/addons"));
 
             assert!(result.is_ok());
             let installed = result.unwrap();
@@ -674,6 +893,49 @@ mod tests {
         }
     }
 
+    mod verify_archive_checksum_tests {
+        use super::*;
+
+        fn setup_service() -> DefaultInstallService {
+            let mut mock_file_service = MockDefaultFileService::new();
+            mock_file_service
+                .expect_sha256_file()
+                .with(mockall::predicate::eq(PathBuf::from("/cache/asset.zip")))
+                .returning(|_| Ok("abc".to_string()));
+
+            DefaultInstallService::new(
+                Arc::new(mock_file_service),
+                Box::new(MockDefaultAppConfig::new()),
+                Arc::new(PluginParser::new(Arc::new(MockDefaultFileService::new()))),
+                vec![],
+            )
+        }
+
+        #[test]
+        fn test_verify_archive_checksum_returns_checksum() {
+            let service = setup_service();
+            let plugin = create_test_plugin("test_plugin", "1.0.0", None);
+
+            let result = service.verify_archive_checksum(&plugin, Path::new("/cache/asset.zip"));
+            assert_eq!(result.unwrap(), "sha256:abc");
+        }
+
+        #[test]
+        fn test_verify_archive_checksum_fails_on_mismatch() {
+            let service = setup_service();
+            let mut plugin = create_test_plugin("test_plugin", "1.0.0", None);
+            plugin.checksum = Some("sha256:def".to_string());
+
+            let result = service.verify_archive_checksum(&plugin, Path::new("/cache/asset.zip"));
+            assert!(
+                result
+                    .unwrap_err()
+                    .to_string()
+                    .contains("Checksum mismatch")
+            );
+        }
+    }
+
     mod cleanup_cache_tests {
         use super::*;
 
@@ -786,6 +1048,22 @@ mod tests {
     mod install_tests {
         use super::*;
 
+        /// The installed plugin folders don't exist, so no content hash is recorded
+        fn expect_no_installed_folders(
+            mock_app_config: &mut MockDefaultAppConfig,
+            mock_file_service: &mut MockDefaultFileService,
+        ) {
+            mock_app_config
+                .expect_get_project_addons_dir()
+                .return_const(PathBuf::from("/addons"));
+            mock_file_service
+                .expect_directory_exists()
+                .with(mockall::predicate::function(|path: &Path| {
+                    path.starts_with("/addons")
+                }))
+                .returning(|_| false);
+        }
+
         #[tokio::test]
         async fn test_install_with_empty_plugin_list() {
             let mut mock_file_service = MockDefaultFileService::new();
@@ -807,8 +1085,7 @@ mod tests {
                 vec![],
             );
 
-            let operation_manager =
-                Arc::new(OperationManager::new(crate::ui::Operation::Install).unwrap());
+            let operation_manager = Arc::new(crate::ui::NoopReporter);
             let result = service.install(&[], operation_manager).await;
 
             assert!(result.is_ok());
@@ -847,8 +1124,7 @@ mod tests {
                 }),
             );
 
-            let operation_manager =
-                Arc::new(OperationManager::new(crate::ui::Operation::Install).unwrap());
+            let operation_manager = Arc::new(crate::ui::NoopReporter);
             let result = service.install(&[plugin], operation_manager).await;
 
             assert!(result.is_ok());
@@ -860,6 +1136,10 @@ mod tests {
         async fn test_install_with_matching_installer() {
             let mut mock_file_service = MockDefaultFileService::new();
             let mut mock_app_config = MockDefaultAppConfig::new();
+            mock_app_config
+                .expect_get_project_dir()
+                .return_const(PathBuf::new());
+            expect_no_installed_folders(&mut mock_app_config, &mut mock_file_service);
 
             let cache_dir = PathBuf::from("/cache");
             mock_app_config
@@ -890,8 +1170,7 @@ mod tests {
                 vec![Box::new(mock_installer)],
             );
 
-            let operation_manager =
-                Arc::new(OperationManager::new(crate::ui::Operation::Install).unwrap());
+            let operation_manager = Arc::new(crate::ui::NoopReporter);
             let result = service.install(&[plugin], operation_manager).await;
 
             assert!(result.is_ok());
@@ -904,6 +1183,10 @@ mod tests {
         async fn test_install_with_multiple_plugins_same_key_collision() {
             let mut mock_file_service = MockDefaultFileService::new();
             let mut mock_app_config = MockDefaultAppConfig::new();
+            mock_app_config
+                .expect_get_project_dir()
+                .return_const(PathBuf::new());
+            expect_no_installed_folders(&mut mock_app_config, &mut mock_file_service);
 
             let cache_dir = PathBuf::from("/cache");
             mock_app_config
@@ -944,8 +1227,7 @@ mod tests {
                 vec![Box::new(mock_installer)],
             );
 
-            let operation_manager =
-                Arc::new(OperationManager::new(crate::ui::Operation::Install).unwrap());
+            let operation_manager = Arc::new(crate::ui::NoopReporter);
             let result = service
                 .install(&[plugin1, plugin2], operation_manager)
                 .await;
@@ -992,8 +1274,7 @@ mod tests {
                 }),
             );
 
-            let operation_manager =
-                Arc::new(OperationManager::new(crate::ui::Operation::Install).unwrap());
+            let operation_manager = Arc::new(crate::ui::NoopReporter);
             let result = service.install(&[plugin], operation_manager).await;
 
             assert!(result.is_err());
@@ -1005,30 +1286,90 @@ mod tests {
             );
         }
 
+        #[tokio::test]
+        async fn test_install_cleans_up_cache_after_failure() {
+            let mut mock_file_service = MockDefaultFileService::new();
+            let mut mock_app_config = MockDefaultAppConfig::new();
+
+            mock_app_config
+                .expect_get_cache_folder_path()
+                .return_const(PathBuf::from("/cache"));
+
+            // No folder left by a crashed run, but the failed installer leaves one behind
+            let removed = Arc::new(std::sync::atomic::AtomicBool::new(false));
+            let mut sequence = mockall::Sequence::new();
+            mock_file_service
+                .expect_directory_exists()
+                .with(mockall::predicate::eq(PathBuf::from("/cache")))
+                .times(1)
+                .in_sequence(&mut sequence)
+                .returning(|_| false);
+            mock_file_service
+                .expect_directory_exists()
+                .with(mockall::predicate::eq(PathBuf::from("/cache")))
+                .times(1)
+                .in_sequence(&mut sequence)
+                .returning(|_| true);
+            let removed_clone = removed.clone();
+            mock_file_service
+                .expect_remove_dir_all()
+                .with(mockall::predicate::eq(PathBuf::from("/cache")))
+                .times(1)
+                .returning(move |_| {
+                    removed_clone.store(true, std::sync::atomic::Ordering::SeqCst);
+                    Ok(())
+                });
+
+            let parser = Arc::new(PluginParser::new(Arc::new(MockDefaultFileService::new())));
+            let mock_installer = MockPluginInstaller::new(true).with_failure("Installation failed");
+            let service = DefaultInstallService::new(
+                Arc::new(mock_file_service),
+                Box::new(mock_app_config),
+                parser,
+                vec![Box::new(mock_installer)],
+            );
+
+            let plugin = create_test_plugin(
+                "test-plugin",
+                "1.0.0",
+                Some(PluginSource::AssetLibrary {
+                    asset_id: "123".to_string(),
+                }),
+            );
+            let operation_manager = Arc::new(crate::ui::NoopReporter);
+            let result = service.install(&[plugin], operation_manager).await;
+
+            assert!(result.is_err());
+            assert!(removed.load(std::sync::atomic::Ordering::SeqCst));
+        }
+
         #[tokio::test]
         async fn test_install_cleans_up_cache_after_success() {
             let mut mock_file_service = MockDefaultFileService::new();
             let mut mock_app_config = MockDefaultAppConfig::new();
+            mock_app_config
+                .expect_get_project_dir()
+                .return_const(PathBuf::new());
+            expect_no_installed_folders(&mut mock_app_config, &mut mock_file_service);
 
             let cache_dir = PathBuf::from("/cache");
 
             mock_app_config
                 .expect_get_cache_folder_path()
-                .times(1)
                 .return_const(PathBuf::from("/cache"));
 
-            // First call for cleanup check, second for actual cleanup
+            // Once for a folder left by a crashed run, once after installing
             let cache_clone = cache_dir.clone();
             mock_file_service
                 .expect_directory_exists()
                 .with(mockall::predicate::eq(cache_dir.clone()))
-                .times(1)
+                .times(2)
                 .returning(move |_| true);
 
             mock_file_service
                 .expect_remove_dir_all()
                 .with(mockall::predicate::eq(cache_clone.clone()))
-                .times(1)
+                .times(2)
                 .returning(|_| Ok(()));
 
             let parser = Arc::new(PluginParser::new(Arc::new(MockDefaultFileService::new())));
@@ -1050,8 +1391,7 @@ mod tests {
                 vec![Box::new(mock_installer)],
             );
 
-            let operation_manager =
-                Arc::new(OperationManager::new(crate::ui::Operation::Install).unwrap());
+            let operation_manager = Arc::new(crate::ui::NoopReporter);
             let result = service.install(&[plugin], operation_manager).await;
 
             assert!(result.is_ok());
@@ -1061,6 +1401,10 @@ mod tests {
         async fn test_install_with_git_source() {
             let mut mock_file_service = MockDefaultFileService::new();
             let mut mock_app_config = MockDefaultAppConfig::new();
+            mock_app_config
+                .expect_get_project_dir()
+                .return_const(PathBuf::new());
+            expect_no_installed_folders(&mut mock_app_config, &mut mock_file_service);
 
             let cache_dir = PathBuf::from("/cache");
             mock_app_config
@@ -1092,8 +1436,7 @@ mod tests {
                 vec![Box::new(mock_installer)],
             );
 
-            let operation_manager =
-                Arc::new(OperationManager::new(crate::ui::Operation::Install).unwrap());
+            let operation_manager = Arc::new(crate::ui::NoopReporter);
             let result = service.install(&[plugin], operation_manager).await;
 
             assert!(result.is_ok());
@@ -1102,6 +1445,64 @@ mod tests {
         }
     }
 
+    mod installed_content_hash_tests {
+        use super::*;
+
+        fn setup_service(directory_exists: bool) -> DefaultInstallService {
+            let mut mock_file_service = MockDefaultFileService::new();
+            let mut mock_app_config = MockDefaultAppConfig::new();
+            mock_app_config
+                .expect_get_project_dir()
+                .return_const(PathBuf::new());
+            mock_app_config
+                .expect_get_project_addons_dir()
+                .return_const(PathBuf::from("addons"));
+            mock_file_service
+                .expect_directory_exists()
+                .returning(move |_| directory_exists);
+            mock_file_service
+                .expect_sha256_dir()
+                .returning(|path, _| Ok(path.display().to_string()));
+
+            DefaultInstallService::new(
+                Arc::new(mock_file_service),
+                Box::new(mock_app_config),
+                Arc::new(PluginParser::new(Arc::new(MockDefaultFileService::new()))),
+                vec![],
+            )
+        }
+
+        #[test]
+        fn test_installed_content_hash_should_cover_sub_assets() {
+            let service = setup_service(true);
+            let mut plugin = create_test_plugin("gut", "9.6.0", None);
+
+            let hash = service
+                .installed_content_hash("gut", &plugin, &NoopTask::default())
+                .unwrap();
+            plugin.sub_assets = vec!["gut_extras".to_string()];
+            let hash_with_sub_assets = service
+                .installed_content_hash("gut", &plugin, &NoopTask::default())
+                .unwrap();
+
+            assert!(hash.as_ref().unwrap().starts_with("sha256:"));
+            assert_ne!(hash, hash_with_sub_assets);
+        }
+
+        #[test]
+        fn test_installed_content_hash_without_folder_should_return_none() {
+            let service = setup_service(false);
+            let plugin = create_test_plugin("gut", "9.6.0", None);
+
+            assert_eq!(
+                service
+                    .installed_content_hash("gut", &plugin, &NoopTask::default())
+                    .unwrap(),
+                None
+            );
+        }
+    }
+
     mod default_install_service_tests {
         use super::*;
 
@@ -0,0 +1,138 @@
+use crate::utils::Utils;
+
+use anyhow::{Context, Result};
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::debug;
+
+/// How long `gdm outdated` and `gdm search` reuse Asset Library responses by default
+pub const DEFAULT_METADATA_TTL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedResponse {
+    /// Seconds since the Unix epoch
+    fetched_at: u64,
+    data: Value,
+}
+
+/// Asset Library responses stored on disk and shared between runs and projects,
+/// so repeated `gdm outdated` and `gdm search` don't query every asset again
+#[derive(Debug, Clone)]
+pub struct MetadataCache {
+    dir: PathBuf,
+    ttl: Duration,
+}
+
+impl MetadataCache {
+    pub fn new(dir: impl Into<PathBuf>, ttl: Duration) -> MetadataCache {
+        MetadataCache {
+            dir: dir.into(),
+            ttl,
+        }
+    }
+
+    /// Cache in the `metadata` folder of [`Utils::cache_dir`].
+    /// None if the folder can't be determined or `ttl` is zero.
+    pub fn in_cache_dir(ttl: Duration) -> Option<MetadataCache> {
+        if ttl.is_zero() {
+            return None;
+        }
+        Some(MetadataCache::new(
+            Utils::cache_dir()?.join("metadata"),
+            ttl,
+        ))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The response stored for `key`, unless it's missing, unreadable or older than the TTL
+    pub fn get(&self, key: &str) -> Option<Value> {
+        let content = std::fs::read_to_string(self.path(key)).ok()?;
+        let cached: CachedResponse = serde_json::from_str(&content).ok()?;
+        if Self::now().saturating_sub(cached.fetched_at) >= self.ttl.as_secs() {
+            debug!("Cached metadata expired: {}", key);
+            return None;
+        }
+        debug!("Using cached metadata: {}", key);
+        Some(cached.data)
+    }
+
+    pub fn insert(&self, key: &str, data: &Value) -> Result<()> {
+        std::fs::create_dir_all(&self.dir).with_context(|| {
+            format!(
+                "Failed to create metadata cache folder: {}",
+                self.dir.display()
+            )
+        })?;
+        let cached = CachedResponse {
+            fetched_at: Self::now(),
+            data: data.clone(),
+        };
+        let path = self.path(key);
+        std::fs::write(&path, serde_json::to_string(&cached)?)
+            .with_context(|| format!("Failed to write cached metadata: {}", path.display()))
+    }
+
+    /// Files are named by the SHA-256 of the key, which contains the registry URL and query
+    fn path(&self, key: &str) -> PathBuf {
+        let digest = ring::digest::digest(&ring::digest::SHA256, key.as_bytes());
+        let name = digest
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+        self.dir.join(format!("{}.json", name))
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_should_return_inserted_response() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let cache = MetadataCache::new(dir.path(), DEFAULT_METADATA_TTL);
+        let data = serde_json::json!({ "asset_id": "1709" });
+
+        cache
+            .insert("https://example.com/asset/1709", &data)
+            .unwrap();
+
+        assert_eq!(cache.get("https://example.com/asset/1709"), Some(data));
+        assert_eq!(cache.get("https://example.com/asset/1710"), None);
+    }
+
+    #[test]
+    fn test_get_should_ignore_expired_response() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let cache = MetadataCache::new(dir.path(), DEFAULT_METADATA_TTL);
+        let expired = CachedResponse {
+            fetched_at: MetadataCache::now() - DEFAULT_METADATA_TTL.as_secs(),
+            data: serde_json::json!({ "asset_id": "1709" }),
+        };
+        std::fs::write(
+            cache.path("https://example.com/asset/1709"),
+            serde_json::to_string(&expired).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(cache.get("https://example.com/asset/1709"), None);
+    }
+
+    #[test]
+    fn test_in_cache_dir_with_zero_ttl_should_disable_cache() {
+        assert!(MetadataCache::in_cache_dir(Duration::ZERO).is_none());
+    }
+}
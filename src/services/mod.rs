@@ -1,22 +1,56 @@
+mod advisory_feed;
+mod archive_cache;
+mod asset_index;
+mod bundle;
+mod cache_budget;
+mod config_backups;
+mod content_store;
+mod credential_helper;
 mod extract;
 mod file;
+mod file_watcher;
 mod git;
+mod git_hooks;
+mod godot_editor;
 mod http;
 mod install;
+mod metadata_cache;
 mod plugin;
 mod plugin_parser;
+#[cfg(any(test, feature = "http-recording"))]
+mod recording;
+mod signature_verifier;
+mod update_check;
 
+pub use advisory_feed::{AdvisoryFeedLoader, DEFAULT_ADVISORY_FEED_URL};
+pub use archive_cache::{ArchiveCache, ArchiveStatus, VerifiedArchive};
+pub use asset_index::AssetIndex;
+pub use bundle::{Bundle, BundleContents};
+pub use cache_budget::{CacheBudget, DEFAULT_CACHE_SIZE, EvictedEntry};
+pub use config_backups::{ConfigBackup, ConfigBackups, DEFAULT_CONFIG_BACKUPS};
+pub use content_store::ContentStore;
+pub use credential_helper::CredentialHelper;
 pub use extract::{DefaultExtractService, ExtractService};
 pub use file::{DefaultFileService, FileService};
+pub use file_watcher::FileWatcher;
 pub use git::{DefaultGitService, GitService};
-pub use http::{DefaultHttpService, HttpService};
+pub use git_hooks::{GitHook, GitHooks, HookChange};
+pub use godot_editor::GodotEditor;
+pub use http::{DefaultHttpService, HttpService, USER_AGENT};
 pub use install::{DefaultInstallService, InstallService};
-pub use plugin::{DefaultPluginService, PluginService};
+pub use metadata_cache::{DEFAULT_METADATA_TTL, MetadataCache};
+pub use plugin::{AddOptions, DefaultPluginService, PluginService};
 pub use plugin_parser::PluginParser;
+#[cfg(any(test, feature = "http-recording"))]
+pub use recording::{RECORD_HTTP_ENV, RecordMode, RecordingHttpService};
+pub use signature_verifier::SignatureVerifier;
+pub use update_check::{RELEASES_PAGE_URL, UpdateCheck};
 
 #[cfg(test)]
 pub use file::MockDefaultFileService;
 #[cfg(test)]
+pub use git::MockDefaultGitService;
+#[cfg(test)]
 pub use http::MockDefaultHttpService;
 #[cfg(test)]
 pub use install::MockDefaultInstallService;
@@ -1,19 +1,46 @@
-use crate::api::{AssetListResponse, AssetResponse, AssetStoreAPI, DefaultAssetStoreAPI};
+use crate::api::{
+    AssetListResponse, AssetResponse, AssetSort, AssetStoreAPI, DefaultAssetStoreAPI,
+};
 use crate::config::{
-    AppConfig, DefaultAppConfig, DefaultGdmConfig, DefaultGodotConfig, GdmConfig, GodotConfig,
+    AppConfig, DefaultAppConfig, DefaultGdmConfig, DefaultGdmConfigMetadata, DefaultGodotConfig,
+    GdmConfig, GdmConfigMetadata, GodotConfig,
+};
+use crate::error::{ErrorKind, GdmError};
+use crate::models::{
+    AdvisoryFeed, AuditFinding, ChangeSummary, Credit, Credits, DependencyGraph, FolderOwner,
+    FolderReport, FolderRole, GitFetchOptions, OutdatedPlugin, Plugin, PluginListing, PluginSource,
+    PluginStats, ProjectStats, UpdateChannel, UpdateLevel, UpdateStatus,
 };
-use crate::models::{Plugin, PluginSource};
-use crate::services::{DefaultFileService, DefaultInstallService, FileService, InstallService};
-use crate::ui::{Operation, OperationManager};
+use crate::services::{
+    ArchiveCache, AssetIndex, DefaultFileService, DefaultGitService, DefaultInstallService,
+    FileService, GitService, InstallService, PluginParser,
+};
+use crate::ui::timings::{self, Phase};
+use crate::ui::{Operation, SilentInterface, UserInterface};
 use crate::utils::Utils;
 
 use anyhow::{Context, Result, bail};
-use futures::future::try_join_all;
+use futures::stream::{self, StreamExt, TryStreamExt};
 use std::collections::{BTreeMap, HashMap, HashSet};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tracing::info;
 
+/// Most Asset Library or git remote requests sent at once when checking plugins for updates,
+/// so projects with many plugins don't trip rate limits
+const MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// How `gdm add` records a new plugin
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AddOptions {
+    /// How much of a git plugin's repository is fetched
+    pub git_fetch: GitFetchOptions,
+    /// List the plugin in dev_plugins, so `gdm install --no-dev` skips it
+    pub dev: bool,
+    /// Whether the plugin is left disabled in project.godot, or None to keep its state in gdm.json
+    pub no_enable: Option<bool>,
+}
+
 pub struct DefaultPluginService {
     pub godot_config: Box<dyn GodotConfig>,
     pub gdm_config: Box<dyn GdmConfig>,
@@ -21,29 +48,53 @@ pub struct DefaultPluginService {
     pub file_service: Arc<dyn FileService + Send + Sync>,
     pub asset_store_api: Arc<dyn AssetStoreAPI + Send + Sync>,
     pub install_service: Arc<dyn InstallService + Send + Sync>,
+    pub git_service: Arc<dyn GitService + Send + Sync>,
+    pub archive_cache: Option<ArchiveCache>,
+    /// Refuse plugins outside project.licenses instead of only warning
+    pub verify_license: bool,
+    pub ui: Arc<dyn UserInterface>,
 }
 
 impl Default for DefaultPluginService {
     fn default() -> Self {
-        let asset_store_api = Arc::new(DefaultAssetStoreAPI::default());
-        let file_service = Arc::new(DefaultFileService);
-        let install_service = Arc::new(DefaultInstallService::default());
+        Self::for_app_config(DefaultAppConfig::default())
+    }
+}
 
-        // Create app config for staging service
-        let app_config = DefaultAppConfig::default();
+impl DefaultPluginService {
+    /// Wires the default services with `app_config` instead of the global configuration,
+    /// e.g. to apply the project settings of a reloaded configuration file
+    pub fn for_app_config(app_config: DefaultAppConfig) -> Self {
+        let asset_store_api = Arc::new(DefaultAssetStoreAPI::for_app_config(app_config.clone()));
+        let file_service = Arc::new(DefaultFileService);
+        let install_service = Arc::new(DefaultInstallService::with_asset_store_api(
+            asset_store_api.clone(),
+            app_config.clone(),
+        ));
+        let git_service = Arc::new(DefaultGitService {
+            app_config: app_config.clone(),
+        });
 
         Self {
-            godot_config: Box::new(DefaultGodotConfig::default()),
-            gdm_config: Box::new(DefaultGdmConfig::default()),
+            godot_config: Box::new(DefaultGodotConfig::new(
+                Box::new(DefaultFileService),
+                app_config.clone(),
+            )),
+            gdm_config: Box::new(DefaultGdmConfig::new(
+                app_config.clone(),
+                file_service.clone(),
+            )),
             app_config,
             file_service,
             asset_store_api,
             install_service,
+            git_service,
+            archive_cache: ArchiveCache::in_cache_dir(),
+            verify_license: true,
+            ui: Arc::new(SilentInterface),
         }
     }
-}
 
-impl DefaultPluginService {
     #[allow(unused)]
     pub fn new(
         godot_config: Box<dyn GodotConfig>,
@@ -53,6 +104,9 @@ impl DefaultPluginService {
         asset_store_api: Arc<dyn AssetStoreAPI + Send + Sync>,
         install_service: Arc<dyn InstallService + Send + Sync>,
     ) -> Self {
+        let git_service = Arc::new(DefaultGitService {
+            app_config: app_config.clone(),
+        });
         Self {
             godot_config,
             gdm_config,
@@ -60,20 +114,147 @@ impl DefaultPluginService {
             file_service,
             asset_store_api,
             install_service,
+            git_service,
+            archive_cache: None,
+            verify_license: true,
+            ui: Arc::new(SilentInterface),
+        }
+    }
+
+    /// Shows progress, status messages and prompts through `ui` instead of discarding them
+    pub fn with_ui(mut self, ui: Arc<dyn UserInterface>) -> Self {
+        self.ui = ui;
+        self
+    }
+
+    /// Looks up plugins through `asset_store_api`, e.g. one with a metadata cache for read-only commands
+    pub fn with_asset_store_api(
+        mut self,
+        asset_store_api: Arc<dyn AssetStoreAPI + Send + Sync>,
+    ) -> Self {
+        self.asset_store_api = asset_store_api;
+        self
+    }
+
+    /// Checks git plugins for updates through `git_service`
+    pub fn with_git_service(mut self, git_service: Arc<dyn GitService + Send + Sync>) -> Self {
+        self.git_service = git_service;
+        self
+    }
+
+    /// Only warns about plugins whose license is outside project.licenses, e.g. for --no-verify-license
+    pub fn with_verify_license(mut self, verify_license: bool) -> Self {
+        self.verify_license = verify_license;
+        self
+    }
+
+    /// Tells which plugins that were removed from the Asset Library can still be installed from `archive_cache`
+    pub fn with_archive_cache(mut self, archive_cache: Option<ArchiveCache>) -> Self {
+        self.archive_cache = archive_cache;
+        self
+    }
+
+    /// Reports what the command changed, along with which of the config, project and credits files it wrote
+    fn report_summary(&self, mut summary: ChangeSummary) -> ChangeSummary {
+        let credits_file_path = self.app_config.get_credits_file_path();
+        let mut paths = vec![
+            self.app_config.get_config_file_path(),
+            self.app_config.get_godot_project_file_path(),
+        ];
+        paths.extend(credits_file_path.as_deref());
+        // Listed relative to the project, like the files the CLI writes to its working directory
+        let project_dir = self.app_config.get_project_dir();
+        summary.modified_files = DefaultFileService::take_modified_files(&paths)
+            .into_iter()
+            .map(|path| match path.strip_prefix(&project_dir) {
+                Ok(relative_path) => relative_path.to_path_buf(),
+                Err(_) => path,
+            })
+            .collect();
+        if !summary.is_empty() {
+            self.ui.status(&format!("\n{}", summary));
+        }
+        summary
+    }
+
+    /// `version` if it's given, otherwise the Godot version of project.godot
+    fn effective_godot_version(&self, version: &str) -> Result<String> {
+        let parsed_version = self.godot_config.get_godot_version_from_project()?;
+        let effective_version = if version.is_empty() {
+            if parsed_version.is_empty() {
+                bail!(
+                    GdmError::new(
+                        ErrorKind::ProjectFile,
+                        "Couldn't determine Godot version from project.godot."
+                    )
+                    .with_hint("Provide a version using --godot-version.")
+                );
+            }
+            parsed_version
+        } else {
+            version.to_string()
+        };
+        Ok(effective_version)
+    }
+
+    /// Chooses one of the assets whose title matches `name` when there's more than one:
+    /// the asset titled exactly `name`, ignoring case, or else the one the user picks.
+    /// Returns the ID of the chosen asset, or `None` when nothing was picked.
+    async fn pick_asset(&self, name: &str, godot_version: &str) -> Result<Option<String>> {
+        let params = HashMap::from([
+            ("filter".to_string(), name.to_string()),
+            ("godot_version".to_string(), godot_version.to_string()),
+        ]);
+        let candidates = self.asset_store_api.get_assets(params).await?.result;
+
+        let query = name.trim().to_lowercase();
+        let mut exact_matches = candidates
+            .iter()
+            .filter(|asset| asset.title.trim().to_lowercase() == query);
+        if let (Some(asset), None) = (exact_matches.next(), exact_matches.next()) {
+            self.ui.status(&format!(
+                "Found {} assets matching \"{}\", using \"{}\" (asset ID {}) as its title matches exactly.",
+                candidates.len(),
+                name,
+                asset.title,
+                asset.asset_id
+            ));
+            return Ok(Some(asset.asset_id.clone()));
         }
+
+        let options = candidates
+            .iter()
+            .map(|asset| {
+                format!(
+                    "{} by {} ({})",
+                    asset.title, asset.author, asset.version_string
+                )
+            })
+            .collect::<Vec<_>>();
+        let picked = self.ui.select(
+            &format!(
+                "Found {} assets matching \"{}\". Which one do you want to add?",
+                candidates.len(),
+                name
+            ),
+            &options,
+        )?;
+        Ok(picked
+            .and_then(|index| candidates.get(index))
+            .map(|asset| asset.asset_id.clone()))
     }
 }
 
 impl PluginService for DefaultPluginService {
     async fn process_install(&self, plugins: &[Plugin]) -> Result<BTreeMap<String, Plugin>> {
-        let operation_manager = Arc::new(OperationManager::new(Operation::Install)?);
+        let reporter = self.ui.progress(Operation::Install)?;
 
         let results = self
             .install_service
-            .install(plugins, operation_manager.clone())
+            .install(plugins, reporter.clone())
             .await?;
 
-        operation_manager.finish();
+        reporter.finish();
 
         self.finish_plugins_operation(&results)?;
 
@@ -85,21 +266,69 @@ impl PluginService for DefaultPluginService {
             return Ok(());
         }
 
-        let operation_manager = OperationManager::new(Operation::Finished)?;
+        let reporter = self.ui.progress(Operation::Finished)?;
         for (index, plugin) in plugins.values().enumerate() {
-            let finished_bar = operation_manager.add_progress_bar(
+            let finished_task = reporter.started(
+                &Operation::Finished,
                 index,
                 plugins.len(),
                 &plugin.title,
                 &plugin.get_version(),
             )?;
-            finished_bar.finish();
+            finished_task.finished();
         }
-        operation_manager.finish();
+        reporter.finish();
         info!("Finished processing {} plugins successfully", plugins.len());
         Ok(())
     }
 
+    /// Checks the licenses of plugins about to be installed against project.licenses.
+    /// Plugins without a known license, e.g. from git, aren't checked.
+    fn check_license_policy(&self, plugins: &[Plugin]) -> Result<()> {
+        let Some(policy) = self.app_config.get_license_policy() else {
+            return Ok(());
+        };
+        let refused: Vec<String> = plugins
+            .iter()
+            .filter_map(|plugin| {
+                let license = plugin.license.as_deref().filter(|l| !l.is_empty())?;
+                (!policy.allows(license)).then(|| format!("{} ({})", plugin.title, license))
+            })
+            .collect();
+        if refused.is_empty() {
+            return Ok(());
+        }
+        let message = format!(
+            "The project's license policy doesn't allow {}.",
+            refused.join(", ")
+        );
+        if self.verify_license {
+            bail!(GdmError::new(ErrorKind::LicensePolicy, message));
+        }
+        self.ui.warning(&message);
+        Ok(())
+    }
+
+    fn warn_incompatible_plugins<'a>(
+        &self,
+        plugins: impl IntoIterator<Item = &'a Plugin>,
+    ) -> Result<()> {
+        let plugins: Vec<&Plugin> = plugins
+            .into_iter()
+            .filter(|plugin| plugin.godot_version.is_some())
+            .collect();
+        if plugins.is_empty() {
+            return Ok(());
+        }
+        let project_godot_version = self.godot_config.get_godot_version_from_project()?;
+        for plugin in plugins {
+            if let Some(warning) = plugin.check_godot_compatibility(&project_godot_version) {
+                self.ui.warning(&warning);
+            }
+        }
+        Ok(())
+    }
+
     /// Helper to find metadata for a plugin before adding it (Asset Lib only)
     async fn find_asset_metadata(
         &self,
@@ -109,46 +338,169 @@ impl PluginService for DefaultPluginService {
     ) -> Result<AssetResponse> {
         let godot_version = self.godot_config.get_godot_version_from_project()?;
 
-        if !version.is_empty() && !asset_id.is_empty() {
-            return self
-                .asset_store_api
-                .get_asset_by_id_and_version(asset_id, version)
-                .await;
-        }
+        let reporter = self.ui.progress(Operation::Resolve)?;
+        let resolve_task = reporter.started(
+            &Operation::Resolve,
+            0,
+            1,
+            if name.is_empty() { asset_id } else { name },
+            if version.is_empty() {
+                "latest"
+            } else {
+                version
+            },
+        )?;
+
+        let asset = async {
+            if !version.is_empty() && !asset_id.is_empty() {
+                return self
+                    .asset_store_api
+                    .get_asset_by_id_and_version(asset_id, version)
+                    .await;
+            }
 
-        if !name.is_empty() && !version.is_empty() {
-            return self
-                .asset_store_api
-                .find_asset_by_asset_name_and_version_and_godot_version(
-                    name,
-                    version,
-                    &godot_version,
-                )
-                .await;
-        }
+            if !name.is_empty() && !version.is_empty() {
+                return self
+                    .asset_store_api
+                    .find_asset_by_asset_name_and_version_and_godot_version(
+                        name,
+                        version,
+                        &godot_version,
+                    )
+                    .await;
+            }
+
+            if !name.is_empty() || !asset_id.is_empty() {
+                return self
+                    .asset_store_api
+                    .find_asset_by_id_or_name_and_version(asset_id, name, &godot_version)
+                    .await;
+            }
 
-        if !name.is_empty() || !asset_id.is_empty() {
-            return self
-                .asset_store_api
-                .find_asset_by_id_or_name_and_version(asset_id, name, &godot_version)
-                .await;
+            bail!(GdmError::new(
+                ErrorKind::Usage,
+                "No name or asset ID provided"
+            ))
         }
+        .await;
 
-        bail!("No name or asset ID provided")
+        resolve_task.finished();
+        reporter.finish();
+
+        match asset {
+            Err(e)
+                if !name.is_empty()
+                    && GdmError::find(&e).is_some_and(|e| e.kind == ErrorKind::Conflict) =>
+            {
+                match self.pick_asset(name, &godot_version).await? {
+                    Some(picked) if version.is_empty() => {
+                        self.asset_store_api.get_asset_by_id(&picked).await
+                    }
+                    Some(picked) => {
+                        self.asset_store_api
+                            .get_asset_by_id_and_version(&picked, version)
+                            .await
+                    }
+                    None => Err(e),
+                }
+            }
+            asset => asset,
+        }
     }
 
-    async fn install_all_plugins(&self) -> Result<BTreeMap<String, Plugin>> {
+    async fn install_plugins(
+        &self,
+        names: &[String],
+        include_dev: bool,
+        locked: bool,
+    ) -> Result<BTreeMap<String, Plugin>> {
         if !self.gdm_config.has_installed_plugins()? {
-            bail!("No plugins installed.");
+            bail!(
+                GdmError::new(ErrorKind::NotFound, "No plugins installed.")
+                    .with_hint("Add a plugin with gdm add.")
+            );
         }
 
+        // Nothing is resolved with --frozen, so gdm.json must record everything to verify
+        let locked = locked || self.app_config.get_frozen();
         let all_plugins_map = self.gdm_config.get_plugins()?;
-        let all_plugins: Vec<Plugin> = all_plugins_map.values().cloned().collect();
+        let selected_plugins = self.select_plugins_by_names(names)?;
+        let plugins_to_check: Vec<_> = selected_plugins
+            .iter()
+            .filter(|(_, plugin)| include_dev || !plugin.dev)
+            .collect();
+        let mut up_to_date = BTreeMap::new();
+        let mut plugins_to_install = Vec::new();
+        let reporter = self.ui.progress(Operation::Verify)?;
+        for (index, &(name, plugin)) in plugins_to_check.iter().enumerate() {
+            if locked {
+                plugin.require_integrity_hashes()?;
+            }
+            let mut already_installed = false;
+            if plugin.content_hash.is_some() {
+                let verify_task = reporter.started(
+                    &Operation::Verify,
+                    index,
+                    plugins_to_check.len(),
+                    name,
+                    &plugin.get_version(),
+                )?;
+                let installed_hash = self.install_service.installed_content_hash(
+                    name,
+                    plugin,
+                    verify_task.as_ref(),
+                )?;
+                verify_task.finished();
+                already_installed = installed_hash == plugin.content_hash;
+            }
+            if already_installed {
+                info!("{} {} is already installed", name, plugin.get_version());
+                up_to_date.insert(name.clone(), plugin.clone());
+            } else {
+                plugins_to_install.push(plugin.clone());
+            }
+        }
+        reporter.finish();
 
-        let installed_plugins = self.process_install(&all_plugins).await?;
+        let mut installed_plugins = if plugins_to_install.is_empty() {
+            BTreeMap::new()
+        } else {
+            self.process_install(&plugins_to_install).await?
+        };
+        if locked {
+            for (name, installed) in &installed_plugins {
+                if let Some(recorded) = all_plugins_map.get(name) {
+                    recorded.verify_content_hash(installed.content_hash.as_deref())?;
+                }
+            }
+        }
+        let mut summary = ChangeSummary::from_installed(&all_plugins_map, &installed_plugins);
+        summary.up_to_date = up_to_date
+            .iter()
+            .map(|(name, plugin)| (name.clone(), plugin.get_version()))
+            .collect();
+        installed_plugins.extend(up_to_date);
+        self.warn_incompatible_plugins(installed_plugins.values())?;
+        if !include_dev {
+            summary.skipped = selected_plugins
+                .iter()
+                .filter(|(_, plugin)| plugin.dev)
+                .map(|(name, _)| (name.clone(), "dev plugin".to_string()))
+                .collect();
+        }
 
-        self.add_plugins(&installed_plugins)?;
+        if include_dev {
+            self.add_plugins(&installed_plugins)?;
+        } else {
+            // Dev plugins aren't installed, so they must not be enabled in project.godot either
+            let plugin_config = self.gdm_config.add_plugins(&installed_plugins)?;
+            let (plugin_config, dev_plugins) = plugin_config.without_dev_plugins();
+            self.godot_config
+                .save_with_removed(plugin_config, dev_plugins)?;
+            self.update_credits()?;
+        }
         info!("All plugins installed successfully");
+        self.report_summary(summary);
         Ok(installed_plugins)
     }
 
@@ -159,12 +511,16 @@ impl PluginService for DefaultPluginService {
         version: Option<String>,
         git_url: Option<String>,
         git_reference: Option<String>,
-    ) -> Result<()> {
+        options: AddOptions,
+    ) -> Result<ChangeSummary> {
         let is_asset_based = asset_id.is_some() || name.is_some() || version.is_some();
         let is_git_based = git_url.is_some() || git_reference.is_some();
 
         if is_asset_based && is_git_based {
-            bail!("Cannot specify name/asset_id/version together with git URL/reference.")
+            bail!(GdmError::new(
+                ErrorKind::Usage,
+                "Cannot specify name/asset_id/version together with git URL/reference."
+            ))
         }
 
         let plugin_to_install: Plugin;
@@ -175,41 +531,64 @@ impl PluginService for DefaultPluginService {
             let version = version.unwrap_or_default();
 
             if !name.is_empty() && !asset_id.is_empty() {
-                bail!("Cannot specify both name and asset ID.")
+                bail!(GdmError::new(
+                    ErrorKind::Usage,
+                    "Cannot specify both name and asset ID."
+                ))
             }
 
             if name.is_empty() && asset_id.is_empty() {
-                bail!("Either name or asset ID must be provided.")
+                bail!(GdmError::new(
+                    ErrorKind::Usage,
+                    "Either name or asset ID must be provided."
+                ))
             }
 
             // 1. Verify availability in store and get metadata
             let asset_response = self.find_asset_metadata(&name, &asset_id, &version).await?;
 
             // 2. Check overlap with existing
+            let mut new_plugin = Plugin::from(asset_response.clone());
             if let Some(existing) = self
                 .gdm_config
                 .get_plugin_by_asset_id(&asset_response.asset_id)?
             {
-                let new_plugin = Plugin::from(asset_response.clone());
+                if new_plugin < existing
+                    && !self.ui.confirm(&format!(
+                        "Downgrade plugin '{}' from {} to {}?",
+                        existing.title,
+                        existing.get_version(),
+                        new_plugin.get_version()
+                    ))?
+                {
+                    self.ui.status("Downgrade cancelled.");
+                    return Ok(ChangeSummary::default());
+                }
                 if new_plugin != existing {
-                    println!(
+                    self.ui.status(&format!(
                         "Updating plugin '{}' from {} to {}",
                         existing.title,
                         existing.get_version(),
                         new_plugin.get_version()
-                    );
+                    ));
                 } else {
-                    println!("Plugin '{}' is already in dependencies.", existing.title);
+                    self.ui.status(&format!(
+                        "Plugin '{}' is already in dependencies.",
+                        existing.title
+                    ));
+                    new_plugin.checksum = existing.checksum.clone();
                 }
+                new_plugin.addons_dir = existing.addons_dir.clone();
             }
 
-            plugin_to_install = Plugin::from(asset_response);
+            plugin_to_install = new_plugin;
         } else if is_git_based {
-            let git_url = git_url.ok_or_else(|| anyhow::anyhow!("Git URL must be provided."))?;
+            let git_url = git_url
+                .ok_or_else(|| GdmError::new(ErrorKind::Usage, "Git URL must be provided."))?;
             let reference = git_reference.unwrap_or_else(|| "main".to_string());
 
             if git_url.is_empty() {
-                bail!("Git URL must be provided.")
+                bail!(GdmError::new(ErrorKind::Usage, "Git URL must be provided."))
             }
 
             plugin_to_install = Plugin {
@@ -217,13 +596,40 @@ impl PluginService for DefaultPluginService {
                     url: git_url,
                     reference,
                 }),
+                git_fetch: options.git_fetch,
                 ..Plugin::default()
             };
         } else {
-            bail!("Either name, asset_id, version OR git URL/reference must be provided.")
+            bail!(GdmError::new(
+                ErrorKind::Usage,
+                "Either name, asset_id, version OR git URL/reference must be provided."
+            ))
         }
 
-        let installed = self.process_install(&[plugin_to_install]).await?;
+        self.check_license_policy(std::slice::from_ref(&plugin_to_install))?;
+        let plugins_before = self.gdm_config.get_plugins()?;
+        let mut installed = self.process_install(&[plugin_to_install]).await?;
+        for plugin in installed.values_mut() {
+            plugin.dev = options.dev;
+            plugin.enabled = options.no_enable != Some(true);
+        }
+        self.warn_incompatible_plugins(installed.values())?;
+
+        // Re-added plugins stay disabled in gdm.json unless they're enabled first
+        if options.no_enable == Some(false) {
+            let disabled: HashSet<String> = installed
+                .keys()
+                .filter(|key| {
+                    plugins_before
+                        .get(*key)
+                        .is_some_and(|plugin| !plugin.enabled)
+                })
+                .cloned()
+                .collect();
+            if !disabled.is_empty() {
+                self.gdm_config.enable_plugins(disabled)?;
+            }
+        }
 
         self.add_plugins(&installed)?;
 
@@ -231,12 +637,16 @@ impl PluginService for DefaultPluginService {
             "Plugins installed successfully: {:?}",
             installed.keys().collect::<Vec<_>>()
         );
-        Ok(())
+        Ok(self.report_summary(ChangeSummary::from_installed(&plugins_before, &installed)))
     }
 
     fn add_plugins(&self, plugins: &BTreeMap<String, Plugin>) -> Result<()> {
-        let plugin_config = self.gdm_config.add_plugins(plugins)?;
-        self.godot_config.save(plugin_config)?;
+        let subject = self.app_config.get_config_file_path().display().to_string();
+        timings::measure_sync(&subject, Phase::Config, || -> Result<()> {
+            let plugin_config = self.gdm_config.add_plugins(plugins)?;
+            self.godot_config.save(plugin_config)
+        })?;
+        self.update_credits()?;
         info!(
             "Added {} plugins to configuration successfully",
             plugins.len()
@@ -244,217 +654,960 @@ impl PluginService for DefaultPluginService {
         Ok(())
     }
 
-    async fn remove_plugin_by_name(&self, name: &str) -> Result<()> {
+    fn list_plugins(&self) -> Result<PluginListing> {
+        let gdm_config = self.gdm_config.load()?;
+        let unmanaged = self.godot_config.get_unmanaged_plugins(&gdm_config)?;
+        Ok(PluginListing {
+            plugins: gdm_config.plugins,
+            unmanaged,
+        })
+    }
+
+    async fn remove_plugin_by_name(&self, name: &str) -> Result<ChangeSummary> {
         if !self.gdm_config.has_installed_plugins()? {
-            bail!("No plugins installed.");
+            bail!(
+                GdmError::new(ErrorKind::NotFound, "No plugins installed.")
+                    .with_hint("Add a plugin with gdm add.")
+            );
         }
 
-        let installed_plugin = self.gdm_config.get_plugin_by_name(name);
-        let addon_folder = self.app_config.get_addon_folder_path();
+        match self.gdm_config.get_plugin_by_name(name) {
+            Some((plugin_name, plugin)) => self.remove_plugin(plugin_name, plugin),
+            None => Err(self.not_installed_error(name)?.into()),
+        }
+    }
 
-        match installed_plugin {
-            Some((plugin_name, plugin)) => {
-                let plugin_folder_path = Utils::plugin_name_to_addon_folder_path(
-                    &addon_folder,
-                    Path::new(plugin_name.as_str()),
-                );
+    async fn remove_plugin_by_asset_id(&self, asset_id: &str) -> Result<ChangeSummary> {
+        if !self.gdm_config.has_installed_plugins()? {
+            bail!(
+                GdmError::new(ErrorKind::NotFound, "No plugins installed.")
+                    .with_hint("Add a plugin with gdm add.")
+            );
+        }
 
-                if self.file_service.directory_exists(&plugin_folder_path) {
-                    println!("Removing plugin folder: {}", plugin_folder_path.display());
-                    self.file_service.remove_dir_all(&plugin_folder_path)?
-                } else {
-                    println!("Plugin folder does not exist, removing from config only.");
-                }
+        let not_installed = || {
+            GdmError::new(
+                ErrorKind::NotFound,
+                format!("No plugin with asset ID {} is installed.", asset_id),
+            )
+            .with_hint("Run gdm list to see the installed plugins.")
+        };
+        let Some(plugin) = self.gdm_config.get_plugin_by_asset_id(asset_id)? else {
+            bail!(not_installed());
+        };
+        let Some(plugin_name) = self
+            .gdm_config
+            .get_plugins()?
+            .into_iter()
+            .find(|(_, installed)| installed.source == plugin.source)
+            .map(|(key, _)| key)
+        else {
+            bail!(not_installed());
+        };
+        self.remove_plugin(plugin_name, plugin)
+    }
 
-                for asset in &plugin.sub_assets {
-                    let sub_path = Utils::plugin_name_to_addon_folder_path(
-                        &addon_folder,
-                        Path::new(asset.as_str()),
-                    );
-                    if self.file_service.directory_exists(&sub_path) {
-                        println!("Removing sub-asset folder: {}", sub_path.display());
-                        self.file_service.remove_dir_all(&sub_path)?
-                    }
-                }
+    /// Deletes the plugin's folders and removes it from the configuration files
+    fn remove_plugin(&self, plugin_name: String, plugin: Plugin) -> Result<ChangeSummary> {
+        let addon_folder = self
+            .app_config
+            .get_project_dir()
+            .join(plugin.get_addons_dir(self.app_config.get_project_addons_dir()));
+        let plugin_folder_path =
+            Utils::plugin_name_to_addon_folder_path(&addon_folder, Path::new(plugin_name.as_str()));
+
+        if !self.ui.confirm(&format!(
+            "Remove plugin {} and delete {}?",
+            plugin_name,
+            plugin_folder_path.display()
+        ))? {
+            self.ui.status("Remove cancelled.");
+            return Ok(ChangeSummary::default());
+        }
 
-                let plugin_config = self
-                    .gdm_config
-                    .remove_plugins(HashSet::from([plugin_name.clone()]))
-                    .context(format!(
-                        "Failed to remove plugin {} from configuration",
-                        plugin_name
-                    ))?;
-
-                self.godot_config.save(plugin_config)?;
-                println!("Plugin {} removed successfully.", plugin_name);
-                Ok(())
+        if self.file_service.directory_exists(&plugin_folder_path) {
+            self.ui.status(&format!(
+                "Removing plugin folder: {}",
+                plugin_folder_path.display()
+            ));
+            self.file_service.remove_dir_all(&plugin_folder_path)?
+        } else {
+            self.ui
+                .status("Plugin folder does not exist, removing from config only.");
+        }
+
+        for asset in &plugin.sub_assets {
+            let sub_path =
+                Utils::plugin_name_to_addon_folder_path(&addon_folder, Path::new(asset.as_str()));
+            if self.file_service.directory_exists(&sub_path) {
+                self.ui.status(&format!(
+                    "Removing sub-asset folder: {}",
+                    sub_path.display()
+                ));
+                self.file_service.remove_dir_all(&sub_path)?
             }
-            None => {
-                println!("Plugin {} is not installed.", name);
-                Ok(())
+        }
+
+        let plugin_config = self
+            .gdm_config
+            .remove_plugins(HashSet::from([plugin_name.clone()]))
+            .context(format!(
+                "Failed to remove plugin {} from configuration",
+                plugin_name
+            ))?;
+
+        self.godot_config
+            .save_with_removed(plugin_config, vec![plugin])?;
+        self.update_credits()?;
+        self.ui
+            .success(&format!("Plugin {} removed successfully.", plugin_name));
+        Ok(self.report_summary(ChangeSummary {
+            removed: vec![plugin_name],
+            ..ChangeSummary::default()
+        }))
+    }
+
+    /// Error for a name that matches no plugin, suggesting the closest one, or more than one
+    fn not_installed_error(&self, name: &str) -> Result<GdmError> {
+        let plugins = self.gdm_config.get_plugins()?;
+        let candidates = DefaultGdmConfigMetadata::new(plugins.clone()).find_plugins_by_name(name);
+        if candidates.len() > 1 {
+            let keys = candidates
+                .iter()
+                .map(|(key, _)| key.as_str())
+                .collect::<Vec<_>>();
+            return Ok(GdmError::new(
+                ErrorKind::Conflict,
+                format!(
+                    "Plugin name {} is ambiguous, it matches {}.",
+                    name,
+                    keys.join(", ")
+                ),
+            )
+            .with_hint("Use the full name or alias of one of them."));
+        }
+        let names = plugins
+            .iter()
+            .flat_map(|(key, plugin)| std::iter::once(key.as_str()).chain(plugin.alias.as_deref()));
+        let error = GdmError::new(
+            ErrorKind::NotFound,
+            format!("Plugin {} is not installed.", name),
+        )
+        .with_plugin(name);
+        Ok(match Utils::closest_match(name, names) {
+            Some(suggestion) => error.with_hint(format!("Did you mean {}?", suggestion)),
+            None => error.with_hint("Run gdm list to see the installed plugins."),
+        })
+    }
+
+    /// Plugins in the configuration file matching the names, aliases or Asset Library IDs,
+    /// or all of them when no names are given
+    fn select_plugins_by_names(&self, names: &[String]) -> Result<BTreeMap<String, Plugin>> {
+        let plugins = self.gdm_config.get_plugins()?;
+        if names.is_empty() {
+            return Ok(plugins);
+        }
+        let mut selected_plugins = BTreeMap::new();
+        for name in names {
+            let found = self.gdm_config.get_plugin_by_name(name).or_else(|| {
+                plugins
+                    .iter()
+                    .find(|(_, plugin)| {
+                        matches!(&plugin.source, Some(PluginSource::AssetLibrary { asset_id }) if asset_id == name)
+                    })
+                    .map(|(key, plugin)| (key.clone(), plugin.clone()))
+            });
+            let Some((key, plugin)) = found else {
+                bail!(self.not_installed_error(name)?);
+            };
+            selected_plugins.insert(key, plugin);
+        }
+        Ok(selected_plugins)
+    }
+
+    /// Resolves plugin names or aliases to Asset Library IDs, leaving out git plugins.
+    /// Returns None when no names are given.
+    fn resolve_asset_ids_by_names(&self, names: &[String]) -> Result<Option<HashSet<String>>> {
+        if names.is_empty() {
+            return Ok(None);
+        }
+        let mut asset_ids = HashSet::new();
+        for name in names {
+            let Some((_, plugin)) = self.gdm_config.get_plugin_by_name(name) else {
+                bail!(self.not_installed_error(name)?);
+            };
+            if let Some(PluginSource::AssetLibrary { asset_id }) = plugin.source {
+                asset_ids.insert(asset_id);
             }
         }
+        Ok(Some(asset_ids))
     }
 
-    /// Fetches plugins listed in the dependency file without version pinning (for update checking)
+    /// Fetches plugins listed in the dependency file without version pinning (for update checking).
+    /// Plugins on the edge channel get the newest submitted edit if it's newer than the approved version.
+    /// Plugins whose asset was removed from the library are left out with a warning.
     async fn fetch_latest_assets(&self) -> Result<Vec<AssetResponse>> {
         let plugins = self.gdm_config.get_plugins()?;
         let godot_version = self.godot_config.get_godot_version_from_project()?;
+        let reporter = self.ui.progress(Operation::Resolve)?;
+        let total = plugins
+            .values()
+            .filter(|plugin| matches!(plugin.source, Some(PluginSource::AssetLibrary { .. })))
+            .count();
 
         let mut assets_futures = Vec::new();
+        let mut asset_plugins = Vec::new();
 
         for plugin in plugins.values() {
             if let Some(PluginSource::AssetLibrary { asset_id }) = &plugin.source {
+                asset_plugins.push(plugin);
                 let id = asset_id.clone();
                 let g_ver = godot_version.clone();
                 let api = self.asset_store_api.clone();
+                let channel = plugin.channel;
+                let subject = plugin.title.clone();
 
-                assets_futures.push(async move {
-                    api.find_asset_by_id_or_name_and_version(&id, "", &g_ver)
+                let fetch_latest = async move {
+                    let asset = match api
+                        .find_asset_by_id_or_name_and_version(&id, "", &g_ver)
                         .await
+                    {
+                        Ok(asset) => asset,
+                        Err(e)
+                            if GdmError::find(&e)
+                                .is_some_and(|e| e.kind == ErrorKind::NotFound) =>
+                        {
+                            return Ok(None);
+                        }
+                        Err(e) => return Err(e),
+                    };
+                    if channel == UpdateChannel::Edge
+                        && let Some(edit) = api.get_newest_asset_edit(&id).await?
+                        && Utils::parse_semantic_version(&edit.version_string)
+                            > Utils::parse_semantic_version(&asset.version_string)
+                    {
+                        info!("Using unreviewed edit {} of {}", edit.version_string, id);
+                        return Ok(Some(edit));
+                    }
+                    anyhow::Ok(Some(asset))
+                };
+                let index = assets_futures.len();
+                let version = plugin.get_version();
+                let reporter = reporter.clone();
+                assets_futures.push(async move {
+                    // Started when the request is sent, so only running requests show progress
+                    let resolve_task =
+                        reporter.started(&Operation::Resolve, index, total, &subject, &version)?;
+                    let asset = timings::measure(&subject, Phase::Metadata, fetch_latest).await;
+                    resolve_task.finished();
+                    asset
                 });
             }
         }
 
-        let fetched_assets: Vec<AssetResponse> = try_join_all(assets_futures)
-            .await
-            .context("Failed to fetch latest plugins from Asset Store API")?;
+        let fetched_assets: Result<Vec<_>> = stream::iter(assets_futures)
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .try_collect()
+            .await;
+        reporter.finish();
+        let fetched_assets =
+            fetched_assets.context("Failed to fetch latest plugins from Asset Store API")?;
 
-        Ok(fetched_assets)
+        for (plugin, _) in asset_plugins
+            .iter()
+            .zip(&fetched_assets)
+            .filter(|(_, asset)| asset.is_none())
+        {
+            self.ui.warning(&self.delisted_warning(plugin));
+        }
+        Ok(fetched_assets.into_iter().flatten().collect())
     }
 
-    async fn check_outdated_plugins(&self) -> Result<()> {
-        if !self.gdm_config.has_installed_plugins()? {
-            bail!("No plugins installed.");
+    fn delisted_warning(&self, plugin: &Plugin) -> String {
+        let asset_id = match &plugin.source {
+            Some(PluginSource::AssetLibrary { asset_id }) => asset_id.as_str(),
+            _ => "",
+        };
+        let message = format!(
+            "{} (asset {}) is no longer in the Asset Library, it may have been removed or delisted. It stays at {}.",
+            plugin.title,
+            asset_id,
+            plugin.get_version()
+        );
+        match self
+            .archive_cache
+            .as_ref()
+            .and_then(|cache| cache.get(asset_id, &plugin.version))
+        {
+            Some(archive) => format!(
+                "{} gdm install falls back to its last downloaded archive {}",
+                message,
+                archive.display()
+            ),
+            None => format!(
+                "{} No archive of it is cached, so it can't be installed again. Consider replacing it.",
+                message
+            ),
         }
+    }
 
-        let installed_latest = self.fetch_latest_assets().await?;
-        let mut plugins_to_update = Vec::new();
-
-        println!("{0: <40} {1: <20} {2: <20}", "Plugin", "Current", "Latest");
-
-        for asset in installed_latest {
-            let current_plugin_opt = self.gdm_config.get_plugin_by_asset_id(&asset.asset_id)?;
+    /// The newest version of an Asset Library plugin newer than `current` that `level` allows:
+    /// `latest` if it does, otherwise the newest one in the asset's edits
+    async fn newest_allowed_asset(
+        &self,
+        current: &Plugin,
+        latest: &Plugin,
+        include_prerelease: bool,
+        level: UpdateLevel,
+    ) -> Result<Option<Plugin>> {
+        if !current.is_updated_by(latest, include_prerelease) {
+            return Ok(None);
+        }
+        if level.allows_plugin(current, latest) {
+            return Ok(Some(latest.clone()));
+        }
+        let Some(PluginSource::AssetLibrary { asset_id }) = &current.source else {
+            return Ok(None);
+        };
+        let current_version = Utils::parse_semantic_version(&current.version);
+        let versions = self
+            .asset_store_api
+            .get_asset_versions(asset_id, &latest.version)
+            .await?;
+        let wanted = versions
+            .iter()
+            .map(|version| (version, Utils::parse_semantic_version(version)))
+            .filter(|(_, version)| {
+                include_prerelease || current.allow_prerelease || version.pre.is_empty()
+            })
+            .filter(|(_, version)| {
+                *version > current_version && level.allows(&current_version, version)
+            })
+            .max_by(|(_, a), (_, b)| a.cmp(b));
+        let Some((version, _)) = wanted else {
+            return Ok(None);
+        };
+        let asset = self
+            .asset_store_api
+            .get_asset_by_id_and_version(asset_id, version)
+            .await?;
+        let mut wanted = Plugin::from(asset);
+        wanted.addons_dir = current.addons_dir.clone();
+        Ok(Some(wanted))
+    }
 
-            if let Some(curr) = current_plugin_opt {
-                let latest_plugin = Plugin::from(asset);
-                let has_update = latest_plugin > curr;
+    /// Checks the remote of each git plugin. Plugins pinned to a version tag, e.g. `v9.2.0`, are updated
+    /// to the newest version tag, and plugins tracking a branch when it moved past the installed commit
+    /// and its addons tree differs from the checksum.
+    /// Plugins pinned to other tags or commits are always up to date.
+    async fn fetch_latest_git_plugins(
+        &self,
+        plugins: Vec<Plugin>,
+        include_prerelease: bool,
+        level: UpdateLevel,
+    ) -> Result<Vec<OutdatedPlugin>> {
+        let mut git_futures = Vec::new();
+
+        for plugin in plugins {
+            let Some(PluginSource::Git { url, reference }) = plugin.source.clone() else {
+                continue;
+            };
+            let git_service = self.git_service.clone();
+            let include_prerelease = include_prerelease || plugin.allow_prerelease;
+            let subject = url.clone();
+
+            let fetch_latest = move || -> Result<OutdatedPlugin> {
+                let refs = git_service.list_remote_refs(&url)?;
+                let remote_commit = |name: String| {
+                    refs.iter()
+                        .find(|(remote_ref, _)| *remote_ref == name)
+                        .map(|(_, commit)| commit.clone())
+                };
+                let mut latest = plugin.clone();
+                latest.checksum = None;
+                latest.content_hash = None;
+                let at_tag = |tag: &str| {
+                    let mut plugin = latest.clone();
+                    plugin.source = Some(PluginSource::Git {
+                        url: url.clone(),
+                        reference: tag.to_string(),
+                    });
+                    plugin.commit = remote_commit(format!("refs/tags/{}", tag));
+                    plugin
+                };
 
-                if has_update {
-                    plugins_to_update.push(latest_plugin.clone());
+                if remote_commit(format!("refs/tags/{}", reference)).is_some() {
+                    let current_version = Utils::parse_version_tag(&reference);
+                    let tags = || {
+                        refs.iter()
+                            .filter_map(|(remote_ref, _)| remote_ref.strip_prefix("refs/tags/"))
+                    };
+                    let allowed_tags = tags().filter(|tag| {
+                        match (&current_version, Utils::parse_version_tag(tag)) {
+                            (Some(current), Some(version)) => level.allows(current, &version),
+                            _ => true,
+                        }
+                    });
+                    let wanted =
+                        Utils::newest_version_tag(allowed_tags, &reference, include_prerelease);
+                    let newest = Utils::newest_version_tag(tags(), &reference, include_prerelease);
+                    let (wanted, latest, status) = match (wanted, newest) {
+                        (Some(wanted), Some(newest)) => (
+                            at_tag(wanted),
+                            at_tag(newest),
+                            UpdateStatus::UpdateAvailable,
+                        ),
+                        (None, Some(newest)) => {
+                            (plugin.clone(), at_tag(newest), UpdateStatus::Blocked)
+                        }
+                        _ => (plugin.clone(), latest, UpdateStatus::UpToDate),
+                    };
+                    return Ok(OutdatedPlugin {
+                        current: plugin,
+                        wanted,
+                        latest,
+                        status,
+                    });
                 }
 
-                println!(
-                    "{0: <40} {1: <20} {2: <20} {3}",
-                    curr.title,
-                    curr.get_version(),
-                    latest_plugin.get_version(),
-                    if has_update { "(update available)" } else { "" }
-                );
-            }
-        }
-        println!();
+                let status =
+                    if let Some(commit) = remote_commit(format!("refs/heads/{}", reference)) {
+                        latest.commit = Some(commit.clone());
+                        if plugin.commit == Some(commit) {
+                            UpdateStatus::UpToDate
+                        } else {
+                            // The branch moved, but commits outside the addons folder don't need a reinstall
+                            let tree_id = git_service.fetch_addons_tree_id(&url, &reference)?;
+                            if plugin.checksum == Some(format!("git-tree:{}", tree_id)) {
+                                UpdateStatus::UpToDate
+                            } else {
+                                UpdateStatus::UpdateAvailable
+                            }
+                        }
+                    } else {
+                        UpdateStatus::UpToDate
+                    };
 
-        if plugins_to_update.is_empty() {
-            println!("All plugins are up to date.");
-        } else {
-            println!("To update plugins, use: gdm update");
+                let wanted = if status == UpdateStatus::UpdateAvailable {
+                    latest.clone()
+                } else {
+                    plugin.clone()
+                };
+                Ok(OutdatedPlugin {
+                    current: plugin,
+                    wanted,
+                    latest,
+                    status,
+                })
+            };
+            git_futures.push(async move {
+                let fetch_latest = tokio::task::spawn_blocking(fetch_latest);
+                timings::measure(&subject, Phase::Metadata, fetch_latest).await?
+            });
         }
-        Ok(())
-    }
 
-    async fn update_plugins(&self) -> Result<BTreeMap<String, Plugin>> {
-        let plugins_map = self.gdm_config.get_plugins()?;
+        stream::iter(git_futures)
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .try_collect()
+            .await
+            .context("Failed to check git plugins for updates")
+    }
 
-        if plugins_map.is_empty() {
-            bail!("No plugins installed.");
+    async fn check_outdated_plugins(
+        &self,
+        include_prerelease: bool,
+        level: UpdateLevel,
+    ) -> Result<Vec<OutdatedPlugin>> {
+        if !self.gdm_config.has_installed_plugins()? {
+            bail!(
+                GdmError::new(ErrorKind::NotFound, "No plugins installed.")
+                    .with_hint("Add a plugin with gdm add.")
+            );
         }
 
         let installed_latest = self.fetch_latest_assets().await?;
-        let mut plugins_to_install = Vec::new();
+        let mut outdated_plugins = Vec::new();
 
+        let plugins: Vec<Plugin> = self.gdm_config.get_plugins()?.into_values().collect();
+        let delisted_plugins: Vec<OutdatedPlugin> = plugins
+            .iter()
+            .filter(|plugin| match &plugin.source {
+                Some(PluginSource::AssetLibrary { asset_id }) => !installed_latest
+                    .iter()
+                    .any(|asset| asset.asset_id == *asset_id),
+                _ => false,
+            })
+            .map(|plugin| OutdatedPlugin {
+                current: plugin.clone(),
+                wanted: plugin.clone(),
+                latest: plugin.clone(),
+                status: UpdateStatus::Delisted,
+            })
+            .collect();
         for asset in installed_latest {
-            if let Some(curr) = self.gdm_config.get_plugin_by_asset_id(&asset.asset_id)? {
-                let latest_plugin = Plugin::from(asset);
-                if latest_plugin > curr {
-                    plugins_to_install.push(latest_plugin);
-                }
+            if let Some(current) = self.gdm_config.get_plugin_by_asset_id(&asset.asset_id)? {
+                let latest = Plugin::from(asset);
+                let wanted = if current.pinned {
+                    None
+                } else {
+                    self.newest_allowed_asset(&current, &latest, include_prerelease, level)
+                        .await?
+                };
+                outdated_plugins.push(OutdatedPlugin::new(
+                    current,
+                    wanted,
+                    latest,
+                    include_prerelease,
+                ));
             }
         }
+        outdated_plugins.extend(delisted_plugins);
+        outdated_plugins.extend(
+            self.fetch_latest_git_plugins(plugins, include_prerelease, level)
+                .await?,
+        );
+        self.warn_incompatible_plugins(outdated_plugins.iter().map(|p| &p.current))?;
+        Ok(outdated_plugins)
+    }
 
-        if plugins_to_install.is_empty() {
-            println!("All plugins are up to date.");
-            return Ok(BTreeMap::new());
+    /// Matches the installed plugins against the advisory feed, the most severe findings first
+    fn audit_plugins(&self, feed: &AdvisoryFeed) -> Result<Vec<AuditFinding>> {
+        if !self.gdm_config.has_installed_plugins()? {
+            bail!(
+                GdmError::new(ErrorKind::NotFound, "No plugins installed.")
+                    .with_hint("Add a plugin with gdm add.")
+            );
         }
 
-        let updated_plugins = self.process_install(&plugins_to_install).await?;
+        let mut findings: Vec<AuditFinding> = self
+            .gdm_config
+            .get_plugins()?
+            .into_iter()
+            .flat_map(|(name, plugin)| {
+                feed.advisories
+                    .iter()
+                    .filter(|advisory| advisory.affects(&plugin))
+                    .map(|advisory| AuditFinding {
+                        name: name.clone(),
+                        plugin: plugin.clone(),
+                        advisory: advisory.clone(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        findings.sort_by(|a, b| {
+            b.advisory
+                .severity
+                .cmp(&a.advisory.severity)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        Ok(findings)
+    }
 
-        self.add_plugins(&updated_plugins)?;
-        println!("Plugins updated successfully.");
+    /// Attributions of the plugins in the configuration file. Authors are read from the
+    /// installed plugin.cfg files, so plugins that aren't installed have none.
+    fn credits(&self) -> Result<Credits> {
+        let parser = PluginParser::new(self.file_service.clone());
+        let project_dir = self.app_config.get_project_dir();
+        let asset_page_base_url = self.app_config.api_base_url.trim_end_matches("/api");
+        let credits = self
+            .gdm_config
+            .get_plugins()?
+            .into_values()
+            .map(|plugin| Credit {
+                author: plugin
+                    .plugin_cfg_path
+                    .as_ref()
+                    .and_then(|path| parser.read_author(&project_dir.join(path))),
+                version: plugin.get_version(),
+                license: plugin.license.clone().filter(|license| !license.is_empty()),
+                source_url: match &plugin.source {
+                    Some(PluginSource::AssetLibrary { asset_id }) => {
+                        Some(format!("{}/asset/{}", asset_page_base_url, asset_id))
+                    }
+                    Some(PluginSource::Git { url, .. }) => Some(url.clone()),
+                    None => None,
+                },
+                title: plugin.title,
+            })
+            .collect();
+        Ok(Credits { credits })
+    }
+
+    /// Sizes of the plugins' addon folders, and when their assets were last updated according to
+    /// the asset index. Doesn't query the Asset Library.
+    fn project_stats(&self) -> Result<ProjectStats> {
+        if !self.gdm_config.has_installed_plugins()? {
+            bail!(
+                GdmError::new(ErrorKind::NotFound, "No plugins installed.")
+                    .with_hint("Add a plugin with gdm add.")
+            );
+        }
+
+        let project_dir = self.app_config.get_project_dir();
+        let project_addons_dir = self.app_config.get_project_addons_dir();
+        let asset_index = AssetIndex::in_cache_dir(&self.app_config.api_base_url);
+        let mut plugins = Vec::new();
+        for (name, plugin) in self.gdm_config.get_plugins()? {
+            let addons_dir = project_dir.join(plugin.get_addons_dir(project_addons_dir.clone()));
+            let mut size = 0;
+            for folder in std::iter::once(&name).chain(&plugin.sub_assets) {
+                let path = Utils::plugin_name_to_addon_folder_path(&addons_dir, Path::new(folder));
+                if self.file_service.directory_exists(&path) {
+                    size += self.file_service.dir_size(&path)?;
+                }
+            }
+            let modify_date = match (&plugin.source, &asset_index) {
+                (Some(PluginSource::AssetLibrary { asset_id }), Some(asset_index)) => asset_index
+                    .get(asset_id)
+                    .map(|asset| asset.modify_date)
+                    .filter(|modify_date| !modify_date.is_empty()),
+                _ => None,
+            };
+            plugins.push(PluginStats {
+                name,
+                plugin,
+                size,
+                modify_date,
+            });
+        }
+        Ok(ProjectStats { plugins })
+    }
+
+    /// Finds the plugins that install an addon folder and the plugins whose autoloads use it.
+    /// `folder` is a folder name, e.g. `gut`, or a path from the project root, e.g. `addons/gut`.
+    fn explain_folder(&self, folder: &str) -> Result<FolderReport> {
+        let folder = folder
+            .trim_start_matches("res://")
+            .replace('\\', "/")
+            .trim_end_matches('/')
+            .to_string();
+        let matches = |path: &Path| {
+            path == Path::new(&folder)
+                || (!folder.contains('/') && path.file_name() == Some(folder.as_ref()))
+        };
+
+        let plugins = self.gdm_config.get_plugins()?;
+        let project_addons_dir = self.app_config.get_project_addons_dir();
+        let mut owners = Vec::new();
+        let mut folder_path = None;
+        for (name, plugin) in &plugins {
+            let addons_dir = plugin.get_addons_dir(project_addons_dir.clone());
+            let folders = std::iter::once((name, FolderRole::Main)).chain(
+                plugin
+                    .sub_assets
+                    .iter()
+                    .map(|sub| (sub, FolderRole::SubAsset)),
+            );
+            for (folder_name, role) in folders {
+                let path =
+                    Utils::plugin_name_to_addon_folder_path(&addons_dir, Path::new(folder_name));
+                if matches(&path) {
+                    owners.push(FolderOwner {
+                        name: name.clone(),
+                        plugin: plugin.clone(),
+                        role,
+                    });
+                    folder_path.get_or_insert(path);
+                }
+            }
+        }
+        let folder_path = folder_path.unwrap_or_else(|| {
+            if folder.contains('/') {
+                PathBuf::from(&folder)
+            } else {
+                project_addons_dir.join(&folder)
+            }
+        });
+
+        let resource_path: Vec<_> = folder_path
+            .iter()
+            .map(|part| part.to_string_lossy())
+            .collect();
+        let resource_prefix = format!("res://{}/", resource_path.join("/"));
+        let dependents = plugins
+            .iter()
+            .filter(|(name, _)| !owners.iter().any(|owner| &owner.name == *name))
+            .filter(|(_, plugin)| {
+                plugin
+                    .autoload
+                    .values()
+                    .any(|path| path.trim_start_matches('*').starts_with(&resource_prefix))
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        Ok(FolderReport {
+            exists: self
+                .file_service
+                .directory_exists(&self.app_config.get_project_dir().join(&folder_path)),
+            folder: folder_path,
+            owners,
+            dependents,
+        })
+    }
+
+    /// The plugins in the configuration file and their sub-assets
+    fn dependency_graph(&self) -> Result<DependencyGraph> {
+        let gdm_config = self.gdm_config.load()?;
+        let project = gdm_config
+            .project
+            .and_then(|project| project.name)
+            .or_else(|| {
+                std::env::current_dir()
+                    .ok()?
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+            })
+            .unwrap_or_else(|| "project".to_string());
+        Ok(DependencyGraph::new(project, &gdm_config.plugins))
+    }
+
+    fn write_credits(&self, path: &Path) -> Result<()> {
+        let credits = self.credits()?;
+        self.file_service
+            .write_file(path, &credits.to_string())
+            .with_context(|| format!("Failed to write credits to {}", path.display()))
+    }
+
+    /// Rewrites project.credits after the plugins changed, if the project has one
+    fn update_credits(&self) -> Result<()> {
+        match self.app_config.get_credits_file_path() {
+            Some(path) => self.write_credits(&path),
+            None => Ok(()),
+        }
+    }
+
+    async fn update_plugins(
+        &self,
+        names: &[String],
+        include_prerelease: bool,
+        level: UpdateLevel,
+    ) -> Result<BTreeMap<String, Plugin>> {
+        let plugins_map = self.gdm_config.get_plugins()?;
+
+        if plugins_map.is_empty() {
+            bail!(
+                GdmError::new(ErrorKind::NotFound, "No plugins installed.")
+                    .with_hint("Add a plugin with gdm add.")
+            );
+        }
+
+        let selected_asset_ids = self.resolve_asset_ids_by_names(names)?;
+        let installed_latest = self.fetch_latest_assets().await?;
+        let mut plugins_to_install = Vec::new();
+        let mut held_back = Vec::new();
+
+        for asset in installed_latest {
+            if let Some(asset_ids) = &selected_asset_ids
+                && !asset_ids.contains(&asset.asset_id)
+            {
+                continue;
+            }
+            if let Some(curr) = self.gdm_config.get_plugin_by_asset_id(&asset.asset_id)? {
+                let mut latest_plugin = Plugin::from(asset);
+                latest_plugin.addons_dir = curr.addons_dir.clone();
+                if !curr.is_updated_by(&latest_plugin, include_prerelease) {
+                    continue;
+                }
+                let name = plugins_map
+                    .iter()
+                    .find(|(_, plugin)| plugin.source == curr.source)
+                    .map_or(curr.title.clone(), |(key, _)| key.clone());
+                if curr.pinned && selected_asset_ids.is_none() {
+                    held_back.push((
+                        name,
+                        format!(
+                            "pinned by gdm downgrade, {} is available",
+                            latest_plugin.get_version()
+                        ),
+                    ));
+                } else if let Some(wanted) = self
+                    .newest_allowed_asset(&curr, &latest_plugin, include_prerelease, level)
+                    .await?
+                {
+                    plugins_to_install.push(wanted);
+                } else {
+                    held_back.push((
+                        name,
+                        format!(
+                            "{} is available, use {}",
+                            latest_plugin.get_version(),
+                            level.next_flag()
+                        ),
+                    ));
+                }
+            }
+        }
+        let git_plugins = self.select_plugins_by_names(names)?.into_values().collect();
+        for git_plugin in self
+            .fetch_latest_git_plugins(git_plugins, include_prerelease, level)
+            .await?
+        {
+            if git_plugin.status == UpdateStatus::UpdateAvailable {
+                plugins_to_install.push(git_plugin.wanted);
+            }
+        }
+
+        if plugins_to_install.is_empty() {
+            self.ui.success("All plugins are up to date.");
+            self.report_summary(ChangeSummary {
+                skipped: held_back,
+                ..ChangeSummary::default()
+            });
+            return Ok(BTreeMap::new());
+        }
+
+        self.check_license_policy(&plugins_to_install)?;
+        let updated_plugins = self.process_install(&plugins_to_install).await?;
+        self.warn_incompatible_plugins(updated_plugins.values())?;
+
+        self.add_plugins(&updated_plugins)?;
+        self.ui.success("Plugins updated successfully.");
+        let mut summary = ChangeSummary::from_installed(&plugins_map, &updated_plugins);
+        summary.skipped = held_back;
+        self.report_summary(summary);
         Ok(updated_plugins)
     }
 
+    async fn downgrade_plugin(&self, name: &str, version: &str) -> Result<ChangeSummary> {
+        if !self.gdm_config.has_installed_plugins()? {
+            bail!(
+                GdmError::new(ErrorKind::NotFound, "No plugins installed.")
+                    .with_hint("Add a plugin with gdm add.")
+            );
+        }
+        let Some((plugin_name, existing)) = self.gdm_config.get_plugin_by_name(name) else {
+            bail!(self.not_installed_error(name)?);
+        };
+        let Some(PluginSource::AssetLibrary { asset_id }) = &existing.source else {
+            bail!(
+                GdmError::new(
+                    ErrorKind::Usage,
+                    format!("Plugin {} isn't from the Asset Library.", plugin_name),
+                )
+                .with_plugin(&plugin_name)
+                .with_hint(
+                    "Add it again with gdm add --git <url> --ref <tag> to pick an older version."
+                )
+            );
+        };
+
+        let asset_response = self.find_asset_metadata("", asset_id, version).await?;
+        let older_plugin = Plugin::from(asset_response);
+        if older_plugin >= existing {
+            bail!(
+                GdmError::new(
+                    ErrorKind::Usage,
+                    format!(
+                        "Version {} of {} isn't older than the installed {}.",
+                        older_plugin.get_version(),
+                        plugin_name,
+                        existing.get_version()
+                    ),
+                )
+                .with_plugin(&plugin_name)
+                .with_hint("Use gdm update or gdm add --version to move to a newer version.")
+            );
+        }
+
+        let older_version = older_plugin.get_version();
+        let mut warning = format!(
+            "Warning: resources, scenes or project settings saved with {} {} may not load with {}.",
+            plugin_name,
+            existing.get_version(),
+            older_version
+        );
+        if !UpdateLevel::Minor.allows_plugin(&older_plugin, &existing) {
+            warning.push_str(" It's a major version older, which may break compatibility.");
+        }
+        self.ui.warning(&warning);
+        if !self.ui.confirm(&format!(
+            "Downgrade plugin {} from {} to {}?",
+            plugin_name,
+            existing.get_version(),
+            older_version
+        ))? {
+            self.ui.status("Downgrade cancelled.");
+            return Ok(ChangeSummary::default());
+        }
+
+        let plugins_before = self.gdm_config.get_plugins()?;
+        let mut installed = self
+            .process_install(&[older_plugin.with_settings_from(&existing)])
+            .await?;
+        for plugin in installed.values_mut() {
+            plugin.pinned = true;
+        }
+        self.warn_incompatible_plugins(installed.values())?;
+        self.add_plugins(&installed)?;
+        self.ui.success(&format!(
+            "Plugin {} downgraded to {} and pinned, gdm update leaves it unless it's named.",
+            plugin_name, older_version
+        ));
+        Ok(self.report_summary(ChangeSummary::from_installed(&plugins_before, &installed)))
+    }
+
     async fn get_asset_list_response_by_name_or_version(
         &self,
         name: &str,
         version: &str,
+        category: Option<&str>,
     ) -> Result<AssetListResponse> {
-        let parsed_version = self.godot_config.get_godot_version_from_project()?;
-
         if name.is_empty() {
-            bail!("No name provided")
+            bail!(GdmError::new(ErrorKind::Usage, "No name provided"))
         }
 
-        let effective_version = if version.is_empty() {
-            if parsed_version.is_empty() {
-                bail!(
-                    "Couldn't determine Godot version from project.godot. Please provide a version using --godot-version."
-                );
-            }
-            parsed_version
-        } else {
-            version.to_string()
-        };
-
-        let params = HashMap::from([
+        let mut params = HashMap::from([
             ("filter".to_string(), name.to_string()),
-            ("godot_version".to_string(), effective_version),
+            (
+                "godot_version".to_string(),
+                self.effective_godot_version(version)?,
+            ),
         ]);
+        if let Some(category) = category {
+            params.insert("category".to_string(), category.to_string());
+        }
 
         let asset_results = self.asset_store_api.get_assets(params).await?;
         Ok(asset_results)
     }
 
-    async fn search_assets_by_name_or_version(&self, name: &str, version: &str) -> Result<()> {
-        let asset_list_response = self
-            .get_asset_list_response_by_name_or_version(name, version)
-            .await?;
-
-        match asset_list_response.result.len() {
-            0 => println!("No assets found matching \"{}\"", name),
-            1 => println!("Found 1 asset matching \"{}\":", name),
-            n => println!("Found {} assets matching \"{}\":", n, name),
-        }
-
-        asset_list_response.print_info();
-
-        if asset_list_response.result.len() == 1 {
-            let asset = asset_list_response.result.first().unwrap();
-            println!(
-                "To install the plugin, use: gdm add \"{}\" or gdm add --asset-id {}",
-                asset.title, asset.asset_id
-            );
-        } else {
-            println!(
-                "To install a plugin, use: gdm add --asset-id <asset_id> or narrow down your search"
-            );
+    async fn browse_assets(
+        &self,
+        sort: AssetSort,
+        page: usize,
+        max_results: usize,
+        category: Option<&str>,
+        version: &str,
+    ) -> Result<AssetListResponse> {
+        let mut params = HashMap::from([
+            ("type".to_string(), "addon".to_string()),
+            ("sort".to_string(), sort.as_param().to_string()),
+            ("page".to_string(), page.to_string()),
+            ("max_results".to_string(), max_results.to_string()),
+            (
+                "godot_version".to_string(),
+                self.effective_godot_version(version)?,
+            ),
+        ]);
+        if let Some(category) = category {
+            params.insert("category".to_string(), category.to_string());
         }
-        Ok(())
+        self.asset_store_api.get_assets(params).await
     }
 }
 
+#[allow(async_fn_in_trait)]
 pub trait PluginService {
-    async fn install_all_plugins(&self) -> Result<BTreeMap<String, Plugin>>;
+    /// Installs the named plugins, or all of them when `names` is empty. With `locked`, every
+    /// plugin must match the checksum and content hash recorded in gdm.json.
+    async fn install_plugins(
+        &self,
+        names: &[String],
+        include_dev: bool,
+        locked: bool,
+    ) -> Result<BTreeMap<String, Plugin>>;
 
     async fn add_plugin(
         &self,
@@ -463,26 +1616,96 @@ pub trait PluginService {
         version: Option<String>,
         git_url: Option<String>,
         git_reference: Option<String>,
-    ) -> Result<()>;
+        options: AddOptions,
+    ) -> Result<ChangeSummary>;
 
     fn add_plugins(&self, plugins: &BTreeMap<String, Plugin>) -> Result<()>;
 
-    async fn remove_plugin_by_name(&self, name: &str) -> Result<()>;
+    /// Plugins in the configuration file and unmanaged plugins enabled in project.godot
+    fn list_plugins(&self) -> Result<PluginListing>;
 
-    async fn fetch_latest_assets(&self) -> Result<Vec<AssetResponse>>;
+    /// Removes the plugin's folders and configuration, returning what changed
+    async fn remove_plugin_by_name(&self, name: &str) -> Result<ChangeSummary>;
 
-    async fn check_outdated_plugins(&self) -> Result<()>;
-    async fn update_plugins(&self) -> Result<BTreeMap<String, Plugin>>;
+    /// Removes the plugin installed from the given Asset Library ID
+    async fn remove_plugin_by_asset_id(&self, asset_id: &str) -> Result<ChangeSummary>;
+    fn remove_plugin(&self, plugin_name: String, plugin: Plugin) -> Result<ChangeSummary>;
 
+    async fn fetch_latest_assets(&self) -> Result<Vec<AssetResponse>>;
+    fn delisted_warning(&self, plugin: &Plugin) -> String;
+    async fn newest_allowed_asset(
+        &self,
+        current: &Plugin,
+        latest: &Plugin,
+        include_prerelease: bool,
+        level: UpdateLevel,
+    ) -> Result<Option<Plugin>>;
+
+    async fn fetch_latest_git_plugins(
+        &self,
+        plugins: Vec<Plugin>,
+        include_prerelease: bool,
+        level: UpdateLevel,
+    ) -> Result<Vec<OutdatedPlugin>>;
+
+    /// Compares each plugin to its latest version, and to the newest one `gdm update` would
+    /// install at `level`
+    async fn check_outdated_plugins(
+        &self,
+        include_prerelease: bool,
+        level: UpdateLevel,
+    ) -> Result<Vec<OutdatedPlugin>>;
+    fn audit_plugins(&self, feed: &AdvisoryFeed) -> Result<Vec<AuditFinding>>;
+    fn credits(&self) -> Result<Credits>;
+    fn dependency_graph(&self) -> Result<DependencyGraph>;
+    fn explain_folder(&self, folder: &str) -> Result<FolderReport>;
+    fn project_stats(&self) -> Result<ProjectStats>;
+    /// Writes the attributions of the plugins as Markdown
+    fn write_credits(&self, path: &Path) -> Result<()>;
+    fn update_credits(&self) -> Result<()>;
+    async fn update_plugins(
+        &self,
+        names: &[String],
+        include_prerelease: bool,
+        level: UpdateLevel,
+    ) -> Result<BTreeMap<String, Plugin>>;
+    fn select_plugins_by_names(&self, names: &[String]) -> Result<BTreeMap<String, Plugin>>;
+    fn resolve_asset_ids_by_names(&self, names: &[String]) -> Result<Option<HashSet<String>>>;
+    fn not_installed_error(&self, name: &str) -> Result<GdmError>;
+
+    /// Installs an older version of an Asset Library plugin and pins it, so `update` leaves it there
+    async fn downgrade_plugin(&self, name: &str, version: &str) -> Result<ChangeSummary>;
+
+    /// Searches the Asset Library, only in the category with the ID `category` if it's given
     async fn get_asset_list_response_by_name_or_version(
         &self,
         name: &str,
         version: &str,
+        category: Option<&str>,
+    ) -> Result<AssetListResponse>;
+
+    /// Lists addons for the Godot version, `page` counts from 0
+    async fn browse_assets(
+        &self,
+        sort: AssetSort,
+        page: usize,
+        max_results: usize,
+        category: Option<&str>,
+        version: &str,
     ) -> Result<AssetListResponse>;
-    async fn search_assets_by_name_or_version(&self, name: &str, version: &str) -> Result<()>;
 
     fn finish_plugins_operation(&self, plugins: &BTreeMap<String, Plugin>) -> Result<()>;
 
+    /// Fails, or only warns without license verification, when a plugin's license isn't in the
+    /// allow list of project.licenses or is in its deny list
+    fn check_license_policy(&self, plugins: &[Plugin]) -> Result<()>;
+
+    /// Warns about each plugin that targets a Godot version the project can't run it on
+    fn warn_incompatible_plugins<'a>(
+        &self,
+        plugins: impl IntoIterator<Item = &'a Plugin>,
+    ) -> Result<()>;
+
     async fn process_install(&self, plugins: &[Plugin]) -> Result<BTreeMap<String, Plugin>>;
 
     async fn find_asset_metadata(
@@ -496,21 +1719,29 @@ pub trait PluginService {
 #[cfg(test)]
 mod tests {
     use anyhow::Ok;
-    use std::collections::BTreeMap;
-    use std::path::PathBuf;
+    use std::collections::{BTreeMap, HashSet};
+    use std::path::{Path, PathBuf};
     use std::sync::Arc;
 
     use mockall::predicate::*;
 
+    use super::MAX_CONCURRENT_REQUESTS;
     use crate::api::{
-        Asset, AssetListItem, AssetListResponse, AssetResponse, MockDefaultAssetStoreAPI,
+        Asset, AssetListItem, AssetListResponse, AssetResponse, AssetSort, DefaultAssetStoreAPI,
+        MockDefaultAssetStoreAPI,
     };
     use crate::config::{
-        DefaultAppConfig, DefaultGdmConfigMetadata, MockDefaultGdmConfig, MockDefaultGodotConfig,
+        AppConfig, DefaultAppConfig, DefaultGdmConfigMetadata, LicensePolicy, MockDefaultGdmConfig,
+        MockDefaultGodotConfig,
+    };
+    use crate::error::{ErrorKind, GdmError};
+    use crate::models::{
+        AdvisoryFeed, Credit, FolderRole, Plugin, PluginSource, UpdateChannel, UpdateLevel,
+        UpdateStatus,
     };
-    use crate::models::{Plugin, PluginSource};
     use crate::services::{
-        DefaultPluginService, MockDefaultFileService, MockDefaultInstallService, PluginService,
+        AddOptions, ArchiveCache, DefaultPluginService, MockDefaultFileService,
+        MockDefaultGitService, MockDefaultInstallService, PluginService,
     };
 
     // Helper to setup the service with specific versioning scenarios
@@ -577,6 +1808,9 @@ mod tests {
         plugin_config_repository
             .expect_add_plugins()
             .returning(|_| Ok(DefaultGdmConfigMetadata::default()));
+        plugin_config_repository
+            .expect_get_plugins()
+            .returning(|| Ok(BTreeMap::new()));
 
         // Setup asset store API
         let asset_id_for_api = asset_id.to_string();
@@ -686,7 +1920,7 @@ mod tests {
                 ))
             });
 
-        let app_config = DefaultAppConfig::default();
+        let app_config = DefaultAppConfig::default().with_assume_yes(true);
         let asset_store_api_arc = Arc::new(asset_store_api);
         let install_service_arc = Arc::new(install_service);
 
@@ -702,6 +1936,11 @@ mod tests {
 
     // Helper to setup standard mocks
     fn setup_plugin_service_mocks() -> DefaultPluginService {
+        setup_plugin_service_mocks_with_test_plugin_enabled(true)
+    }
+
+    /// A disabled test_plugin expects `gdm add` to enable it once
+    fn setup_plugin_service_mocks_with_test_plugin_enabled(enabled: bool) -> DefaultPluginService {
         let mut godot_config_repository = MockDefaultGodotConfig::default();
         let mut install_service = MockDefaultInstallService::default();
 
@@ -718,7 +1957,8 @@ mod tests {
                         .unwrap_or(&plugin.title)
                         .to_string()
                 } else {
-                    plugin.title.clone()
+                    // Asset Library plugins are unpacked into e.g. addons/test_plugin
+                    plugin.title.to_lowercase().replace(' ', "_")
                 };
                 result.insert(folder_name, plugin.clone());
             }
@@ -760,19 +2000,32 @@ mod tests {
 
         let file_service = Arc::new(MockDefaultFileService::default());
 
-        plugin_config_repository.expect_get_plugins().returning(|| {
-            Ok(BTreeMap::from([(
-                String::from("test_plugin"),
-                Plugin::new_asset_store_plugin(
-                    String::from("1234"),
-                    Some("addons/test_plugin/plugin.cfg".into()),
-                    String::from("Test Plugin"),
-                    String::from("1.1.1"),
-                    String::from("MIT"),
-                    vec![],
-                ),
-            )]))
-        });
+        plugin_config_repository
+            .expect_get_plugins()
+            .returning(move || {
+                Ok(BTreeMap::from([(
+                    String::from("test_plugin"),
+                    Plugin {
+                        enabled,
+                        ..Plugin::new_asset_store_plugin(
+                            String::from("1234"),
+                            Some("addons/test_plugin/plugin.cfg".into()),
+                            String::from("Test Plugin"),
+                            String::from("1.1.1"),
+                            String::from("MIT"),
+                            vec![],
+                        )
+                    },
+                )]))
+            });
+
+        if !enabled {
+            plugin_config_repository
+                .expect_enable_plugins()
+                .with(eq(HashSet::from(["test_plugin".to_string()])))
+                .times(1)
+                .returning(|_plugin_keys| Ok(DefaultGdmConfigMetadata::default()));
+        }
 
         plugin_config_repository
             .expect_get_plugin_by_asset_id()
@@ -929,7 +2182,7 @@ mod tests {
         let name = "some_non_existent_plugin_name";
         let version = "4.5";
         let result_list = plugin_service
-            .get_asset_list_response_by_name_or_version(name, version)
+            .get_asset_list_response_by_name_or_version(name, version, None)
             .await;
         assert!(result_list.is_ok());
         let result = result_list.unwrap();
@@ -943,7 +2196,7 @@ mod tests {
         let name = "Test Plugin";
         let version = "4.5";
         let result = plugin_service
-            .get_asset_list_response_by_name_or_version(name, version)
+            .get_asset_list_response_by_name_or_version(name, version, None)
             .await;
         assert!(result.is_ok());
         let assets = result.unwrap();
@@ -959,17 +2212,43 @@ mod tests {
         let name = "";
         let version = "4.5";
         let result = plugin_service
-            .get_asset_list_response_by_name_or_version(name, version)
+            .get_asset_list_response_by_name_or_version(name, version, None)
             .await;
         assert!(result.is_err());
     }
 
-    // install_all_plugins
+    // browse_assets
+
+    #[tokio::test]
+    async fn test_browse_assets_should_request_sorted_page_of_addons() {
+        let mut asset_store_api = MockDefaultAssetStoreAPI::default();
+        asset_store_api
+            .expect_get_assets()
+            .withf(|params| {
+                params.get("type").map(String::as_str) == Some("addon")
+                    && params.get("sort").map(String::as_str) == Some("updated")
+                    && params.get("page").map(String::as_str) == Some("2")
+                    && params.get("max_results").map(String::as_str) == Some("20")
+                    && params.get("godot_version").map(String::as_str) == Some("4.5")
+                    && params.get("category").map(String::as_str) == Some("5")
+            })
+            .returning(|_| Ok(AssetListResponse::new(vec![])));
+        let plugin_service =
+            setup_plugin_service_mocks().with_asset_store_api(Arc::new(asset_store_api));
+
+        let result = plugin_service
+            .browse_assets(AssetSort::Updated, 2, 20, Some("5"), "4.5")
+            .await;
+
+        assert!(result.unwrap().result.is_empty());
+    }
+
+    // install_plugins
 
     #[tokio::test]
     async fn test_install_plugins_should_install_all_plugins_in_config() {
         let plugin_service = setup_plugin_service_mocks();
-        let result = plugin_service.install_all_plugins().await;
+        let result = plugin_service.install_plugins(&[], true, false).await;
         assert!(result.is_ok());
         let installed_plugins = result.unwrap();
 
@@ -988,49 +2267,529 @@ mod tests {
         assert_eq!(installed_plugins, expected_plugins);
     }
 
+    #[tokio::test]
+    async fn test_install_plugins_without_dev_should_skip_dev_plugins() {
+        let mut dev_plugin = Plugin::create_mock_plugin_2();
+        dev_plugin.dev = true;
+        let plugins = BTreeMap::from([
+            ("awesome_plugin".to_string(), Plugin::create_mock_plugin_1()),
+            ("super_plugin".to_string(), dev_plugin),
+        ]);
+
+        let mut install_service = MockDefaultInstallService::default();
+        install_service
+            .expect_install()
+            .withf(|plugins, _| plugins.len() == 1 && !plugins[0].dev)
+            .times(1)
+            .returning(|plugins, _| {
+                Ok(BTreeMap::from([(
+                    "awesome_plugin".to_string(),
+                    plugins[0].clone(),
+                )]))
+            });
+
+        let mut plugin_config_repository = MockDefaultGdmConfig::default();
+        plugin_config_repository
+            .expect_has_installed_plugins()
+            .returning(|| Ok(true));
+        let all_plugins = plugins.clone();
+        plugin_config_repository
+            .expect_get_plugins()
+            .returning(move || Ok(all_plugins.clone()));
+        plugin_config_repository
+            .expect_add_plugins()
+            .returning(move |_plugins| Ok(DefaultGdmConfigMetadata::new(plugins.clone())));
+
+        let mut godot_config_repository = MockDefaultGodotConfig::default();
+        godot_config_repository
+            .expect_save_with_removed()
+            .withf(|plugin_config, removed| {
+                plugin_config.plugins.len() == 1
+                    && plugin_config.plugins.contains_key("awesome_plugin")
+                    && removed.len() == 1
+                    && removed[0].dev
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let plugin_service = DefaultPluginService::new(
+            Box::new(godot_config_repository),
+            Box::new(plugin_config_repository),
+            DefaultAppConfig::default(),
+            Arc::new(MockDefaultFileService::default()),
+            Arc::new(MockDefaultAssetStoreAPI::default()),
+            Arc::new(install_service),
+        );
+
+        let result = plugin_service.install_plugins(&[], false, false).await;
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_install_plugins_should_skip_plugins_with_unchanged_content() {
+        let mut unchanged_plugin = Plugin::create_mock_plugin_1();
+        unchanged_plugin.content_hash = Some("sha256:abc".to_string());
+        let mut changed_plugin = Plugin::create_mock_plugin_2();
+        changed_plugin.content_hash = Some("sha256:def".to_string());
+        let plugins = BTreeMap::from([
+            ("awesome_plugin".to_string(), unchanged_plugin),
+            ("super_plugin".to_string(), changed_plugin),
+        ]);
+
+        let mut install_service = MockDefaultInstallService::default();
+        install_service
+            .expect_installed_content_hash()
+            .returning(|_, _, _| Ok(Some("sha256:abc".to_string())));
+        install_service
+            .expect_install()
+            .withf(|plugins, _| plugins.len() == 1 && plugins[0].title == "Super Plugin")
+            .times(1)
+            .returning(|plugins, _| {
+                Ok(BTreeMap::from([(
+                    "super_plugin".to_string(),
+                    plugins[0].clone(),
+                )]))
+            });
+
+        let mut plugin_config_repository = MockDefaultGdmConfig::default();
+        plugin_config_repository
+            .expect_has_installed_plugins()
+            .returning(|| Ok(true));
+        let all_plugins = plugins.clone();
+        plugin_config_repository
+            .expect_get_plugins()
+            .returning(move || Ok(all_plugins.clone()));
+        plugin_config_repository
+            .expect_add_plugins()
+            .withf(|plugins| plugins.len() == 2)
+            .returning(move |_plugins| Ok(DefaultGdmConfigMetadata::new(plugins.clone())));
+
+        let mut godot_config_repository = MockDefaultGodotConfig::default();
+        godot_config_repository.expect_save().returning(|_| Ok(()));
+
+        let plugin_service = DefaultPluginService::new(
+            Box::new(godot_config_repository),
+            Box::new(plugin_config_repository),
+            DefaultAppConfig::default(),
+            Arc::new(MockDefaultFileService::default()),
+            Arc::new(MockDefaultAssetStoreAPI::default()),
+            Arc::new(install_service),
+        );
+
+        let result = plugin_service
+            .install_plugins(&[], true, false)
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_install_plugins_locked_should_refuse_changed_content() {
+        let mut plugin = Plugin::create_mock_plugin_1();
+        plugin.checksum = Some("sha256:abc".to_string());
+        plugin.content_hash = Some("sha256:def".to_string());
+        let plugins = BTreeMap::from([("awesome_plugin".to_string(), plugin)]);
+
+        let mut install_service = MockDefaultInstallService::default();
+        install_service
+            .expect_installed_content_hash()
+            .returning(|_, _, _| Ok(None));
+        install_service.expect_install().returning(|plugins, _| {
+            let mut installed = plugins[0].clone();
+            installed.content_hash = Some("sha256:123".to_string());
+            Ok(BTreeMap::from([("awesome_plugin".to_string(), installed)]))
+        });
+
+        let mut plugin_config_repository = MockDefaultGdmConfig::default();
+        plugin_config_repository
+            .expect_has_installed_plugins()
+            .returning(|| Ok(true));
+        plugin_config_repository
+            .expect_get_plugins()
+            .returning(move || Ok(plugins.clone()));
+        plugin_config_repository.expect_add_plugins().never();
+
+        let plugin_service = DefaultPluginService::new(
+            Box::new(MockDefaultGodotConfig::default()),
+            Box::new(plugin_config_repository),
+            DefaultAppConfig::default(),
+            Arc::new(MockDefaultFileService::default()),
+            Arc::new(MockDefaultAssetStoreAPI::default()),
+            Arc::new(install_service),
+        );
+
+        let error = plugin_service
+            .install_plugins(&[], true, true)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            GdmError::find(&error).unwrap().kind,
+            ErrorKind::ChecksumMismatch
+        );
+        assert!(error.to_string().contains("Content hash mismatch"));
+    }
+
+    fn setup_select_plugins_mocks() -> DefaultPluginService {
+        let mut plugin_config_repository = MockDefaultGdmConfig::default();
+        plugin_config_repository.expect_get_plugins().returning(|| {
+            Ok(BTreeMap::from([
+                ("awesome_plugin".to_string(), Plugin::create_mock_plugin_1()),
+                ("super_plugin".to_string(), Plugin::create_mock_plugin_2()),
+            ]))
+        });
+        plugin_config_repository
+            .expect_get_plugin_by_name()
+            .returning(|name| {
+                (name == "awesome_plugin")
+                    .then(|| (name.to_string(), Plugin::create_mock_plugin_1()))
+            });
+
+        DefaultPluginService::new(
+            Box::new(MockDefaultGodotConfig::default()),
+            Box::new(plugin_config_repository),
+            DefaultAppConfig::default(),
+            Arc::new(MockDefaultFileService::default()),
+            Arc::new(MockDefaultAssetStoreAPI::default()),
+            Arc::new(MockDefaultInstallService::default()),
+        )
+    }
+
+    #[test]
+    fn test_select_plugins_by_names_should_resolve_names_and_asset_ids() {
+        let plugin_service = setup_select_plugins_mocks();
+
+        let selected = plugin_service
+            .select_plugins_by_names(&["awesome_plugin".to_string(), "12345".to_string()])
+            .unwrap();
+
+        assert_eq!(
+            selected.keys().collect::<Vec<_>>(),
+            vec!["awesome_plugin", "super_plugin"]
+        );
+    }
+
+    #[test]
+    fn test_select_plugins_by_names_with_unknown_name_should_return_err() {
+        let plugin_service = setup_select_plugins_mocks();
+
+        let result = plugin_service.select_plugins_by_names(&["super_plugn".to_string()]);
+
+        let error = result.unwrap_err();
+        assert_eq!(error.to_string(), "Plugin super_plugn is not installed.");
+    }
+
+    #[test]
+    fn test_not_installed_error_with_ambiguous_name_should_list_candidates() {
+        let mut plugin_config_repository = MockDefaultGdmConfig::default();
+        plugin_config_repository.expect_get_plugins().returning(|| {
+            Ok(BTreeMap::from([
+                ("phantom_camera".to_string(), Plugin::create_mock_plugin_1()),
+                ("phantom_tools".to_string(), Plugin::create_mock_plugin_2()),
+            ]))
+        });
+        let plugin_service = DefaultPluginService::new(
+            Box::new(MockDefaultGodotConfig::default()),
+            Box::new(plugin_config_repository),
+            DefaultAppConfig::default(),
+            Arc::new(MockDefaultFileService::default()),
+            Arc::new(MockDefaultAssetStoreAPI::default()),
+            Arc::new(MockDefaultInstallService::default()),
+        );
+
+        let error = plugin_service.not_installed_error("phantom").unwrap();
+
+        assert_eq!(error.kind, ErrorKind::Conflict);
+        assert_eq!(
+            error.to_string(),
+            "Plugin name phantom is ambiguous, it matches phantom_camera, phantom_tools."
+        );
+    }
+
     // add_plugin tests (Replaces old install_plugin tests)
 
     #[tokio::test]
     async fn test_add_plugin_with_asset_id_and_no_version_should_install_asset() {
         let plugin_service = setup_plugin_service_mocks();
         let result = plugin_service
-            .add_plugin(Some("1234".to_string()), None, None, None, None)
+            .add_plugin(
+                Some("1234".to_string()),
+                None,
+                None,
+                None,
+                None,
+                AddOptions::default(),
+            )
+            .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_add_plugin_without_no_enable_should_enable_disabled_plugin() {
+        let plugin_service = setup_plugin_service_mocks_with_test_plugin_enabled(false);
+        let result = plugin_service
+            .add_plugin(
+                Some("1234".to_string()),
+                None,
+                None,
+                None,
+                None,
+                AddOptions {
+                    no_enable: Some(false),
+                    ..AddOptions::default()
+                },
+            )
+            .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_add_plugin_with_only_version_should_return_err() {
+        let plugin_service = setup_plugin_service_mocks();
+        // Providing only version
+        let result = plugin_service
+            .add_plugin(
+                None,
+                None,
+                Some("1.1.1".to_string()),
+                None,
+                None,
+                AddOptions::default(),
+            )
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_add_plugin_with_asset_id_and_version_should_install_plugin() {
+        let plugin_service = setup_plugin_service_mocks();
+        let result = plugin_service
+            .add_plugin(
+                Some("1234".to_string()),
+                None,
+                Some("1.1.1".to_string()),
+                None,
+                None,
+                AddOptions::default(),
+            )
+            .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_add_plugin_with_name_should_install_plugin() {
+        let plugin_service = setup_plugin_service_mocks();
+        let result = plugin_service
+            .add_plugin(
+                None,
+                Some("Test Plugin".to_string()),
+                None,
+                None,
+                None,
+                AddOptions::default(),
+            )
             .await;
         assert!(result.is_ok());
     }
 
-    #[tokio::test]
-    async fn test_add_plugin_with_only_version_should_return_err() {
-        let plugin_service = setup_plugin_service_mocks();
-        // Providing only version
-        let result = plugin_service
-            .add_plugin(None, None, Some("1.1.1".to_string()), None, None)
-            .await;
-        assert!(result.is_err());
-    }
+    /// Picks the given option from every list it's shown
+    struct PickingInterface(Option<usize>);
+
+    impl crate::ui::UserInterface for PickingInterface {
+        fn progress(
+            &self,
+            _operation: crate::ui::Operation,
+        ) -> anyhow::Result<Arc<dyn crate::ui::ProgressReporter>> {
+            Ok(Arc::new(crate::ui::NoopReporter))
+        }
+
+        fn status(&self, _message: &str) {}
+
+        fn success(&self, _message: &str) {}
+
+        fn warning(&self, _message: &str) {}
+
+        fn confirm(&self, _question: &str) -> anyhow::Result<bool> {
+            Ok(true)
+        }
+
+        fn select(&self, _question: &str, options: &[String]) -> anyhow::Result<Option<usize>> {
+            assert_eq!(
+                options,
+                [
+                    "Dialogue Manager by Nathan Hoad (3.4.0)",
+                    "Dialogic by Jowan Spooner (2.0.0)"
+                ]
+            );
+            Ok(self.0)
+        }
+    }
+
+    fn setup_ambiguous_name_mocks(picked: Option<usize>) -> DefaultPluginService {
+        let mut godot_config_repository = MockDefaultGodotConfig::default();
+        godot_config_repository
+            .expect_get_godot_version_from_project()
+            .returning(|| Ok("4.5".to_string()));
+
+        let mut asset_store_api = MockDefaultAssetStoreAPI::default();
+        asset_store_api
+            .expect_find_asset_by_id_or_name_and_version()
+            .with(eq(""), eq("dialogue"), eq("4.5"))
+            .returning(|_, _, _| {
+                Err(GdmError::new(
+                    ErrorKind::Conflict,
+                    "Expected to find exactly one asset matching \"dialogue\", but found 2.",
+                )
+                .into())
+            });
+        asset_store_api.expect_get_assets().returning(|_params| {
+            let asset = |asset_id: &str, title: &str, author: &str, version: &str| {
+                AssetListItem::new(
+                    asset_id.to_string(),
+                    title.to_string(),
+                    author.to_string(),
+                    "Tools".to_string(),
+                    "4.5".to_string(),
+                    "5".to_string(),
+                    "MIT".to_string(),
+                    "??".to_string(),
+                    "11".to_string(),
+                    version.to_string(),
+                    "2023-10-01".to_string(),
+                )
+            };
+            Ok(AssetListResponse::new(vec![
+                asset("1207", "Dialogue Manager", "Nathan Hoad", "3.4.0"),
+                asset("2116", "Dialogic", "Jowan Spooner", "2.0.0"),
+            ]))
+        });
+        asset_store_api
+            .expect_get_asset_by_id()
+            .with(eq("2116"))
+            .returning(|asset_id| {
+                Ok(AssetResponse::new(
+                    asset_id.to_string(),
+                    "Dialogic".to_string(),
+                    "11".to_string(),
+                    "2.0.0".to_string(),
+                    "4.5".to_string(),
+                    "5".to_string(),
+                    "MIT".to_string(),
+                    "Some description".to_string(),
+                    "GitHub".to_string(),
+                    "commit_hash".to_string(),
+                    "2023-10-01".to_string(),
+                    "https://example.com/dialogic.zip".to_string(),
+                ))
+            });
+
+        DefaultPluginService::new(
+            Box::new(godot_config_repository),
+            Box::new(MockDefaultGdmConfig::default()),
+            DefaultAppConfig::default(),
+            Arc::new(MockDefaultFileService::default()),
+            Arc::new(asset_store_api),
+            Arc::new(MockDefaultInstallService::default()),
+        )
+        .with_ui(Arc::new(PickingInterface(picked)))
+    }
+
+    #[tokio::test]
+    async fn test_find_asset_metadata_with_ambiguous_name_should_use_picked_asset() {
+        let plugin_service = setup_ambiguous_name_mocks(Some(1));
+
+        let asset = plugin_service
+            .find_asset_metadata("dialogue", "", "")
+            .await
+            .unwrap();
+
+        assert_eq!(asset.asset_id, "2116");
+        assert_eq!(asset.title, "Dialogic");
+    }
+
+    #[tokio::test]
+    async fn test_find_asset_metadata_with_exact_title_should_use_it_without_asking() {
+        let mut godot_config_repository = MockDefaultGodotConfig::default();
+        godot_config_repository
+            .expect_get_godot_version_from_project()
+            .returning(|| Ok("4.5".to_string()));
+
+        let mut asset_store_api = MockDefaultAssetStoreAPI::default();
+        asset_store_api
+            .expect_find_asset_by_id_or_name_and_version()
+            .returning(|_, _, _| {
+                Err(GdmError::new(ErrorKind::Conflict, "Found 2 assets matching \"gut\".").into())
+            });
+        asset_store_api.expect_get_assets().returning(|_params| {
+            let asset = |asset_id: &str, title: &str| {
+                AssetListItem::new(
+                    asset_id.to_string(),
+                    title.to_string(),
+                    "Author".to_string(),
+                    "Tools".to_string(),
+                    "4.5".to_string(),
+                    "5".to_string(),
+                    "MIT".to_string(),
+                    "??".to_string(),
+                    "11".to_string(),
+                    "1.0.0".to_string(),
+                    "2023-10-01".to_string(),
+                )
+            };
+            Ok(AssetListResponse::new(vec![
+                asset("1709", "GUT - Godot Unit Testing (Godot 4)"),
+                asset("54", "Gut"),
+            ]))
+        });
+        asset_store_api
+            .expect_get_asset_by_id()
+            .with(eq("54"))
+            .returning(|asset_id| {
+                Ok(AssetResponse::new(
+                    asset_id.to_string(),
+                    "Gut".to_string(),
+                    "11".to_string(),
+                    "1.0.0".to_string(),
+                    "4.5".to_string(),
+                    "5".to_string(),
+                    "MIT".to_string(),
+                    "Some description".to_string(),
+                    "GitHub".to_string(),
+                    "commit_hash".to_string(),
+                    "2023-10-01".to_string(),
+                    "https://example.com/gut.zip".to_string(),
+                ))
+            });
+
+        // The picker would fail the test, as it expects other options
+        let plugin_service = DefaultPluginService::new(
+            Box::new(godot_config_repository),
+            Box::new(MockDefaultGdmConfig::default()),
+            DefaultAppConfig::default(),
+            Arc::new(MockDefaultFileService::default()),
+            Arc::new(asset_store_api),
+            Arc::new(MockDefaultInstallService::default()),
+        )
+        .with_ui(Arc::new(PickingInterface(None)));
+
+        let asset = plugin_service
+            .find_asset_metadata("GUT", "", "")
+            .await
+            .unwrap();
 
-    #[tokio::test]
-    async fn test_add_plugin_with_asset_id_and_version_should_install_plugin() {
-        let plugin_service = setup_plugin_service_mocks();
-        let result = plugin_service
-            .add_plugin(
-                Some("1234".to_string()),
-                None,
-                Some("1.1.1".to_string()),
-                None,
-                None,
-            )
-            .await;
-        assert!(result.is_ok());
+        assert_eq!(asset.asset_id, "54");
     }
 
     #[tokio::test]
-    async fn test_add_plugin_with_name_should_install_plugin() {
-        let plugin_service = setup_plugin_service_mocks();
-        let result = plugin_service
-            .add_plugin(None, Some("Test Plugin".to_string()), None, None, None)
-            .await;
-        assert!(result.is_ok());
+    async fn test_find_asset_metadata_with_ambiguous_name_and_nothing_picked_should_return_err() {
+        let plugin_service = setup_ambiguous_name_mocks(None);
+
+        let error = plugin_service
+            .find_asset_metadata("dialogue", "", "")
+            .await
+            .unwrap_err();
+
+        assert_eq!(GdmError::find(&error).unwrap().kind, ErrorKind::Conflict);
     }
 
     // Error cases for add_plugin
@@ -1064,7 +2823,14 @@ mod tests {
         );
 
         let result = plugin_service
-            .add_plugin(Some("99999".to_string()), None, None, None, None)
+            .add_plugin(
+                Some("99999".to_string()),
+                None,
+                None,
+                None,
+                None,
+                AddOptions::default(),
+            )
             .await;
         assert!(result.is_err());
     }
@@ -1088,11 +2854,116 @@ mod tests {
                 Some("1.5.0".to_string()),
                 None,
                 None,
+                AddOptions::default(),
             )
             .await;
         assert!(result.is_ok());
     }
 
+    // downgrade_plugin
+
+    fn setup_downgrade_mocks(installed_version: &str) -> DefaultPluginService {
+        let mut installed = Plugin::create_mock_plugin_1();
+        installed.version = installed_version.to_string();
+
+        let mut plugin_config_repository = MockDefaultGdmConfig::default();
+        plugin_config_repository
+            .expect_has_installed_plugins()
+            .returning(|| Ok(true));
+        let installed_by_name = installed.clone();
+        plugin_config_repository
+            .expect_get_plugin_by_name()
+            .returning(move |name| Some((name.to_string(), installed_by_name.clone())));
+        plugin_config_repository
+            .expect_get_plugins()
+            .returning(move || {
+                Ok(BTreeMap::from([(
+                    "awesome_plugin".to_string(),
+                    installed.clone(),
+                )]))
+            });
+        plugin_config_repository
+            .expect_add_plugins()
+            .withf(|plugins| plugins.values().all(|plugin| plugin.pinned))
+            .returning(|_| Ok(DefaultGdmConfigMetadata::default()));
+
+        let mut godot_config_repository = MockDefaultGodotConfig::default();
+        godot_config_repository
+            .expect_get_godot_version_from_project()
+            .returning(|| Ok("4.5".to_string()));
+        godot_config_repository.expect_save().returning(|_| Ok(()));
+
+        let mut asset_store_api = MockDefaultAssetStoreAPI::default();
+        asset_store_api
+            .expect_get_asset_by_id_and_version()
+            .returning(|asset_id, version| {
+                Ok(AssetResponse::new(
+                    asset_id.to_string(),
+                    "Awesome Plugin".to_string(),
+                    "11".to_string(),
+                    version.to_string(),
+                    "4.5".to_string(),
+                    "5".to_string(),
+                    "MIT".to_string(),
+                    "Some description".to_string(),
+                    "GitHub".to_string(),
+                    "commit_hash".to_string(),
+                    "2023-10-01".to_string(),
+                    format!("https://example.com/{}.zip", asset_id),
+                ))
+            });
+
+        let mut install_service = MockDefaultInstallService::default();
+        install_service.expect_install().returning(|plugins, _| {
+            Ok(BTreeMap::from([(
+                "awesome_plugin".to_string(),
+                plugins[0].clone(),
+            )]))
+        });
+
+        DefaultPluginService::new(
+            Box::new(godot_config_repository),
+            Box::new(plugin_config_repository),
+            DefaultAppConfig::default().with_assume_yes(true),
+            Arc::new(MockDefaultFileService::default()),
+            Arc::new(asset_store_api),
+            Arc::new(install_service),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_downgrade_plugin_should_install_older_version_and_pin_it() {
+        let plugin_service = setup_downgrade_mocks("1.2.0");
+
+        let summary = plugin_service
+            .downgrade_plugin("awesome_plugin", "1.0.0")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            summary.updated,
+            vec![(
+                "awesome_plugin".to_string(),
+                "1.2.0".to_string(),
+                "1.0.0".to_string()
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_downgrade_plugin_to_newer_version_should_return_err() {
+        let plugin_service = setup_downgrade_mocks("1.0.0");
+
+        let result = plugin_service
+            .downgrade_plugin("awesome_plugin", "1.2.0")
+            .await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Version 1.2.0 of awesome_plugin isn't older than the installed 1.0.0."
+        );
+    }
+
     #[tokio::test]
     async fn test_add_plugin_with_name_and_version_should_install_correct_version() {
         let plugin_service = setup_plugin_service_with_versions(
@@ -1110,6 +2981,7 @@ mod tests {
                 Some("9.1.0".to_string()),
                 None,
                 None,
+                AddOptions::default(),
             )
             .await;
 
@@ -1121,6 +2993,19 @@ mod tests {
     fn setup_update_plugin_mocks(
         current_plugin_version: &str,
         update_plugin_version: &str,
+    ) -> DefaultPluginService {
+        setup_update_plugin_mocks_with_versions(
+            current_plugin_version,
+            update_plugin_version,
+            &[current_plugin_version, update_plugin_version],
+        )
+    }
+
+    /// Like `setup_update_plugin_mocks`, with `versions` in the asset's edits
+    fn setup_update_plugin_mocks_with_versions(
+        current_plugin_version: &str,
+        update_plugin_version: &str,
+        versions: &[&str],
     ) -> DefaultPluginService {
         let mut godot_config_repository = MockDefaultGodotConfig::default();
         let mut install_service = MockDefaultInstallService::default();
@@ -1230,6 +3115,11 @@ mod tests {
                 ))
             });
 
+        let asset_versions: Vec<String> = versions.iter().map(|v| v.to_string()).collect();
+        asset_store_api
+            .expect_get_asset_versions()
+            .returning(move |_, _| Ok(asset_versions.clone()));
+
         // This mock is crucial for `fetch_latest_assets` inside update_plugins
         let asset_store_plugin_version = update_plugin_version.to_string();
         asset_store_api
@@ -1283,46 +3173,349 @@ mod tests {
         let asset_store_api_arc = Arc::new(asset_store_api);
         let install_service_arc = Arc::new(install_service);
 
-        DefaultPluginService::new(
+        DefaultPluginService::new(
+            Box::new(godot_config_repository),
+            Box::new(plugin_config_repository),
+            app_config,
+            file_service,
+            asset_store_api_arc,
+            install_service_arc,
+        )
+    }
+
+    #[test]
+    fn test_for_app_config_should_wire_services_with_the_given_config() {
+        let app_config = DefaultAppConfig::default()
+            .with_project_addons_dir(Some("plugins".to_string()))
+            .with_trusted_keys(vec!["ABCD1234".to_string()]);
+
+        let plugin_service = DefaultPluginService::for_app_config(app_config);
+
+        assert_eq!(
+            plugin_service.app_config.get_project_addons_dir(),
+            PathBuf::from("plugins")
+        );
+        assert_eq!(
+            plugin_service.git_service.app_config().get_trusted_keys(),
+            ["ABCD1234".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_plugins_should_return_correct_plugins_if_there_is_an_update_1() {
+        let plugin_service = setup_update_plugin_mocks("1.1.1", "1.2.0");
+        let result = plugin_service
+            .update_plugins(&[], false, UpdateLevel::Minor)
+            .await;
+        assert!(result.is_ok());
+
+        let updated_plugins = result.unwrap();
+        let expected_updated_plugins = BTreeMap::from([(
+            String::from("test_plugin"),
+            Plugin::new_asset_store_plugin(
+                String::from("1234"),
+                Some("addons/test_plugin/plugin.cfg".into()),
+                String::from("Test Plugin"),
+                String::from("1.2.0"),
+                String::from("MIT"),
+                vec![],
+            ),
+        )]);
+        assert_eq!(updated_plugins, expected_updated_plugins);
+    }
+
+    #[tokio::test]
+    async fn test_update_plugins_should_return_correct_plugins_if_there_is_no_update() {
+        let plugin_service = setup_update_plugin_mocks("1.1.1", "1.1.1");
+        let result = plugin_service
+            .update_plugins(&[], false, UpdateLevel::Minor)
+            .await;
+        assert!(result.is_ok());
+
+        let updated_plugins = result.unwrap();
+        let expected_updated_plugins = BTreeMap::from([]);
+        assert_eq!(updated_plugins, expected_updated_plugins);
+    }
+
+    #[tokio::test]
+    async fn test_update_plugins_should_skip_pre_release_by_default() {
+        let plugin_service = setup_update_plugin_mocks("1.1.1", "1.2.0-rc1");
+        let result = plugin_service
+            .update_plugins(&[], false, UpdateLevel::Minor)
+            .await;
+        assert!(result.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_plugins_with_pre_should_include_pre_release() {
+        let plugin_service = setup_update_plugin_mocks("1.1.1", "1.2.0-rc1");
+        let result = plugin_service
+            .update_plugins(&[], true, UpdateLevel::Minor)
+            .await;
+
+        let updated_plugins = result.unwrap();
+        assert_eq!(updated_plugins["test_plugin"].version, "1.2.0-rc1");
+    }
+
+    #[tokio::test]
+    async fn test_update_plugins_should_hold_back_major_update_by_default() {
+        let plugin_service = setup_update_plugin_mocks("1.1.1", "2.0.0");
+        let result = plugin_service
+            .update_plugins(&[], false, UpdateLevel::Minor)
+            .await;
+        assert!(result.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_plugins_should_install_newest_allowed_version_below_major_update() {
+        let plugin_service =
+            setup_update_plugin_mocks_with_versions("1.2.0", "2.0.0", &["1.2.0", "1.3.0", "2.0.0"]);
+        let result = plugin_service
+            .update_plugins(&[], false, UpdateLevel::Minor)
+            .await;
+        assert_eq!(result.unwrap()["test_plugin"].version, "1.3.0");
+    }
+
+    #[tokio::test]
+    async fn test_update_plugins_with_major_should_include_major_update() {
+        let plugin_service = setup_update_plugin_mocks("1.1.1", "2.0.0");
+        let result = plugin_service
+            .update_plugins(&[], false, UpdateLevel::Major)
+            .await;
+        assert_eq!(result.unwrap()["test_plugin"].version, "2.0.0");
+    }
+
+    #[tokio::test]
+    async fn test_update_plugins_with_patch_should_skip_minor_update() {
+        let plugin_service = setup_update_plugin_mocks("1.1.1", "1.2.0");
+        let result = plugin_service
+            .update_plugins(&[], false, UpdateLevel::Patch)
+            .await;
+        assert!(result.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_plugins_with_unknown_name_should_return_err() {
+        let mut plugin_config_repository = MockDefaultGdmConfig::default();
+        plugin_config_repository.expect_get_plugins().returning(|| {
+            Ok(BTreeMap::from([(
+                "plugin_1".to_string(),
+                Plugin::create_mock_plugin_1(),
+            )]))
+        });
+        plugin_config_repository
+            .expect_get_plugin_by_name()
+            .returning(|_| None);
+
+        let plugin_service = DefaultPluginService::new(
+            Box::new(MockDefaultGodotConfig::default()),
+            Box::new(plugin_config_repository),
+            DefaultAppConfig::default(),
+            Arc::new(MockDefaultFileService::default()),
+            Arc::new(MockDefaultAssetStoreAPI::default()),
+            Arc::new(MockDefaultInstallService::default()),
+        );
+
+        let result = plugin_service
+            .update_plugins(&["gut".to_string()], false, UpdateLevel::Minor)
+            .await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Plugin gut is not installed."
+        );
+    }
+
+    #[test]
+    fn test_resolve_asset_ids_by_names_should_resolve_aliases() {
+        let mut plugin_config_repository = MockDefaultGdmConfig::default();
+        plugin_config_repository
+            .expect_get_plugin_by_name()
+            .with(eq("awesome"))
+            .returning(|_| Some(("plugin_1".to_string(), Plugin::create_mock_plugin_1())));
+
+        let plugin_service = DefaultPluginService::new(
+            Box::new(MockDefaultGodotConfig::default()),
+            Box::new(plugin_config_repository),
+            DefaultAppConfig::default(),
+            Arc::new(MockDefaultFileService::default()),
+            Arc::new(MockDefaultAssetStoreAPI::default()),
+            Arc::new(MockDefaultInstallService::default()),
+        );
+
+        assert_eq!(
+            plugin_service.resolve_asset_ids_by_names(&[]).unwrap(),
+            None
+        );
+        assert_eq!(
+            plugin_service
+                .resolve_asset_ids_by_names(&["awesome".to_string()])
+                .unwrap(),
+            Some(HashSet::from(["54321".to_string()]))
+        );
+    }
+
+    // fetch_latest_assets
+
+    fn setup_fetch_latest_assets_mocks(channel: UpdateChannel) -> DefaultPluginService {
+        let mut godot_config_repository = MockDefaultGodotConfig::default();
+        godot_config_repository
+            .expect_get_godot_version_from_project()
+            .returning(|| Ok("4.5".to_string()));
+
+        let mut plugin_config_repository = MockDefaultGdmConfig::default();
+        plugin_config_repository
+            .expect_get_plugins()
+            .returning(move || {
+                let mut plugin = Plugin::create_mock_plugin_1();
+                plugin.channel = channel;
+                Ok(BTreeMap::from([("plugin_1".to_string(), plugin)]))
+            });
+
+        let asset_response = |version: &str| AssetResponse {
+            asset_id: "54321".to_string(),
+            title: "Awesome Plugin".to_string(),
+            version_string: version.to_string(),
+            ..AssetResponse::default()
+        };
+        let mut asset_store_api = MockDefaultAssetStoreAPI::default();
+        asset_store_api
+            .expect_find_asset_by_id_or_name_and_version()
+            .returning(move |_, _, _| Ok(asset_response("1.0.0")));
+        asset_store_api
+            .expect_get_newest_asset_edit()
+            .times(if channel == UpdateChannel::Edge { 1 } else { 0 })
+            .returning(move |_| Ok(Some(asset_response("1.1.0"))));
+
+        DefaultPluginService::new(
+            Box::new(godot_config_repository),
+            Box::new(plugin_config_repository),
+            DefaultAppConfig::default(),
+            Arc::new(MockDefaultFileService::default()),
+            Arc::new(asset_store_api),
+            Arc::new(MockDefaultInstallService::default()),
+        )
+    }
+
+    /// Answers every request with asset 1709 after a short delay, counting requests in flight
+    #[derive(Default)]
+    struct SlowHttpService {
+        in_flight: std::sync::atomic::AtomicUsize,
+        peak: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl crate::services::HttpService for SlowHttpService {
+        async fn get(
+            &self,
+            _url: String,
+            _params: std::collections::HashMap<String, String>,
+        ) -> anyhow::Result<serde_json::Value> {
+            use std::sync::atomic::Ordering;
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(in_flight, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(serde_json::from_str(include_str!(
+                "../../tests/mocks/http/asset_1709.json"
+            ))?)
+        }
+
+        async fn get_file(&self, _url: String) -> anyhow::Result<reqwest::Response> {
+            Err(anyhow::anyhow!("get_file is not used by this test"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_latest_assets_should_limit_concurrent_requests() {
+        let mut godot_config_repository = MockDefaultGodotConfig::default();
+        godot_config_repository
+            .expect_get_godot_version_from_project()
+            .returning(|| Ok("4.5".to_string()));
+        let mut plugin_config_repository = MockDefaultGdmConfig::default();
+        plugin_config_repository.expect_get_plugins().returning(|| {
+            Ok((0..20)
+                .map(|id| {
+                    let mut plugin = Plugin::create_mock_plugin_1();
+                    plugin.source = Some(PluginSource::AssetLibrary {
+                        asset_id: id.to_string(),
+                    });
+                    (format!("plugin_{}", id), plugin)
+                })
+                .collect())
+        });
+        let http_service = Arc::new(SlowHttpService::default());
+        let plugin_service = DefaultPluginService::new(
             Box::new(godot_config_repository),
             Box::new(plugin_config_repository),
-            app_config,
-            file_service,
-            asset_store_api_arc,
-            install_service_arc,
-        )
+            DefaultAppConfig::default(),
+            Arc::new(MockDefaultFileService::default()),
+            Arc::new(DefaultAssetStoreAPI::new(
+                http_service.clone(),
+                DefaultAppConfig::default(),
+                Arc::new(MockDefaultFileService::default()),
+            )),
+            Arc::new(MockDefaultInstallService::default()),
+        );
+
+        let assets = plugin_service.fetch_latest_assets().await.unwrap();
+
+        assert_eq!(assets.len(), 20);
+        assert_eq!(
+            http_service.peak.load(std::sync::atomic::Ordering::SeqCst),
+            MAX_CONCURRENT_REQUESTS
+        );
     }
 
     #[tokio::test]
-    async fn test_update_plugins_should_return_correct_plugins_if_there_is_an_update_1() {
-        let plugin_service = setup_update_plugin_mocks("1.1.1", "1.2.0");
-        let result = plugin_service.update_plugins().await;
-        assert!(result.is_ok());
-
-        let updated_plugins = result.unwrap();
-        let expected_updated_plugins = BTreeMap::from([(
-            String::from("test_plugin"),
-            Plugin::new_asset_store_plugin(
-                String::from("1234"),
-                Some("addons/test_plugin/plugin.cfg".into()),
-                String::from("Test Plugin"),
-                String::from("1.2.0"),
-                String::from("MIT"),
-                vec![],
-            ),
-        )]);
-        assert_eq!(updated_plugins, expected_updated_plugins);
+    async fn test_fetch_latest_assets_should_use_approved_version_on_stable_channel() {
+        let plugin_service = setup_fetch_latest_assets_mocks(UpdateChannel::Stable);
+        let assets = plugin_service.fetch_latest_assets().await.unwrap();
+        assert_eq!(assets[0].version_string, "1.0.0");
     }
 
     #[tokio::test]
-    async fn test_update_plugins_should_return_correct_plugins_if_there_is_no_update() {
-        let plugin_service = setup_update_plugin_mocks("1.1.1", "1.1.1");
-        let result = plugin_service.update_plugins().await;
-        assert!(result.is_ok());
+    async fn test_fetch_latest_assets_should_use_newer_edit_on_edge_channel() {
+        let plugin_service = setup_fetch_latest_assets_mocks(UpdateChannel::Edge);
+        let assets = plugin_service.fetch_latest_assets().await.unwrap();
+        assert_eq!(assets[0].version_string, "1.1.0");
+    }
 
-        let updated_plugins = result.unwrap();
-        let expected_updated_plugins = BTreeMap::from([]);
-        assert_eq!(updated_plugins, expected_updated_plugins);
+    // list_plugins
+
+    #[test]
+    fn test_list_plugins_should_include_unmanaged_plugins() {
+        let mut godot_config_repository = MockDefaultGodotConfig::default();
+        godot_config_repository
+            .expect_get_unmanaged_plugins()
+            .times(1)
+            .returning(|_| Ok(vec!["res://addons/manual_plugin/plugin.cfg".to_string()]));
+
+        let mut plugin_config_repository = MockDefaultGdmConfig::default();
+        plugin_config_repository
+            .expect_load()
+            .times(1)
+            .returning(|| {
+                Ok(DefaultGdmConfigMetadata::new(BTreeMap::from([(
+                    "awesome_plugin".to_string(),
+                    Plugin::create_mock_plugin_1(),
+                )])))
+            });
+
+        let plugin_service = DefaultPluginService::new(
+            Box::new(godot_config_repository),
+            Box::new(plugin_config_repository),
+            DefaultAppConfig::default(),
+            Arc::new(MockDefaultFileService::default()),
+            Arc::new(MockDefaultAssetStoreAPI::default()),
+            Arc::new(MockDefaultInstallService::default()),
+        );
+
+        let listing = plugin_service.list_plugins().unwrap();
+        assert_eq!(listing.plugins.len(), 1);
+        assert_eq!(
+            listing.unmanaged,
+            vec!["res://addons/manual_plugin/plugin.cfg".to_string()]
+        );
     }
 
     // remove_plugin_by_name
@@ -1331,8 +3524,8 @@ mod tests {
     async fn test_remove_plugin_by_name_should_remove_plugin() {
         let mut godot_config_repository = MockDefaultGodotConfig::default();
         godot_config_repository
-            .expect_save()
-            .returning(|_path| Ok(()));
+            .expect_save_with_removed()
+            .returning(|_path, _removed| Ok(()));
         godot_config_repository
             .expect_validate_project_file()
             .returning(|| Ok(()));
@@ -1363,7 +3556,7 @@ mod tests {
         let asset_store = Arc::new(MockDefaultAssetStoreAPI::default());
         let file_service_arc = Arc::new(file_service);
         let install_service_arc = Arc::new(MockDefaultInstallService::default());
-        let app_config = DefaultAppConfig::default();
+        let app_config = DefaultAppConfig::default().with_assume_yes(true);
 
         let plugin_service = DefaultPluginService::new(
             Box::new(godot_config_repository),
@@ -1378,6 +3571,46 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    // warn_incompatible_plugins
+
+    #[test]
+    fn test_warn_incompatible_plugins_without_godot_versions_should_skip_project_lookup() {
+        let plugin_service = DefaultPluginService::new(
+            Box::new(MockDefaultGodotConfig::default()),
+            Box::new(MockDefaultGdmConfig::default()),
+            DefaultAppConfig::default(),
+            Arc::new(MockDefaultFileService::default()),
+            Arc::new(MockDefaultAssetStoreAPI::default()),
+            Arc::new(MockDefaultInstallService::default()),
+        );
+
+        let plugins = [Plugin::create_mock_plugin_1()];
+        assert!(plugin_service.warn_incompatible_plugins(&plugins).is_ok());
+    }
+
+    #[test]
+    fn test_warn_incompatible_plugins_should_check_against_project_version() {
+        let mut godot_config_repository = MockDefaultGodotConfig::default();
+        godot_config_repository
+            .expect_get_godot_version_from_project()
+            .times(1)
+            .returning(|| Ok("4.5".to_string()));
+
+        let plugin_service = DefaultPluginService::new(
+            Box::new(godot_config_repository),
+            Box::new(MockDefaultGdmConfig::default()),
+            DefaultAppConfig::default(),
+            Arc::new(MockDefaultFileService::default()),
+            Arc::new(MockDefaultAssetStoreAPI::default()),
+            Arc::new(MockDefaultInstallService::default()),
+        );
+
+        let mut plugin = Plugin::create_mock_plugin_1();
+        plugin.godot_version = Some("4.6".to_string());
+        let plugins = [plugin, Plugin::create_mock_plugin_2()];
+        assert!(plugin_service.warn_incompatible_plugins(&plugins).is_ok());
+    }
+
     // finish_plugins_operation
 
     #[test]
@@ -1423,6 +3656,20 @@ mod tests {
     fn setup_check_outdated_mocks(
         installed_plugins: Vec<(&str, &str, &str)>, // (asset_id, title, version)
         latest_plugins: Vec<(&str, &str, &str)>,    // (asset_id, title, version)
+    ) -> DefaultPluginService {
+        let edits: Vec<(&str, &str)> = installed_plugins
+            .iter()
+            .chain(latest_plugins.iter())
+            .map(|(asset_id, _, version)| (*asset_id, *version))
+            .collect();
+        setup_check_outdated_mocks_with_edits(installed_plugins, latest_plugins, edits)
+    }
+
+    /// Like `setup_check_outdated_mocks`, with the versions in the assets' edits
+    fn setup_check_outdated_mocks_with_edits(
+        installed_plugins: Vec<(&str, &str, &str)>, // (asset_id, title, version)
+        latest_plugins: Vec<(&str, &str, &str)>,    // (asset_id, title, version)
+        edits: Vec<(&str, &str)>,                   // (asset_id, version)
     ) -> DefaultPluginService {
         let mut godot_config_repository = MockDefaultGodotConfig::default();
         godot_config_repository
@@ -1482,6 +3729,25 @@ mod tests {
                     .cloned())
             });
 
+        // Assets missing from the latest versions were removed from the library
+        for (asset_id, _, _) in installed_plugins.iter().filter(|(id, _, _)| {
+            !latest_plugins
+                .iter()
+                .any(|(latest_id, _, _)| latest_id == id)
+        }) {
+            let asset_id_owned = asset_id.to_string();
+            asset_store_api
+                .expect_find_asset_by_id_or_name_and_version()
+                .withf(move |id, _, _| id == asset_id_owned)
+                .returning(|id, _, _| {
+                    Err(GdmError::new(
+                        ErrorKind::NotFound,
+                        format!("No asset found with ID '{}'", id),
+                    )
+                    .into())
+                });
+        }
+
         // Setup API to return latest versions
         for (asset_id, title, version) in latest_plugins {
             let asset_id_owned = asset_id.to_string();
@@ -1509,6 +3775,38 @@ mod tests {
                 });
         }
 
+        let edits: Vec<(String, String)> = edits
+            .into_iter()
+            .map(|(asset_id, version)| (asset_id.to_string(), version.to_string()))
+            .collect();
+        asset_store_api
+            .expect_get_asset_versions()
+            .returning(move |asset_id, _| {
+                Ok(edits
+                    .iter()
+                    .filter(|(id, _)| id == asset_id)
+                    .map(|(_, version)| version.clone())
+                    .collect())
+            });
+        asset_store_api
+            .expect_get_asset_by_id_and_version()
+            .returning(|asset_id, version| {
+                Ok(AssetResponse::new(
+                    asset_id.to_string(),
+                    "Test Plugin".to_string(),
+                    "11".to_string(),
+                    version.to_string(),
+                    "4.5".to_string(),
+                    "5".to_string(),
+                    "MIT".to_string(),
+                    "Description".to_string(),
+                    "GitHub".to_string(),
+                    "commit_hash".to_string(),
+                    "2023-10-01".to_string(),
+                    format!("https://example.com/{}.zip", asset_id),
+                ))
+            });
+
         let app_config = DefaultAppConfig::default();
         let file_service = Arc::new(MockDefaultFileService::default());
         let install_service_arc = Arc::new(MockDefaultInstallService::default());
@@ -1524,6 +3822,111 @@ mod tests {
         )
     }
 
+    fn setup_git_plugin_service(
+        mut git_service: MockDefaultGitService,
+        reference: &str,
+    ) -> DefaultPluginService {
+        let mut plugin = Plugin::create_mock_plugin_1();
+        plugin.source = Some(PluginSource::Git {
+            url: "https://github.com/bitwes/Gut.git".to_string(),
+            reference: reference.to_string(),
+        });
+        plugin.checksum = Some("git-tree:abc".to_string());
+
+        git_service.expect_list_remote_refs().returning(|_| {
+            Ok(vec![
+                ("refs/heads/main".to_string(), "1111".to_string()),
+                ("refs/tags/v9.1.0".to_string(), "2222".to_string()),
+                ("refs/tags/v9.2.0".to_string(), "3333".to_string()),
+            ])
+        });
+        let mut plugin_config_repository = MockDefaultGdmConfig::default();
+        plugin_config_repository
+            .expect_get_plugins()
+            .returning(move || Ok(BTreeMap::from([("gut".to_string(), plugin.clone())])));
+
+        DefaultPluginService::new(
+            Box::new(MockDefaultGodotConfig::default()),
+            Box::new(plugin_config_repository),
+            DefaultAppConfig::default(),
+            Arc::new(MockDefaultFileService::default()),
+            Arc::new(MockDefaultAssetStoreAPI::default()),
+            Arc::new(MockDefaultInstallService::default()),
+        )
+        .with_git_service(Arc::new(git_service))
+    }
+
+    #[tokio::test]
+    async fn test_fetch_latest_git_plugins_should_find_newer_version_tag() {
+        let plugin_service = setup_git_plugin_service(MockDefaultGitService::default(), "v9.1.0");
+        let plugins = plugin_service.gdm_config.get_plugins().unwrap();
+
+        let outdated = plugin_service
+            .fetch_latest_git_plugins(plugins.into_values().collect(), false, UpdateLevel::Major)
+            .await
+            .unwrap();
+
+        assert_eq!(outdated[0].status, UpdateStatus::UpdateAvailable);
+        assert!(matches!(
+            &outdated[0].latest.source,
+            Some(PluginSource::Git { reference, .. }) if reference == "v9.2.0"
+        ));
+        assert_eq!(outdated[0].latest.checksum, None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_latest_git_plugins_should_respect_update_level() {
+        let plugin_service = setup_git_plugin_service(MockDefaultGitService::default(), "v9.1.0");
+        let plugins = plugin_service.gdm_config.get_plugins().unwrap();
+
+        let outdated = plugin_service
+            .fetch_latest_git_plugins(plugins.into_values().collect(), false, UpdateLevel::Patch)
+            .await
+            .unwrap();
+
+        assert_eq!(outdated[0].status, UpdateStatus::Blocked);
+        assert_eq!(outdated[0].wanted, outdated[0].current);
+        assert!(matches!(
+            &outdated[0].latest.source,
+            Some(PluginSource::Git { reference, .. }) if reference == "v9.2.0"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_latest_git_plugins_with_installed_branch_commit_should_skip_fetch() {
+        let mut git_service = MockDefaultGitService::default();
+        git_service.expect_fetch_addons_tree_id().never();
+        let plugin_service = setup_git_plugin_service(git_service, "main");
+        let mut plugins = plugin_service.gdm_config.get_plugins().unwrap();
+        plugins.get_mut("gut").unwrap().commit = Some("1111".to_string());
+
+        let outdated = plugin_service
+            .fetch_latest_git_plugins(plugins.into_values().collect(), false, UpdateLevel::Major)
+            .await
+            .unwrap();
+
+        assert_eq!(outdated[0].status, UpdateStatus::UpToDate);
+        assert_eq!(outdated[0].latest.commit, Some("1111".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_latest_git_plugins_should_compare_branch_tree_to_checksum() {
+        let mut git_service = MockDefaultGitService::default();
+        git_service
+            .expect_fetch_addons_tree_id()
+            .withf(|_, reference| reference == "main")
+            .returning(|_, _| Ok("abc".to_string()));
+        let plugin_service = setup_git_plugin_service(git_service, "main");
+        let plugins = plugin_service.gdm_config.get_plugins().unwrap();
+
+        let outdated = plugin_service
+            .fetch_latest_git_plugins(plugins.into_values().collect(), false, UpdateLevel::Major)
+            .await
+            .unwrap();
+
+        assert_eq!(outdated[0].status, UpdateStatus::UpToDate);
+    }
+
     #[tokio::test]
     async fn test_check_outdated_plugins_with_no_updates_available() {
         let installed = vec![
@@ -1536,7 +3939,9 @@ mod tests {
         ];
 
         let plugin_service = setup_check_outdated_mocks(installed, latest);
-        let result = plugin_service.check_outdated_plugins().await;
+        let result = plugin_service
+            .check_outdated_plugins(false, UpdateLevel::Major)
+            .await;
 
         assert!(result.is_ok());
     }
@@ -1553,9 +3958,13 @@ mod tests {
         ];
 
         let plugin_service = setup_check_outdated_mocks(installed, latest);
-        let result = plugin_service.check_outdated_plugins().await;
+        let result = plugin_service
+            .check_outdated_plugins(false, UpdateLevel::Major)
+            .await;
 
-        assert!(result.is_ok());
+        let statuses: Vec<UpdateStatus> = result.unwrap().iter().map(|p| p.status).collect();
+        assert!(statuses.contains(&UpdateStatus::UpdateAvailable));
+        assert!(statuses.contains(&UpdateStatus::UpToDate));
     }
 
     #[tokio::test]
@@ -1570,7 +3979,9 @@ mod tests {
         ];
 
         let plugin_service = setup_check_outdated_mocks(installed, latest);
-        let result = plugin_service.check_outdated_plugins().await;
+        let result = plugin_service
+            .check_outdated_plugins(false, UpdateLevel::Major)
+            .await;
 
         assert!(result.is_ok());
     }
@@ -1581,7 +3992,9 @@ mod tests {
         let latest = vec![("1234", "Single Plugin", "1.0.1")]; // Patch update
 
         let plugin_service = setup_check_outdated_mocks(installed, latest);
-        let result = plugin_service.check_outdated_plugins().await;
+        let result = plugin_service
+            .check_outdated_plugins(false, UpdateLevel::Major)
+            .await;
 
         assert!(result.is_ok());
     }
@@ -1609,7 +4022,9 @@ mod tests {
             install_service,
         );
 
-        let result = plugin_service.check_outdated_plugins().await;
+        let result = plugin_service
+            .check_outdated_plugins(false, UpdateLevel::Major)
+            .await;
 
         assert!(result.is_err());
         assert_eq!(result.unwrap_err().to_string(), "No plugins installed.");
@@ -1631,7 +4046,9 @@ mod tests {
         ];
 
         let plugin_service = setup_check_outdated_mocks(installed, latest);
-        let result = plugin_service.check_outdated_plugins().await;
+        let result = plugin_service
+            .check_outdated_plugins(false, UpdateLevel::Major)
+            .await;
 
         assert!(result.is_ok());
     }
@@ -1650,7 +4067,9 @@ mod tests {
         ];
 
         let plugin_service = setup_check_outdated_mocks(installed, latest);
-        let result = plugin_service.check_outdated_plugins().await;
+        let result = plugin_service
+            .check_outdated_plugins(false, UpdateLevel::Major)
+            .await;
 
         assert!(result.is_ok());
     }
@@ -1662,7 +4081,9 @@ mod tests {
         let latest = vec![("1234", "Test Plugin", "2.0.0")];
 
         let plugin_service = setup_check_outdated_mocks(installed, latest);
-        let result = plugin_service.check_outdated_plugins().await;
+        let result = plugin_service
+            .check_outdated_plugins(false, UpdateLevel::Major)
+            .await;
 
         assert!(result.is_ok());
 
@@ -1671,4 +4092,267 @@ mod tests {
         let test_plugin = plugins.values().next().unwrap();
         assert_eq!(test_plugin.get_version(), "1.0.0"); // Should still be old version
     }
+
+    #[tokio::test]
+    async fn test_check_outdated_plugins_should_block_updates_beyond_level() {
+        let installed = vec![
+            ("1234", "Minor Update Plugin", "1.0.0"),
+            ("5678", "Major Update Plugin", "2.0.0"),
+        ];
+        let latest = vec![
+            ("1234", "Minor Update Plugin", "1.1.0"),
+            ("5678", "Major Update Plugin", "3.0.0"),
+        ];
+
+        let plugin_service = setup_check_outdated_mocks(installed, latest);
+        let result = plugin_service
+            .check_outdated_plugins(false, UpdateLevel::Minor)
+            .await
+            .unwrap();
+
+        let by_title = |title: &str| {
+            result
+                .iter()
+                .find(|plugin| plugin.current.title == title)
+                .unwrap()
+        };
+        let minor = by_title("Minor Update Plugin");
+        assert_eq!(minor.status, UpdateStatus::UpdateAvailable);
+        assert_eq!(minor.wanted.get_version(), "1.1.0");
+        let major = by_title("Major Update Plugin");
+        assert_eq!(major.status, UpdateStatus::Blocked);
+        assert_eq!(major.wanted.get_version(), "2.0.0");
+        assert_eq!(major.latest.get_version(), "3.0.0");
+    }
+
+    #[tokio::test]
+    async fn test_check_outdated_plugins_should_want_newest_allowed_edit_below_major_update() {
+        let installed = vec![("1234", "Test Plugin", "1.2.0")];
+        let latest = vec![("1234", "Test Plugin", "2.0.0")];
+        let edits = vec![("1234", "1.2.0"), ("1234", "1.3.0"), ("1234", "2.0.0")];
+
+        let plugin_service = setup_check_outdated_mocks_with_edits(installed, latest, edits);
+        let result = plugin_service
+            .check_outdated_plugins(false, UpdateLevel::Minor)
+            .await
+            .unwrap();
+
+        assert_eq!(result[0].status, UpdateStatus::UpdateAvailable);
+        assert_eq!(result[0].wanted.get_version(), "1.3.0");
+        assert_eq!(result[0].latest.get_version(), "2.0.0");
+    }
+
+    #[tokio::test]
+    async fn test_check_outdated_plugins_should_mark_removed_asset_as_delisted() {
+        let installed = vec![
+            ("1234", "Plugin A", "1.0.0"),
+            ("5678", "Removed Plugin", "2.0.0"),
+        ];
+        let latest = vec![("1234", "Plugin A", "1.1.0")];
+
+        let plugin_service = setup_check_outdated_mocks(installed, latest);
+        let result = plugin_service
+            .check_outdated_plugins(false, UpdateLevel::Major)
+            .await
+            .unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].status, UpdateStatus::UpdateAvailable);
+        assert_eq!(result[1].status, UpdateStatus::Delisted);
+        assert_eq!(result[1].current.title, "Removed Plugin");
+        assert_eq!(result[1].latest.get_version(), "2.0.0");
+    }
+
+    #[test]
+    fn test_delisted_warning_should_point_to_cached_archive() {
+        let cache_dir = temp_dir::TempDir::new().unwrap();
+        let archive = cache_dir.path().join("download.zip");
+        std::fs::write(&archive, "zip").unwrap();
+        let archive_cache = ArchiveCache::new(cache_dir.path().join("archives"));
+        archive_cache
+            .store("5678", "2.0.0", &archive, "sha256:abc")
+            .unwrap();
+        let plugin = Plugin::new_asset_store_plugin(
+            "5678".to_string(),
+            None,
+            "Removed Plugin".to_string(),
+            "2.0.0".to_string(),
+            "MIT".to_string(),
+            vec![],
+        );
+
+        let plugin_service =
+            setup_check_outdated_mocks(vec![], vec![]).with_archive_cache(Some(archive_cache));
+        let warning = plugin_service.delisted_warning(&plugin);
+        assert!(
+            warning.starts_with("Removed Plugin (asset 5678) is no longer in the Asset Library")
+        );
+        assert!(warning.contains("5678-2.0.0.zip"));
+
+        let plugin_service = setup_check_outdated_mocks(vec![], vec![]);
+        let warning = plugin_service.delisted_warning(&plugin);
+        assert!(warning.contains("No archive of it is cached"));
+    }
+
+    #[test]
+    fn test_audit_plugins_should_report_most_severe_findings_first() {
+        let installed = vec![("1234", "Plugin A", "1.0.0"), ("5678", "Plugin B", "2.0.0")];
+        let plugin_service = setup_check_outdated_mocks(installed.clone(), installed);
+        let feed: AdvisoryFeed = serde_json::from_value(serde_json::json!({
+            "advisories": [
+                { "id": "ADV-1", "asset_id": "1234", "kind": "abandoned", "severity": "low", "summary": "Unmaintained" },
+                { "id": "ADV-2", "asset_id": "5678", "versions": "<2.0.0", "kind": "broken", "severity": "high", "summary": "Fixed in 2.0.0" },
+                { "id": "ADV-3", "asset_id": "5678", "kind": "malicious", "severity": "critical", "summary": "Malware" }
+            ]
+        }))
+        .unwrap();
+
+        let findings = plugin_service.audit_plugins(&feed).unwrap();
+
+        let ids: Vec<&str> = findings.iter().map(|f| f.advisory.id.as_str()).collect();
+        assert_eq!(ids, vec!["ADV-3", "ADV-1"]);
+        assert_eq!(findings[0].name, "plugin_b");
+    }
+
+    #[test]
+    fn test_credits_should_list_plugins_with_authors_from_plugin_cfg() {
+        let mut plugin_config_repository = MockDefaultGdmConfig::default();
+        plugin_config_repository.expect_get_plugins().returning(|| {
+            let mut git_plugin = Plugin::create_mock_plugin_2();
+            git_plugin.source = Some(PluginSource::Git {
+                url: "https://github.com/example/super_plugin.git".to_string(),
+                reference: "main".to_string(),
+            });
+            Ok(BTreeMap::from([
+                ("awesome_plugin".to_string(), Plugin::create_mock_plugin_1()),
+                ("super_plugin".to_string(), git_plugin),
+            ]))
+        });
+        let mut file_service = MockDefaultFileService::default();
+        file_service.expect_read_file_cached().returning(|path| {
+            if path == Path::new("addons/awesome_plugin/plugin.cfg") {
+                Ok("[plugin]\nname=\"Awesome Plugin\"\nauthor=\"Jane Doe\"".to_string())
+            } else {
+                Err(anyhow::anyhow!("Not found"))
+            }
+        });
+        let plugin_service = DefaultPluginService::new(
+            Box::new(MockDefaultGodotConfig::default()),
+            Box::new(plugin_config_repository),
+            DefaultAppConfig::default(),
+            Arc::new(file_service),
+            Arc::new(MockDefaultAssetStoreAPI::default()),
+            Arc::new(MockDefaultInstallService::default()),
+        );
+
+        let credits = plugin_service.credits().unwrap().credits;
+
+        assert_eq!(
+            credits,
+            vec![
+                Credit {
+                    title: "Awesome Plugin".to_string(),
+                    author: Some("Jane Doe".to_string()),
+                    version: "1.0.0".to_string(),
+                    license: Some("MIT".to_string()),
+                    source_url: Some(
+                        "https://godotengine.org/asset-library/asset/54321".to_string()
+                    ),
+                },
+                Credit {
+                    title: "Super Plugin".to_string(),
+                    author: None,
+                    version: "2.1.3".to_string(),
+                    license: Some("MIT".to_string()),
+                    source_url: Some("https://github.com/example/super_plugin.git".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_explain_folder_should_find_owners_and_dependents() {
+        let mut plugin_config_repository = MockDefaultGdmConfig::default();
+        plugin_config_repository.expect_get_plugins().returning(|| {
+            let mut dependent = Plugin::create_mock_plugin_1();
+            dependent.autoload = BTreeMap::from([(
+                "Library".to_string(),
+                "*res://addons/sub_asset1/library.gd".to_string(),
+            )]);
+            Ok(BTreeMap::from([
+                ("awesome_plugin".to_string(), dependent),
+                ("some_library".to_string(), Plugin::create_mock_plugin_3()),
+            ]))
+        });
+        let mut file_service = MockDefaultFileService::default();
+        file_service
+            .expect_directory_exists()
+            .returning(|path| path == Path::new("addons/sub_asset1"));
+        let plugin_service = DefaultPluginService::new(
+            Box::new(MockDefaultGodotConfig::default()),
+            Box::new(plugin_config_repository),
+            DefaultAppConfig::default(),
+            Arc::new(file_service),
+            Arc::new(MockDefaultAssetStoreAPI::default()),
+            Arc::new(MockDefaultInstallService::default()),
+        );
+
+        let report = plugin_service
+            .explain_folder("res://addons/sub_asset1/")
+            .unwrap();
+        assert_eq!(report.folder, PathBuf::from("addons/sub_asset1"));
+        assert!(report.exists);
+        assert_eq!(report.owners.len(), 1);
+        assert_eq!(report.owners[0].name, "some_library");
+        assert_eq!(report.owners[0].role, FolderRole::SubAsset);
+        assert_eq!(report.dependents, vec!["awesome_plugin".to_string()]);
+
+        let report = plugin_service.explain_folder("awesome_plugin").unwrap();
+        assert_eq!(report.owners[0].role, FolderRole::Main);
+        assert!(!report.exists);
+        assert!(report.dependents.is_empty());
+
+        let report = plugin_service.explain_folder("unknown").unwrap();
+        assert!(!report.is_managed());
+        assert_eq!(report.folder, PathBuf::from("addons/unknown"));
+    }
+
+    #[test]
+    fn test_check_license_policy_should_refuse_denied_licenses() {
+        let mut plugin_service = setup_check_outdated_mocks(vec![], vec![]);
+        plugin_service.app_config =
+            DefaultAppConfig::default().with_license_policy(Some(LicensePolicy {
+                allow: vec![],
+                deny: vec!["GPLv3".to_string()],
+            }));
+        let plugin = |title: &str, license: &str| {
+            Plugin::new_asset_store_plugin(
+                "1234".to_string(),
+                None,
+                title.to_string(),
+                "1.0.0".to_string(),
+                license.to_string(),
+                vec![],
+            )
+        };
+
+        let result = plugin_service
+            .check_license_policy(&[plugin("Plugin A", "MIT"), plugin("Plugin B", "GPLv3")]);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "The project's license policy doesn't allow Plugin B (GPLv3)."
+        );
+        assert!(
+            plugin_service
+                .check_license_policy(&[plugin("Plugin A", "MIT")])
+                .is_ok()
+        );
+
+        let plugin_service = plugin_service.with_verify_license(false);
+        assert!(
+            plugin_service
+                .check_license_policy(&[plugin("Plugin B", "GPLv3")])
+                .is_ok()
+        );
+    }
 }
@@ -68,6 +68,16 @@ impl PluginParser {
         ))
     }
 
+    /// Reads `author` from a plugin.cfg file. None if the file or the field is missing or empty.
+    pub fn read_author(&self, path: &Path) -> Option<String> {
+        let content = self.file_service.read_file_cached(path).ok()?;
+        content
+            .lines()
+            .find_map(|line| line.strip_prefix("author="))
+            .map(|author| author.trim().trim_matches('"').to_string())
+            .filter(|author| !author.is_empty())
+    }
+
     /// Finds all plugin.cfg files in addon folders and creates Plugin instances
     /// with an optional base directory
     pub fn create_plugins_from_addon_folders_with_base(
@@ -274,6 +284,29 @@ version="1.0.0""#;
 
     // determine_best_main_plugin_match tests
 
+    #[test]
+    fn test_read_author_should_return_author_of_plugin_cfg() {
+        let mut files = HashMap::new();
+        files.insert(
+            "addons/test/plugin.cfg".to_string(),
+            "[plugin]\nname=\"Test Plugin\"\nauthor=\"Jane Doe\"\nversion=\"1.0.0\"".to_string(),
+        );
+        files.insert(
+            "addons/other/plugin.cfg".to_string(),
+            "[plugin]\nname=\"Other\"\nauthor=\"\"".to_string(),
+        );
+        let parser = PluginParser::new(Arc::new(create_mock_file_service_with_files(files)));
+
+        assert_eq!(
+            parser.read_author(Path::new("addons/test/plugin.cfg")),
+            Some("Jane Doe".to_string())
+        );
+        assert_eq!(
+            parser.read_author(Path::new("addons/other/plugin.cfg")),
+            None
+        );
+    }
+
     #[test]
     fn test_determine_best_main_plugin_match_exact() {
         let mock_service = MockDefaultFileService::new();
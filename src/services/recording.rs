@@ -0,0 +1,223 @@
+use crate::services::HttpService;
+
+use anyhow::{Context, Result, bail};
+use reqwest::Response;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::info;
+use url::Url;
+
+/// Environment variable that makes `RecordingHttpService::from_env` record instead of replay
+pub const RECORD_HTTP_ENV: &str = "GDM_RECORD_HTTP";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RecordMode {
+    /// Calls the wrapped service and saves each response
+    Record,
+    /// Answers from saved responses without touching the network
+    Replay,
+}
+
+/// Wraps an `HttpService` to save Asset Library responses to files and replay them later,
+/// so tests don't depend on godotengine.org being reachable
+pub struct RecordingHttpService {
+    inner: Arc<dyn HttpService + Send + Sync>,
+    fixtures_dir: PathBuf,
+    mode: RecordMode,
+}
+
+impl RecordingHttpService {
+    pub fn new(
+        inner: Arc<dyn HttpService + Send + Sync>,
+        fixtures_dir: impl Into<PathBuf>,
+        mode: RecordMode,
+    ) -> RecordingHttpService {
+        RecordingHttpService {
+            inner,
+            fixtures_dir: fixtures_dir.into(),
+            mode,
+        }
+    }
+
+    /// Records when GDM_RECORD_HTTP is set, replays otherwise
+    pub fn from_env(
+        inner: Arc<dyn HttpService + Send + Sync>,
+        fixtures_dir: impl Into<PathBuf>,
+    ) -> RecordingHttpService {
+        let mode = match std::env::var_os(RECORD_HTTP_ENV) {
+            Some(value) if !value.is_empty() && value != "0" => RecordMode::Record,
+            _ => RecordMode::Replay,
+        };
+        RecordingHttpService::new(inner, fixtures_dir, mode)
+    }
+
+    /// File name of a request, built from the URL path and the query parameters sorted by name,
+    /// e.g. `asset_edit_asset_1709_page_0_status_new_accepted`
+    fn fixture_name(url: &str, params: &HashMap<String, String>) -> Result<String> {
+        let url = Url::parse(url)?;
+        let sorted_params: BTreeMap<&String, &String> = params.iter().collect();
+        let mut parts: Vec<&str> = url
+            .path_segments()
+            .map(|segments| segments.collect())
+            .unwrap_or_default();
+        // The base path, e.g. /asset-library/api, is the same for every request
+        if let Some(position) = parts.iter().position(|part| *part == "api") {
+            parts.drain(..=position);
+        }
+        let mut name = parts.join("_");
+        for (key, value) in sorted_params {
+            name.push_str(&format!("_{}_{}", key, value));
+        }
+        let name = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>()
+            .split('_')
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("_");
+        Ok(name)
+    }
+
+    fn fixture_path(&self, name: &str, extension: &str) -> PathBuf {
+        self.fixtures_dir.join(format!("{}.{}", name, extension))
+    }
+
+    fn read_fixture(path: &Path, url: &str) -> Result<Vec<u8>> {
+        if !path.exists() {
+            bail!(
+                "No recorded response for {} in {}. Record it with {}=1 cargo test",
+                url,
+                path.display(),
+                RECORD_HTTP_ENV
+            )
+        }
+        std::fs::read(path)
+            .with_context(|| format!("Failed to read recorded response: {}", path.display()))
+    }
+
+    fn write_fixture(path: &Path, content: &[u8]) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to save recorded response: {}", path.display()))?;
+        info!("Recorded response: {}", path.display());
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpService for RecordingHttpService {
+    async fn get(&self, url: String, params: HashMap<String, String>) -> Result<Value> {
+        let path = self.fixture_path(&Self::fixture_name(&url, &params)?, "json");
+        match self.mode {
+            RecordMode::Record => {
+                let data = self.inner.get(url, params).await?;
+                Self::write_fixture(&path, serde_json::to_string_pretty(&data)?.as_bytes())?;
+                Ok(data)
+            }
+            RecordMode::Replay => {
+                let content = Self::read_fixture(&path, &url)?;
+                Ok(serde_json::from_slice(&content)?)
+            }
+        }
+    }
+
+    async fn get_file(&self, url: String) -> Result<Response> {
+        let path = self.fixture_path(&Self::fixture_name(&url, &HashMap::new())?, "bin");
+        let content = match self.mode {
+            RecordMode::Record => {
+                let response = self.inner.get_file(url).await?;
+                let content = response.bytes().await?.to_vec();
+                Self::write_fixture(&path, &content)?;
+                content
+            }
+            RecordMode::Replay => Self::read_fixture(&path, &url)?,
+        };
+        Ok(::http::Response::builder()
+            .status(200)
+            .body(content)?
+            .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::MockDefaultHttpService;
+
+    const BASE_URL: &str = "https://godotengine.org/asset-library/api";
+
+    #[test]
+    fn test_fixture_name_should_sort_params() {
+        let params = HashMap::from([
+            ("status".to_string(), "new accepted".to_string()),
+            ("asset".to_string(), "1709".to_string()),
+            ("page".to_string(), "0".to_string()),
+        ]);
+        let name = RecordingHttpService::fixture_name(&format!("{}/asset/edit", BASE_URL), &params)
+            .unwrap();
+        assert_eq!(name, "asset_edit_asset_1709_page_0_status_new_accepted");
+    }
+
+    #[tokio::test]
+    async fn test_replay_should_not_call_inner_service() {
+        let mut mock_http_service = MockDefaultHttpService::new();
+        mock_http_service.expect_get().never();
+        let http_service = RecordingHttpService::new(
+            Arc::new(mock_http_service),
+            "tests/mocks/http",
+            RecordMode::Replay,
+        );
+
+        let data = http_service
+            .get(format!("{}/asset/1709", BASE_URL), HashMap::new())
+            .await
+            .unwrap();
+        assert_eq!(data["asset_id"], "1709");
+    }
+
+    #[tokio::test]
+    async fn test_replay_without_fixture_should_fail() {
+        let http_service = RecordingHttpService::new(
+            Arc::new(MockDefaultHttpService::new()),
+            "tests/mocks/http",
+            RecordMode::Replay,
+        );
+
+        let result = http_service
+            .get(format!("{}/asset/0", BASE_URL), HashMap::new())
+            .await;
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("No recorded response")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_record_should_save_response() {
+        let fixtures_dir = temp_dir::TempDir::new().unwrap();
+        let mut mock_http_service = MockDefaultHttpService::new();
+        mock_http_service
+            .expect_get()
+            .returning(|_, _| Ok(serde_json::json!({ "asset_id": "42" })));
+        let http_service = RecordingHttpService::new(
+            Arc::new(mock_http_service),
+            fixtures_dir.path(),
+            RecordMode::Record,
+        );
+
+        http_service
+            .get(format!("{}/asset/42", BASE_URL), HashMap::new())
+            .await
+            .unwrap();
+
+        let saved = std::fs::read_to_string(fixtures_dir.path().join("asset_42.json")).unwrap();
+        assert!(saved.contains("\"asset_id\": \"42\""));
+    }
+}
@@ -0,0 +1,344 @@
+use crate::config::{AppConfig, DefaultAppConfig};
+use crate::error::{ErrorKind, GdmError};
+
+use anyhow::{Result, bail};
+use std::ffi::OsStr;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use tracing::debug;
+
+const PGP_SIGNATURE: &[u8] = b"-----BEGIN PGP SIGNATURE-----";
+const SSH_SIGNATURE: &[u8] = b"-----BEGIN SSH SIGNATURE-----";
+/// Principal the trusted SSH keys are listed under in the allowed signers file
+const SSH_PRINCIPAL: &str = "gdm";
+
+/// Checks signatures of git tags and commits against `project.trusted_keys` in gdm.json.
+/// GPG signatures are verified by `gpg` with the local keyring and accepted if the signing key or
+/// its primary key has a trusted fingerprint. SSH signatures are verified by `ssh-keygen -Y verify`
+/// with the trusted SSH public keys.
+#[derive(Debug, Clone)]
+pub struct SignatureVerifier {
+    trusted_keys: Vec<String>,
+}
+
+impl SignatureVerifier {
+    pub fn new(trusted_keys: Vec<String>) -> SignatureVerifier {
+        SignatureVerifier { trusted_keys }
+    }
+
+    /// The verifier of the current configuration
+    pub fn from_config(app_config: &DefaultAppConfig) -> SignatureVerifier {
+        SignatureVerifier::new(app_config.get_trusted_keys())
+    }
+
+    /// Verifies that `signature` of `payload` was made with a trusted key. The files the tools
+    /// read are written to `work_dir`. `subject` names what was signed, e.g. `tag v1.2.0 of <url>`.
+    pub fn verify(
+        &self,
+        signature: &[u8],
+        payload: &[u8],
+        work_dir: &Path,
+        subject: &str,
+    ) -> Result<()> {
+        let signature = signature.trim_ascii_start();
+        if signature.starts_with(SSH_SIGNATURE) {
+            self.verify_ssh(signature, payload, work_dir, subject)
+        } else if signature.starts_with(PGP_SIGNATURE) {
+            self.verify_gpg(signature, payload, work_dir, subject)
+        } else {
+            bail!(Self::error(format!(
+                "The signature of {} is neither a GPG nor an SSH signature",
+                subject
+            )))
+        }
+    }
+
+    /// Splits the data of a tag into the signed part and the signature appended to its message.
+    /// None if the tag isn't signed.
+    pub fn split_signed_tag(data: &[u8]) -> Option<(&[u8], &[u8])> {
+        let mut start = None;
+        let mut offset = 0;
+        for line in data.split_inclusive(|&b| b == b'\n') {
+            if line.starts_with(PGP_SIGNATURE) || line.starts_with(SSH_SIGNATURE) {
+                start = Some(offset);
+            }
+            offset += line.len();
+        }
+        start.map(|start| data.split_at(start))
+    }
+
+    fn verify_ssh(
+        &self,
+        signature: &[u8],
+        payload: &[u8],
+        work_dir: &Path,
+        subject: &str,
+    ) -> Result<()> {
+        let allowed_signers: String = self
+            .trusted_keys
+            .iter()
+            .filter(|key| Self::is_ssh_key(key))
+            .map(|key| format!("{} {}\n", SSH_PRINCIPAL, key.trim()))
+            .collect();
+        if allowed_signers.is_empty() {
+            bail!(Self::error(format!(
+                "{} is signed with an SSH key, but project.trusted_keys has no SSH public keys",
+                subject
+            )));
+        }
+        let allowed_signers_file = work_dir.join("allowed_signers");
+        let signature_file = work_dir.join("signature");
+        fs::write(&allowed_signers_file, allowed_signers)?;
+        fs::write(&signature_file, signature)?;
+
+        let output = Self::run(
+            "ssh-keygen",
+            [
+                OsStr::new("-Y"),
+                OsStr::new("verify"),
+                OsStr::new("-f"),
+                allowed_signers_file.as_os_str(),
+                OsStr::new("-I"),
+                OsStr::new(SSH_PRINCIPAL),
+                OsStr::new("-n"),
+                OsStr::new("git"),
+                OsStr::new("-s"),
+                signature_file.as_os_str(),
+            ],
+            payload,
+        )?;
+        if !output.status.success() {
+            // ssh-keygen prints why the signature was refused to stdout or stderr
+            let reason = [&output.stderr, &output.stdout]
+                .iter()
+                .map(|out| String::from_utf8_lossy(out).trim().to_string())
+                .find(|out| !out.is_empty())
+                .unwrap_or_else(|| output.status.to_string());
+            bail!(Self::error(format!(
+                "{} isn't signed by a trusted SSH key: {}",
+                subject, reason
+            )));
+        }
+        debug!(
+            "{}: {}",
+            subject,
+            String::from_utf8_lossy(&output.stdout).trim()
+        );
+        Ok(())
+    }
+
+    fn verify_gpg(
+        &self,
+        signature: &[u8],
+        payload: &[u8],
+        work_dir: &Path,
+        subject: &str,
+    ) -> Result<()> {
+        let signature_file = work_dir.join("signature.asc");
+        fs::write(&signature_file, signature)?;
+
+        let output = Self::run(
+            "gpg",
+            [
+                OsStr::new("--status-fd"),
+                OsStr::new("1"),
+                OsStr::new("--verify"),
+                signature_file.as_os_str(),
+                OsStr::new("-"),
+            ],
+            payload,
+        )?;
+        let status = String::from_utf8_lossy(&output.stdout);
+        let fingerprints = Self::valid_signature_fingerprints(&status);
+        if output.status.success()
+            && let Some(fingerprint) = fingerprints.iter().find(|f| self.trusts_fingerprint(f))
+        {
+            debug!("{} is signed by GPG key {}", subject, fingerprint);
+            return Ok(());
+        }
+        if let Some(fingerprint) = fingerprints.first() {
+            bail!(Self::error(format!(
+                "{} is signed by GPG key {}, which isn't in project.trusted_keys",
+                subject, fingerprint
+            )));
+        }
+        let error = Self::error(format!(
+            "{} has no valid GPG signature: {}",
+            subject,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+        if status
+            .lines()
+            .any(|line| line.starts_with("[GNUPG:] NO_PUBKEY"))
+        {
+            bail!(error.with_hint(
+                "Import the signing key into your keyring with gpg --import, and add its fingerprint to project.trusted_keys in gdm.json."
+            ));
+        }
+        bail!(error)
+    }
+
+    /// Fingerprints of the signing key and its primary key from the `VALIDSIG` lines of
+    /// `gpg --status-fd` output
+    fn valid_signature_fingerprints(status: &str) -> Vec<String> {
+        let mut fingerprints = Vec::new();
+        for line in status.lines() {
+            let Some(fields) = line.strip_prefix("[GNUPG:] VALIDSIG ") else {
+                continue;
+            };
+            let fields: Vec<&str> = fields.split_whitespace().collect();
+            for fingerprint in [fields.first(), fields.get(9)].into_iter().flatten() {
+                if !fingerprints.iter().any(|f| f == fingerprint) {
+                    fingerprints.push(fingerprint.to_string());
+                }
+            }
+        }
+        fingerprints
+    }
+
+    fn trusts_fingerprint(&self, fingerprint: &str) -> bool {
+        self.trusted_keys
+            .iter()
+            .filter(|key| !Self::is_ssh_key(key))
+            .any(|key| Self::normalize_fingerprint(key) == Self::normalize_fingerprint(fingerprint))
+    }
+
+    /// Fingerprints are compared without case, spaces and a `0x` prefix, as `gpg` prints them
+    /// in several ways
+    fn normalize_fingerprint(fingerprint: &str) -> String {
+        let fingerprint: String = fingerprint
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_ascii_uppercase();
+        fingerprint
+            .strip_prefix("0X")
+            .map(str::to_string)
+            .unwrap_or(fingerprint)
+    }
+
+    fn is_ssh_key(key: &str) -> bool {
+        let key = key.trim_start();
+        ["ssh-", "ecdsa-", "sk-"]
+            .iter()
+            .any(|prefix| key.starts_with(prefix))
+    }
+
+    fn run<'a>(
+        program: &str,
+        args: impl IntoIterator<Item = &'a OsStr>,
+        stdin: &[u8],
+    ) -> Result<Output> {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                Self::error(format!("Failed to run {}: {}", program, e)).with_hint(format!(
+                    "Install {} to verify the signatures of git plugins.",
+                    program
+                ))
+            })?;
+        if let Some(mut child_stdin) = child.stdin.take() {
+            child_stdin.write_all(stdin)?;
+        }
+        Ok(child.wait_with_output()?)
+    }
+
+    fn error(message: String) -> GdmError {
+        GdmError::new(ErrorKind::SignatureInvalid, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_signature_fingerprints_should_list_signing_and_primary_keys() {
+        let status = "[GNUPG:] NEWSIG\n\
+            [GNUPG:] GOODSIG 0123456789ABCDEF Jane Doe <jane@example.com>\n\
+            [GNUPG:] VALIDSIG 1111222233334444555566667777888899990000 2026-01-01 1767225600 0 4 0 22 8 00 AAAABBBBCCCCDDDDEEEEFFFF0000111122223333\n\
+            [GNUPG:] TRUST_UNDEFINED 0 pgp\n";
+
+        assert_eq!(
+            SignatureVerifier::valid_signature_fingerprints(status),
+            vec![
+                "1111222233334444555566667777888899990000".to_string(),
+                "AAAABBBBCCCCDDDDEEEEFFFF0000111122223333".to_string(),
+            ]
+        );
+        assert!(SignatureVerifier::valid_signature_fingerprints("[GNUPG:] BADSIG 0123").is_empty());
+    }
+
+    #[test]
+    fn test_split_signed_tag_should_split_at_last_signature() {
+        let tag = b"object 0000\ntype commit\ntag v1.0.0\n\nRelease\n-----BEGIN SSH SIGNATURE-----\nU1NI\n-----END SSH SIGNATURE-----\n";
+
+        let (payload, signature) = SignatureVerifier::split_signed_tag(tag).unwrap();
+
+        assert_eq!(
+            payload,
+            b"object 0000\ntype commit\ntag v1.0.0\n\nRelease\n"
+        );
+        assert_eq!(
+            signature,
+            b"-----BEGIN SSH SIGNATURE-----\nU1NI\n-----END SSH SIGNATURE-----\n"
+        );
+        assert!(SignatureVerifier::split_signed_tag(b"object 0000\n\nRelease\n").is_none());
+    }
+
+    #[test]
+    fn test_trusts_fingerprint_should_ignore_case_and_spaces() {
+        let verifier = SignatureVerifier::new(vec![
+            "aaaa bbbb cccc dddd eeee  ffff 0000 1111 2222 3333".to_string(),
+            "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAI jane@example.com".to_string(),
+        ]);
+
+        assert!(verifier.trusts_fingerprint("AAAABBBBCCCCDDDDEEEEFFFF0000111122223333"));
+        assert!(!verifier.trusts_fingerprint("1111222233334444555566667777888899990000"));
+        assert!(
+            !verifier.trusts_fingerprint("ssh-ed25519AAAAC3NzaC1lZDI1NTE5AAAAIjane@example.com")
+        );
+    }
+
+    #[test]
+    fn test_verify_should_refuse_unknown_signature_format() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let verifier = SignatureVerifier::new(Vec::new());
+
+        let error = verifier
+            .verify(
+                b"-----BEGIN SIGNED MESSAGE-----",
+                b"tree 0000",
+                dir.path(),
+                "commit 1234567",
+            )
+            .unwrap_err();
+
+        let gdm_error = GdmError::find(&error).unwrap();
+        assert_eq!(gdm_error.kind, ErrorKind::SignatureInvalid);
+    }
+
+    #[test]
+    fn test_verify_should_refuse_ssh_signature_without_ssh_keys() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let verifier =
+            SignatureVerifier::new(vec!["AAAABBBBCCCCDDDDEEEEFFFF0000111122223333".to_string()]);
+
+        let error = verifier
+            .verify(
+                b"-----BEGIN SSH SIGNATURE-----\n-----END SSH SIGNATURE-----\n",
+                b"tree 0000",
+                dir.path(),
+                "commit 1234567",
+            )
+            .unwrap_err();
+
+        assert!(error.to_string().contains("has no SSH public keys"));
+    }
+}
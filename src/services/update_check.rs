@@ -0,0 +1,149 @@
+use crate::services::USER_AGENT;
+use crate::utils::Utils;
+
+use anyhow::{Context, Result};
+use semver::Version;
+use serde_derive::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+const RELEASES_URL: &str = "https://api.github.com/repos/k0psutin/gdm/releases/latest";
+pub const RELEASES_PAGE_URL: &str = "https://github.com/k0psutin/gdm/releases";
+const CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Result of the last check, stored in update-check.json in [`Utils::cache_dir`]
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct UpdateCheckState {
+    /// Seconds since the Unix epoch
+    checked_at: u64,
+    latest_version: Option<String>,
+}
+
+impl UpdateCheckState {
+    fn is_due(&self, now: u64) -> bool {
+        now.saturating_sub(self.checked_at) >= CHECK_INTERVAL.as_secs()
+    }
+
+    /// The latest release if it's newer than the running gdm
+    fn newer_version(&self, current: &Version) -> Option<Version> {
+        let latest = self.latest_version.as_deref()?;
+        let latest = Version::parse(latest.trim_start_matches('v')).ok()?;
+        (latest > *current).then_some(latest)
+    }
+}
+
+#[derive(Deserialize)]
+struct LatestRelease {
+    tag_name: String,
+}
+
+/// Checks the gdm release feed at most once a day in the background
+pub struct UpdateCheck {
+    state_file: PathBuf,
+}
+
+impl UpdateCheck {
+    /// None if the cache directory can't be determined
+    pub fn new() -> Option<UpdateCheck> {
+        Some(UpdateCheck {
+            state_file: Utils::cache_dir()?.join("update-check.json"),
+        })
+    }
+
+    /// Starts the check on a background task. The task resolves to a newer gdm version,
+    /// from the release feed if the last check was more than a day ago, otherwise from the cache.
+    pub fn spawn(self) -> JoinHandle<Option<Version>> {
+        tokio::spawn(async move {
+            let current = Version::parse(env!("CARGO_PKG_VERSION")).ok()?;
+            match self.latest_state().await {
+                Ok(state) => state.newer_version(&current),
+                Err(e) => {
+                    debug!("Update check failed: {:#}", e);
+                    None
+                }
+            }
+        })
+    }
+
+    async fn latest_state(&self) -> Result<UpdateCheckState> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let cached = self.read_state().unwrap_or_default();
+        if !cached.is_due(now) {
+            return Ok(cached);
+        }
+
+        // Store the timestamp before fetching, so an unreachable feed is retried tomorrow
+        let mut state = UpdateCheckState {
+            checked_at: now,
+            latest_version: cached.latest_version,
+        };
+        self.write_state(&state)?;
+        state.latest_version = Some(Self::fetch_latest_version().await?);
+        self.write_state(&state)?;
+        Ok(state)
+    }
+
+    async fn fetch_latest_version() -> Result<String> {
+        let client = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+        let release: LatestRelease = client
+            .get(RELEASES_URL)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(release.tag_name)
+    }
+
+    fn read_state(&self) -> Result<UpdateCheckState> {
+        let content = std::fs::read_to_string(&self.state_file)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    fn write_state(&self, state: &UpdateCheckState) -> Result<()> {
+        if let Some(parent) = self.state_file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.state_file, serde_json::to_string(state)?)
+            .with_context(|| format!("Failed to write {}", self.state_file.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_due_should_wait_a_day_between_checks() {
+        let state = UpdateCheckState {
+            checked_at: 1_000_000,
+            latest_version: None,
+        };
+        assert!(!state.is_due(1_000_000 + 60));
+        assert!(state.is_due(1_000_000 + CHECK_INTERVAL.as_secs()));
+        assert!(UpdateCheckState::default().is_due(1_000_000));
+    }
+
+    #[test]
+    fn test_newer_version_should_only_return_newer_releases() {
+        let current = Version::parse("1.2.2").unwrap();
+        let state = |latest: &str| UpdateCheckState {
+            checked_at: 0,
+            latest_version: Some(latest.to_string()),
+        };
+
+        assert_eq!(
+            state("v1.3.0").newer_version(&current),
+            Some(Version::parse("1.3.0").unwrap())
+        );
+        assert_eq!(state("1.2.2").newer_version(&current), None);
+        assert_eq!(state("v1.0.0").newer_version(&current), None);
+        assert_eq!(state("nightly").newer_version(&current), None);
+    }
+}
@@ -0,0 +1,49 @@
+/// Field separator of `--format csv` and `--format tsv`, for spreadsheets and scripts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Delimiter {
+    Comma,
+    Tab,
+}
+
+impl Delimiter {
+    /// Joins the fields into one line. CSV fields with commas, quotes or line breaks are quoted.
+    /// TSV can't quote, so tabs and line breaks in fields become spaces.
+    pub fn row<S: AsRef<str>>(&self, fields: &[S]) -> String {
+        let fields = fields.iter().map(|field| {
+            let field = field.as_ref();
+            match self {
+                Delimiter::Comma if field.contains([',', '"', '\n', '\r']) => {
+                    format!("\"{}\"", field.replace('"', "\"\""))
+                }
+                Delimiter::Comma => field.to_string(),
+                Delimiter::Tab => field.replace(['\t', '\n', '\r'], " "),
+            }
+        });
+        let separator = match self {
+            Delimiter::Comma => ",",
+            Delimiter::Tab => "\t",
+        };
+        fields.collect::<Vec<_>>().join(separator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_should_quote_csv_fields() {
+        assert_eq!(
+            Delimiter::Comma.row(&["gut", "GUT, \"Godot 4\"", "9.6.0", ""]),
+            "gut,\"GUT, \"\"Godot 4\"\"\",9.6.0,"
+        );
+    }
+
+    #[test]
+    fn test_row_should_replace_tabs_in_tsv_fields() {
+        assert_eq!(
+            Delimiter::Tab.row(&["gut", "GUT\tGodot\n4", "9.6.0"]),
+            "gut\tGUT Godot 4\t9.6.0"
+        );
+    }
+}
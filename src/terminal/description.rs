@@ -0,0 +1,153 @@
+use regex::{Captures, Regex};
+
+/// Widest the text is wrapped to, even on wide terminals
+const MAX_WIDTH: usize = 100;
+
+/// BBCode tags that are removed, keeping their text. Other bracketed words like `[WIP]` stay.
+const BBCODE_TAGS: &str =
+    "b|i|u|s|code|center|left|right|color|size|font|indent|quote|table|cell|p|ul|ol";
+
+/// Converts an Asset Library description to plain terminal text. BBCode and HTML tags are
+/// removed, links keep their URL, list items become bullets and lines are wrapped to `width`.
+pub fn render_description(description: &str, width: usize) -> String {
+    let text = strip_markup(description);
+    let width = width.clamp(20, MAX_WIDTH);
+
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        match line
+            .strip_prefix("- ")
+            .or_else(|| line.strip_prefix("* "))
+            .or_else(|| line.strip_prefix("• "))
+        {
+            Some(item) => lines.extend(wrap(item.trim(), width, "• ", "  ")),
+            None if line.is_empty() => {
+                if lines.last().is_some_and(|last| !last.is_empty()) {
+                    lines.push(String::new());
+                }
+            }
+            None => lines.extend(wrap(line, width, "", "")),
+        }
+    }
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+/// The terminal's width for [`render_description`], 80 if stdout isn't a terminal
+pub fn description_width() -> usize {
+    console::Term::stdout()
+        .size_checked()
+        .map(|(_, columns)| columns as usize)
+        .unwrap_or(80)
+}
+
+fn strip_markup(description: &str) -> String {
+    let text = description.replace("\r\n", "\n").replace('\r', "\n");
+
+    let url_with_text = Regex::new(r"(?is)\[url=([^\]]+)\](.*?)\[/url\]").unwrap();
+    let text = url_with_text.replace_all(&text, |captures: &Captures| {
+        link(&captures[2], &captures[1])
+    });
+    let html_link = Regex::new(r#"(?is)<a\s[^>]*href=["']([^"']+)["'][^>]*>(.*?)</a>"#).unwrap();
+    let text = html_link.replace_all(&text, |captures: &Captures| {
+        link(&captures[2], &captures[1])
+    });
+    let images = Regex::new(r"(?is)\[img[^\]]*\].*?\[/img\]|<img[^>]*>").unwrap();
+    let text = images.replace_all(&text, "");
+
+    let line_breaks = Regex::new(r"(?i)<br\s*/?>").unwrap();
+    let text = line_breaks.replace_all(&text, "\n");
+    let paragraphs = Regex::new(r"(?i)</?p\s*>|</?h[1-6][^>]*>").unwrap();
+    let text = paragraphs.replace_all(&text, "\n\n");
+    let list_items = Regex::new(r"(?i)<li[^>]*>|\[\*\]").unwrap();
+    let text = list_items.replace_all(&text, "\n• ");
+    let lists = Regex::new(r"(?i)</li>|</?[uo]l[^>]*>|\[/?list[^\]]*\]").unwrap();
+    let text = lists.replace_all(&text, "\n");
+
+    let html_tags = Regex::new(r"</?[a-zA-Z][^>]*>").unwrap();
+    let text = html_tags.replace_all(&text, "");
+    let bbcode_tags = Regex::new(&format!(
+        r"(?i)\[/?(?:{})(?:=[^\]]*)?\]|\[/?url\]",
+        BBCODE_TAGS
+    ))
+    .unwrap();
+    let text = bbcode_tags.replace_all(&text, "");
+
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// `text (url)`, or only the URL when it's also the text
+fn link(text: &str, url: &str) -> String {
+    let text = text.trim();
+    if text.is_empty() || text == url {
+        url.to_string()
+    } else {
+        format!("{} ({})", text, url)
+    }
+}
+
+/// Wraps `line` at word boundaries, starting with `first` and indenting the rest with `rest`.
+/// Words longer than the width, e.g. URLs, get a line of their own.
+fn wrap(line: &str, width: usize, first: &str, rest: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = first.to_string();
+    let mut is_empty = true;
+    for word in line.split_whitespace() {
+        if !is_empty && current.chars().count() + 1 + word.chars().count() > width {
+            lines.push(current);
+            current = rest.to_string();
+            is_empty = true;
+        }
+        if !is_empty {
+            current.push(' ');
+        }
+        current.push_str(word);
+        is_empty = false;
+    }
+    lines.push(current);
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_description_should_strip_bbcode_and_html() {
+        let description = "[b]GUT[/b] is a <i>unit testing</i> tool.<br>Docs: \
+            [url=https://gut.readthedocs.io]Read the docs[/url] &amp; more. [WIP]\r\n\
+            [img]https://example.com/logo.png[/img]";
+        assert_eq!(
+            render_description(description, 80),
+            "GUT is a unit testing tool.\n\
+             Docs: Read the docs (https://gut.readthedocs.io) & more. [WIP]"
+        );
+    }
+
+    #[test]
+    fn test_render_description_should_render_lists_as_bullets() {
+        let description =
+            "Features:\n[list][*]Asserts[*]Doubles[/list]<ul><li>Spies</li></ul>\n- Mocks";
+        assert_eq!(
+            render_description(description, 80),
+            "Features:\n\n• Asserts\n• Doubles\n\n• Spies\n\n• Mocks"
+        );
+    }
+
+    #[test]
+    fn test_render_description_should_wrap_long_lines() {
+        let description = "- one two three four five six seven eight nine ten eleven twelve";
+        assert_eq!(
+            render_description(description, 30),
+            "• one two three four five six\n  seven eight nine ten eleven\n  twelve"
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use std::fmt;
+use std::path::Path;
+
+/// Severity of a GitHub Actions workflow annotation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnnotationLevel {
+    Notice,
+    Warning,
+    Error,
+}
+
+/// A workflow command like `::warning file=gdm.json,line=4,title=Outdated plugin::...`
+/// that GitHub Actions shows on the run and inline on the pull request diff
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    pub level: AnnotationLevel,
+    pub file: String,
+    pub line: Option<usize>,
+    pub title: String,
+    pub message: String,
+}
+
+impl Annotation {
+    /// Annotation on the line of the configuration file with the plugin's title
+    pub fn for_plugin(
+        level: AnnotationLevel,
+        config_file: &Path,
+        plugin_title: &str,
+        title: &str,
+        message: String,
+    ) -> Annotation {
+        let quoted_title = format!("\"{}\"", plugin_title);
+        let line = std::fs::read_to_string(config_file)
+            .ok()
+            .and_then(|content| {
+                content
+                    .lines()
+                    .position(|line| line.contains(&quoted_title))
+            })
+            .map(|index| index + 1);
+        Annotation {
+            level,
+            file: config_file.display().to_string(),
+            line,
+            title: title.to_string(),
+            message,
+        }
+    }
+
+    /// Escapes the message as GitHub requires
+    fn escape_data(value: &str) -> String {
+        value
+            .replace('%', "%25")
+            .replace('\r', "%0D")
+            .replace('\n', "%0A")
+    }
+
+    /// Escapes a property value, which also can't contain `:` or `,`
+    fn escape_property(value: &str) -> String {
+        Self::escape_data(value)
+            .replace(':', "%3A")
+            .replace(',', "%2C")
+    }
+}
+
+impl fmt::Display for Annotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let command = match self.level {
+            AnnotationLevel::Notice => "notice",
+            AnnotationLevel::Warning => "warning",
+            AnnotationLevel::Error => "error",
+        };
+        write!(
+            f,
+            "::{} file={}",
+            command,
+            Self::escape_property(&self.file)
+        )?;
+        if let Some(line) = self.line {
+            write!(f, ",line={}", line)?;
+        }
+        write!(
+            f,
+            ",title={}::{}",
+            Self::escape_property(&self.title),
+            Self::escape_data(&self.message)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_should_escape_properties_and_message() {
+        let annotation = Annotation {
+            level: AnnotationLevel::Warning,
+            file: "gdm.json".to_string(),
+            line: Some(4),
+            title: "Outdated plugin: gut, 9.6.0".to_string(),
+            message: "100% outdated\nUpdate it".to_string(),
+        };
+
+        assert_eq!(
+            annotation.to_string(),
+            "::warning file=gdm.json,line=4,title=Outdated plugin%3A gut%2C 9.6.0::100%25 outdated%0AUpdate it"
+        );
+    }
+
+    #[test]
+    fn test_for_plugin_should_find_title_line() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let config_file = dir.path().join("gdm.json");
+        std::fs::write(
+            &config_file,
+            "{\n  \"plugins\": {\n    \"gut\": {\n      \"title\": \"Gut\",\n      \"version\": \"9.6.0\"\n",
+        )
+        .unwrap();
+
+        let annotation = Annotation::for_plugin(
+            AnnotationLevel::Error,
+            &config_file,
+            "Gut",
+            "Advisory",
+            "Broken".to_string(),
+        );
+        assert_eq!(annotation.line, Some(4));
+
+        let annotation = Annotation::for_plugin(
+            AnnotationLevel::Error,
+            &config_file,
+            "Missing",
+            "Advisory",
+            "Broken".to_string(),
+        );
+        assert_eq!(annotation.line, None);
+    }
+}
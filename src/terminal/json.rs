@@ -0,0 +1,148 @@
+use gdm_core::error::GdmError;
+use gdm_core::ui::{Operation, ProgressReporter, ProgressTask};
+
+use anyhow::Result;
+use serde_json::json;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// A failed command as a JSON object for `--output-format json`. `code`, `plugin` and `hint` are null
+/// for errors without a category, which exit with 1.
+pub fn error_json(error: &anyhow::Error) -> serde_json::Value {
+    let gdm_error = GdmError::find(error);
+    json!({
+        "event": "error",
+        "code": gdm_error.map(|e| e.kind.code()),
+        "exit_code": gdm_error.map_or(1, |e| e.kind.exit_code()),
+        "message": error.to_string(),
+        "plugin": gdm_error.and_then(GdmError::plugin),
+        "hint": gdm_error.and_then(GdmError::hint),
+    })
+}
+
+/// Prints started, message and finished events as JSON lines on stderr.
+/// Byte counts are only included in the finished event, not printed for every chunk.
+#[derive(Debug, Default)]
+pub struct JsonReporter;
+
+impl ProgressReporter for JsonReporter {
+    fn started(
+        &self,
+        operation: &Operation,
+        index: usize,
+        total: usize,
+        title: &str,
+        version: &str,
+    ) -> Result<Arc<dyn ProgressTask>> {
+        let task = JsonTask {
+            operation: operation.action_verb().to_lowercase(),
+            title: title.to_string(),
+            version: version.to_string(),
+            position: AtomicU64::new(0),
+        };
+        task.emit(json!({
+            "event": "started",
+            "operation": task.operation,
+            "index": index + 1,
+            "total": total,
+            "title": task.title,
+            "version": task.version,
+        }));
+        Ok(Arc::new(task))
+    }
+
+    fn set_total_plugins(&self, _total: usize) {}
+
+    fn add_downloaded_bytes(&self, _bytes: u64) {}
+
+    fn plugin_installed(&self) {}
+
+    fn warning(&self, message: &str) {
+        eprintln!("{}", json!({ "event": "warning", "message": message }));
+    }
+
+    fn finish(&self) {}
+}
+
+struct JsonTask {
+    operation: String,
+    title: String,
+    version: String,
+    position: AtomicU64,
+}
+
+impl JsonTask {
+    fn emit(&self, event: serde_json::Value) {
+        eprintln!("{}", event);
+    }
+}
+
+impl ProgressTask for JsonTask {
+    fn set_length(&self, _length: u64) {}
+
+    fn bytes(&self, bytes: u64) {
+        self.position.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    fn position(&self) -> u64 {
+        self.position.load(Ordering::Relaxed)
+    }
+
+    fn message(&self, message: &str) {
+        self.emit(json!({
+            "event": "message",
+            "operation": self.operation,
+            "title": self.title,
+            "version": self.version,
+            "message": message,
+        }));
+    }
+
+    fn finished(&self) {
+        self.emit(json!({
+            "event": "finished",
+            "operation": self.operation,
+            "title": self.title,
+            "version": self.version,
+            "bytes": self.position(),
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::{Context, anyhow};
+    use gdm_core::error::ErrorKind;
+
+    #[test]
+    fn test_error_json_should_include_code_plugin_and_hint() {
+        let error = Err::<(), _>(anyhow!(
+            GdmError::new(ErrorKind::NotFound, "Plugin gtu is not installed.")
+                .with_plugin("gtu")
+                .with_hint("Did you mean gut?")
+        ))
+        .context("Failed to update plugins")
+        .unwrap_err();
+
+        assert_eq!(
+            error_json(&error),
+            json!({
+                "event": "error",
+                "code": "GDM-202",
+                "exit_code": 6,
+                "message": "Failed to update plugins",
+                "plugin": "gtu",
+                "hint": "Did you mean gut?",
+            })
+        );
+    }
+
+    #[test]
+    fn test_error_json_without_category_should_use_exit_code_1() {
+        let json = error_json(&anyhow!("Something went wrong"));
+        assert_eq!(json["code"], serde_json::Value::Null);
+        assert_eq!(json["exit_code"], 1);
+        assert_eq!(json["message"], "Something went wrong");
+    }
+}
@@ -0,0 +1,665 @@
+mod delimited;
+mod description;
+mod github;
+mod json;
+mod plain;
+pub mod porcelain;
+pub mod redact;
+mod style;
+
+pub use delimited::Delimiter;
+pub use description::{description_width, render_description};
+pub use github::{Annotation, AnnotationLevel};
+use json::JsonReporter;
+pub use json::error_json;
+use plain::PlainLog;
+pub use style::{error, highlight, hint, success, version_change, warning};
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use gdm_core::ui::{NoopReporter, Operation, ProgressReporter, ProgressTask, UserInterface};
+use indicatif::{HumanBytes, MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::io::{BufRead, IsTerminal, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// How an `Operation` is drawn as a progress bar
+pub trait OperationStyle {
+    fn progress_bar_style(&self) -> Result<ProgressStyle>;
+    fn plain_progress_bar_style(&self) -> Result<ProgressStyle>;
+    fn default_progress_bar_length(&self) -> u64;
+    fn progress_chars(&self) -> &'static str;
+    fn create_progress_bar(
+        &self,
+        m: &MultiProgress,
+        index: usize,
+        total: usize,
+        title: &str,
+        version: &str,
+    ) -> Result<ProgressBar>;
+}
+
+impl OperationStyle for Operation {
+    fn progress_bar_style(&self) -> Result<ProgressStyle> {
+        let template = match self {
+            Operation::Resolve => "{spinner:.green} {prefix} {msg} [{elapsed_precise}]",
+            Operation::Install => {
+                "{spinner:.green} {prefix} {msg} [{elapsed_precise}] {bytes} ({bytes_per_sec}) [{eta}]"
+            }
+            Operation::Finished => "{prefix} {msg}",
+            Operation::Verify => {
+                "{spinner:.green} {msg} [{elapsed_precise}] [{bar:30}] {pos}/{len} [{eta}]"
+            }
+        };
+
+        ProgressStyle::with_template(template)
+            .context("Failed to create progress bar style")
+            .map(|style| style.progress_chars(self.progress_chars()))
+    }
+
+    /// Style for logs that can't redraw lines: no spinner or timers, so repeated lines can be skipped
+    fn plain_progress_bar_style(&self) -> Result<ProgressStyle> {
+        let template = match self {
+            Operation::Resolve => "{prefix} {msg}…",
+            Operation::Install => "{prefix} {msg}… {percent}%",
+            Operation::Finished => "{prefix} {msg}",
+            Operation::Verify => "{msg}… {pos}/{len}",
+        };
+
+        ProgressStyle::with_template(template).context("Failed to create progress bar style")
+    }
+
+    fn default_progress_bar_length(&self) -> u64 {
+        match self {
+            Operation::Finished => 1,
+            _ => 500,
+        }
+    }
+
+    fn progress_chars(&self) -> &'static str {
+        "#>-"
+    }
+
+    fn create_progress_bar(
+        &self,
+        m: &MultiProgress,
+        index: usize,
+        total: usize,
+        title: &str,
+        version: &str,
+    ) -> Result<ProgressBar> {
+        let pb = m.add(ProgressBar::new(self.default_progress_bar_length()));
+        pb.set_style(self.progress_bar_style()?);
+        pb.set_prefix(format!("[{}/{}]", index + 1, total));
+        pb.set_message(format!("{}: {} ({})", self.action_verb(), title, version));
+        // Keeps spinners moving while waiting for metadata, a git fetch or the first bytes
+        if !matches!(self, Operation::Finished) {
+            pb.enable_steady_tick(Duration::from_millis(100));
+        }
+        Ok(pb)
+    }
+}
+
+pub struct OperationManager {
+    multi_progress: MultiProgress,
+    main_progress: ProgressBar,
+    operation: Operation,
+    plain: bool,
+    total_plugins: AtomicUsize,
+    installed_plugins: AtomicUsize,
+    downloaded_bytes: AtomicU64,
+}
+
+impl OperationManager {
+    pub fn new(operation: Operation) -> Result<Self> {
+        // indicatif draws to stderr, and prints nothing at all when it isn't a terminal
+        let plain = !std::io::stderr().is_terminal();
+        let multi_progress = if plain {
+            MultiProgress::with_draw_target(ProgressDrawTarget::term_like_with_hz(
+                Box::new(PlainLog::default()),
+                1,
+            ))
+        } else {
+            MultiProgress::new()
+        };
+        let main_progress = multi_progress.add(ProgressBar::no_length());
+
+        main_progress.set_style(
+            ProgressStyle::with_template("{msg}")
+                .map_err(|e| anyhow::anyhow!("Failed to create main progress style: {}", e))?,
+        );
+        main_progress.set_message(Self::get_main_message_by_operation(&operation));
+
+        Ok(Self {
+            multi_progress,
+            main_progress,
+            operation,
+            plain,
+            total_plugins: AtomicUsize::new(0),
+            installed_plugins: AtomicUsize::new(0),
+            downloaded_bytes: AtomicU64::new(0),
+        })
+    }
+
+    fn update_main_message(&self) {
+        let total = self.total_plugins.load(Ordering::Relaxed);
+        if total < 2 {
+            return;
+        }
+        self.main_progress.set_message(format!(
+            "{}: {}/{} installed, {} downloaded",
+            Self::get_main_message_by_operation(&self.operation),
+            self.installed_plugins.load(Ordering::Relaxed),
+            total,
+            HumanBytes(self.downloaded_bytes.load(Ordering::Relaxed))
+        ));
+    }
+
+    fn get_main_message_by_operation(operation: &Operation) -> String {
+        match operation {
+            Operation::Resolve => "Resolving plugins".to_string(),
+            Operation::Install => "Installing plugins".to_string(),
+            Operation::Finished => "Installation complete".to_string(),
+            Operation::Verify => "Verifying".to_string(),
+        }
+    }
+
+    /// Adds a bar for a task of `operation`, drawn plain when stderr isn't a terminal
+    pub fn add_operation_bar(
+        &self,
+        operation: &Operation,
+        index: usize,
+        total: usize,
+        title: &str,
+        version: &str,
+    ) -> Result<ProgressBar> {
+        let pb =
+            operation.create_progress_bar(&self.multi_progress, index, total, title, version)?;
+        if self.plain {
+            pb.set_style(operation.plain_progress_bar_style()?);
+        }
+        Ok(pb)
+    }
+}
+
+/// Bar of an `OperationManager` task. Finished bars stay on screen, the others are cleared.
+struct BarTask {
+    bar: ProgressBar,
+    operation: Operation,
+}
+
+impl ProgressTask for BarTask {
+    fn set_length(&self, length: u64) {
+        self.bar.set_length(length);
+    }
+
+    fn bytes(&self, bytes: u64) {
+        self.bar.inc(bytes);
+    }
+
+    fn position(&self) -> u64 {
+        self.bar.position()
+    }
+
+    fn message(&self, message: &str) {
+        self.bar.set_message(message.to_string());
+    }
+
+    fn finished(&self) {
+        match self.operation {
+            Operation::Finished => self.bar.finish(),
+            _ => self.bar.finish_and_clear(),
+        }
+    }
+}
+
+/// Progress bars drawn with indicatif
+impl ProgressReporter for OperationManager {
+    fn started(
+        &self,
+        operation: &Operation,
+        index: usize,
+        total: usize,
+        title: &str,
+        version: &str,
+    ) -> Result<Arc<dyn ProgressTask>> {
+        let bar = self.add_operation_bar(operation, index, total, title, version)?;
+        Ok(Arc::new(BarTask {
+            bar,
+            operation: operation.clone(),
+        }))
+    }
+
+    /// Batches of several plugins show overall progress in the main line,
+    /// e.g. "Installing plugins: 3/12 installed, 45.00 MiB downloaded"
+    fn set_total_plugins(&self, total: usize) {
+        self.total_plugins.store(total, Ordering::Relaxed);
+        self.update_main_message();
+    }
+
+    fn add_downloaded_bytes(&self, bytes: u64) {
+        self.downloaded_bytes.fetch_add(bytes, Ordering::Relaxed);
+        self.update_main_message();
+    }
+
+    fn plugin_installed(&self) {
+        self.installed_plugins.fetch_add(1, Ordering::Relaxed);
+        self.update_main_message();
+    }
+
+    /// Printed above the bars, so it stays on screen when they are cleared
+    fn warning(&self, message: &str) {
+        self.multi_progress
+            .suspend(|| eprintln!("{}", warning(message)));
+    }
+
+    fn finish(&self) {
+        match self.operation {
+            Operation::Finished => self.main_progress.finish(),
+            _ => self.main_progress.finish_and_clear(),
+        }
+    }
+}
+
+/// How `outdated`, `audit` and `validate` print their results, set with --format
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum ReportFormat {
+    /// Tables and messages for reading in a terminal
+    #[default]
+    Text,
+    /// GitHub Actions workflow annotations, shown inline on pull requests
+    Github,
+    /// Comma-separated values with a header row, for spreadsheets
+    Csv,
+    /// Tab-separated values with a header row
+    Tsv,
+}
+
+impl ReportFormat {
+    /// Separator of the CSV and TSV formats, None for the others
+    pub fn delimiter(&self) -> Option<Delimiter> {
+        match self {
+            ReportFormat::Csv => Some(Delimiter::Comma),
+            ReportFormat::Tsv => Some(Delimiter::Tab),
+            ReportFormat::Text | ReportFormat::Github => None,
+        }
+    }
+}
+
+/// How `list` prints its table, set with --format
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum TableFormat {
+    /// A table for reading in a terminal
+    #[default]
+    Text,
+    /// Comma-separated values with a header row, for spreadsheets
+    Csv,
+    /// Tab-separated values with a header row
+    Tsv,
+}
+
+impl TableFormat {
+    /// Separator of the CSV and TSV formats, None for text
+    pub fn delimiter(&self) -> Option<Delimiter> {
+        match self {
+            TableFormat::Csv => Some(Delimiter::Comma),
+            TableFormat::Tsv => Some(Delimiter::Tab),
+            TableFormat::Text => None,
+        }
+    }
+}
+
+/// How errors are printed on stderr, set with --output-format or GDM_OUTPUT_FORMAT
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    /// `error[GDM-xxx]:` with the message and a hint
+    #[default]
+    Text,
+    /// One JSON object with the code, message, plugin and hint, for tools wrapping gdm
+    Json,
+}
+
+/// How progress is reported, set with --progress or GDM_PROGRESS
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum ProgressFormat {
+    /// Progress bars on terminals, plain lines otherwise
+    #[default]
+    Bars,
+    /// One JSON object per event on stderr, for CI and other tools
+    Json,
+    /// No progress output, e.g. with --quiet
+    None,
+}
+
+impl ProgressFormat {
+    /// Creates the reporter for a batch of `operation` tasks
+    pub fn reporter(&self, operation: Operation) -> Result<Arc<dyn ProgressReporter>> {
+        Ok(match self {
+            ProgressFormat::Bars => Arc::new(OperationManager::new(operation)?),
+            ProgressFormat::Json => Arc::new(JsonReporter),
+            ProgressFormat::None => Arc::new(NoopReporter),
+        })
+    }
+}
+
+/// Prints status messages to stdout, warnings and questions to stderr and draws progress
+/// in the chosen format. Status messages and progress are hidden with --quiet.
+pub struct Terminal {
+    quiet: bool,
+    assume_yes: bool,
+    progress: ProgressFormat,
+}
+
+impl Terminal {
+    pub fn new(quiet: bool, assume_yes: bool, progress: ProgressFormat) -> Terminal {
+        Terminal {
+            quiet,
+            assume_yes,
+            progress: if quiet {
+                ProgressFormat::None
+            } else {
+                progress
+            },
+        }
+    }
+}
+
+impl UserInterface for Terminal {
+    fn progress(&self, operation: Operation) -> Result<Arc<dyn ProgressReporter>> {
+        self.progress.reporter(operation)
+    }
+
+    fn status(&self, message: &str) {
+        if !self.quiet {
+            println!("{}", message);
+        }
+    }
+
+    fn success(&self, message: &str) {
+        if !self.quiet {
+            println!("{}", success(message));
+        }
+    }
+
+    fn warning(&self, message: &str) {
+        eprintln!("{}", warning(message));
+    }
+
+    fn confirm(&self, question: &str) -> Result<bool> {
+        confirm(question, self.assume_yes)
+    }
+
+    fn select(&self, question: &str, options: &[String]) -> Result<Option<usize>> {
+        select(question, options, self.assume_yes)
+    }
+}
+
+/// Asks a yes/no question before a destructive operation. Answers yes without asking
+/// when `assume_yes` is set or stdin isn't a terminal, e.g. in CI.
+pub fn confirm(question: &str, assume_yes: bool) -> Result<bool> {
+    let stdin = std::io::stdin();
+    if assume_yes || !stdin.is_terminal() {
+        return Ok(true);
+    }
+    eprint!("{} [y/N] ", question);
+    std::io::stderr().flush()?;
+    let mut answer = String::new();
+    stdin
+        .lock()
+        .read_line(&mut answer)
+        .context("Failed to read answer")?;
+    Ok(is_yes(&answer))
+}
+
+fn is_yes(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Lists numbered options and asks which one to use. Picks nothing without asking when
+/// `assume_yes` is set or stdin isn't a terminal, as there's no sensible default.
+pub fn select(question: &str, options: &[String], assume_yes: bool) -> Result<Option<usize>> {
+    let stdin = std::io::stdin();
+    if assume_yes || options.is_empty() || !stdin.is_terminal() {
+        return Ok(None);
+    }
+    eprintln!("{}", question);
+    for (index, option) in options.iter().enumerate() {
+        eprintln!("  {}) {}", index + 1, option);
+    }
+    eprint!(
+        "Enter a number (1-{}), or leave empty to cancel: ",
+        options.len()
+    );
+    std::io::stderr().flush()?;
+    let mut answer = String::new();
+    stdin
+        .lock()
+        .read_line(&mut answer)
+        .context("Failed to read answer")?;
+    Ok(parse_choice(&answer, options.len()))
+}
+
+/// The 0-based index of a 1-based choice, if it's one of the `count` options
+fn parse_choice(answer: &str, count: usize) -> Option<usize> {
+    answer
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|choice| (1..=count).contains(choice))
+        .map(|choice| choice - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_bar_style_install() {
+        let operation = Operation::Install;
+        let style = operation.progress_bar_style();
+        assert!(style.is_ok());
+    }
+
+    #[test]
+    fn test_progress_bar_style_finished() {
+        let operation = Operation::Finished;
+        let style = operation.progress_bar_style();
+        assert!(style.is_ok());
+    }
+
+    #[test]
+    fn test_plain_progress_bar_style() {
+        let m = MultiProgress::with_draw_target(ProgressDrawTarget::hidden());
+        let pb = Operation::Install
+            .create_progress_bar(&m, 0, 1, "gut", "9.3.0")
+            .unwrap();
+        pb.set_style(Operation::Install.plain_progress_bar_style().unwrap());
+        assert_eq!(pb.message(), "Downloading: gut (9.3.0)");
+        assert_eq!(pb.prefix(), "[1/1]");
+        assert!(Operation::Finished.plain_progress_bar_style().is_ok());
+    }
+
+    #[test]
+    fn test_resolve_styles() {
+        assert!(Operation::Resolve.progress_bar_style().is_ok());
+        assert!(Operation::Resolve.plain_progress_bar_style().is_ok());
+    }
+
+    #[test]
+    fn test_add_operation_bar_resolve() {
+        let manager = OperationManager::new(Operation::Install).unwrap();
+        let pb = manager
+            .add_operation_bar(&Operation::Resolve, 0, 2, "Gut", "9.3.0")
+            .unwrap();
+        assert_eq!(pb.message(), "Resolving: Gut (9.3.0)");
+        pb.finish_and_clear();
+    }
+
+    #[test]
+    fn test_default_progress_bar_length_finished() {
+        let operation = Operation::Finished;
+        assert_eq!(operation.default_progress_bar_length(), 1);
+    }
+
+    #[test]
+    fn test_default_progress_bar_length_install() {
+        let operation = Operation::Install;
+        assert_eq!(operation.default_progress_bar_length(), 500);
+    }
+
+    #[test]
+    fn test_progress_chars() {
+        let operation = Operation::Install;
+        assert_eq!(operation.progress_chars(), "#>-");
+    }
+
+    #[test]
+    fn test_create_progress_bar_install() {
+        let operation = Operation::Install;
+        let m = MultiProgress::new();
+        let result = operation.create_progress_bar(&m, 1, 5, "Test Plugin", "1.0.0");
+        assert!(result.is_ok());
+        let pb = result.unwrap();
+        assert_eq!(pb.length().unwrap(), 500);
+    }
+
+    #[test]
+    fn test_create_progress_bar_finished() {
+        let operation = Operation::Finished;
+        let m = MultiProgress::new();
+        let result = operation.create_progress_bar(&m, 1, 1, "Finished Plugin", "3.0.0");
+        assert!(result.is_ok());
+        let pb = result.unwrap();
+        assert_eq!(pb.length().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_new_operation_manager_install() {
+        let result = OperationManager::new(Operation::Install);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_new_operation_manager_finished() {
+        let result = OperationManager::new(Operation::Finished);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_get_main_message_by_operation_install() {
+        let message = OperationManager::get_main_message_by_operation(&Operation::Install);
+        assert_eq!(message, "Installing plugins");
+    }
+
+    #[test]
+    fn test_get_main_message_by_operation_finished() {
+        let message = OperationManager::get_main_message_by_operation(&Operation::Finished);
+        assert_eq!(message, "Installation complete");
+    }
+
+    #[test]
+    fn test_add_progress_bar_install() {
+        let manager = OperationManager::new(Operation::Install).unwrap();
+        let result = manager.started(&Operation::Install, 1, 5, "Test Plugin", "1.0.0");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_add_progress_bar_finished() {
+        let manager = OperationManager::new(Operation::Finished).unwrap();
+        let result = manager.started(&Operation::Finished, 1, 1, "Finished Plugin", "3.0.0");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_add_multiple_progress_bars() {
+        let manager = OperationManager::new(Operation::Install).unwrap();
+        let result1 = manager.started(&Operation::Install, 1, 2, "Plugin 1", "1.0.0");
+        let result2 = manager.started(&Operation::Install, 2, 2, "Plugin 2", "2.0.0");
+        assert!(result1.is_ok());
+        assert!(result2.is_ok());
+    }
+
+    #[test]
+    fn test_main_message_should_show_overall_progress_for_batches() {
+        let manager = OperationManager::new(Operation::Install).unwrap();
+        manager.set_total_plugins(3);
+        manager.add_downloaded_bytes(1024 * 1024);
+        manager.plugin_installed();
+        assert_eq!(
+            manager.main_progress.message(),
+            "Installing plugins: 1/3 installed, 1.00 MiB downloaded"
+        );
+    }
+
+    #[test]
+    fn test_main_message_should_stay_plain_for_single_plugin() {
+        let manager = OperationManager::new(Operation::Install).unwrap();
+        manager.set_total_plugins(1);
+        manager.plugin_installed();
+        assert_eq!(manager.main_progress.message(), "Installing plugins");
+    }
+
+    #[test]
+    fn test_reporter_should_match_format() {
+        assert!(ProgressFormat::Bars.reporter(Operation::Install).is_ok());
+        assert!(ProgressFormat::Json.reporter(Operation::Install).is_ok());
+        assert!(ProgressFormat::None.reporter(Operation::Install).is_ok());
+    }
+
+    #[test]
+    fn test_terminal_should_hide_progress_when_quiet() {
+        let terminal = Terminal::new(true, true, ProgressFormat::Json);
+        assert_eq!(terminal.progress, ProgressFormat::None);
+        assert!(terminal.confirm("Remove plugin gut?").is_ok());
+    }
+
+    #[test]
+    fn test_finish() {
+        let manager = OperationManager::new(Operation::Install).unwrap();
+        manager.finish();
+    }
+
+    #[test]
+    fn test_is_yes() {
+        assert!(is_yes("y\n"));
+        assert!(is_yes(" YES "));
+        assert!(!is_yes("\n"));
+        assert!(!is_yes("no"));
+    }
+
+    #[test]
+    fn test_confirm_with_assume_yes() {
+        assert!(confirm("Remove plugin gut?", true).unwrap());
+    }
+
+    #[test]
+    fn test_parse_choice() {
+        assert_eq!(parse_choice("2\n", 3), Some(1));
+        assert_eq!(parse_choice(" 1 ", 3), Some(0));
+        assert_eq!(parse_choice("\n", 3), None);
+        assert_eq!(parse_choice("0", 3), None);
+        assert_eq!(parse_choice("4", 3), None);
+        assert_eq!(parse_choice("dialogue", 3), None);
+    }
+
+    #[test]
+    fn test_select_with_assume_yes_should_pick_nothing() {
+        let options = vec!["Dialogue Manager".to_string(), "Dialogic".to_string()];
+        assert_eq!(select("Which one?", &options, true).unwrap(), None);
+    }
+
+    #[test]
+    fn test_operation_manager_workflow() {
+        let manager = OperationManager::new(Operation::Install).unwrap();
+        let task = manager
+            .started(&Operation::Install, 1, 1, "Workflow Plugin", "1.0.0")
+            .unwrap();
+        task.set_length(10);
+        task.bytes(4);
+        assert_eq!(task.position(), 4);
+        task.finished();
+        manager.finish();
+    }
+}
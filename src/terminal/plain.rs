@@ -0,0 +1,117 @@
+use indicatif::TermLike;
+use std::collections::HashSet;
+use std::io;
+use std::sync::Mutex;
+
+/// Draw target for logs that can't redraw lines, e.g. CI or a pipe.
+/// Ignores cursor movement and prints each distinct progress line once to stderr.
+#[derive(Debug, Default)]
+pub struct PlainLog {
+    state: Mutex<PlainLogState>,
+}
+
+#[derive(Debug, Default)]
+struct PlainLogState {
+    current_line: String,
+    printed_lines: HashSet<String>,
+}
+
+impl PlainLog {
+    fn push(&self, s: &str, end_of_line: bool) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.current_line.push_str(s);
+        if end_of_line {
+            let line = std::mem::take(&mut state.current_line);
+            if let Some(line) = state.take_new_line(line) {
+                eprintln!("{}", line);
+            }
+        }
+    }
+}
+
+impl PlainLogState {
+    /// Returns the trimmed line if it has text and hasn't been printed yet
+    fn take_new_line(&mut self, line: String) -> Option<String> {
+        let line = line.trim().to_string();
+        if line.is_empty() || !self.printed_lines.insert(line.clone()) {
+            return None;
+        }
+        Some(line)
+    }
+}
+
+impl TermLike for PlainLog {
+    fn width(&self) -> u16 {
+        200
+    }
+
+    fn move_cursor_up(&self, _n: usize) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn move_cursor_down(&self, _n: usize) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn move_cursor_right(&self, _n: usize) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn move_cursor_left(&self, _n: usize) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn write_line(&self, s: &str) -> io::Result<()> {
+        self.push(s, true);
+        Ok(())
+    }
+
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        self.push(s, false);
+        Ok(())
+    }
+
+    fn clear_line(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Ends the last line of a frame, which indicatif writes without a newline
+    fn flush(&self) -> io::Result<()> {
+        self.push("", true);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_new_line_should_skip_repeated_and_empty_lines() {
+        let mut state = PlainLogState::default();
+        assert_eq!(
+            state.take_new_line("\r[1/1] Downloading: gut (9.3.0)… 45%   ".to_string()),
+            Some("[1/1] Downloading: gut (9.3.0)… 45%".to_string())
+        );
+        assert_eq!(
+            state.take_new_line("[1/1] Downloading: gut (9.3.0)… 45%".to_string()),
+            None
+        );
+        assert_eq!(state.take_new_line(" ".to_string()), None);
+        assert_eq!(
+            state.take_new_line("[1/1] Downloading: gut (9.3.0)… 90%".to_string()),
+            Some("[1/1] Downloading: gut (9.3.0)… 90%".to_string())
+        );
+    }
+
+    #[test]
+    fn test_plain_log_should_join_partial_writes() {
+        let log = PlainLog::default();
+        log.write_str("[1/1] ").unwrap();
+        log.write_str("Installed: gut (9.3.0)").unwrap();
+        log.flush().unwrap();
+        let state = log.state.lock().unwrap();
+        assert!(state.current_line.is_empty());
+        assert!(state.printed_lines.contains("[1/1] Installed: gut (9.3.0)"));
+    }
+}
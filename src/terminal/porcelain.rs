@@ -0,0 +1,37 @@
+/// A `--porcelain` record for scripts: its kind, e.g. `plugin` or `update`, followed by
+/// tab-separated fields. Kinds, field order and escaping don't change between releases, new
+/// fields are only appended. Backslashes, tabs and line breaks in fields are escaped as `\\`,
+/// `\t`, `\n` and `\r`, so every record is exactly one line.
+pub fn line<S: AsRef<str>>(kind: &str, fields: &[S]) -> String {
+    std::iter::once(kind.to_string())
+        .chain(fields.iter().map(|field| escape(field.as_ref())))
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
+fn escape(field: &str) -> String {
+    let mut escaped = String::with_capacity(field.len());
+    for c in field.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_should_escape_fields() {
+        assert_eq!(
+            line("plugin", &["gut", "9.6.0", "", "C:\\addons\tgut\nnext"]),
+            "plugin\tgut\t9.6.0\t\tC:\\\\addons\\tgut\\nnext"
+        );
+    }
+}
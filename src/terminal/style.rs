@@ -0,0 +1,60 @@
+use console::{Style, StyledObject};
+use gdm_core::models::VersionChange;
+
+/// Completed operations, e.g. "Plugins updated successfully."
+pub fn success<D>(value: D) -> StyledObject<D> {
+    Style::new().green().apply_to(value)
+}
+
+/// Warnings printed to stderr
+pub fn warning<D>(value: D) -> StyledObject<D> {
+    Style::new().for_stderr().yellow().apply_to(value)
+}
+
+/// The `error[GDM-xxx]:` prefix printed to stderr
+pub fn error<D>(value: D) -> StyledObject<D> {
+    Style::new().for_stderr().red().bold().apply_to(value)
+}
+
+/// Parts of search results that match the query
+pub fn highlight<D>(value: D) -> StyledObject<D> {
+    Style::new().bold().underlined().apply_to(value)
+}
+
+/// The changed part of a newer version: red for major, yellow for minor, green for patch
+pub fn version_change<D>(value: D, change: VersionChange) -> StyledObject<D> {
+    let style = match change {
+        VersionChange::Major => Style::new().red().bold(),
+        VersionChange::Minor => Style::new().yellow(),
+        VersionChange::Patch | VersionChange::PreRelease => Style::new().green(),
+    };
+    style.apply_to(value)
+}
+
+/// Remediation hints printed to stderr below an error
+pub fn hint<D>(value: D) -> StyledObject<D> {
+    Style::new().for_stderr().dim().apply_to(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gdm_core::config::ColorChoice;
+
+    #[test]
+    fn test_styles_should_keep_text_without_colors() {
+        ColorChoice::Never.apply();
+        assert_eq!(
+            success("Plugins updated successfully.").to_string(),
+            "Plugins updated successfully."
+        );
+        assert_eq!(warning("Warning").to_string(), "Warning");
+        assert_eq!(error("error[GDM-202]:").to_string(), "error[GDM-202]:");
+        assert_eq!(hint("hint:").to_string(), "hint:");
+        assert_eq!(highlight("Gut").to_string(), "Gut");
+        assert_eq!(
+            version_change("10.0.0", VersionChange::Major).to_string(),
+            "10.0.0"
+        );
+    }
+}
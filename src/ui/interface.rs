@@ -0,0 +1,51 @@
+use crate::ui::{NoopReporter, Operation, ProgressReporter};
+
+use anyhow::Result;
+use std::sync::Arc;
+
+/// How the services talk to the user. The gdm binary prints to the terminal,
+/// editor plugins and other frontends can show progress and questions their own way.
+pub trait UserInterface: Send + Sync {
+    /// Starts reporting progress for a batch of `operation` tasks
+    fn progress(&self, operation: Operation) -> Result<Arc<dyn ProgressReporter>>;
+
+    /// Status message, e.g. "Removing plugin folder: addons/gut"
+    fn status(&self, message: &str);
+
+    /// Completed operation, e.g. "Plugins updated successfully."
+    fn success(&self, message: &str);
+
+    /// Something the user should know about that doesn't stop the command
+    fn warning(&self, message: &str);
+
+    /// Asks a yes/no question before a destructive operation
+    fn confirm(&self, question: &str) -> Result<bool>;
+
+    /// Asks the user to pick one of `options`, e.g. when a search matches several assets.
+    /// Returns the index of the picked option, or `None` when nothing was picked.
+    fn select(&self, question: &str, options: &[String]) -> Result<Option<usize>>;
+}
+
+/// Shows nothing, answers yes to every question and picks nothing. The default when gdm is used as a library.
+#[derive(Debug, Default)]
+pub struct SilentInterface;
+
+impl UserInterface for SilentInterface {
+    fn progress(&self, _operation: Operation) -> Result<Arc<dyn ProgressReporter>> {
+        Ok(Arc::new(NoopReporter))
+    }
+
+    fn status(&self, _message: &str) {}
+
+    fn success(&self, _message: &str) {}
+
+    fn warning(&self, _message: &str) {}
+
+    fn confirm(&self, _question: &str) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn select(&self, _question: &str, _options: &[String]) -> Result<Option<usize>> {
+        Ok(None)
+    }
+}
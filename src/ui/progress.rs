@@ -0,0 +1,119 @@
+use crate::ui::Operation;
+
+use anyhow::Result;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::warn;
+
+/// Receives progress from installers and services, so they don't depend on how it's shown
+pub trait ProgressReporter: Send + Sync {
+    /// A task started, e.g. resolving, downloading or extracting a plugin
+    fn started(
+        &self,
+        operation: &Operation,
+        index: usize,
+        total: usize,
+        title: &str,
+        version: &str,
+    ) -> Result<Arc<dyn ProgressTask>>;
+
+    /// Sets how many plugins the batch installs
+    fn set_total_plugins(&self, total: usize);
+
+    /// Counts a finished download towards the overall progress
+    fn add_downloaded_bytes(&self, bytes: u64);
+
+    /// Counts an installed plugin towards the overall progress
+    fn plugin_installed(&self);
+
+    /// Something the user should know about that doesn't stop the batch, e.g. a fallback to a cached archive
+    fn warning(&self, message: &str);
+
+    /// All tasks of the batch are done
+    fn finish(&self);
+}
+
+/// Progress of a single task started with `ProgressReporter::started`
+pub trait ProgressTask: Send + Sync {
+    /// Sets how many bytes, or steps like archive entries, the task has
+    fn set_length(&self, length: u64);
+
+    /// Advances the task by `bytes` bytes or steps
+    fn bytes(&self, bytes: u64);
+
+    /// Bytes or steps reported so far
+    fn position(&self) -> u64;
+
+    /// Replaces the task's status message
+    fn message(&self, message: &str);
+
+    fn finished(&self);
+}
+
+/// Reports nothing, for --quiet and for using gdm as a library
+#[derive(Debug, Default)]
+pub struct NoopReporter;
+
+impl ProgressReporter for NoopReporter {
+    fn started(
+        &self,
+        _operation: &Operation,
+        _index: usize,
+        _total: usize,
+        _title: &str,
+        _version: &str,
+    ) -> Result<Arc<dyn ProgressTask>> {
+        Ok(Arc::new(NoopTask::default()))
+    }
+
+    fn set_total_plugins(&self, _total: usize) {}
+
+    fn add_downloaded_bytes(&self, _bytes: u64) {}
+
+    fn plugin_installed(&self) {}
+
+    fn warning(&self, message: &str) {
+        warn!("{}", message);
+    }
+
+    fn finish(&self) {}
+}
+
+/// Only counts the reported bytes
+#[derive(Debug, Default)]
+pub struct NoopTask {
+    position: AtomicU64,
+}
+
+impl ProgressTask for NoopTask {
+    fn set_length(&self, _length: u64) {}
+
+    fn bytes(&self, bytes: u64) {
+        self.position.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    fn position(&self) -> u64 {
+        self.position.load(Ordering::Relaxed)
+    }
+
+    fn message(&self, _message: &str) {}
+
+    fn finished(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_noop_task_should_count_bytes() {
+        let task = NoopReporter
+            .started(&Operation::Install, 0, 1, "Gut", "9.3.0")
+            .unwrap();
+        task.set_length(100);
+        task.bytes(40);
+        task.bytes(2);
+        task.finished();
+        assert_eq!(task.position(), 42);
+    }
+}
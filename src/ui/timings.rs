@@ -0,0 +1,164 @@
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static TIMINGS: Mutex<Vec<Timing>> = Mutex::new(Vec::new());
+
+/// Step of an install that `--timings` reports separately
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase {
+    Metadata,
+    Download,
+    Extract,
+    Move,
+    Config,
+}
+
+impl Phase {
+    const ALL: [Phase; 5] = [
+        Phase::Metadata,
+        Phase::Download,
+        Phase::Extract,
+        Phase::Move,
+        Phase::Config,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Phase::Metadata => "Metadata",
+            Phase::Download => "Download",
+            Phase::Extract => "Extract",
+            Phase::Move => "Move",
+            Phase::Config => "Config",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Timing {
+    subject: String,
+    phase: Phase,
+    elapsed: Duration,
+}
+
+/// Records how long a phase took for a plugin, or for the whole project with `Phase::Config`
+pub fn record(subject: &str, phase: Phase, elapsed: Duration) {
+    let mut timings = TIMINGS.lock().unwrap_or_else(|e| e.into_inner());
+    timings.push(Timing {
+        subject: subject.to_string(),
+        phase,
+        elapsed,
+    });
+}
+
+/// Awaits `future` and records its duration
+pub async fn measure<T>(subject: &str, phase: Phase, future: impl Future<Output = T>) -> T {
+    let start = Instant::now();
+    let output = future.await;
+    record(subject, phase, start.elapsed());
+    output
+}
+
+/// Runs `f` and records its duration
+pub fn measure_sync<T>(subject: &str, phase: Phase, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let output = f();
+    record(subject, phase, start.elapsed());
+    output
+}
+
+/// Table of the recorded durations, one row per plugin. None if nothing was recorded.
+pub fn summary() -> Option<String> {
+    let timings = TIMINGS.lock().unwrap_or_else(|e| e.into_inner());
+    if timings.is_empty() {
+        return None;
+    }
+    Some(format_table(&timings))
+}
+
+fn format_table(timings: &[Timing]) -> String {
+    let mut subjects: Vec<&str> = Vec::new();
+    for timing in timings {
+        if !subjects.contains(&timing.subject.as_str()) {
+            subjects.push(&timing.subject);
+        }
+    }
+
+    let mut table = format!("{0: <40}", "Plugin");
+    for phase in Phase::ALL {
+        table.push_str(&format!(" {0: <10}", phase.label()));
+    }
+    table.push_str(" Total");
+
+    for subject in subjects {
+        let mut total = Duration::ZERO;
+        table.push_str(&format!("\n{0: <40}", subject));
+        for phase in Phase::ALL {
+            let elapsed: Option<Duration> = timings
+                .iter()
+                .filter(|t| t.subject == subject && t.phase == phase)
+                .map(|t| t.elapsed)
+                .reduce(|a, b| a + b);
+            let cell = match elapsed {
+                Some(elapsed) => {
+                    total += elapsed;
+                    format_duration(elapsed)
+                }
+                None => "-".to_string(),
+            };
+            table.push_str(&format!(" {0: <10}", cell));
+        }
+        table.push_str(&format!(" {}", format_duration(total)));
+    }
+    table
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.2}s", duration.as_secs_f64())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(subject: &str, phase: Phase, millis: u64) -> Timing {
+        Timing {
+            subject: subject.to_string(),
+            phase,
+            elapsed: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    fn test_format_table_should_sum_phases_per_plugin() {
+        let table = format_table(&[
+            timing("Gut", Phase::Metadata, 250),
+            timing("Gut", Phase::Download, 1500),
+            timing("Dialogue Manager", Phase::Download, 500),
+            timing("Gut", Phase::Download, 500),
+            timing("project", Phase::Config, 10),
+        ]);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("Plugin"));
+        assert!(lines[0].ends_with("Total"));
+        assert_eq!(
+            lines[1].split_whitespace().collect::<Vec<_>>(),
+            ["Gut", "0.25s", "2.00s", "-", "-", "-", "2.25s"]
+        );
+        assert_eq!(
+            lines[2].split_whitespace().collect::<Vec<_>>(),
+            ["Dialogue", "Manager", "-", "0.50s", "-", "-", "-", "0.50s"]
+        );
+        assert_eq!(
+            lines[3].split_whitespace().collect::<Vec<_>>(),
+            ["project", "-", "-", "-", "-", "0.01s", "0.01s"]
+        );
+    }
+
+    #[test]
+    fn test_measure_sync_should_return_output() {
+        assert_eq!(measure_sync("Gut", Phase::Move, || 42), 42);
+    }
+}
@@ -2,13 +2,61 @@ pub struct Utils;
 
 use regex::Regex;
 use semver::Version;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
+use unicode_normalization::UnicodeNormalization;
 
 impl Utils {
+    /// gdm's folder in the platform cache directory, e.g. `~/.cache/gdm` on Linux,
+    /// `~/Library/Caches/gdm` on macOS and `%LOCALAPPDATA%\gdm` on Windows.
+    /// None if the directory can't be determined.
+    pub fn cache_dir() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("gdm"))
+    }
+
+    /// gdm's folder in the platform configuration directory, e.g. `~/.config/gdm` on Linux,
+    /// `~/Library/Application Support/gdm` on macOS and `%APPDATA%\gdm` on Windows
+    pub fn config_dir() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("gdm"))
+    }
+
     pub fn plugin_name_to_addon_folder_path(addon_folder: &Path, plugin_name: &Path) -> PathBuf {
         addon_folder.join(plugin_name)
     }
 
+    /// Turns an asset title into a folder name that works on every platform: the title in
+    /// Unicode NFKC form, without control characters or characters Windows doesn't allow,
+    /// with runs of whitespace collapsed into an underscore and no leading or trailing dots.
+    /// None if nothing usable is left, or the name is reserved on Windows, e.g. `CON`.
+    ///
+    /// ```sanitize_folder_name("Dialogue: Manager?") // returns Some("Dialogue_Manager")```
+    pub fn sanitize_folder_name(title: &str) -> Option<String> {
+        let replaced: String = title
+            .nfkc()
+            .map(|c| {
+                if c.is_control()
+                    || matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*')
+                {
+                    ' '
+                } else {
+                    c
+                }
+            })
+            .collect();
+        let name = replaced
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join("_")
+            .trim_matches(|c| c == '.' || c == '_')
+            .to_string();
+        let stem = name.split('.').next().unwrap_or_default().to_uppercase();
+        let reserved = matches!(stem.as_str(), "CON" | "PRN" | "AUX" | "NUL")
+            || (stem.len() == 4
+                && (stem.starts_with("COM") || stem.starts_with("LPT"))
+                && stem.ends_with(|c: char| c.is_ascii_digit()));
+        (!name.is_empty() && !reserved).then_some(name)
+    }
+
     /// Parse a Godot asset version string into a semantic version
     ///
     /// Godot Asset Store might use version strings like "11" or "2.0" which are not valid semantic versions.
@@ -51,12 +99,233 @@ impl Utils {
         // Unable to parse version, return default 0.0.0
         Version::new(0, 0, 0)
     }
+
+    /// Returns the candidate closest to `name`, ignoring case, if it's similar enough to be a typo
+    ///
+    /// ```closest_match("gtu", ["gut", "dialogue_manager"]) // returns Some("gut")```
+    pub fn closest_match<'a>(
+        name: &str,
+        candidates: impl IntoIterator<Item = &'a str>,
+    ) -> Option<&'a str> {
+        let name = name.to_lowercase();
+        // Allow one typo for short names and roughly one per three characters for longer ones
+        let max_distance = (name.chars().count() / 3).max(1);
+        candidates
+            .into_iter()
+            .map(|candidate| {
+                let distance = strsim::osa_distance(&name, &candidate.to_lowercase());
+                (candidate, distance)
+            })
+            .filter(|(_, distance)| *distance <= max_distance)
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(candidate, _)| candidate)
+    }
+
+    /// How well `title` matches a search `query`, ignoring case. Titles containing the query
+    /// score above 1.0, the earlier it appears the higher. Others score the average Jaro-Winkler
+    /// similarity of each query word to its closest title word, from 0.0 to 1.0.
+    ///
+    /// ```fuzzy_match_score("dialog", "Dialogue Manager") // returns 2.0```
+    pub fn fuzzy_match_score(query: &str, title: &str) -> f64 {
+        let query = query.trim().to_lowercase();
+        let title = title.to_lowercase();
+        if query.is_empty() {
+            return 0.0;
+        }
+        if let Some(position) = title.find(&query) {
+            return 2.0 - position as f64 / title.len() as f64;
+        }
+        let query_words: Vec<&str> = query.split_whitespace().collect();
+        let title_words: Vec<&str> = title
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect();
+        let total: f64 = query_words
+            .iter()
+            .map(|query_word| {
+                title_words
+                    .iter()
+                    .map(|title_word| strsim::jaro_winkler(query_word, title_word))
+                    .fold(0.0, f64::max)
+            })
+            .sum();
+        total / query_words.len() as f64
+    }
+
+    /// Byte ranges of `title` that contain the query, or its words if the whole query doesn't
+    /// appear, ignoring case. Sorted and non-overlapping, for highlighting search results.
+    ///
+    /// ```fuzzy_match_ranges("gut", "Gut - Godot Unit Testing") // returns [0..3]```
+    pub fn fuzzy_match_ranges(query: &str, title: &str) -> Vec<Range<usize>> {
+        let lowercase_title = title.to_lowercase();
+        // Lowercasing some characters changes their length, so byte offsets wouldn't match
+        if lowercase_title.len() != title.len() {
+            return Vec::new();
+        }
+        let query = query.trim().to_lowercase();
+        let words: Vec<&str> = if lowercase_title.contains(&query) {
+            vec![query.as_str()]
+        } else {
+            query.split_whitespace().collect()
+        };
+        let mut ranges: Vec<Range<usize>> = words
+            .iter()
+            .filter(|word| !word.is_empty())
+            .flat_map(|word| {
+                lowercase_title
+                    .match_indices(word)
+                    .map(|(start, word)| start..start + word.len())
+            })
+            .collect();
+        ranges.sort_by_key(|range| range.start);
+        let mut merged: Vec<Range<usize>> = Vec::new();
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        merged
+    }
+
+    /// Parse the output of `godot --version` into a major.minor Godot version
+    ///
+    /// ```parse_godot_version_output("4.5.1.stable.official.f62fdbde1") // returns Some("4.5")```
+    pub fn parse_godot_version_output(output: &str) -> Option<String> {
+        let version_regex = Regex::new(r"^(\d+)\.(\d+)").unwrap();
+
+        output.lines().find_map(|line| {
+            version_regex
+                .captures(line.trim())
+                .map(|captures| format!("{}.{}", &captures[1], &captures[2]))
+        })
+    }
+
+    /// Parse a git tag like `v9.2.0` or `9.2` into a semantic version, None for other tags like `latest`
+    pub fn parse_version_tag(tag: &str) -> Option<Version> {
+        let tag_regex = Regex::new(r"^v?(\d+(\.\d+){0,2}(-[0-9A-Za-z.-]+)?)$").unwrap();
+        let captures = tag_regex.captures(tag)?;
+        Some(Utils::parse_semantic_version(&captures[1]))
+    }
+
+    /// Returns the tag with the highest version that is newer than `current_tag`.
+    /// Pre-release tags are only considered with `include_prerelease`.
+    ///
+    /// ```newest_version_tag(["v1.0.0", "v1.1.0", "v2.0.0-rc1"], "v1.0.0", false) // returns Some("v1.1.0")```
+    pub fn newest_version_tag<'a>(
+        tags: impl IntoIterator<Item = &'a str>,
+        current_tag: &str,
+        include_prerelease: bool,
+    ) -> Option<&'a str> {
+        let current_version = Utils::parse_version_tag(current_tag)?;
+        tags.into_iter()
+            .filter_map(|tag| Utils::parse_version_tag(tag).map(|version| (tag, version)))
+            .filter(|(_, version)| include_prerelease || version.pre.is_empty())
+            .filter(|(_, version)| *version > current_version)
+            .max_by(|(_, a), (_, b)| a.cmp(b))
+            .map(|(tag, _)| tag)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_closest_match() {
+        let candidates = ["gut", "dialogue_manager", "Phantom Camera"];
+        assert_eq!(Utils::closest_match("gtu", candidates), Some("gut"));
+        assert_eq!(
+            Utils::closest_match("dialog_manager", candidates),
+            Some("dialogue_manager")
+        );
+        assert_eq!(
+            Utils::closest_match("phantom camra", candidates),
+            Some("Phantom Camera")
+        );
+        assert_eq!(Utils::closest_match("beehave", candidates), None);
+    }
+
+    #[test]
+    fn test_fuzzy_match_score() {
+        let query = "dialog";
+        assert_eq!(Utils::fuzzy_match_score(query, "Dialogue Manager"), 2.0);
+        assert!(
+            Utils::fuzzy_match_score(query, "Dialogue Manager")
+                > Utils::fuzzy_match_score(query, "Simple Dialog")
+        );
+        assert!(
+            Utils::fuzzy_match_score(query, "Simple Dialog")
+                > Utils::fuzzy_match_score(query, "Dialgue Nodes")
+        );
+        assert!(
+            Utils::fuzzy_match_score("phantom cam", "Phantom Camera")
+                > Utils::fuzzy_match_score("phantom cam", "Camera Shake")
+        );
+        assert!(
+            Utils::fuzzy_match_score("unit test", "Gut - Godot Unit Testing")
+                > Utils::fuzzy_match_score("unit test", "Godot Jolt")
+        );
+        assert_eq!(Utils::fuzzy_match_score("", "Godot Jolt"), 0.0);
+    }
+
+    #[test]
+    fn test_fuzzy_match_ranges() {
+        assert_eq!(
+            Utils::fuzzy_match_ranges("gut", "Gut - Godot Unit Testing"),
+            vec![0..3]
+        );
+        assert_eq!(
+            Utils::fuzzy_match_ranges("unit test", "Gut - Godot Unit Testing"),
+            vec![12..21]
+        );
+        assert_eq!(
+            Utils::fuzzy_match_ranges("testing unit", "Gut - Godot Unit Testing"),
+            vec![12..16, 17..24]
+        );
+        assert_eq!(
+            Utils::fuzzy_match_ranges("dialog dialogue", "Dialogue Manager"),
+            vec![0..8]
+        );
+        assert!(Utils::fuzzy_match_ranges("beehave", "Godot Jolt").is_empty());
+    }
+
+    #[test]
+    fn test_sanitize_folder_name() {
+        assert_eq!(
+            Utils::sanitize_folder_name("Dialogue: Manager?"),
+            Some("Dialogue_Manager".to_string())
+        );
+        assert_eq!(
+            Utils::sanitize_folder_name("  Phantom \t Camera\n"),
+            Some("Phantom_Camera".to_string())
+        );
+        assert_eq!(
+            Utils::sanitize_folder_name("ｇｕｔ ①"),
+            Some("gut_1".to_string())
+        );
+        assert_eq!(
+            Utils::sanitize_folder_name("<C:\\Tools|*>..."),
+            Some("C_Tools".to_string())
+        );
+        assert_eq!(
+            Utils::sanitize_folder_name("Café"),
+            Some("Café".to_string())
+        );
+    }
+
+    #[test]
+    fn test_sanitize_folder_name_without_usable_name() {
+        assert_eq!(Utils::sanitize_folder_name("???"), None);
+        assert_eq!(Utils::sanitize_folder_name(" ... "), None);
+        assert_eq!(Utils::sanitize_folder_name("con"), None);
+        assert_eq!(Utils::sanitize_folder_name("LPT1.txt"), None);
+        assert_eq!(
+            Utils::sanitize_folder_name("Console"),
+            Some("Console".to_string())
+        );
+    }
+
     #[test]
     fn test_plugin_name_to_addon_folder_path() {
         let plugin_name = Path::new("some_plugin");
@@ -155,6 +424,25 @@ mod tests {
         assert_eq!(parsed.patch, 0);
     }
 
+    #[test]
+    fn test_parse_godot_version_output_stable() {
+        let version = Utils::parse_godot_version_output("4.5.1.stable.official.f62fdbde1\n");
+        assert_eq!(version, Some("4.5".to_string()));
+    }
+
+    #[test]
+    fn test_parse_godot_version_output_with_preceding_lines() {
+        let version =
+            Utils::parse_godot_version_output("Godot Engine v3.6\n3.6.stable.official.de2f0f147\n");
+        assert_eq!(version, Some("3.6".to_string()));
+    }
+
+    #[test]
+    fn test_parse_godot_version_output_invalid() {
+        let version = Utils::parse_godot_version_output("command not found");
+        assert_eq!(version, None);
+    }
+
     #[test]
     fn test_parse_semantic_version_invalid() {
         let version = "not_a_version";
@@ -163,4 +451,28 @@ mod tests {
         assert_eq!(parsed.minor, 0);
         assert_eq!(parsed.patch, 0);
     }
+
+    #[test]
+    fn test_parse_version_tag() {
+        assert_eq!(
+            Utils::parse_version_tag("v9.2.0"),
+            Some(Version::new(9, 2, 0))
+        );
+        assert_eq!(Utils::parse_version_tag("1.4"), Some(Version::new(1, 4, 0)));
+        assert_eq!(Utils::parse_version_tag("latest"), None);
+    }
+
+    #[test]
+    fn test_newest_version_tag_should_skip_pre_release() {
+        let tags = ["v1.0.0", "v1.1.0", "v2.0.0-rc1", "nightly"];
+        assert_eq!(
+            Utils::newest_version_tag(tags, "v1.0.0", false),
+            Some("v1.1.0")
+        );
+        assert_eq!(
+            Utils::newest_version_tag(tags, "v1.0.0", true),
+            Some("v2.0.0-rc1")
+        );
+        assert_eq!(Utils::newest_version_tag(tags, "v1.1.0", false), None);
+    }
 }
@@ -0,0 +1,126 @@
+mod setup;
+
+mod audit_command_tests {
+    use crate::setup;
+
+    use predicates::prelude::*;
+
+    const ADVISORY_FEED: &str = r#"{
+  "advisories": [
+    {
+      "id": "GDM-ADV-0001",
+      "asset_id": "1709",
+      "versions": ">=9.0.0, <9.6.1",
+      "kind": "broken",
+      "severity": "medium",
+      "summary": "Test runner crashes on Godot 4.5",
+      "action": "Update to 9.6.1"
+    },
+    {
+      "id": "GDM-ADV-0002",
+      "asset_id": "1234",
+      "kind": "malicious",
+      "severity": "critical",
+      "summary": "Uploads project files"
+    }
+  ]
+}"#;
+
+    fn create_feed(dir: &temp_dir::TempDir) -> String {
+        let path = dir.child("advisories.json");
+        std::fs::write(&path, ADVISORY_FEED).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_audit_should_report_affected_plugin_and_fail() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+        let feed = create_feed(&temp_dir);
+
+        cmd.arg("audit")
+            .arg("--feed")
+            .arg(feed)
+            .assert()
+            .code(8)
+            .stdout(predicate::str::contains(
+                "gut 9.6.0: Test runner crashes on Godot 4.5 (broken, GDM-ADV-0001)",
+            ))
+            .stdout(predicate::str::contains(
+                "Suggested action: Update to 9.6.1",
+            ))
+            .stdout(predicate::str::contains("GDM-ADV-0002").not())
+            .stderr(predicate::str::contains("error[GDM-401]"));
+    }
+
+    #[test]
+    fn test_audit_with_higher_fail_on_should_succeed() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+        let feed = create_feed(&temp_dir);
+
+        cmd.arg("audit")
+            .arg("--feed")
+            .arg(feed)
+            .arg("--fail-on")
+            .arg("high")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("GDM-ADV-0001"));
+    }
+
+    #[test]
+    fn test_audit_with_missing_feed_should_fail() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("audit")
+            .arg("--feed")
+            .arg("missing.json")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "Advisory feed missing.json doesn't exist",
+            ));
+    }
+
+    #[test]
+    fn test_audit_with_github_format_should_print_annotations() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+        let feed = create_feed(&temp_dir);
+
+        cmd.arg("audit")
+            .arg("--feed")
+            .arg(feed)
+            .arg("--fail-on")
+            .arg("high")
+            .arg("--format")
+            .arg("github")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("::warning file="))
+            .stdout(predicate::str::contains(
+                "gdm.json,line=8,title=Advisory GDM-ADV-0001::gut 9.6.0: Test runner crashes on Godot 4.5 (broken, medium severity). Suggested action: Update to 9.6.1",
+            ));
+    }
+
+    #[test]
+    fn test_audit_with_csv_format_should_print_rows() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+        let feed = create_feed(&temp_dir);
+
+        cmd.arg("audit")
+            .arg("--feed")
+            .arg(feed)
+            .arg("--format")
+            .arg("csv")
+            .assert()
+            .code(8)
+            .stdout(predicate::str::diff(
+                "severity,plugin,version,advisory,kind,summary,action\n\
+                 medium,gut,9.6.0,GDM-ADV-0001,broken,Test runner crashes on Godot 4.5,Update to 9.6.1\n",
+            ));
+    }
+}
@@ -0,0 +1,56 @@
+mod setup;
+
+mod browse_command_tests {
+    use crate::setup;
+
+    use predicates::prelude::*;
+
+    #[test]
+    fn test_browse_command_help() {
+        let (mut cmd, _temp_dir) = setup::get_bin();
+        cmd.arg("browse")
+            .arg("--help")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "List top-rated or recently updated addons",
+            ))
+            .stdout(predicate::str::contains("--sort"))
+            .stdout(predicate::str::contains("--page"));
+    }
+
+    #[test]
+    fn test_browse_without_project_godot_should_fail() {
+        let (mut cmd, _temp_dir) = setup::get_bin();
+        cmd.arg("browse")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "No project.godot file found in the current directory",
+            ));
+    }
+
+    #[test]
+    fn test_browse_should_reject_page_zero() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        cmd.arg("browse")
+            .arg("--page")
+            .arg("0")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("invalid value '0'"));
+    }
+
+    #[test]
+    fn test_browse_should_reject_unknown_sort() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        cmd.arg("browse")
+            .arg("--sort")
+            .arg("downloads")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "[possible values: rating, updated, name]",
+            ));
+    }
+}
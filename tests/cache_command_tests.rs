@@ -0,0 +1,38 @@
+mod setup;
+
+mod cache_command_tests {
+    use crate::setup;
+
+    use predicates::prelude::*;
+
+    // dirs reads XDG_CACHE_HOME only on Linux
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_cache_verify_should_report_and_delete_corrupted_archives() {
+        let (mut cmd, temp_dir) = setup::get_bin();
+        let archive = temp_dir.child("cache/gdm/archives/1709-9.2.0.zip");
+        std::fs::create_dir_all(archive.parent().unwrap()).unwrap();
+        std::fs::write(&archive, "not a zip").unwrap();
+
+        cmd.arg("cache")
+            .arg("verify")
+            .env("XDG_CACHE_HOME", temp_dir.child("cache"))
+            .assert()
+            .code(7)
+            .stdout(predicate::str::contains("1709-9.2.0.zip: unreadable zip"))
+            .stderr(predicate::str::contains(
+                "1 of 1 cached archives are corrupted",
+            ));
+        assert!(archive.exists());
+
+        let (mut cmd, _work_dir) = setup::get_bin();
+        cmd.arg("cache")
+            .arg("verify")
+            .arg("--delete")
+            .env("XDG_CACHE_HOME", temp_dir.child("cache"))
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("removed"));
+        assert!(!archive.exists());
+    }
+}
@@ -0,0 +1,45 @@
+mod setup;
+
+mod categories_command_tests {
+    use crate::setup;
+
+    use predicates::prelude::*;
+
+    #[test]
+    fn test_categories_command_help() {
+        let (mut cmd, _temp_dir) = setup::get_bin();
+        cmd.arg("categories")
+            .arg("--help")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "List the Asset Library's categories",
+            ));
+    }
+
+    #[test]
+    fn test_categories_without_project_should_list_categories() {
+        let (mut cmd, _temp_dir) = setup::get_bin();
+        cmd.arg("categories")
+            .arg("--metadata-ttl")
+            .arg("0")
+            .timeout(std::time::Duration::from_secs(30))
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("ID     Name                 Type"))
+            .stdout(predicate::str::contains("Addons"));
+    }
+
+    #[test]
+    fn test_search_category_should_conflict_with_offline() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        cmd.arg("search")
+            .arg("dialogue")
+            .arg("--category")
+            .arg("Tools")
+            .arg("--offline")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+}
@@ -24,6 +24,16 @@ mod cli_basic_tests {
             .stdout(predicate::str::contains("Usage:"));
     }
 
+    #[test]
+    fn test_cli_help_should_show_command_aliases() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        cmd.arg("--help")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("[aliases: i]"))
+            .stdout(predicate::str::contains("[aliases: rm]"));
+    }
+
     #[test]
     fn test_cli_version_flag() {
         let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
@@ -33,6 +43,25 @@ mod cli_basic_tests {
             .stdout(predicate::str::contains("gdm"));
     }
 
+    #[test]
+    fn test_command_in_project_subfolder_should_point_to_project() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        let addons_dir = temp_dir.child("addons");
+        std::fs::create_dir_all(&addons_dir).unwrap();
+
+        cmd.current_dir(&addons_dir)
+            .arg("list")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "No project.godot file found in the current directory",
+            ))
+            .stderr(predicate::str::contains(format!(
+                "hint: Found project.godot in {}",
+                temp_dir.path().display()
+            )));
+    }
+
     #[test]
     fn test_invalid_command() {
         let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
@@ -66,6 +95,165 @@ mod cli_basic_tests {
         cmd.arg("--quiet").arg("--help").assert().success();
     }
 
+    #[test]
+    fn test_color_always_should_color_errors() {
+        let (mut cmd, _temp_dir) = setup::get_bin();
+        cmd.arg("list")
+            .arg("--color")
+            .arg("always")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("\u{1b}["));
+    }
+
+    #[test]
+    fn test_color_never_should_not_color_errors() {
+        let (mut cmd, _temp_dir) = setup::get_bin();
+        cmd.env("CLICOLOR_FORCE", "1")
+            .arg("list")
+            .arg("--color")
+            .arg("never")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("error[GDM-102]:"))
+            .stderr(predicate::str::contains("\u{1b}[").not());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_color_in_global_config_should_apply_unless_flag_is_given() {
+        let (mut cmd, temp_dir) = setup::get_bin();
+        let config_dir = temp_dir.child("config");
+        std::fs::create_dir_all(config_dir.join("gdm")).unwrap();
+        std::fs::write(config_dir.join("gdm/config.json"), r#"{"color": "always"}"#).unwrap();
+
+        cmd.env("XDG_CONFIG_HOME", &config_dir)
+            .arg("list")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("\u{1b}["));
+        setup::get_cmd(&temp_dir)
+            .env("XDG_CONFIG_HOME", &config_dir)
+            .arg("list")
+            .arg("--color")
+            .arg("never")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("\u{1b}[").not());
+    }
+
+    #[test]
+    fn test_output_json_should_print_error_object() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        let output = cmd
+            .arg("info")
+            .arg("gtu")
+            .arg("--output-format")
+            .arg("json")
+            .assert()
+            .failure()
+            .code(6)
+            .get_output()
+            .stderr
+            .clone();
+        let error: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(error["event"], "error");
+        assert_eq!(error["code"], "GDM-202");
+        assert_eq!(error["message"], "Plugin gtu is not installed.");
+        assert_eq!(error["plugin"], "gtu");
+        assert_eq!(error["hint"], "Did you mean gut?");
+    }
+
+    #[test]
+    fn test_color_flag_rejects_unknown_value() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        cmd.arg("--color")
+            .arg("sometimes")
+            .arg("list")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("invalid value 'sometimes'"));
+    }
+
+    #[test]
+    fn test_log_file_should_record_debug_logs_without_verbose() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&_temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+        cmd.arg("--log-file")
+            .arg("gdm.log")
+            .arg("list")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("DEBUG").not());
+
+        let log = std::fs::read_to_string(_temp_dir.child("gdm.log")).unwrap();
+        assert!(log.contains("DEBUG"));
+    }
+
+    #[test]
+    fn test_log_level_should_filter_log_file() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&_temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+        cmd.arg("--log-file")
+            .arg("gdm.log")
+            .arg("--log-level")
+            .arg("info")
+            .arg("list")
+            .assert()
+            .success();
+
+        let log = std::fs::read_to_string(_temp_dir.child("gdm.log")).unwrap();
+        assert!(log.contains("INFO"));
+        assert!(!log.contains("DEBUG"));
+    }
+
+    #[test]
+    fn test_timings_without_installs_should_not_print_table() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&_temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+        cmd.arg("list")
+            .arg("--timings")
+            .assert()
+            .success()
+            .stderr(predicate::str::contains("Metadata").not());
+    }
+
+    #[test]
+    fn test_no_update_check_env_should_be_accepted() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&_temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+        cmd.env("GDM_NO_UPDATE_CHECK", "1")
+            .arg("list")
+            .assert()
+            .success()
+            .stderr(predicate::str::contains("is available").not());
+    }
+
+    #[test]
+    fn test_metadata_ttl_should_reject_non_numbers() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        cmd.arg("--metadata-ttl")
+            .arg("soon")
+            .arg("list")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("invalid value 'soon'"));
+    }
+
+    #[test]
+    fn test_godot_version_flag_is_global() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        cmd.arg("install")
+            .arg("--godot-version")
+            .arg("4.5")
+            .arg("--help")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("--godot-version"));
+    }
+
     #[test]
     fn test_all_subcommands_listed_in_help() {
         let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
@@ -77,6 +265,7 @@ mod cli_basic_tests {
             .stdout(predicate::str::contains("outdated"))
             .stdout(predicate::str::contains("remove"))
             .stdout(predicate::str::contains("search"))
-            .stdout(predicate::str::contains("update"));
+            .stdout(predicate::str::contains("update"))
+            .stdout(predicate::str::contains("validate"));
     }
 }
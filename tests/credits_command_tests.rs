@@ -0,0 +1,82 @@
+mod setup;
+
+mod credits_command_tests {
+    use crate::setup;
+
+    use predicates::prelude::*;
+
+    const GDM_JSON_WITH_CREDITS_FILE: &str = r#"{
+  "project": {
+    "credits": "CREDITS.md"
+  },
+  "plugins": {
+    "gut": {
+      "source": {
+        "asset_id": "1709"
+      },
+      "plugin_cfg_path": "addons/gut/plugin.cfg",
+      "title": "GUT - Godot Unit Testing (Godot 4)",
+      "version": "9.6.0",
+      "license": "MIT",
+      "sub_assets": []
+    }
+  }
+}"#;
+
+    #[test]
+    fn test_credits_should_print_attributions() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("credits")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "## GUT - Godot Unit Testing (Godot 4)",
+            ))
+            .stdout(predicate::str::contains("- Version: 9.6.0"))
+            .stdout(predicate::str::contains("- License: MIT"))
+            .stdout(predicate::str::contains(
+                "- Source: <https://godotengine.org/asset-library/asset/1709>",
+            ));
+    }
+
+    #[test]
+    fn test_credits_with_output_should_write_author_from_plugin_cfg() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+        std::fs::create_dir_all(temp_dir.child("addons/gut")).unwrap();
+        std::fs::write(
+            temp_dir.child("addons/gut/plugin.cfg"),
+            "[plugin]\nname=\"Gut\"\nauthor=\"Butch Wesley\"\nversion=\"9.6.0\"\n",
+        )
+        .unwrap();
+
+        cmd.arg("credits")
+            .arg("--output")
+            .arg("CREDITS.md")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Wrote credits to CREDITS.md."));
+
+        let credits = std::fs::read_to_string(temp_dir.child("CREDITS.md")).unwrap();
+        assert!(credits.contains("- Author: Butch Wesley"));
+    }
+
+    #[test]
+    fn test_remove_should_update_project_credits_file() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, GDM_JSON_WITH_CREDITS_FILE);
+        std::fs::write(temp_dir.child("CREDITS.md"), "outdated").unwrap();
+
+        cmd.arg("remove")
+            .arg("gut")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Modified: gdm.json, CREDITS.md"));
+
+        let credits = std::fs::read_to_string(temp_dir.child("CREDITS.md")).unwrap();
+        assert!(credits.starts_with("# Credits"));
+        assert!(!credits.contains("GUT"));
+    }
+}
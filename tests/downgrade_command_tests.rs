@@ -0,0 +1,59 @@
+mod setup;
+
+mod downgrade_command_tests {
+    use crate::setup;
+
+    use predicates::prelude::*;
+
+    #[test]
+    fn test_downgrade_command_requires_name_and_version() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        cmd.arg("downgrade")
+            .arg("gut")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "required arguments were not provided",
+            ));
+    }
+
+    #[test]
+    fn test_downgrade_with_misspelled_name_should_suggest_closest_plugin() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&_temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("downgrade")
+            .arg("gtu")
+            .arg("9.4.0")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("Plugin gtu is not installed."))
+            .stderr(predicate::str::contains("hint: Did you mean gut?"));
+    }
+
+    #[test]
+    fn test_downgrade_git_plugin_should_fail() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(
+            &_temp_dir,
+            r#"{
+  "plugins": {
+    "local_tools": {
+      "source": { "url": "https://example.com/local_tools.git", "reference": "main" },
+      "title": "Local Tools",
+      "version": "1.0.0"
+    }
+  }
+}"#,
+        );
+
+        cmd.arg("downgrade")
+            .arg("local_tools")
+            .arg("0.9.0")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "Plugin local_tools isn't from the Asset Library.",
+            ));
+    }
+}
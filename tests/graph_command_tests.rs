@@ -0,0 +1,38 @@
+mod setup;
+
+mod graph_command_tests {
+    use crate::setup;
+
+    use predicates::prelude::*;
+
+    #[test]
+    fn test_graph_should_print_dot_by_default() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("graph")
+            .assert()
+            .success()
+            .stdout(predicate::str::starts_with("digraph gdm {"))
+            .stdout(predicate::str::contains(
+                "plugin_0 [label=\"gut 9.6.0\\nAsset Library (1709)\"];",
+            ))
+            .stdout(predicate::str::contains("project -> plugin_0;"));
+    }
+
+    #[test]
+    fn test_graph_with_mermaid_format() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("graph")
+            .arg("--format")
+            .arg("mermaid")
+            .assert()
+            .success()
+            .stdout(predicate::str::starts_with("flowchart LR"))
+            .stdout(predicate::str::contains(
+                "plugin_0(\"gut 9.6.0<br>Asset Library (1709)\")",
+            ));
+    }
+}
@@ -0,0 +1,44 @@
+mod setup;
+
+mod hook_command_tests {
+    use crate::setup;
+
+    use predicates::prelude::*;
+
+    #[test]
+    fn test_hook_install_and_uninstall() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        gix::init(temp_dir.path()).unwrap();
+
+        cmd.arg("hook")
+            .arg("install")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Git hooks installed."));
+
+        let post_merge = temp_dir.child(".git/hooks/post-merge");
+        let content = std::fs::read_to_string(&post_merge).unwrap();
+        assert!(content.contains("  gdm install || exit $?\n"));
+        let pre_commit = std::fs::read_to_string(temp_dir.child(".git/hooks/pre-commit")).unwrap();
+        assert!(pre_commit.contains("  gdm validate || exit $?\n"));
+
+        let mut cmd = setup::get_cmd(&temp_dir);
+        cmd.arg("hook")
+            .arg("uninstall")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Git hooks uninstalled."));
+        assert!(!post_merge.exists());
+    }
+
+    #[test]
+    fn test_hook_install_outside_git_repository_should_fail() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+
+        cmd.arg("hook")
+            .arg("install")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("isn't in a git repository"));
+    }
+}
@@ -0,0 +1,130 @@
+mod setup;
+
+mod info_command_tests {
+    use crate::setup;
+
+    use predicates::prelude::*;
+
+    #[test]
+    fn test_info_command_help() {
+        let (mut cmd, _temp_dir) = setup::get_bin();
+        cmd.arg("info")
+            .arg("--help")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Show the details and description"))
+            .stdout(predicate::str::contains("--asset-id"));
+    }
+
+    #[test]
+    fn test_info_requires_name_or_asset_id() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        cmd.arg("info")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "required arguments were not provided",
+            ));
+    }
+
+    #[test]
+    fn test_info_not_installed_plugin_should_suggest_name() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("info")
+            .arg("gtu")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("Plugin gtu is not installed."))
+            .stderr(predicate::str::contains("hint: Did you mean gut?"));
+    }
+
+    #[test]
+    fn test_info_git_plugin_should_fail() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(
+            &temp_dir,
+            r#"{
+  "plugins": {
+    "local_tools": {
+      "source": { "url": "https://example.com/local_tools.git", "reference": "main" },
+      "title": "Local Tools",
+      "version": "1.0.0"
+    }
+  }
+}"#,
+        );
+
+        cmd.arg("info")
+            .arg("local_tools")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "Plugin local_tools isn't from the Asset Library.",
+            ));
+    }
+
+    // `true` and `false` stand in for a browser
+    #[cfg(unix)]
+    #[test]
+    fn test_info_open_should_open_asset_page() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("info")
+            .arg("gut")
+            .arg("--open")
+            .env("BROWSER", "true")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "Opened https://godotengine.org/asset-library/asset/1709",
+            ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_info_open_git_plugin_should_open_repository() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(
+            &temp_dir,
+            r#"{
+  "plugins": {
+    "local_tools": {
+      "source": { "url": "git@example.com:team/local_tools.git", "reference": "main" },
+      "title": "Local Tools",
+      "version": "1.0.0"
+    }
+  }
+}"#,
+        );
+
+        cmd.arg("info")
+            .arg("local_tools")
+            .arg("--open")
+            .env("BROWSER", "true")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "Opened https://example.com/team/local_tools",
+            ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_info_open_should_fail_when_browser_fails() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+
+        cmd.arg("info")
+            .arg("--asset-id")
+            .arg("1709")
+            .arg("--open")
+            .env("BROWSER", "false")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "Failed to open https://godotengine.org/asset-library/asset/1709 in a browser",
+            ));
+    }
+}
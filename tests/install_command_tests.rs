@@ -78,12 +78,137 @@ mod install_command_tests {
     }
 
     #[test]
-    fn test_install_no_arguments_accepted() {
+    fn test_install_locked_without_recorded_hashes_should_fail() {
         let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&_temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("install")
+            .arg("--locked")
+            .assert()
+            .failure()
+            .code(7)
+            .stderr(predicate::str::contains(
+                "has no checksum and content_hash in gdm.json",
+            ));
+
+        let addons_path = _temp_dir.path().join("addons").join("gut");
+        assert!(!addons_path.exists(), "Nothing should be installed");
+    }
+
+    // dirs reads XDG_CACHE_HOME only on Linux
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_install_frozen_without_cached_archive_should_fail() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        let gdm_json = setup::GDM_JSON_WITH_ONE_PLUGIN.replace(
+            "\"sub_assets\": []",
+            "\"sub_assets\": [],\n      \"checksum\": \"sha256:abc\",\n      \"content_hash\": \"sha256:def\"",
+        );
+        setup::create_gdm_json(&temp_dir, &gdm_json);
+
+        cmd.arg("install")
+            .arg("--frozen")
+            .env("XDG_CACHE_HOME", temp_dir.child("cache"))
+            .assert()
+            .failure()
+            .code(6)
+            .stderr(predicate::str::contains(
+                "isn't in the archive cache, and --frozen doesn't download it",
+            ));
+    }
+
+    #[test]
+    fn test_install_names_with_workspace_should_fail() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        cmd.arg("install")
+            .arg("gut")
+            .arg("--workspace")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+
+    const WORKSPACE_JSON: &str = r#"{
+  "projects": ["game", "tools"]
+}"#;
+
+    #[test]
+    fn test_install_workspace_without_workspace_file_should_fail() {
+        let (mut cmd, _temp_dir) = setup::get_bin();
+
+        cmd.arg("install")
+            .arg("--workspace")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "No gdm-workspace.json file found in the current directory",
+            ));
+    }
+
+    #[test]
+    fn test_install_workspace_with_missing_project_should_fail() {
+        let (mut cmd, temp_dir) = setup::get_bin();
+        std::fs::write(temp_dir.child("gdm-workspace.json"), WORKSPACE_JSON).unwrap();
+
+        cmd.arg("install")
+            .arg("--workspace")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "Workspace project not found: game",
+            ));
+    }
+
+    #[test]
+    fn test_install_workspace_should_skip_projects_without_plugins() {
+        let (mut cmd, temp_dir) = setup::get_bin();
+        std::fs::write(temp_dir.child("gdm-workspace.json"), WORKSPACE_JSON).unwrap();
+        for project in ["game", "tools"] {
+            std::fs::create_dir(temp_dir.child(project)).unwrap();
+            std::fs::write(
+                temp_dir.child(project).join("project.godot"),
+                setup::MINIMAL_PROJECT_GODOT,
+            )
+            .unwrap();
+        }
+
         cmd.arg("install")
-            .arg("extra-arg")
+            .arg("--workspace")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("No plugins to install for game"))
+            .stdout(predicate::str::contains("No plugins to install for tools"));
+    }
+
+    #[test]
+    fn test_install_workspace_frozen_should_forward_frozen_to_projects() {
+        let (mut cmd, temp_dir) = setup::get_bin();
+        std::fs::write(temp_dir.child("gdm-workspace.json"), WORKSPACE_JSON).unwrap();
+        for project in ["game", "tools"] {
+            std::fs::create_dir(temp_dir.child(project)).unwrap();
+            std::fs::write(
+                temp_dir.child(project).join("project.godot"),
+                setup::MINIMAL_PROJECT_GODOT,
+            )
+            .unwrap();
+        }
+        let gdm_json = setup::GDM_JSON_WITH_ONE_PLUGIN.replace(
+            "\"sub_assets\": []",
+            "\"sub_assets\": [],\n      \"checksum\": \"sha256:abc\",\n      \"content_hash\": \"sha256:def\"",
+        );
+        std::fs::write(temp_dir.child("game").join("gdm.json"), gdm_json).unwrap();
+
+        cmd.arg("--frozen")
+            .arg("install")
+            .arg("--workspace")
+            .env("XDG_CACHE_HOME", temp_dir.child("cache"))
             .assert()
             .failure()
-            .stderr(predicate::str::contains("unexpected argument"));
+            .stderr(predicate::str::contains(
+                "isn't in the archive cache, and --frozen doesn't download it",
+            ))
+            .stderr(predicate::str::contains(
+                "Failed to install plugins for game",
+            ));
     }
 }
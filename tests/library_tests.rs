@@ -0,0 +1,64 @@
+mod setup;
+
+mod library_tests {
+    use crate::setup;
+
+    use gdm_core::Gdm;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn test_list_should_read_project_dir() {
+        let project_dir = setup::setup_test_dir();
+        setup::create_project_godot(&project_dir, setup::MINIMAL_PROJECT_GODOT);
+        setup::create_gdm_json(&project_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        let gdm = Gdm::builder()
+            .project_dir(project_dir.path())
+            .build()
+            .unwrap();
+        let listing = gdm.list().await.unwrap();
+
+        assert_eq!(listing.plugins.keys().collect::<Vec<_>>(), vec!["gut"]);
+        assert!(listing.unmanaged.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_remove_should_return_summary() {
+        let project_dir = setup::setup_test_dir();
+        setup::create_project_godot(&project_dir, setup::MINIMAL_PROJECT_GODOT);
+        setup::create_gdm_json(&project_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+        let plugin_dir = project_dir.path().join("addons/gut");
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        let working_dir = std::env::current_dir().unwrap();
+
+        let gdm = Gdm::builder()
+            .project_dir(project_dir.path())
+            .build()
+            .unwrap();
+        let summary = gdm.remove("gut").await.unwrap();
+
+        assert_eq!(summary.removed, vec!["gut".to_string()]);
+        assert_eq!(summary.modified_files, vec![PathBuf::from("gdm.json")]);
+        assert!(gdm.list().await.unwrap().plugins.is_empty());
+        assert!(!plugin_dir.exists());
+        assert_eq!(std::env::current_dir().unwrap(), working_dir);
+    }
+
+    #[tokio::test]
+    async fn test_list_without_project_godot_should_fail() {
+        let project_dir = setup::setup_test_dir();
+
+        let gdm = Gdm::builder()
+            .project_dir(project_dir.path())
+            .build()
+            .unwrap();
+
+        assert!(
+            gdm.list()
+                .await
+                .unwrap_err()
+                .to_string()
+                .contains("No project.godot file found")
+        );
+    }
+}
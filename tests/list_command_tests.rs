@@ -0,0 +1,321 @@
+mod setup;
+
+mod list_command_tests {
+    use crate::setup;
+
+    use predicates::prelude::*;
+
+    const PROJECT_GODOT_WITH_UNMANAGED_PLUGIN: &str = r#"
+config_version=5
+
+[application]
+
+config/name="Test Project"
+config/features=PackedStringArray("4.6")
+
+[editor_plugins]
+
+enabled=PackedStringArray("res://addons/manual_plugin/plugin.cfg", "res://addons/gut/plugin.cfg")
+"#;
+
+    #[test]
+    fn test_list_command_help() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        cmd.arg("list")
+            .arg("--help")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("List plugins"));
+    }
+
+    #[test]
+    fn test_list_without_project_godot_should_fail() {
+        let (mut cmd, _temp_dir) = setup::get_bin();
+        cmd.arg("list")
+            .assert()
+            .failure()
+            .code(3)
+            .stderr(predicate::str::contains(
+                "error[GDM-102]: No project.godot file found in the current directory",
+            ));
+    }
+
+    #[test]
+    fn test_list_without_plugins() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        cmd.arg("list")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("No plugins installed."));
+    }
+
+    #[test]
+    fn test_list_with_project_flag_should_use_given_project() {
+        let project_dir = setup::setup_test_dir();
+        setup::create_project_godot(&project_dir, setup::MINIMAL_PROJECT_GODOT);
+        setup::create_gdm_json(&project_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        let (mut cmd, _temp_dir) = setup::get_bin();
+        cmd.arg("--project")
+            .arg(project_dir.path())
+            .arg("list")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("gut"))
+            .stdout(predicate::str::contains("9.6.0"));
+    }
+
+    #[test]
+    fn test_list_with_missing_project_directory_should_fail() {
+        let (mut cmd, temp_dir) = setup::get_bin();
+        cmd.arg("list")
+            .arg("--project")
+            .arg(temp_dir.child("missing"))
+            .assert()
+            .code(6)
+            .stderr(predicate::str::contains("Failed to open project directory"));
+    }
+
+    #[test]
+    fn test_list_should_mark_dev_plugins() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(
+            &temp_dir,
+            r#"{
+  "plugins": {},
+  "dev_plugins": {
+    "gut": {
+      "source": { "asset_id": "1709" },
+      "plugin_cfg_path": "addons/gut/plugin.cfg",
+      "title": "Gut",
+      "version": "9.6.0"
+    }
+  }
+}"#,
+        );
+
+        cmd.arg("list")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("gut (dev)"));
+    }
+
+    #[test]
+    fn test_list_should_read_gdm_toml() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_toml(
+            &temp_dir,
+            r#"# Plugins for the demo
+[plugins.gut]
+source = { asset_id = "1709" }
+plugin_cfg_path = "addons/gut/plugin.cfg"
+title = "Gut"
+version = "9.6.0"
+"#,
+        );
+
+        cmd.arg("list")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("gut"))
+            .stdout(predicate::str::contains("9.6.0"));
+    }
+
+    #[test]
+    fn test_list_with_gdm_json_and_gdm_toml_should_fail() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+        setup::create_gdm_toml(&temp_dir, "[plugins]\n");
+
+        cmd.arg("list")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("Found both gdm.json and gdm.toml"));
+    }
+
+    #[test]
+    fn test_list_should_warn_when_godot_version_is_outside_declared_range() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(
+            &temp_dir,
+            r#"{ "project": { "name": "Demo", "godot_version": "<4.0" }, "plugins": {} }"#,
+        );
+
+        cmd.arg("list")
+            .assert()
+            .success()
+            .stderr(predicate::str::contains(
+                "which is outside the range <4.0 declared in gdm.json",
+            ));
+    }
+
+    #[test]
+    fn test_list_should_migrate_legacy_gdm_json() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        let legacy_content = r#"{
+  "plugins": {
+    "gut": { "asset_id": "1709", "title": "Gut", "version": "9.6.0" }
+  }
+}"#;
+        setup::create_gdm_json(&temp_dir, legacy_content);
+
+        cmd.arg("list")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "Migrated gdm.json to format version 2",
+            ))
+            .stdout(predicate::str::contains("Asset Library (1709)"));
+
+        let backup = std::fs::read_to_string(temp_dir.child("gdm.json.v1.bak")).unwrap();
+        assert_eq!(backup, legacy_content);
+        let migrated = std::fs::read_to_string(temp_dir.child("gdm.json")).unwrap();
+        assert!(migrated.contains("\"format_version\": 2"));
+    }
+
+    #[test]
+    fn test_list_should_apply_local_overrides() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+        let local_content = r#"{
+  "plugins": {
+    "gut": { "source": { "asset_id": "1709" }, "title": "Gut", "version": "9.1.0" },
+    "local_tools": {
+      "source": { "url": "https://example.com/local_tools.git", "reference": "main" },
+      "title": "Local Tools",
+      "version": "1.0.0"
+    }
+  }
+}"#;
+        std::fs::write(temp_dir.child("gdm.local.json"), local_content).unwrap();
+
+        cmd.arg("list")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("9.1.0"))
+            .stdout(predicate::str::contains("local_tools"));
+
+        let shared = std::fs::read_to_string(temp_dir.child("gdm.json")).unwrap();
+        assert_eq!(shared, setup::GDM_JSON_WITH_ONE_PLUGIN);
+    }
+
+    #[test]
+    fn test_list_should_show_git_commit() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(
+            &temp_dir,
+            r#"{
+  "plugins": {
+    "local_tools": {
+      "source": { "url": "https://example.com/local_tools.git", "reference": "main" },
+      "title": "Local Tools",
+      "version": "1.0.0",
+      "commit": "1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b"
+    }
+  }
+}"#,
+        );
+
+        cmd.arg("list")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "https://example.com/local_tools.git (main) @ 1a2b3c4",
+            ));
+    }
+
+    #[test]
+    fn test_list_should_show_managed_and_unmanaged_plugins() {
+        let (mut cmd, temp_dir) = setup::get_bin();
+        setup::create_project_godot(&temp_dir, PROJECT_GODOT_WITH_UNMANAGED_PLUGIN);
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("list")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("gut"))
+            .stdout(predicate::str::contains("9.6.0"))
+            .stdout(predicate::str::contains(
+                "res://addons/manual_plugin/plugin.cfg",
+            ))
+            .stdout(predicate::str::contains("unmanaged"))
+            .stdout(predicate::str::contains("res://addons/gut/plugin.cfg").not());
+    }
+
+    #[test]
+    fn test_list_with_csv_format_should_print_rows() {
+        let (mut cmd, temp_dir) = setup::get_bin();
+        setup::create_project_godot(&temp_dir, PROJECT_GODOT_WITH_UNMANAGED_PLUGIN);
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("list")
+            .arg("--format")
+            .arg("csv")
+            .assert()
+            .success()
+            .stdout(predicate::str::starts_with(
+                "name,alias,title,version,source,commit,license,enabled,dev\n",
+            ))
+            .stdout(predicate::str::contains(
+                "gut,,GUT - Godot Unit Testing (Godot 4),9.6.0,Asset Library (1709),,MIT,true,false\n",
+            ))
+            .stdout(predicate::str::contains(
+                "res://addons/manual_plugin/plugin.cfg,,,,unmanaged,,,true,false\n",
+            ));
+    }
+
+    #[test]
+    fn test_list_with_tsv_format_should_separate_with_tabs() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("list")
+            .arg("--format")
+            .arg("tsv")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "gut\t\tGUT - Godot Unit Testing (Godot 4)\t9.6.0\t",
+            ));
+    }
+
+    #[test]
+    fn test_ls_alias_should_list_plugins() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("ls")
+            .arg("--porcelain")
+            .assert()
+            .success()
+            .stdout(predicate::str::starts_with("plugin\tgut\t9.6.0\t"));
+    }
+
+    #[test]
+    fn test_list_porcelain_should_print_stable_records() {
+        let (mut cmd, temp_dir) = setup::get_bin();
+        setup::create_project_godot(&temp_dir, PROJECT_GODOT_WITH_UNMANAGED_PLUGIN);
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("list")
+            .arg("--porcelain")
+            .assert()
+            .success()
+            .stdout(predicate::str::diff(
+                "plugin\tgut\t9.6.0\tasset-library\t1709\t\t\ttrue\tfalse\t\tGUT - Godot Unit Testing (Godot 4)\tMIT\n\
+                 unmanaged\tres://addons/manual_plugin/plugin.cfg\n",
+            ));
+    }
+
+    #[test]
+    fn test_list_porcelain_with_format_should_fail() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        cmd.arg("list")
+            .arg("--porcelain")
+            .arg("--format")
+            .arg("csv")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+}
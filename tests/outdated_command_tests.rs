@@ -47,6 +47,28 @@ mod outdated_command_tests {
             .stderr(predicate::str::contains("No plugins installed."));
     }
 
+    #[test]
+    fn test_outdated_level_flags_should_conflict() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        cmd.arg("outdated")
+            .arg("--patch")
+            .arg("--major")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+
+    #[test]
+    fn test_outdated_with_unknown_fail_on_should_fail() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        cmd.arg("outdated")
+            .arg("--fail-on")
+            .arg("never")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("invalid value 'never'"));
+    }
+
     #[test]
     fn test_outdated_no_arguments_accepted() {
         let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
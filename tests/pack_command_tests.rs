@@ -0,0 +1,37 @@
+mod setup;
+
+mod pack_command_tests {
+    use crate::setup;
+
+    use predicates::prelude::*;
+
+    // dirs reads XDG_CACHE_HOME only on Linux
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_pack_without_cached_archive_should_fail() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("pack")
+            .env("XDG_CACHE_HOME", temp_dir.child("cache"))
+            .assert()
+            .failure()
+            .code(6)
+            .stderr(predicate::str::contains("No cached archive of"));
+        assert!(!temp_dir.child("gdm-bundle.zip").exists());
+    }
+
+    #[test]
+    fn test_unpack_without_bundle_should_fail() {
+        let (mut cmd, temp_dir) = setup::get_bin();
+
+        cmd.arg("unpack")
+            .arg("missing.zip")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "Failed to open bundle missing.zip",
+            ));
+        assert!(!temp_dir.child("gdm.json").exists());
+    }
+}
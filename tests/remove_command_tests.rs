@@ -76,6 +76,108 @@ mod remove_command_tests {
             .stdout(predicate::str::contains("Plugin gut removed successfully."));
     }
 
+    #[test]
+    fn test_remove_should_print_summary() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&_temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("remove")
+            .arg("gut")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Removed: gut"))
+            .stdout(predicate::str::contains("Modified: gdm.json"));
+    }
+
+    #[test]
+    fn test_remove_with_yes_should_not_prompt() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&_temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("remove")
+            .arg("gut")
+            .arg("-y")
+            .assert()
+            .success()
+            .stderr(predicate::str::contains("[y/N]").not())
+            .stdout(predicate::str::contains("Plugin gut removed successfully."));
+    }
+
+    #[test]
+    fn test_remove_with_quiet_should_not_print_status() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&_temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("remove")
+            .arg("gut")
+            .arg("--quiet")
+            .assert()
+            .success()
+            .stdout(predicate::str::is_empty());
+    }
+
+    #[test]
+    fn test_remove_should_remove_plugin_by_alias() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(
+            &_temp_dir,
+            &setup::GDM_JSON_WITH_ONE_PLUGIN.replace(
+                r#""license": "MIT","#,
+                r#""license": "MIT", "alias": "unit-tests","#,
+            ),
+        );
+
+        cmd.arg("remove")
+            .arg("unit-tests")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Plugin gut removed successfully."));
+    }
+
+    #[test]
+    fn test_remove_with_misspelled_name_should_suggest_closest_plugin() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&_temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("remove")
+            .arg("gtu")
+            .assert()
+            .failure()
+            .code(6)
+            .stderr(predicate::str::contains(
+                "error[GDM-202]: Plugin gtu is not installed.",
+            ))
+            .stderr(predicate::str::contains("hint: Did you mean gut?"));
+    }
+
+    #[test]
+    fn test_remove_should_remove_plugin_by_asset_id() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&_temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("remove")
+            .arg("--asset-id")
+            .arg("1709")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Plugin gut removed successfully."));
+    }
+
+    #[test]
+    fn test_remove_with_unknown_asset_id_should_fail() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&_temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("remove")
+            .arg("--asset-id")
+            .arg("1710")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "No plugin with asset ID 1710 is installed.",
+            ));
+    }
+
     #[test]
     fn test_remove_should_remove_folder() {
         let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
@@ -136,4 +238,40 @@ mod remove_command_tests {
             "Sub-asset folder should be removed"
         );
     }
+
+    // Processes are read from /proc only on Linux
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_remove_with_editor_open_should_require_force() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&_temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+        let bin_dir = temp_dir::TempDir::new().unwrap();
+        let godot = bin_dir.child("godot");
+        std::os::unix::fs::symlink("/bin/sh", &godot).unwrap();
+        let mut editor = std::process::Command::new(&godot)
+            .args(["-c", "sleep 30; true", "--editor"])
+            .current_dir(_temp_dir.path())
+            .spawn()
+            .unwrap();
+
+        let refused = cmd.arg("remove").arg("gut").assert();
+        let forced = setup::get_cmd(&_temp_dir)
+            .arg("remove")
+            .arg("gut")
+            .arg("--force")
+            .assert();
+        editor.kill().unwrap();
+        editor.wait().unwrap();
+
+        refused
+            .failure()
+            .code(4)
+            .stderr(predicate::str::contains(
+                "A Godot editor has this project open",
+            ))
+            .stderr(predicate::str::contains("--force"));
+        forced
+            .success()
+            .stdout(predicate::str::contains("Plugin gut removed successfully."));
+    }
 }
@@ -0,0 +1,52 @@
+mod setup;
+
+mod restore_config_command_tests {
+    use crate::setup;
+
+    use predicates::prelude::*;
+
+    // dirs reads XDG_CACHE_HOME only on Linux
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_restore_config_should_restore_latest_backup() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, r#"{ "plugins": {} }"#);
+
+        // Fails without plugins, after backing up the configuration
+        cmd.arg("install")
+            .env("XDG_CACHE_HOME", temp_dir.child("cache"))
+            .assert()
+            .failure();
+        setup::create_gdm_json(&temp_dir, "{ broken");
+
+        let mut cmd = setup::get_cmd(&temp_dir);
+        cmd.arg("restore-config")
+            .arg("--to")
+            .arg("latest")
+            .env("XDG_CACHE_HOME", temp_dir.child("cache"))
+            .assert()
+            .success();
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.child("gdm.json")).unwrap(),
+            r#"{ "plugins": {} }"#
+        );
+
+        let mut cmd = setup::get_cmd(&temp_dir);
+        cmd.arg("restore-config")
+            .arg("--list")
+            .env("XDG_CACHE_HOME", temp_dir.child("cache"))
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("gdm.json, project.godot").count(2));
+    }
+
+    #[test]
+    fn test_restore_config_without_arguments_should_fail() {
+        let (mut cmd, _temp_dir) = setup::get_bin();
+        cmd.arg("restore-config")
+            .assert()
+            .failure()
+            .code(2)
+            .stderr(predicate::str::contains("--to <BACKUP>"));
+    }
+}
@@ -16,6 +16,24 @@ mod search_command_tests {
             .stdout(predicate::str::contains("NAME"));
     }
 
+    #[test]
+    fn test_search_with_missing_credential_helper_should_fail() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        cmd.arg("search")
+            .arg("Godot Unit Testing")
+            .arg("--metadata-ttl")
+            .arg("0")
+            .env(
+                "GDM_CREDENTIAL_HELPER",
+                "gdm-missing-credential-helper token",
+            )
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "Failed to run credential helper gdm-missing-credential-helper",
+            ));
+    }
+
     #[test]
     fn test_search_without_project_godot_should_fail() {
         let (mut cmd, _temp_dir) = setup::get_bin();
@@ -39,6 +57,91 @@ mod search_command_tests {
             ));
     }
 
+    // dirs reads XDG_CACHE_HOME only on Linux
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_search_offline_should_use_asset_index() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        let cache_dir = temp_dir.child("cache");
+        std::fs::create_dir_all(cache_dir.join("gdm")).unwrap();
+        std::fs::write(
+            cache_dir.join("gdm/asset-index.json"),
+            r#"{"https://godotengine.org/asset-library/api": {"1709": {
+                "asset_id": "1709", "title": "Gut - Godot Unit Testing", "author": "bitwes",
+                "category": "Tools", "godot_version": "4.5", "rating": "0", "cost": "MIT",
+                "support_level": "community", "version": "30", "version_string": "9.5.0",
+                "modify_date": "2025-10-01 00:00:00"
+            }}}"#,
+        )
+        .unwrap();
+
+        cmd.arg("search")
+            .arg("godot unit test")
+            .arg("--offline")
+            .env("XDG_CACHE_HOME", &cache_dir)
+            .env(
+                "GDM_CREDENTIAL_HELPER",
+                "gdm-missing-credential-helper token",
+            )
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Found 1 known asset matching"))
+            .stdout(predicate::str::contains("Asset ID: 1709"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_search_should_mark_installed_assets() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+        let cache_dir = temp_dir.child("cache");
+        std::fs::create_dir_all(cache_dir.join("gdm")).unwrap();
+        std::fs::write(
+            cache_dir.join("gdm/asset-index.json"),
+            r#"{"https://godotengine.org/asset-library/api": {
+                "1709": {
+                    "asset_id": "1709", "title": "Gut - Godot Unit Testing", "author": "bitwes",
+                    "category": "Tools", "godot_version": "4.5", "rating": "0", "cost": "MIT",
+                    "support_level": "community", "version": "30", "version_string": "9.7.0",
+                    "modify_date": "2025-10-01 00:00:00"
+                },
+                "4321": {
+                    "asset_id": "4321", "title": "Godot Unit Test Runner", "author": "someone",
+                    "category": "Tools", "godot_version": "4.5", "rating": "0", "cost": "MIT",
+                    "support_level": "community", "version": "1", "version_string": "1.0.0",
+                    "modify_date": "2025-10-01 00:00:00"
+                }
+            }}"#,
+        )
+        .unwrap();
+
+        cmd.arg("search")
+            .arg("godot unit")
+            .arg("--offline")
+            .env("XDG_CACHE_HOME", &cache_dir)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "Installed: 9.6.0 as \"gut\", 9.7.0 is available",
+            ))
+            .stdout(predicate::str::contains("Installed:").count(1));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_search_offline_without_index_should_find_nothing() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+
+        cmd.arg("search")
+            .arg("Godot Unit Testing")
+            .arg("--offline")
+            .env("XDG_CACHE_HOME", temp_dir.child("cache"))
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("No known assets found matching"))
+            .stdout(predicate::str::contains("search without --offline"));
+    }
+
     #[test]
     fn test_search_with_exact_plugin_name_single_result() {
         let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
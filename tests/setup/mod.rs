@@ -11,12 +11,7 @@ pub fn setup_test_dir() -> TempDir {
 
 pub fn get_cmd(temp_dir: &TempDir) -> Command {
     let mut cmd = cargo::cargo_bin_cmd!(pkg_name!());
-    cmd.env("API_BASE_URL", "https://godotengine.org/asset-library/api")
-        .env("CONFIG_FILE_PATH", temp_dir.child("gdm.json"))
-        .env("CACHE_FOLDER_PATH", temp_dir.child(".gdm"))
-        .env("GODOT_PROJECT_FILE_PATH", temp_dir.child("project.godot"))
-        .env("ADDON_FOLDER_PATH", temp_dir.child("addons"))
-        .current_dir(temp_dir);
+    cmd.current_dir(temp_dir);
     cmd
 }
 
@@ -24,12 +19,7 @@ pub fn get_bin() -> (Command, TempDir) {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
     let mut cmd = cargo::cargo_bin_cmd!(pkg_name!());
 
-    cmd.env("API_BASE_URL", "https://godotengine.org/asset-library/api")
-        .env("CONFIG_FILE_PATH", temp_dir.child("gdm.json"))
-        .env("CACHE_FOLDER_PATH", temp_dir.child(".gdm"))
-        .env("GODOT_PROJECT_FILE_PATH", temp_dir.child("project.godot"))
-        .env("ADDON_FOLDER_PATH", temp_dir.child("addons"))
-        .current_dir(&temp_dir);
+    cmd.current_dir(&temp_dir);
 
     (cmd, temp_dir)
 }
@@ -50,6 +40,11 @@ pub fn create_gdm_json(dir: &TempDir, content: &str) {
     fs::write(gdm_path, content).expect("Failed to write gdm.json");
 }
 
+pub fn create_gdm_toml(dir: &TempDir, content: &str) {
+    let gdm_path = dir.child("gdm.toml");
+    fs::write(gdm_path, content).expect("Failed to write gdm.toml");
+}
+
 pub const MINIMAL_PROJECT_GODOT: &str = r#"
 ; Engine configuration file.
 ; It's best edited using the editor UI and not directly,
@@ -0,0 +1,60 @@
+mod setup;
+
+mod stats_command_tests {
+    use crate::setup;
+
+    use predicates::prelude::*;
+
+    #[test]
+    fn test_stats_command_help() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        cmd.arg("stats")
+            .arg("--help")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Summarize the managed plugins"));
+    }
+
+    #[test]
+    fn test_stats_without_gdm_json_should_fail() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        cmd.arg("stats")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("No plugins installed."));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_stats_should_summarize_plugins() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+        let gut_dir = temp_dir.child("addons/gut");
+        std::fs::create_dir_all(gut_dir.join("scripts")).unwrap();
+        std::fs::write(gut_dir.join("plugin.cfg"), "a".repeat(1000)).unwrap();
+        std::fs::write(gut_dir.join("scripts/gut.gd"), "a".repeat(1048)).unwrap();
+        let cache_dir = temp_dir.child("cache");
+        std::fs::create_dir_all(cache_dir.join("gdm")).unwrap();
+        std::fs::write(
+            cache_dir.join("gdm/asset-index.json"),
+            r#"{"https://godotengine.org/asset-library/api": {"1709": {
+                "asset_id": "1709", "title": "Gut - Godot Unit Testing", "author": "bitwes",
+                "category": "Tools", "godot_version": "4.5", "rating": "0", "cost": "MIT",
+                "support_level": "community", "version": "30", "version_string": "9.6.0",
+                "modify_date": "2025-10-01 00:00:00"
+            }}}"#,
+        )
+        .unwrap();
+
+        cmd.arg("stats")
+            .env("XDG_CACHE_HOME", &cache_dir)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Plugins: 1 (1 Asset Library)"))
+            .stdout(predicate::str::is_match(r"gut\s+2\.00 KiB").unwrap())
+            .stdout(predicate::str::is_match(r"MIT\s+1").unwrap())
+            .stdout(predicate::str::contains(
+                "Least recently updated: gut, GUT - Godot Unit Testing (Godot 4) 9.6.0 (updated 2025-10-01 00:00:00)",
+            ));
+    }
+}
@@ -15,6 +15,17 @@ mod update_command_tests {
             .stdout(predicate::str::contains("update"));
     }
 
+    #[test]
+    fn test_update_with_patch_and_major_should_fail() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        cmd.arg("update")
+            .arg("--patch")
+            .arg("--major")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+
     #[test]
     fn test_update_without_project_godot_should_fail() {
         let (mut cmd, _temp_dir) = setup::get_bin();
@@ -48,12 +59,29 @@ mod update_command_tests {
     }
 
     #[test]
-    fn test_update_no_arguments_accepted() {
+    fn test_update_with_unknown_name_should_fail() {
         let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&_temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
         cmd.arg("update")
             .arg("extra-arg")
             .assert()
             .failure()
-            .stderr(predicate::str::contains("unexpected argument"));
+            .code(6)
+            .stderr(predicate::str::contains(
+                "error[GDM-202]: Plugin extra-arg is not installed.",
+            ))
+            .stderr(predicate::str::contains("hint: "));
+    }
+
+    #[test]
+    fn test_update_with_misspelled_name_should_suggest_closest_plugin() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&_temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+        cmd.arg("update")
+            .arg("gtu")
+            .assert()
+            .failure()
+            .code(6)
+            .stderr(predicate::str::contains("hint: Did you mean gut?"));
     }
 }
@@ -0,0 +1,141 @@
+mod setup;
+
+mod validate_command_tests {
+    use crate::setup;
+
+    use predicates::prelude::*;
+
+    #[test]
+    fn test_validate_command_help() {
+        let (mut cmd, _temp_dir) = setup::get_bin();
+        cmd.arg("validate")
+            .arg("--help")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Check the configuration file"));
+    }
+
+    #[test]
+    fn test_validate_valid_config() {
+        let (mut cmd, temp_dir) = setup::get_bin();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("validate")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("gdm.json is valid"));
+    }
+
+    #[test]
+    fn test_validate_without_config_should_fail() {
+        let (mut cmd, _temp_dir) = setup::get_bin();
+        cmd.arg("validate")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "No gdm.json file found in the current directory",
+            ));
+    }
+
+    #[test]
+    fn test_validate_should_report_invalid_field() {
+        let (mut cmd, temp_dir) = setup::get_bin();
+        setup::create_gdm_json(
+            &temp_dir,
+            r#"{
+  "plugins": {
+    "gut": { "title": "Gut", "version": 9 }
+  }
+}"#,
+        );
+
+        cmd.arg("validate")
+            .assert()
+            .failure()
+            .code(3)
+            .stderr(predicate::str::contains("error[GDM-103]"))
+            .stderr(predicate::str::contains("plugins.gut.version"))
+            .stderr(predicate::str::contains("line 3"));
+    }
+
+    #[test]
+    fn test_invalid_config_should_fail_other_commands() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, r#"{ "plugins": { "gut": { "title": "Gut" } } }"#);
+
+        cmd.arg("list")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "plugins.gut: missing field `version`",
+            ));
+    }
+
+    #[test]
+    fn test_validate_with_github_format_should_print_annotation() {
+        let (mut cmd, temp_dir) = setup::get_bin();
+        setup::create_gdm_json(
+            &temp_dir,
+            r#"{
+  "plugins": {
+    "gut": { "title": "Gut", "version": 9 }
+  }
+}"#,
+        );
+
+        cmd.arg("validate")
+            .arg("--format")
+            .arg("github")
+            .assert()
+            .failure()
+            .code(3)
+            .stdout(predicate::str::contains("::error file="))
+            .stdout(predicate::str::contains(
+                "gdm.json,line=3,title=Invalid configuration::",
+            ));
+    }
+
+    #[test]
+    fn test_validate_with_csv_format_should_fail() {
+        let (mut cmd, temp_dir) = setup::get_bin();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("validate")
+            .arg("--format")
+            .arg("csv")
+            .assert()
+            .code(2)
+            .stderr(predicate::str::contains("can't use --format csv or tsv"));
+    }
+
+    #[test]
+    fn test_validate_porcelain_should_print_valid_record() {
+        let (mut cmd, temp_dir) = setup::get_bin();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("validate")
+            .arg("--porcelain")
+            .assert()
+            .success()
+            .stdout(predicate::str::diff("valid\tgdm.json\n"));
+    }
+
+    #[test]
+    fn test_validate_porcelain_should_print_invalid_record() {
+        let (mut cmd, temp_dir) = setup::get_bin();
+        setup::create_gdm_json(
+            &temp_dir,
+            r#"{
+  "plugins": {
+    "gut": { "title": "Gut", "version": 9 }
+  }
+}"#,
+        );
+
+        cmd.arg("validate")
+            .arg("--porcelain")
+            .assert()
+            .code(3)
+            .stdout(predicate::str::starts_with("invalid\tgdm.json\t3\t"));
+    }
+}
@@ -0,0 +1,20 @@
+mod setup;
+
+mod watch_command_tests {
+    use crate::setup;
+
+    use predicates::prelude::*;
+
+    #[test]
+    fn test_watch_command_help() {
+        let (mut cmd, _temp_dir) = setup::get_bin_with_project_godot();
+        cmd.arg("watch")
+            .arg("--help")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "Watch the configuration file and install plugins whenever it changes",
+            ))
+            .stdout(predicate::str::contains("--interval"));
+    }
+}
@@ -0,0 +1,52 @@
+mod setup;
+
+mod why_command_tests {
+    use crate::setup;
+
+    use predicates::prelude::*;
+
+    #[test]
+    fn test_why_should_report_main_folder_of_plugin() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+        std::fs::create_dir_all(temp_dir.child("addons/gut")).unwrap();
+
+        cmd.arg("why")
+            .arg("gut")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "addons/gut is the main folder of plugin gut, GUT - Godot Unit Testing (Godot 4) 9.6.0 from Asset Library (1709).",
+            ))
+            .stdout(predicate::str::contains("No other plugin depends on it."));
+    }
+
+    #[test]
+    fn test_why_with_unmanaged_folder_should_say_so() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+        std::fs::create_dir_all(temp_dir.child("addons/mystery")).unwrap();
+
+        cmd.arg("why")
+            .arg("addons/mystery")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "addons/mystery isn't managed by gdm.",
+            ));
+    }
+
+    #[test]
+    fn test_why_with_missing_folder_should_fail() {
+        let (mut cmd, temp_dir) = setup::get_bin_with_project_godot();
+        setup::create_gdm_json(&temp_dir, setup::GDM_JSON_WITH_ONE_PLUGIN);
+
+        cmd.arg("why")
+            .arg("missing")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "addons/missing doesn't exist and no plugin in the configuration file installs it.",
+            ));
+    }
+}